@@ -6,12 +6,40 @@
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Stream, StreamConfig};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::domain::{AudioDeviceInfo, AudioSample, Psk31Error, Psk31Result};
+use crate::domain::{AudioDeviceInfo, AudioDevices, AudioSample, Psk31Error, Psk31Result};
 use crate::ports::{AudioInput, AudioOutput};
 
+// ---------------------------------------------------------------------------
+// Host (audio API) selection
+// ---------------------------------------------------------------------------
+
+/// Names of the audio hosts (APIs) cpal can use on this machine — e.g. ALSA
+/// and JACK on Linux, WASAPI and ASIO on Windows. The system default is first.
+pub fn list_audio_hosts() -> Vec<String> {
+    let default = cpal::default_host().id();
+    std::iter::once(default)
+        .chain(cpal::available_hosts().into_iter().filter(|&id| id != default))
+        .map(|id| id.name().to_string())
+        .collect()
+}
+
+/// Open the host named `name` (case-insensitive), or the system default for `None`.
+pub fn open_host(name: Option<&str>) -> Psk31Result<cpal::Host> {
+    let Some(name) = name else {
+        return Ok(cpal::default_host());
+    };
+    let id = cpal::available_hosts()
+        .into_iter()
+        .find(|id| id.name().eq_ignore_ascii_case(name))
+        .ok_or_else(|| Psk31Error::Audio(format!("Audio host not available: {name}")))?;
+    cpal::host_from_id(id)
+        .map_err(|e| Psk31Error::Audio(format!("Failed to open audio host {name}: {e}")))
+}
+
 // ---------------------------------------------------------------------------
 // Device enumeration (shared by AudioInput and AudioOutput)
 // ---------------------------------------------------------------------------
@@ -27,8 +55,7 @@ use crate::ports::{AudioInput, AudioOutput};
 /// `output_unverified` is set when a device passes the `default_output_config()`
 /// check but is NOT in `output_devices()` — an unusual edge case that warrants
 /// a separate UI group.
-fn enumerate_devices() -> crate::domain::Psk31Result<Vec<AudioDeviceInfo>> {
-    let host = cpal::default_host();
+fn enumerate_devices(host: &cpal::Host) -> crate::domain::Psk31Result<Vec<AudioDeviceInfo>> {
     let default_input_name = host.default_input_device().and_then(|d| d.name().ok());
     let default_output_name = host.default_output_device().and_then(|d| d.name().ok());
 
@@ -79,10 +106,89 @@ fn enumerate_devices() -> crate::domain::Psk31Result<Vec<AudioDeviceInfo>> {
     Ok(devices)
 }
 
+/// Enumerate `host`'s devices (see [`open_host`]) split into input and output
+/// lists, with the system default flagged separately for each role.
+pub fn list_devices_grouped(host: Option<&str>) -> Psk31Result<AudioDevices> {
+    let host = open_host(host)?;
+    let default_input = host.default_input_device().and_then(|d| d.name().ok());
+    let default_output = host.default_output_device().and_then(|d| d.name().ok());
+    let devices = enumerate_devices(&host)?;
+    Ok(AudioDevices::from_devices(
+        &devices,
+        default_input.as_deref(),
+        default_output.as_deref(),
+    ))
+}
+
+/// Pick the device matching `wanted` from a list of OS-reported names.
+///
+/// OS device names drift across reboots and driver updates (e.g. a trailing
+/// " (2)"), so a saved profile's exact name may no longer exist. Strategy:
+/// 1. Exact name match
+/// 2. Otherwise, a *unique* case-insensitive substring match
+///
+/// Returns the index into `names`, or an error if nothing or more than one
+/// device matches.
+pub(crate) fn match_device_name(names: &[String], wanted: &str) -> Psk31Result<usize> {
+    if let Some(idx) = names.iter().position(|n| n == wanted) {
+        return Ok(idx);
+    }
+
+    let needle = wanted.to_lowercase();
+    let candidates: Vec<usize> = names
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| n.to_lowercase().contains(&needle))
+        .map(|(i, _)| i)
+        .collect();
+
+    match candidates.as_slice() {
+        [idx] => {
+            log::info!("Audio device '{wanted}' matched '{}' by substring", names[*idx]);
+            Ok(*idx)
+        }
+        [] => Err(Psk31Error::Audio(format!("Audio device not found: {wanted}"))),
+        _ => {
+            let matched: Vec<&str> = candidates.iter().map(|&i| names[i].as_str()).collect();
+            Err(Psk31Error::Audio(format!(
+                "Audio device '{wanted}' is ambiguous; matches: {}",
+                matched.join(", ")
+            )))
+        }
+    }
+}
+
+/// Find a device by name among `devices` using [`match_device_name`].
+fn find_device(
+    devices: impl Iterator<Item = cpal::Device>,
+    wanted: &str,
+) -> Psk31Result<cpal::Device> {
+    let mut devices: Vec<cpal::Device> = devices.collect();
+    let names: Vec<String> = devices
+        .iter()
+        .map(|d| d.name().unwrap_or_default())
+        .collect();
+    let idx = match_device_name(&names, wanted)?;
+    Ok(devices.swap_remove(idx))
+}
+
 // ---------------------------------------------------------------------------
 // AudioInput
 // ---------------------------------------------------------------------------
 
+/// Capture rate asked for unless `with_sample_rate` says otherwise
+pub const PREFERRED_SAMPLE_RATE: u32 = 48000;
+
+/// Pick a capture rate from a device's supported `(min, max)` rate ranges:
+/// `preferred` if any range covers it, otherwise the closest rate a range
+/// offers (the higher one on a tie). `None` if the device lists no ranges.
+pub fn choose_sample_rate(ranges: &[(u32, u32)], preferred: u32) -> Option<u32> {
+    ranges
+        .iter()
+        .map(|&(min, max)| preferred.clamp(min, max))
+        .min_by_key(|&rate| (rate.abs_diff(preferred), std::cmp::Reverse(rate)))
+}
+
 /// Audio input adapter backed by cpal.
 ///
 /// Important: `cpal::Stream` is `!Send` — it can only live on the thread that
@@ -91,6 +197,12 @@ fn enumerate_devices() -> crate::domain::Psk31Result<Vec<AudioDeviceInfo>> {
 pub struct CpalAudioInput {
     stream: Option<Stream>,
     running: Arc<AtomicBool>,
+    /// Audio host name; `None` uses the system default
+    host: Option<String>,
+    /// Rate to ask the device for (see `choose_sample_rate`)
+    preferred_rate: u32,
+    /// Rate the running stream was opened at
+    sample_rate: Option<u32>,
 }
 
 impl CpalAudioInput {
@@ -98,13 +210,29 @@ impl CpalAudioInput {
         Self {
             stream: None,
             running: Arc::new(AtomicBool::new(false)),
+            host: None,
+            preferred_rate: PREFERRED_SAMPLE_RATE,
+            sample_rate: None,
         }
     }
+
+    /// Ask for `rate` instead of `PREFERRED_SAMPLE_RATE`; the device may
+    /// still settle on another (see `sample_rate`).
+    pub fn with_sample_rate(mut self, rate: u32) -> Self {
+        self.preferred_rate = rate;
+        self
+    }
+
+    /// Enumerate and open devices on `host` instead of the system default.
+    pub fn with_host(mut self, host: Option<String>) -> Self {
+        self.host = host;
+        self
+    }
 }
 
 impl AudioInput for CpalAudioInput {
     fn list_devices(&self) -> Psk31Result<Vec<AudioDeviceInfo>> {
-        enumerate_devices()
+        enumerate_devices(&open_host(self.host.as_deref())?)
     }
 
     fn start(
@@ -116,21 +244,36 @@ impl AudioInput for CpalAudioInput {
             return Err(Psk31Error::Audio("Audio stream already running".into()));
         }
 
-        let host = cpal::default_host();
-
-        // Find the requested device by name
-        let device = host
-            .input_devices()
-            .map_err(|e| Psk31Error::Audio(format!("Failed to enumerate devices: {e}")))?
-            .find(|d| d.name().map(|n| n == device_id).unwrap_or(false))
-            .ok_or_else(|| {
-                Psk31Error::Audio(format!("Audio device not found: {device_id}"))
-            })?;
-
-        // Configure for 48 kHz mono f32 — standard for ham radio digital modes
+        let host = open_host(self.host.as_deref())?;
+
+        // Find the requested device by name (exact, then unique substring)
+        let device = find_device(
+            host.input_devices()
+                .map_err(|e| Psk31Error::Audio(format!("Failed to enumerate devices: {e}")))?,
+            device_id,
+        )?;
+
+        // Mono f32 at 48 kHz (standard for ham radio digital modes) when the
+        // device offers it; some USB codecs only do 44.1 or 96 kHz. Only the
+        // mono f32 configs count, since that's the stream opened below.
+        let ranges: Vec<(u32, u32)> = device
+            .supported_input_configs()
+            .map_err(|e| Psk31Error::Audio(format!("Failed to query device configs: {e}")))?
+            .filter(|c| c.channels() == 1 && c.sample_format() == cpal::SampleFormat::F32)
+            .map(|c| (c.min_sample_rate().0, c.max_sample_rate().0))
+            .collect();
+        let sample_rate = choose_sample_rate(&ranges, self.preferred_rate).ok_or_else(|| {
+            Psk31Error::Audio(format!("Device '{device_id}' offers no mono f32 input"))
+        })?;
+        if sample_rate != self.preferred_rate {
+            log::warn!(
+                "Input device doesn't support {} Hz; capturing at {sample_rate} Hz",
+                self.preferred_rate
+            );
+        }
         let config = StreamConfig {
             channels: 1,
-            sample_rate: cpal::SampleRate(48000),
+            sample_rate: cpal::SampleRate(sample_rate),
             buffer_size: cpal::BufferSize::Default,
         };
 
@@ -158,6 +301,7 @@ impl AudioInput for CpalAudioInput {
             .map_err(|e| Psk31Error::Audio(format!("Failed to start stream: {e}")))?;
 
         self.stream = Some(stream);
+        self.sample_rate = Some(sample_rate);
 
         Ok(())
     }
@@ -166,12 +310,17 @@ impl AudioInput for CpalAudioInput {
         self.running.store(false, Ordering::SeqCst);
         // Dropping the stream stops capture
         self.stream = None;
+        self.sample_rate = None;
         Ok(())
     }
 
     fn is_running(&self) -> bool {
         self.running.load(Ordering::SeqCst)
     }
+
+    fn sample_rate(&self) -> Option<u32> {
+        self.sample_rate
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -186,20 +335,34 @@ impl AudioInput for CpalAudioInput {
 pub struct CpalAudioOutput {
     stream: Option<Stream>,
     running: Arc<AtomicBool>,
+    /// Latest callback-to-playback delay in microseconds (`LATENCY_UNKNOWN` until measured)
+    latency_us: Arc<AtomicU64>,
+    /// Audio host name; `None` uses the system default
+    host: Option<String>,
 }
 
+const LATENCY_UNKNOWN: u64 = u64::MAX;
+
 impl CpalAudioOutput {
     pub fn new() -> Self {
         Self {
             stream: None,
             running: Arc::new(AtomicBool::new(false)),
+            latency_us: Arc::new(AtomicU64::new(LATENCY_UNKNOWN)),
+            host: None,
         }
     }
+
+    /// Enumerate and open devices on `host` instead of the system default.
+    pub fn with_host(mut self, host: Option<String>) -> Self {
+        self.host = host;
+        self
+    }
 }
 
 impl AudioOutput for CpalAudioOutput {
     fn list_devices(&self) -> Psk31Result<Vec<AudioDeviceInfo>> {
-        enumerate_devices()
+        enumerate_devices(&open_host(self.host.as_deref())?)
     }
 
     fn start(
@@ -211,15 +374,13 @@ impl AudioOutput for CpalAudioOutput {
             return Err(Psk31Error::Audio("Audio output already running".into()));
         }
 
-        let host = cpal::default_host();
+        let host = open_host(self.host.as_deref())?;
 
-        let device = host
-            .devices()
-            .map_err(|e| Psk31Error::Audio(format!("Failed to enumerate devices: {e}")))?
-            .find(|d| d.name().map(|n| n == device_id).unwrap_or(false))
-            .ok_or_else(|| {
-                Psk31Error::Audio(format!("Audio output device not found: {device_id}"))
-            })?;
+        let device = find_device(
+            host.devices()
+                .map_err(|e| Psk31Error::Audio(format!("Failed to enumerate devices: {e}")))?,
+            device_id,
+        )?;
 
         let config = StreamConfig {
             channels: 1,
@@ -231,11 +392,19 @@ impl AudioOutput for CpalAudioOutput {
         running.store(true, Ordering::SeqCst);
 
         let err_running = self.running.clone();
+        let latency_us = self.latency_us.clone();
+        latency_us.store(LATENCY_UNKNOWN, Ordering::Relaxed);
 
         let stream = device
             .build_output_stream(
                 &config,
-                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
+                    // The host's playback timestamp tells us how long this
+                    // buffer sits in the pipeline before reaching the DAC
+                    let ts = info.timestamp();
+                    if let Some(latency) = ts.playback.duration_since(&ts.callback) {
+                        latency_us.store(latency.as_micros() as u64, Ordering::Relaxed);
+                    }
                     callback(data);
                 },
                 move |err| {
@@ -264,6 +433,13 @@ impl AudioOutput for CpalAudioOutput {
     fn is_running(&self) -> bool {
         self.running.load(Ordering::SeqCst)
     }
+
+    fn output_latency(&self) -> Option<Duration> {
+        match self.latency_us.load(Ordering::Relaxed) {
+            LATENCY_UNKNOWN => None,
+            us => Some(Duration::from_micros(us)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -272,6 +448,22 @@ mod tests {
 
     // --- AudioInput tests ---
 
+    #[test]
+    fn choose_sample_rate_prefers_48k_when_offered() {
+        let ranges = [(44100, 44100), (8000, 96000), (96000, 96000)];
+        assert_eq!(choose_sample_rate(&ranges, 48000), Some(48000));
+    }
+
+    #[test]
+    fn choose_sample_rate_falls_back_to_closest() {
+        assert_eq!(choose_sample_rate(&[(44100, 44100), (96000, 96000)], 48000), Some(44100));
+        assert_eq!(choose_sample_rate(&[(96000, 192000)], 48000), Some(96000));
+        assert_eq!(choose_sample_rate(&[(8000, 32000)], 48000), Some(32000));
+        // Equidistant: take the higher rate
+        assert_eq!(choose_sample_rate(&[(40000, 40000), (56000, 56000)], 48000), Some(56000));
+        assert_eq!(choose_sample_rate(&[], 48000), None);
+    }
+
     #[test]
     fn test_new_not_running() {
         let input = CpalAudioInput::new();
@@ -303,6 +495,58 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // --- host selection ---
+
+    #[test]
+    fn hosts_list_default_first() {
+        let hosts = list_audio_hosts();
+        assert_eq!(hosts.first().map(String::as_str), Some(cpal::default_host().id().name()));
+        assert!(open_host(Some(&hosts[0].to_lowercase())).is_ok());
+    }
+
+    #[test]
+    fn unknown_host_errors() {
+        let err = open_host(Some("NoSuchAudioApi")).err().unwrap();
+        assert!(err.to_string().contains("not available"), "got: {err}");
+        assert!(list_devices_grouped(Some("NoSuchAudioApi")).is_err());
+
+        let mut input = CpalAudioInput::new().with_host(Some("NoSuchAudioApi".into()));
+        assert!(input.start("default", Box::new(|_samples| {})).is_err());
+        assert!(!input.is_running());
+    }
+
+    // --- match_device_name ---
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn match_prefers_exact_name_over_substring() {
+        let devices = names(&["USB Audio CODEC (2)", "USB Audio CODEC", "MacBook Pro Microphone"]);
+        assert_eq!(match_device_name(&devices, "USB Audio CODEC").unwrap(), 1);
+    }
+
+    #[test]
+    fn match_falls_back_to_unique_substring_case_insensitive() {
+        let devices = names(&["USB Audio CODEC (2)", "MacBook Pro Microphone"]);
+        assert_eq!(match_device_name(&devices, "usb audio codec").unwrap(), 0);
+    }
+
+    #[test]
+    fn match_rejects_ambiguous_substring() {
+        let devices = names(&["USB Audio CODEC (2)", "USB Audio CODEC (3)"]);
+        let err = match_device_name(&devices, "USB Audio CODEC").unwrap_err();
+        assert!(err.to_string().contains("ambiguous"), "got: {err}");
+    }
+
+    #[test]
+    fn match_reports_not_found() {
+        let devices = names(&["MacBook Pro Microphone"]);
+        let err = match_device_name(&devices, "SignaLink USB").unwrap_err();
+        assert!(err.to_string().contains("not found"), "got: {err}");
+    }
+
     // --- AudioOutput tests ---
 
     #[test]
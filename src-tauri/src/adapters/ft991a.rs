@@ -10,32 +10,12 @@
 
 use std::time::Duration;
 
-use crate::cat::{CatCommand, CatResponse, CatSession};
-use crate::domain::{Frequency, Psk31Error, Psk31Result, RadioStatus};
-use crate::ports::{RadioControl, SerialConnection};
-
-/// US amateur radio bands (FCC Part 97) as (low_hz, high_hz) pairs.
-/// Only frequencies within these bands are accepted.
-const AMATEUR_BANDS_HZ: &[(u64, u64)] = &[
-    (1_800_000, 2_000_000),       // 160m
-    (3_500_000, 4_000_000),       // 80m
-    (5_332_000, 5_405_000),       // 60m
-    (7_000_000, 7_300_000),       // 40m
-    (10_100_000, 10_150_000),     // 30m
-    (14_000_000, 14_350_000),     // 20m
-    (18_068_000, 18_168_000),     // 17m
-    (21_000_000, 21_450_000),     // 15m
-    (24_890_000, 24_990_000),     // 12m
-    (28_000_000, 29_700_000),     // 10m
-    (50_000_000, 54_000_000),     // 6m
-    (144_000_000, 148_000_000),   // 2m
-    (420_000_000, 450_000_000),   // 70cm
-];
-
-/// Check if a frequency falls within a US amateur band.
-fn is_amateur_frequency(hz: u64) -> bool {
-    AMATEUR_BANDS_HZ.iter().any(|&(lo, hi)| hz >= lo && hz <= hi)
-}
+use crate::cat::{CatCommand, CatResponse, CatSession, RIT_MAX_OFFSET_HZ};
+use crate::domain::{
+    is_amateur_frequency, normalize_mode, Frequency, Psk31Error, Psk31Result, RadioFilterState,
+    RadioMemoryChannel, RadioPush, RadioStatus, SerialParams, Vfo,
+};
+use crate::ports::{Clock, RadioControl, SerialConnection, SystemClock};
 
 /// Map a frequency to the FT-991A BS; band-select code.
 ///
@@ -72,11 +52,32 @@ pub struct Ft991aRadio {
 
 impl Ft991aRadio {
     pub fn new(serial: Box<dyn SerialConnection>) -> Self {
-        Self {
-            session: CatSession::new(serial),
-            is_transmitting: false,
-            last_band_code: None,
-        }
+        Self::with_session(CatSession::new(serial))
+    }
+
+    fn with_session(session: CatSession) -> Self {
+        Self { session, is_transmitting: false, last_band_code: None }
+    }
+
+    /// Open on `serial` with the command delay from `params`.
+    pub fn with_params(serial: Box<dyn SerialConnection>, params: &SerialParams) -> Self {
+        Self::with_params_and_clock(serial, params, Box::new(SystemClock))
+    }
+
+    /// `with_params`, timing the session's delays against `clock`.
+    pub fn with_params_and_clock(
+        serial: Box<dyn SerialConnection>,
+        params: &SerialParams,
+        clock: Box<dyn Clock>,
+    ) -> Self {
+        let mut radio = Self::with_session(CatSession::with_clock(serial, clock));
+        radio.session.set_command_delay(Duration::from_millis(params.command_delay_ms));
+        radio
+    }
+
+    /// Minimum delay between CAT commands on this connection
+    pub fn command_delay(&self) -> Duration {
+        self.session.command_delay()
     }
 }
 
@@ -97,6 +98,52 @@ impl RadioControl for Ft991aRadio {
         self.is_transmitting
     }
 
+    fn is_transmitting_remote(&mut self) -> Psk31Result<Option<bool>> {
+        match self.session.execute(&CatCommand::GetTxState)? {
+            CatResponse::TxState(state) => Ok(Some(state != 0)),
+            _ => Err(Psk31Error::Cat("unexpected response for GetTxState".into())),
+        }
+    }
+
+    fn get_filter_state(&mut self) -> Psk31Result<Option<RadioFilterState>> {
+        let narrow = match self.session.execute(&CatCommand::GetNarrowFilter)? {
+            CatResponse::NarrowFilter(on) => on,
+            _ => return Err(Psk31Error::Cat("unexpected response for GetNarrowFilter".into())),
+        };
+        match self.session.execute(&CatCommand::GetFilterWidth)? {
+            CatResponse::FilterWidth(width_code) => {
+                Ok(Some(RadioFilterState { narrow, width_code }))
+            }
+            _ => Err(Psk31Error::Cat("unexpected response for GetFilterWidth".into())),
+        }
+    }
+
+    fn get_active_vfo(&mut self) -> Psk31Result<Option<Vfo>> {
+        self.get_status().map(|status| Some(status.vfo))
+    }
+
+    /// Read the selected memory channel and its stored frequency (MC; then MRnnn;).
+    fn memory_channel_frequency(&mut self) -> Psk31Result<Option<RadioMemoryChannel>> {
+        let channel = match self.session.execute(&CatCommand::GetMemoryChannel)? {
+            CatResponse::MemoryChannel(ch) => ch,
+            _ => return Err(Psk31Error::Cat("unexpected response for GetMemoryChannel".into())),
+        };
+        match self.session.execute(&CatCommand::ReadMemoryChannel(channel))? {
+            CatResponse::MemoryChannelFrequency { channel, frequency_hz } => {
+                Ok(Some(RadioMemoryChannel { channel, frequency_hz: frequency_hz as f64 }))
+            }
+            _ => Err(Psk31Error::Cat("unexpected response for ReadMemoryChannel".into())),
+        }
+    }
+
+    /// The FT-991A won't key while a memory/VFO scan is running.
+    fn get_tx_inhibit(&mut self) -> Psk31Result<Option<String>> {
+        match self.session.execute(&CatCommand::GetScanStatus)? {
+            CatResponse::Scanning(scanning) => Ok(scanning.then(|| "radio is scanning".into())),
+            _ => Err(Psk31Error::Cat("unexpected response for GetScanStatus".into())),
+        }
+    }
+
     fn get_frequency(&mut self) -> Psk31Result<Frequency> {
         match self.session.execute(&CatCommand::GetFrequencyA)? {
             CatResponse::FrequencyHz(hz) => {
@@ -130,6 +177,38 @@ impl RadioControl for Ft991aRadio {
         Ok(())
     }
 
+    fn get_frequency_b(&mut self) -> Psk31Result<Frequency> {
+        match self.session.execute(&CatCommand::GetFrequencyB)? {
+            CatResponse::FrequencyHz(hz) => Ok(Frequency::hz(hz as f64)),
+            _ => Err(Psk31Error::Cat("unexpected response for GetFrequencyB".into())),
+        }
+    }
+
+    /// Same band check as VFO-A, but no `BS;` — band select recalls band
+    /// memory on the main VFO.
+    fn set_frequency_b(&mut self, freq: Frequency) -> Psk31Result<()> {
+        let hz = freq.as_hz() as u64;
+        if !is_amateur_frequency(hz) {
+            return Err(Psk31Error::Cat(format!(
+                "Frequency {hz} Hz is outside US amateur bands"
+            )));
+        }
+        self.session.execute_write_only(&CatCommand::SetFrequencyB(hz))?;
+        Ok(())
+    }
+
+    fn swap_vfo(&mut self) -> Psk31Result<()> {
+        self.session.execute_write_only(&CatCommand::SwapVfo)?;
+        // VFO-A now holds whatever band B was on
+        self.last_band_code = None;
+        Ok(())
+    }
+
+    /// `ST1;`/`ST0;` — write-only
+    fn set_split(&mut self, on: bool) -> Psk31Result<()> {
+        self.session.execute_write_only(&CatCommand::SetSplit(on))
+    }
+
     fn get_mode(&mut self) -> Psk31Result<String> {
         match self.session.execute(&CatCommand::GetMode)? {
             CatResponse::Mode(name) => Ok(name),
@@ -138,6 +217,7 @@ impl RadioControl for Ft991aRadio {
     }
 
     fn set_mode(&mut self, mode: &str) -> Psk31Result<()> {
+        let mode = normalize_mode(mode)?;
         self.session.execute_write_only(&CatCommand::SetMode(mode.to_string()))?;
         Ok(())
     }
@@ -160,11 +240,20 @@ impl RadioControl for Ft991aRadio {
     }
 
     fn get_signal_strength(&mut self) -> Psk31Result<f32> {
-        match self.session.execute(&CatCommand::GetSignalStrength)? {
-            CatResponse::SignalStrength(s) => Ok(s),
-            _ => Err(Psk31Error::Cat(
-                "unexpected response for GetSignalStrength".into(),
-            )),
+        Ok(f32::from(self.read_s_meter()?) / 255.0)
+    }
+
+    fn read_s_meter(&mut self) -> Psk31Result<u8> {
+        match self.session.execute(&CatCommand::GetSMeter)? {
+            CatResponse::SMeter(raw) => Ok(raw),
+            _ => Err(Psk31Error::Cat("unexpected response for GetSMeter".into())),
+        }
+    }
+
+    fn read_swr(&mut self) -> Psk31Result<f32> {
+        match self.session.execute(&CatCommand::GetSwr)? {
+            CatResponse::Swr(ratio) => Ok(ratio),
+            _ => Err(Psk31Error::Cat("unexpected response for GetSwr".into())),
         }
     }
 
@@ -176,6 +265,58 @@ impl RadioControl for Ft991aRadio {
             )),
         }
     }
+
+    /// `AI1;`/`AI0;` — write-only; the FT-991A doesn't acknowledge it.
+    fn set_auto_information(&mut self, enabled: bool) -> Psk31Result<()> {
+        self.session.execute_write_only(&CatCommand::SetAutoInformation(enabled))?;
+        self.session.set_listening(enabled);
+        Ok(())
+    }
+
+    /// `RT1;`/`RT0;` — write-only, like the other setters.
+    fn set_rit_enabled(&mut self, enabled: bool) -> Psk31Result<()> {
+        self.session.execute_write_only(&CatCommand::SetRitEnabled(enabled))
+    }
+
+    fn set_xit_enabled(&mut self, enabled: bool) -> Psk31Result<()> {
+        self.session.execute_write_only(&CatCommand::SetXitEnabled(enabled))
+    }
+
+    /// `RC;` then `RU`/`RD` — RU/RD step from the current offset, so clear
+    /// it first to make the offset absolute.
+    fn set_rit_offset(&mut self, offset_hz: i32) -> Psk31Result<()> {
+        if offset_hz.unsigned_abs() > RIT_MAX_OFFSET_HZ as u32 {
+            return Err(Psk31Error::Cat(format!(
+                "RIT offset {offset_hz} Hz is outside the FT-991A range (±{RIT_MAX_OFFSET_HZ} Hz)"
+            )));
+        }
+        self.session.execute_write_only(&CatCommand::ClearRit)?;
+        if offset_hz != 0 {
+            self.session.execute_write_only(&CatCommand::SetRitOffsetHz(offset_hz))?;
+        }
+        Ok(())
+    }
+
+    fn clear_rit(&mut self) -> Psk31Result<()> {
+        self.session.execute_write_only(&CatCommand::ClearRit)
+    }
+
+    fn poll_unsolicited(&mut self) -> Psk31Result<Vec<RadioPush>> {
+        let mut pushes = Vec::new();
+        for response in self.session.poll_unsolicited()? {
+            match response {
+                CatResponse::FrequencyHz(hz) => {
+                    // A band change at the panel recalls band memory just as
+                    // BS; does, so track it for set_frequency's BS skip
+                    self.last_band_code = is_amateur_frequency(hz).then(|| band_select_code(hz));
+                    pushes.push(RadioPush::Frequency(Frequency::hz(hz as f64)));
+                }
+                CatResponse::Mode(mode) => pushes.push(RadioPush::Mode(mode)),
+                _ => {}
+            }
+        }
+        Ok(pushes)
+    }
 }
 
 /// Safety: auto-release PTT if the radio is dropped while transmitting.
@@ -333,6 +474,20 @@ mod tests {
         assert_eq!(log.lock().unwrap()[0], "MD0C;");
     }
 
+    #[test]
+    fn set_mode_normalizes_alias_spelling() {
+        let (mut radio, log) = make_radio(";");
+        radio.set_mode("usb-d").unwrap();
+        assert_eq!(log.lock().unwrap()[0], "MD0C;");
+    }
+
+    #[test]
+    fn set_mode_rejects_unknown_mode_without_sending() {
+        let (mut radio, log) = make_radio(";");
+        assert!(radio.set_mode("PSK31").is_err());
+        assert!(log.lock().unwrap().is_empty());
+    }
+
     #[test]
     fn get_mode_sends_md0_query() {
         let (mut radio, log) = make_radio("MD0C;");
@@ -383,10 +538,18 @@ mod tests {
 
     #[test]
     fn get_signal_strength_sends_sm0_query() {
-        let (mut radio, log) = make_radio("SM00015;");
+        let (mut radio, log) = make_radio("SM0051;");
         let level = radio.get_signal_strength().unwrap();
         assert_eq!(log.lock().unwrap()[0], "SM0;");
-        assert_eq!(level, 0.5); // 15/30
+        assert_eq!(level, 0.2); // 51/255
+    }
+
+    #[test]
+    fn s_meter_and_signal_strength_share_the_full_scale() {
+        let (mut radio, _) = make_radio("SM0255;");
+        assert_eq!(radio.get_signal_strength().unwrap(), 1.0);
+        let (mut radio, _) = make_radio("SM0255;");
+        assert_eq!(radio.read_s_meter().unwrap(), 255);
     }
 
     // --- Status (IF;) ---
@@ -434,6 +597,71 @@ mod tests {
         );
     }
 
+    // --- VFO-B ---
+
+    #[test]
+    fn set_frequency_b_sends_fb_without_band_select() {
+        let (mut radio, log) = make_radio(";");
+        radio.set_frequency_b(Frequency::hz(7_070_000.0)).unwrap();
+        assert_eq!(*log.lock().unwrap(), ["FB007070000;"]);
+    }
+
+    #[test]
+    fn set_frequency_b_rejects_non_amateur_before_sending() {
+        let (mut radio, log) = make_radio(";");
+        assert!(radio.set_frequency_b(Frequency::hz(10_000_000.0)).is_err());
+        assert!(log.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_frequency_b_sends_fb_query() {
+        let (mut radio, log) = make_radio("FB007070000;");
+        assert_eq!(radio.get_frequency_b().unwrap().as_hz(), 7_070_000.0);
+        assert_eq!(log.lock().unwrap()[0], "FB;");
+    }
+
+    #[test]
+    fn swap_vfo_forgets_the_band_for_the_next_set() {
+        let (mut radio, log) = make_radio(";");
+        radio.set_frequency(Frequency::hz(14_070_000.0)).unwrap();
+        radio.swap_vfo().unwrap();
+        radio.set_frequency(Frequency::hz(14_071_000.0)).unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["BS05;", "FA014070000;", "SV;", "BS05;", "FA014071000;"]
+        );
+    }
+
+    // --- RIT / XIT ---
+
+    #[test]
+    fn set_rit_offset_clears_then_steps() {
+        let (mut radio, log) = make_radio(";");
+        radio.set_rit_offset(-250).unwrap();
+        radio.set_rit_offset(0).unwrap();
+        assert_eq!(*log.lock().unwrap(), ["RC;", "RD0250;", "RC;"]);
+    }
+
+    #[test]
+    fn rit_and_xit_switches_send_rt_and_xt() {
+        let (mut radio, log) = make_radio(";");
+        radio.set_rit_enabled(true).unwrap();
+        radio.set_xit_enabled(false).unwrap();
+        assert_eq!(*log.lock().unwrap(), ["RT1;", "XT0;"]);
+    }
+
+    #[test]
+    fn set_rit_offset_rejects_out_of_range_before_sending() {
+        let (mut radio, log) = make_radio(";");
+        assert!(radio.set_rit_offset(10_000).is_err());
+        assert!(radio.set_rit_offset(-10_000).is_err());
+        assert!(radio.set_rit_offset(i32::MIN).is_err());
+        assert!(
+            log.lock().unwrap().is_empty(),
+            "no bytes should reach the wire for an out-of-range offset"
+        );
+    }
+
     // --- is_amateur_frequency: exact band edges ---
 
     #[test]
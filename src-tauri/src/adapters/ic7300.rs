@@ -0,0 +1,508 @@
+//! Icom IC-7300 radio adapter using CI-V
+//!
+//! CI-V is binary rather than text. Every frame is
+//! `FE FE <to> <from> <command> [sub-command] [data…] FD`; settings are
+//! answered with `FB` (OK) or `FA` (NG), reads with the command followed by
+//! its data. Over USB the radio can also echo our own frame back ("CI-V USB
+//! Echo Back"), so any frame not addressed to us is skipped.
+//!
+//! Frequencies are five bytes of BCD, least significant pair first. The DATA
+//! variants of USB/LSB/FM are the base mode plus the separate data-mode
+//! switch (`1A 06`).
+//!
+//! Covers what the modem needs: PTT, frequency, mode, RF power and the
+//! S-meter. RIT, split and auto-information use the trait defaults.
+//!
+//! Built `with_params`, frames are paced by the profile's command delay and
+//! a reply is waited for at most ten of its read timeouts, as on the FT-991A.
+
+use std::time::{Duration, Instant};
+
+use crate::domain::{
+    is_amateur_frequency, normalize_mode, Frequency, Psk31Error, Psk31Result, RadioStatus,
+    SerialParams, Vfo, DEFAULT_READ_TIMEOUT_MS,
+};
+use crate::ports::{Clock, RadioControl, SerialConnection, SystemClock};
+
+/// IC-7300 default CI-V address
+const RADIO_ADDR: u8 = 0x94;
+/// Our address as controller
+const CONTROLLER_ADDR: u8 = 0xE0;
+
+const PREAMBLE: u8 = 0xFE;
+const END_OF_MESSAGE: u8 = 0xFD;
+const OK: u8 = 0xFB;
+const NG: u8 = 0xFA;
+
+const CMD_READ_FREQ: u8 = 0x03;
+const CMD_READ_MODE: u8 = 0x04;
+const CMD_SET_FREQ: u8 = 0x05;
+const CMD_SET_MODE: u8 = 0x06;
+const CMD_LEVEL: u8 = 0x14;
+const SUB_RF_POWER: u8 = 0x0A;
+const CMD_METER: u8 = 0x15;
+const SUB_S_METER: u8 = 0x02;
+const CMD_EXTENDED: u8 = 0x1A;
+const SUB_DATA_MODE: u8 = 0x06;
+const CMD_TX: u8 = 0x1C;
+const SUB_PTT: u8 = 0x00;
+
+/// Chunk size for each serial read call
+const READ_CHUNK_SIZE: usize = 64;
+/// Max read attempts before giving up, as in `CatSession`
+const RESPONSE_TIMEOUT_READS: usize = 10;
+/// Longest input we'll accumulate looking for our reply
+const MAX_RESPONSE_BYTES: usize = 512;
+
+/// IC-7300 maximum RF power, which the 0–255 power level spans
+const MAX_POWER_WATTS: u32 = 100;
+
+/// Mode name ↔ (CI-V mode code, data switch, filter 1–3)
+const MODES: &[(&str, u8, bool, u8)] = &[
+    ("LSB", 0x00, false, 1),
+    ("USB", 0x01, false, 1),
+    ("AM", 0x02, false, 1),
+    ("AM-N", 0x02, false, 3),
+    ("CW", 0x03, false, 1),
+    ("RTTY-LSB", 0x04, false, 1),
+    ("FM", 0x05, false, 1),
+    ("FM-N", 0x05, false, 2),
+    ("CW-R", 0x07, false, 1),
+    ("RTTY-USB", 0x08, false, 1),
+    ("DATA-LSB", 0x00, true, 1),
+    ("DATA-USB", 0x01, true, 1),
+    ("DATA-FM", 0x05, true, 1),
+];
+
+/// Five-byte BCD frequency, 1 Hz digits first
+fn encode_frequency(hz: u64) -> [u8; 5] {
+    let mut bytes = [0u8; 5];
+    let mut rest = hz;
+    for byte in &mut bytes {
+        *byte = (((rest / 10 % 10) << 4) | (rest % 10)) as u8;
+        rest /= 100;
+    }
+    bytes
+}
+
+fn decode_bcd(bytes: &[u8]) -> Psk31Result<u64> {
+    bytes.iter().rev().try_fold(0u64, |acc, &b| {
+        let (hi, lo) = (b >> 4, b & 0x0F);
+        if hi > 9 || lo > 9 {
+            return Err(Psk31Error::Cat(format!("Invalid BCD byte {b:02X} from radio")));
+        }
+        Ok(acc * 100 + u64::from(hi) * 10 + u64::from(lo))
+    })
+}
+
+/// A 0–255 level as two BCD bytes, most significant first (`0255` → `02 55`)
+fn encode_level(level: u8) -> [u8; 2] {
+    let level = u16::from(level);
+    [(level / 100) as u8, (((level / 10 % 10) << 4) | (level % 10)) as u8]
+}
+
+fn decode_level(bytes: &[u8]) -> Psk31Result<u8> {
+    let level = decode_bcd(&bytes.iter().rev().copied().collect::<Vec<_>>())?;
+    u8::try_from(level).map_err(|_| Psk31Error::Cat(format!("Level {level} out of range")))
+}
+
+/// Icom IC-7300 adapter. Owns the serial connection and tracks TX state.
+pub struct Ic7300Radio {
+    serial: Box<dyn SerialConnection>,
+    is_transmitting: bool,
+    /// Minimum delay between frames
+    command_delay: Duration,
+    /// Longest wait for a reply to one frame
+    response_timeout: Duration,
+    last_command_time: Option<Instant>,
+    clock: Box<dyn Clock>,
+}
+
+impl Ic7300Radio {
+    /// Unpaced adapter with the default read timeout
+    pub fn new(serial: Box<dyn SerialConnection>) -> Self {
+        Self {
+            serial,
+            is_transmitting: false,
+            command_delay: Duration::ZERO,
+            response_timeout: Duration::from_millis(DEFAULT_READ_TIMEOUT_MS)
+                * RESPONSE_TIMEOUT_READS as u32,
+            last_command_time: None,
+            clock: Box::new(SystemClock),
+        }
+    }
+
+    /// Adapter using the command delay and read timeout in `params`.
+    pub fn with_params(serial: Box<dyn SerialConnection>, params: &SerialParams) -> Self {
+        Self::with_params_and_clock(serial, params, Box::new(SystemClock))
+    }
+
+    /// `with_params`, timing the delays against `clock`.
+    pub fn with_params_and_clock(
+        serial: Box<dyn SerialConnection>,
+        params: &SerialParams,
+        clock: Box<dyn Clock>,
+    ) -> Self {
+        let mut radio = Self::new(serial);
+        radio.command_delay = Duration::from_millis(params.command_delay_ms);
+        radio.response_timeout =
+            Duration::from_millis(params.read_timeout_ms) * RESPONSE_TIMEOUT_READS as u32;
+        radio.clock = clock;
+        radio
+    }
+
+    /// Minimum delay between CI-V frames on this connection
+    pub fn command_delay(&self) -> Duration {
+        self.command_delay
+    }
+
+    /// Longest wait for the radio to answer one frame
+    pub fn response_timeout(&self) -> Duration {
+        self.response_timeout
+    }
+
+    /// Sleep if needed to maintain the minimum inter-command delay.
+    fn ensure_command_delay(&self) {
+        if let Some(last) = self.last_command_time {
+            let elapsed = self.clock.now().saturating_duration_since(last);
+            if elapsed < self.command_delay {
+                self.clock.sleep(self.command_delay - elapsed);
+            }
+        }
+    }
+
+    /// Send `body` (command, sub-command, data) and return the radio's reply
+    /// with the addressing stripped: the command and its data, or `[OK]`.
+    fn transact(&mut self, body: &[u8]) -> Psk31Result<Vec<u8>> {
+        let mut frame = vec![PREAMBLE, PREAMBLE, RADIO_ADDR, CONTROLLER_ADDR];
+        frame.extend_from_slice(body);
+        frame.push(END_OF_MESSAGE);
+        log::debug!("CI-V TX: {frame:02X?}");
+        self.ensure_command_delay();
+        self.serial.write_all(&frame)?;
+
+        let reply = self.await_reply(body);
+        // Update timestamp even on error so the next frame still respects the delay
+        self.last_command_time = Some(self.clock.now());
+        reply
+    }
+
+    /// Read until the radio's reply to `body` arrives, for at most
+    /// `response_timeout`
+    fn await_reply(&mut self, body: &[u8]) -> Psk31Result<Vec<u8>> {
+        let deadline = self.clock.now() + self.response_timeout;
+        let mut buf = Vec::with_capacity(READ_CHUNK_SIZE);
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        for _ in 0..RESPONSE_TIMEOUT_READS {
+            if self.clock.now() > deadline {
+                break;
+            }
+            match self.serial.read(&mut chunk) {
+                Ok(n) if n > 0 => buf.extend_from_slice(&chunk[..n]),
+                _ => continue,
+            }
+            while let Some(end) = buf.iter().position(|&b| b == END_OF_MESSAGE) {
+                let frame: Vec<u8> = buf.drain(..=end).collect();
+                if let Some(reply) = reply_payload(&frame) {
+                    log::debug!("CI-V RX: {frame:02X?}");
+                    return match reply.as_slice() {
+                        [NG] => Err(Psk31Error::Cat(format!("Radio NAK for CI-V {body:02X?}"))),
+                        _ => Ok(reply),
+                    };
+                }
+            }
+            if buf.len() > MAX_RESPONSE_BYTES {
+                return Err(Psk31Error::Cat(
+                    "CI-V response too long — framing error, check the baud rate".into(),
+                ));
+            }
+        }
+        Err(Psk31Error::Cat(format!("CI-V {body:02X?}: no response from radio")))
+    }
+
+    /// A setting: the radio must answer OK
+    fn set(&mut self, body: &[u8]) -> Psk31Result<()> {
+        match self.transact(body)?.as_slice() {
+            [OK] => Ok(()),
+            other => Err(Psk31Error::Cat(format!("Unexpected CI-V reply {other:02X?}"))),
+        }
+    }
+
+    /// A read: the reply must repeat `command` (the command and sub-command
+    /// bytes); returns the data after it.
+    fn read(&mut self, command: &[u8]) -> Psk31Result<Vec<u8>> {
+        let reply = self.transact(command)?;
+        reply
+            .strip_prefix(command)
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| Psk31Error::Cat(format!("Unexpected CI-V reply {reply:02X?}")))
+    }
+
+    fn set_ptt(&mut self, on: bool) -> Psk31Result<()> {
+        self.set(&[CMD_TX, SUB_PTT, u8::from(on)])?;
+        self.is_transmitting = on;
+        Ok(())
+    }
+}
+
+/// The payload of a frame addressed from the radio to us, or `None` for our
+/// own echo, another station's traffic or a malformed frame
+fn reply_payload(frame: &[u8]) -> Option<Vec<u8>> {
+    let start = frame.iter().position(|&b| b != PREAMBLE)?;
+    match &frame[start..] {
+        [CONTROLLER_ADDR, RADIO_ADDR, payload @ .., END_OF_MESSAGE] if start >= 2 => {
+            Some(payload.to_vec())
+        }
+        _ => None,
+    }
+}
+
+impl RadioControl for Ic7300Radio {
+    fn ptt_on(&mut self) -> Psk31Result<()> {
+        self.set_ptt(true)
+    }
+
+    fn ptt_off(&mut self) -> Psk31Result<()> {
+        self.set_ptt(false)
+    }
+
+    fn is_transmitting(&self) -> bool {
+        self.is_transmitting
+    }
+
+    fn is_transmitting_remote(&mut self) -> Psk31Result<Option<bool>> {
+        let data = self.read(&[CMD_TX, SUB_PTT])?;
+        Ok(Some(data.first().is_some_and(|&b| b != 0)))
+    }
+
+    fn get_frequency(&mut self) -> Psk31Result<Frequency> {
+        let data = self.read(&[CMD_READ_FREQ])?;
+        if data.len() != 5 {
+            return Err(Psk31Error::Cat(format!("Invalid CI-V frequency {data:02X?}")));
+        }
+        Ok(Frequency::hz(decode_bcd(&data)? as f64))
+    }
+
+    fn set_frequency(&mut self, freq: Frequency) -> Psk31Result<()> {
+        let hz = freq.as_hz() as u64;
+        if !is_amateur_frequency(hz) {
+            return Err(Psk31Error::Cat(format!(
+                "Frequency {hz} Hz is outside US amateur bands"
+            )));
+        }
+        let mut body = vec![CMD_SET_FREQ];
+        body.extend_from_slice(&encode_frequency(hz));
+        self.set(&body)
+    }
+
+    fn get_mode(&mut self) -> Psk31Result<String> {
+        let mode = self.read(&[CMD_READ_MODE])?;
+        let data = self.read(&[CMD_EXTENDED, SUB_DATA_MODE])?;
+        let (code, filter) = match mode.as_slice() {
+            [code, filter, ..] => (*code, *filter),
+            [code] => (*code, 1),
+            [] => return Err(Psk31Error::Cat("Empty CI-V mode reply".into())),
+        };
+        let data_on = data.first().is_some_and(|&b| b != 0);
+        MODES
+            .iter()
+            .filter(|(_, c, d, _)| *c == code && *d == data_on)
+            .find(|(_, _, _, f)| *f == filter)
+            .or_else(|| MODES.iter().find(|(_, c, d, _)| *c == code && *d == data_on))
+            .map(|(name, ..)| name.to_string())
+            .ok_or_else(|| Psk31Error::Cat(format!("Unknown CI-V mode code {code:02X}")))
+    }
+
+    fn set_mode(&mut self, mode: &str) -> Psk31Result<()> {
+        let mode = normalize_mode(mode)?;
+        let &(_, code, data_on, filter) = MODES
+            .iter()
+            .find(|(name, ..)| *name == mode)
+            .ok_or_else(|| Psk31Error::Cat(format!("The IC-7300 has no {mode} mode")))?;
+        self.set(&[CMD_SET_MODE, code, filter])?;
+        self.set(&[CMD_EXTENDED, SUB_DATA_MODE, u8::from(data_on), u8::from(data_on)])
+    }
+
+    fn get_tx_power(&mut self) -> Psk31Result<u32> {
+        let level = decode_level(&self.read(&[CMD_LEVEL, SUB_RF_POWER])?)?;
+        Ok((u32::from(level) * MAX_POWER_WATTS + 127) / 255)
+    }
+
+    fn set_tx_power(&mut self, watts: u32) -> Psk31Result<()> {
+        if watts > MAX_POWER_WATTS {
+            return Err(Psk31Error::Cat(format!(
+                "TX power {watts} W exceeds IC-7300 maximum ({MAX_POWER_WATTS} W)"
+            )));
+        }
+        let level = ((watts * 255 + MAX_POWER_WATTS / 2) / MAX_POWER_WATTS) as u8;
+        let mut body = vec![CMD_LEVEL, SUB_RF_POWER];
+        body.extend_from_slice(&encode_level(level));
+        self.set(&body)
+    }
+
+    /// The 0–255 meter scale (S9+60 at the top), normalised
+    fn get_signal_strength(&mut self) -> Psk31Result<f32> {
+        let level = decode_level(&self.read(&[CMD_METER, SUB_S_METER])?)?;
+        Ok(f32::from(level) / 255.0)
+    }
+
+    /// No single status read on CI-V: frequency and mode separately
+    fn get_status(&mut self) -> Psk31Result<RadioStatus> {
+        Ok(RadioStatus {
+            frequency_hz: self.get_frequency()?.as_hz() as u64,
+            mode: self.get_mode()?,
+            is_transmitting: self.is_transmitting,
+            rit_offset_hz: 0,
+            rit_enabled: false,
+            split: false,
+            vfo: Vfo::A,
+        })
+    }
+}
+
+/// Safety: release PTT if the radio is dropped while transmitting, retrying
+/// like the FT-991A adapter does.
+impl Drop for Ic7300Radio {
+    fn drop(&mut self) {
+        if self.is_transmitting {
+            for delay_ms in [0, 10, 50] {
+                if delay_ms > 0 {
+                    std::thread::sleep(Duration::from_millis(delay_ms));
+                }
+                if self.set_ptt(false).is_ok() {
+                    return;
+                }
+            }
+            eprintln!("CRITICAL: Failed to release PTT on drop. Radio may still be transmitting!");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::FakeClock;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    /// Answers each frame written with the next scripted reply, echoing the
+    /// frame first like a radio with CI-V USB Echo Back on.
+    struct CivSerial {
+        written: Arc<Mutex<Vec<Vec<u8>>>>,
+        replies: VecDeque<Vec<u8>>,
+        pending: Vec<u8>,
+    }
+
+    impl SerialConnection for CivSerial {
+        fn write(&mut self, data: &[u8]) -> Psk31Result<usize> {
+            self.written.lock().unwrap().push(data.to_vec());
+            self.pending.extend_from_slice(data);
+            if let Some(body) = self.replies.pop_front() {
+                self.pending.extend([PREAMBLE, PREAMBLE, CONTROLLER_ADDR, RADIO_ADDR]);
+                self.pending.extend(body);
+                self.pending.push(END_OF_MESSAGE);
+            }
+            Ok(data.len())
+        }
+        fn read(&mut self, buf: &mut [u8]) -> Psk31Result<usize> {
+            let n = self.pending.len().min(buf.len());
+            buf[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            Ok(n)
+        }
+        fn close(&mut self) -> Psk31Result<()> {
+            Ok(())
+        }
+        fn is_connected(&self) -> bool {
+            true
+        }
+    }
+
+    fn make_radio(replies: &[&[u8]]) -> (Ic7300Radio, Arc<Mutex<Vec<Vec<u8>>>>) {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let serial = CivSerial {
+            written: written.clone(),
+            replies: replies.iter().map(|r| r.to_vec()).collect(),
+            pending: Vec::new(),
+        };
+        (Ic7300Radio::new(Box::new(serial)), written)
+    }
+
+    #[test]
+    fn frequency_is_little_endian_bcd() {
+        assert_eq!(encode_frequency(14_070_000), [0x00, 0x00, 0x07, 0x14, 0x00]);
+        assert_eq!(decode_bcd(&[0x00, 0x50, 0x03, 0x07, 0x00]).unwrap(), 7_035_000);
+        assert!(decode_bcd(&[0x0A]).is_err());
+    }
+
+    #[test]
+    fn set_frequency_sends_addressed_frame() {
+        let (mut radio, written) = make_radio(&[&[OK]]);
+        radio.set_frequency(Frequency::hz(7_070_000.0)).unwrap();
+        assert_eq!(
+            written.lock().unwrap()[0],
+            [0xFE, 0xFE, 0x94, 0xE0, 0x05, 0x00, 0x00, 0x07, 0x07, 0x00, 0xFD]
+        );
+    }
+
+    #[test]
+    fn set_frequency_rejects_non_amateur_before_sending() {
+        let (mut radio, written) = make_radio(&[]);
+        assert!(radio.set_frequency(Frequency::hz(10_000_000.0)).is_err());
+        assert!(written.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_frequency_skips_the_echo() {
+        let (mut radio, _) = make_radio(&[&[0x03, 0x00, 0x00, 0x07, 0x14, 0x00]]);
+        assert_eq!(radio.get_frequency().unwrap().as_hz(), 14_070_000.0);
+    }
+
+    #[test]
+    fn data_usb_is_usb_with_the_data_switch() {
+        let (mut radio, written) =
+            make_radio(&[&[OK], &[OK], &[0x04, 0x01, 0x01], &[0x1A, 0x06, 0x01, 0x01]]);
+        radio.set_mode("DATA-USB").unwrap();
+        let written = written.lock().unwrap().clone();
+        assert_eq!(written[0][4..], [0x06, 0x01, 0x01, 0xFD]);
+        assert_eq!(written[1][4..], [0x1A, 0x06, 0x01, 0x01, 0xFD]);
+        assert_eq!(radio.get_mode().unwrap(), "DATA-USB");
+    }
+
+    #[test]
+    fn tx_power_maps_watts_to_the_level_scale() {
+        let (mut radio, written) = make_radio(&[&[OK], &[0x14, 0x0A, 0x01, 0x28]]);
+        radio.set_tx_power(50).unwrap();
+        assert_eq!(written.lock().unwrap()[0][4..], [0x14, 0x0A, 0x01, 0x28, 0xFD]);
+        assert_eq!(radio.get_tx_power().unwrap(), 50);
+        assert!(radio.set_tx_power(101).is_err());
+    }
+
+    #[test]
+    fn ng_reply_is_an_error() {
+        let (mut radio, _) = make_radio(&[&[NG]]);
+        assert!(radio.ptt_on().is_err());
+        assert!(!radio.is_transmitting());
+    }
+
+    #[test]
+    fn serial_params_pace_frames_and_bound_the_reply_wait() {
+        let serial = CivSerial {
+            written: Arc::new(Mutex::new(Vec::new())),
+            replies: VecDeque::from([vec![OK], vec![OK]]),
+            pending: Vec::new(),
+        };
+        let params =
+            SerialParams { command_delay_ms: 150, read_timeout_ms: 250, ..SerialParams::new(19200) };
+        let clock = FakeClock::new();
+        let mut radio =
+            Ic7300Radio::with_params_and_clock(Box::new(serial), &params, Box::new(clock.clone()));
+        assert_eq!(radio.command_delay(), Duration::from_millis(150));
+        assert_eq!(radio.response_timeout(), Duration::from_millis(2500));
+
+        radio.ptt_on().unwrap();
+        assert!(clock.take_sleeps().is_empty());
+        radio.ptt_off().unwrap();
+        assert_eq!(clock.take_sleeps(), vec![Duration::from_millis(150)]);
+    }
+}
@@ -0,0 +1,124 @@
+//! Mock audio output for exercising TX without a soundcard.
+//!
+//! `MockAudioOutput` never opens a device. `start` hands the callback to a
+//! shared slot, and the test pulls buffers from it through a `MockPlayer`,
+//! standing in for the device clock. Everything pulled is captured in order,
+//! so a test can check exactly what TX would have sent to the rig.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::domain::{AudioDeviceInfo, AudioSample, Psk31Error, Psk31Result};
+use crate::ports::AudioOutput;
+
+type Callback = Box<dyn FnMut(&mut [AudioSample]) + Send + 'static>;
+
+#[derive(Default)]
+struct Playback {
+    callback: Option<Callback>,
+    captured: Vec<AudioSample>,
+}
+
+/// Output device that plays into a buffer, one `MockPlayer::pull` at a time
+pub struct MockAudioOutput {
+    devices: Vec<String>,
+    fail_start: bool,
+    playback: Arc<Mutex<Playback>>,
+}
+
+impl MockAudioOutput {
+    /// A single output device named "Mock Output"
+    pub fn new() -> Self {
+        Self::with_devices(&["Mock Output"])
+    }
+
+    /// Output devices to list, by name
+    pub fn with_devices(names: &[&str]) -> Self {
+        Self {
+            devices: names.iter().map(|n| n.to_string()).collect(),
+            fail_start: false,
+            playback: Arc::default(),
+        }
+    }
+
+    /// Fail every `start`, like a device that vanished after listing
+    pub fn failing(mut self) -> Self {
+        self.fail_start = true;
+        self
+    }
+
+    /// Handle for pulling buffers from the running callback, usable from
+    /// another thread while TX owns the output.
+    pub fn player(&self) -> MockPlayer {
+        MockPlayer { playback: self.playback.clone() }
+    }
+}
+
+impl Default for MockAudioOutput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioOutput for MockAudioOutput {
+    fn list_devices(&self) -> Psk31Result<Vec<AudioDeviceInfo>> {
+        Ok(self
+            .devices
+            .iter()
+            .map(|name| AudioDeviceInfo {
+                id: name.clone(),
+                name: name.clone(),
+                is_input: false,
+                is_output: true,
+                is_default: false,
+                output_unverified: false,
+            })
+            .collect())
+    }
+
+    fn start(&mut self, device_id: &str, callback: Callback) -> Psk31Result<()> {
+        if self.fail_start || !self.devices.iter().any(|d| d == device_id) {
+            return Err(Psk31Error::Audio(format!("Audio device not found: {device_id}")));
+        }
+        self.playback.lock().unwrap().callback = Some(callback);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Psk31Result<()> {
+        self.playback.lock().unwrap().callback = None;
+        Ok(())
+    }
+
+    fn is_running(&self) -> bool {
+        self.playback.lock().unwrap().callback.is_some()
+    }
+
+    /// Samples leave the mock the moment they are pulled
+    fn output_latency(&self) -> Option<Duration> {
+        Some(Duration::ZERO)
+    }
+}
+
+/// The "device" side of a `MockAudioOutput`
+#[derive(Clone)]
+pub struct MockPlayer {
+    playback: Arc<Mutex<Playback>>,
+}
+
+impl MockPlayer {
+    /// Run the callback for one buffer of `frames` samples and capture it.
+    /// Returns the buffer, or `None` if the output isn't running.
+    pub fn pull(&self, frames: usize) -> Option<Vec<AudioSample>> {
+        let mut playback = self.playback.lock().unwrap();
+        let callback = playback.callback.as_mut()?;
+        let mut buf = vec![0.0; frames];
+        callback(&mut buf);
+        playback.captured.extend_from_slice(&buf);
+        Some(buf)
+    }
+
+    /// Everything pulled so far, in order
+    pub fn captured(&self) -> Vec<AudioSample> {
+        self.playback.lock().unwrap().captured.clone()
+    }
+}
@@ -7,7 +7,8 @@
 //! Every RadioControl call is logged at INFO level so you can verify
 //! exactly what the UI would send to a real radio.
 
-use crate::domain::{Frequency, Psk31Result, RadioStatus};
+use crate::cat::RIT_MAX_OFFSET_HZ;
+use crate::domain::{normalize_mode, Frequency, Psk31Error, Psk31Result, RadioStatus, Vfo};
 use crate::ports::RadioControl;
 
 /// Default frequency: 20m PSK-31 calling frequency
@@ -19,9 +20,17 @@ const DEFAULT_TX_POWER_W: u32 = 25;
 
 pub struct MockRadio {
     frequency: f64,
+    frequency_b: f64,
     mode: String,
     tx_power: u32,
     is_transmitting: bool,
+    /// When set, frequencies snap to the nearest multiple, like a real rig's tuning step
+    tuning_step_hz: Option<f64>,
+    /// When set, reported by `get_tx_inhibit`
+    tx_inhibit: Option<String>,
+    rit_enabled: bool,
+    rit_offset_hz: i32,
+    split: bool,
 }
 
 impl MockRadio {
@@ -32,11 +41,29 @@ impl MockRadio {
         );
         Self {
             frequency: DEFAULT_FREQ_HZ,
+            frequency_b: DEFAULT_FREQ_HZ,
             mode: DEFAULT_MODE.to_string(),
             tx_power: DEFAULT_TX_POWER_W,
             is_transmitting: false,
+            tuning_step_hz: None,
+            tx_inhibit: None,
+            rit_enabled: false,
+            rit_offset_hz: 0,
+            split: false,
         }
     }
+
+    /// Round every frequency set to the nearest multiple of `step_hz`.
+    pub fn with_tuning_step(mut self, step_hz: f64) -> Self {
+        self.tuning_step_hz = Some(step_hz);
+        self
+    }
+
+    /// Report TX as inhibited for `reason`, like a rig that is scanning.
+    pub fn with_tx_inhibit(mut self, reason: &str) -> Self {
+        self.tx_inhibit = Some(reason.to_string());
+        self
+    }
 }
 
 impl RadioControl for MockRadio {
@@ -56,6 +83,11 @@ impl RadioControl for MockRadio {
         self.is_transmitting
     }
 
+    fn is_transmitting_remote(&mut self) -> Psk31Result<Option<bool>> {
+        log::info!("[MOCK RADIO] GET TX  → TX; → TX{}", u8::from(self.is_transmitting));
+        Ok(Some(self.is_transmitting))
+    }
+
     fn get_frequency(&mut self) -> Psk31Result<Frequency> {
         let hz = self.frequency as u64;
         log::info!(
@@ -71,7 +103,33 @@ impl RadioControl for MockRadio {
             "[MOCK RADIO] SET FREQ → FA{hz:011};  ({:.3} MHz)",
             freq.as_hz() / 1e6
         );
-        self.frequency = freq.as_hz();
+        self.frequency = match self.tuning_step_hz {
+            Some(step) => (freq.as_hz() / step).round() * step,
+            None => freq.as_hz(),
+        };
+        Ok(())
+    }
+
+    fn get_frequency_b(&mut self) -> Psk31Result<Frequency> {
+        log::info!("[MOCK RADIO] GET FREQ B → FB; → FB{:011};", self.frequency_b as u64);
+        Ok(Frequency::hz(self.frequency_b))
+    }
+
+    fn set_frequency_b(&mut self, freq: Frequency) -> Psk31Result<()> {
+        log::info!("[MOCK RADIO] SET FREQ B → FB{:011};", freq.as_hz() as u64);
+        self.frequency_b = freq.as_hz();
+        Ok(())
+    }
+
+    fn swap_vfo(&mut self) -> Psk31Result<()> {
+        log::info!("[MOCK RADIO] SWAP VFO → SV;");
+        std::mem::swap(&mut self.frequency, &mut self.frequency_b);
+        Ok(())
+    }
+
+    fn set_split(&mut self, on: bool) -> Psk31Result<()> {
+        log::info!("[MOCK RADIO] SET SPLIT → ST{};", u8::from(on));
+        self.split = on;
         Ok(())
     }
 
@@ -81,6 +139,7 @@ impl RadioControl for MockRadio {
     }
 
     fn set_mode(&mut self, mode: &str) -> Psk31Result<()> {
+        let mode = normalize_mode(mode)?;
         log::info!("[MOCK RADIO] SET MODE → MD0?; → {mode}");
         self.mode = mode.to_string();
         Ok(())
@@ -102,6 +161,16 @@ impl RadioControl for MockRadio {
         Ok(0.3) // S3 approximately
     }
 
+    fn read_s_meter(&mut self) -> Psk31Result<u8> {
+        log::info!("[MOCK RADIO] READ S-METER → SM0; → SM0065;  (S5)");
+        Ok(65)
+    }
+
+    fn read_swr(&mut self) -> Psk31Result<f32> {
+        log::info!("[MOCK RADIO] READ SWR → RM6; → RM6026;  (1.2)");
+        Ok(1.2)
+    }
+
     fn get_status(&mut self) -> Psk31Result<RadioStatus> {
         log::info!(
             "[MOCK RADIO] GET STATUS → IF; → {:.3} MHz, mode={}",
@@ -112,9 +181,41 @@ impl RadioControl for MockRadio {
             frequency_hz: self.frequency as u64,
             mode: self.mode.clone(),
             is_transmitting: self.is_transmitting,
-            rit_offset_hz: 0,
-            rit_enabled: false,
-            split: false,
+            rit_offset_hz: self.rit_offset_hz,
+            rit_enabled: self.rit_enabled,
+            split: self.split,
+            vfo: Vfo::A,
         })
     }
+
+    fn set_rit_enabled(&mut self, enabled: bool) -> Psk31Result<()> {
+        log::info!("[MOCK RADIO] SET RIT → RT{};", u8::from(enabled));
+        self.rit_enabled = enabled;
+        Ok(())
+    }
+
+    fn set_xit_enabled(&mut self, enabled: bool) -> Psk31Result<()> {
+        log::info!("[MOCK RADIO] SET XIT → XT{};", u8::from(enabled));
+        Ok(())
+    }
+
+    fn set_rit_offset(&mut self, offset_hz: i32) -> Psk31Result<()> {
+        if offset_hz.unsigned_abs() > RIT_MAX_OFFSET_HZ as u32 {
+            return Err(Psk31Error::Cat(format!("RIT offset {offset_hz} Hz is out of range")));
+        }
+        log::info!("[MOCK RADIO] SET RIT OFFSET → RC; then {offset_hz:+} Hz");
+        self.rit_offset_hz = offset_hz;
+        Ok(())
+    }
+
+    fn clear_rit(&mut self) -> Psk31Result<()> {
+        log::info!("[MOCK RADIO] CLEAR RIT → RC;");
+        self.rit_offset_hz = 0;
+        Ok(())
+    }
+
+    fn get_tx_inhibit(&mut self) -> Psk31Result<Option<String>> {
+        log::info!("[MOCK RADIO] GET TX INHIBIT → SC; → {:?}", self.tx_inhibit);
+        Ok(self.tx_inhibit.clone())
+    }
 }
@@ -3,4 +3,10 @@
 pub mod cpal_audio;
 pub mod serial_port;
 pub mod ft991a;
+pub mod ic7300;
 pub mod mock_radio;
+pub mod mock_audio;
+pub mod rigctld;
+pub mod radio_registry;
+pub mod wav_file;
+pub mod wav_input;
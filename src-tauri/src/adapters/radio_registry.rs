@@ -0,0 +1,148 @@
+//! Radio adapters by `Configuration.radio_type`
+//!
+//! `connect_serial` opens the port, then looks the profile's radio type up
+//! here to pick the adapter that drives it. Adding a radio means adding its
+//! adapter module and one entry to `RADIO_TYPES`.
+
+use crate::adapters::ft991a::Ft991aRadio;
+use crate::adapters::ic7300::Ic7300Radio;
+use crate::adapters::mock_radio::MockRadio;
+use crate::domain::{Psk31Error, Psk31Result, SerialParams};
+use crate::ports::{Clock, RadioControl, RadioFactory, SerialConnection};
+
+fn ft991a(
+    serial: Box<dyn SerialConnection>,
+    params: &SerialParams,
+    clock: Box<dyn Clock>,
+) -> Box<dyn RadioControl> {
+    Box::new(Ft991aRadio::with_params_and_clock(serial, params, clock))
+}
+
+fn ic7300(
+    serial: Box<dyn SerialConnection>,
+    params: &SerialParams,
+    clock: Box<dyn Clock>,
+) -> Box<dyn RadioControl> {
+    Box::new(Ic7300Radio::with_params_and_clock(serial, params, clock))
+}
+
+/// The mock never talks to the port it is handed
+fn mock(
+    _serial: Box<dyn SerialConnection>,
+    _params: &SerialParams,
+    _clock: Box<dyn Clock>,
+) -> Box<dyn RadioControl> {
+    Box::new(MockRadio::new())
+}
+
+/// Supported radio types and the adapter each one gets
+pub const RADIO_TYPES: &[(&str, RadioFactory)] =
+    &[("FT-991A", ft991a), ("IC-7300", ic7300), ("Mock", mock)];
+
+/// The factory for `radio_type`, or an "unsupported radio type" error
+pub fn radio_factory(radio_type: &str) -> Psk31Result<RadioFactory> {
+    RADIO_TYPES
+        .iter()
+        .find(|(name, _)| *name == radio_type)
+        .map(|(_, factory)| *factory)
+        .ok_or_else(|| {
+            let known: Vec<&str> = RADIO_TYPES.iter().map(|(name, _)| *name).collect();
+            Psk31Error::Config(format!(
+                "Unsupported radio type '{radio_type}' (supported: {})",
+                known.join(", ")
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::FakeClock;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Records what an adapter writes; never answers
+    struct SilentSerial(Arc<Mutex<Vec<u8>>>);
+
+    impl SerialConnection for SilentSerial {
+        fn write(&mut self, data: &[u8]) -> Psk31Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(data);
+            Ok(data.len())
+        }
+        fn read(&mut self, _buf: &mut [u8]) -> Psk31Result<usize> {
+            Ok(0)
+        }
+        fn close(&mut self) -> Psk31Result<()> {
+            Ok(())
+        }
+        fn is_connected(&self) -> bool {
+            true
+        }
+    }
+
+    /// Bytes the adapter built for `radio_type` sends for PTT on
+    fn ptt_on_wire(radio_type: &str) -> Vec<u8> {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let serial = Box::new(SilentSerial(written.clone()));
+        let mut radio = radio_factory(radio_type).unwrap()(
+            serial,
+            &SerialParams::new(38400),
+            Box::<FakeClock>::default(),
+        );
+        let _ = radio.ptt_on();
+        let wire = written.lock().unwrap().clone();
+        wire
+    }
+
+    #[test]
+    fn each_radio_type_gets_its_own_adapter() {
+        // FT-991A speaks text CAT, the IC-7300 binary CI-V, the mock nothing
+        assert_eq!(ptt_on_wire("FT-991A"), b"TX1;");
+        assert_eq!(ptt_on_wire("IC-7300"), [0xFE, 0xFE, 0x94, 0xE0, 0x1C, 0x00, 0x01, 0xFD]);
+        assert!(ptt_on_wire("Mock").is_empty());
+    }
+
+    /// Answers every CI-V frame with OK
+    struct CivOkSerial(Vec<u8>);
+
+    impl SerialConnection for CivOkSerial {
+        fn write(&mut self, data: &[u8]) -> Psk31Result<usize> {
+            self.0.extend_from_slice(&[0xFE, 0xFE, 0xE0, 0x94, 0xFB, 0xFD]);
+            Ok(data.len())
+        }
+        fn read(&mut self, buf: &mut [u8]) -> Psk31Result<usize> {
+            let n = self.0.len().min(buf.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0.drain(..n);
+            Ok(n)
+        }
+        fn close(&mut self) -> Psk31Result<()> {
+            Ok(())
+        }
+        fn is_connected(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn serial_params_set_ic7300_command_delay() {
+        let params = SerialParams { command_delay_ms: 120, ..SerialParams::new(19200) };
+        let clock = FakeClock::new();
+        let mut radio = radio_factory("IC-7300").unwrap()(
+            Box::new(CivOkSerial(Vec::new())),
+            &params,
+            Box::new(clock.clone()),
+        );
+
+        radio.ptt_on().unwrap();
+        assert!(clock.take_sleeps().is_empty());
+        radio.ptt_off().unwrap();
+        assert_eq!(clock.take_sleeps(), vec![Duration::from_millis(120)]);
+    }
+
+    #[test]
+    fn unknown_radio_type_is_unsupported() {
+        let err = radio_factory("TS-590").err().unwrap();
+        assert!(err.to_string().contains("Unsupported radio type 'TS-590'"), "{err}");
+    }
+}
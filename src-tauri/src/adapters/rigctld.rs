@@ -0,0 +1,352 @@
+//! Hamlib `rigctld` protocol handler (driving adapter)
+//!
+//! External tools such as fldigi (in rigctld mode) talk to a rig through
+//! Hamlib's line-oriented TCP protocol:
+//! - Send `f` (get frequency) → reply `14070000\n`
+//! - Send `M PKTUSB 0` (set mode) → reply `RPRT 0\n`
+//! - Send `\dump_state` on connect to learn the rig's capabilities
+//!
+//! This module translates those lines into `RadioControl` calls. The
+//! request/response logic is pure; `run_server` is the socket loop, and the
+//! caller decides how each line reaches the radio (and under which lock).
+
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::domain::{Frequency, Psk31Error};
+use crate::ports::RadioControl;
+
+/// Hamlib model number reported for the FT-991A (RIG_MODEL_FT991).
+pub const HAMLIB_MODEL_FT991: u32 = 1035;
+
+/// TCP port rigctld listens on by default, so clients find us unconfigured.
+pub const DEFAULT_RIGCTLD_PORT: u16 = 4532;
+
+/// How often the accept loop checks for a stop request
+const ACCEPT_POLL: Duration = Duration::from_millis(100);
+
+/// Hamlib mode name ↔ our mode name (as used in `cat::MODE_TABLE`).
+///
+/// The first entry for a given Hamlib name wins when mapping inbound `M`
+/// commands; the first entry for a given mode name wins for outbound `m` replies.
+pub const HAMLIB_MODE_TABLE: &[(&str, &str)] = &[
+    ("USB", "USB"),
+    ("LSB", "LSB"),
+    ("CW", "CW"),
+    ("CWR", "CW-R"),
+    ("AM", "AM"),
+    ("AM", "AM-N"),
+    ("FM", "FM"),
+    ("FM", "FM-N"),
+    ("RTTY", "RTTY-LSB"),
+    ("RTTYR", "RTTY-USB"),
+    ("PKTLSB", "DATA-LSB"),
+    ("PKTUSB", "DATA-USB"),
+    ("PKTFM", "DATA-FM"),
+];
+
+// Hamlib return codes (sent as `RPRT -n`)
+const RIG_OK: i32 = 0;
+const RIG_EINVAL: i32 = 1;
+const RIG_ENIMPL: i32 = 4;
+const RIG_EIO: i32 = 6;
+
+/// Passband (Hz) reported alongside the mode in `m` replies.
+const DEFAULT_PASSBAND_HZ: u32 = 3000;
+
+/// Map a Hamlib mode name to our mode name.
+pub fn mode_from_hamlib(name: &str) -> Option<&'static str> {
+    HAMLIB_MODE_TABLE
+        .iter()
+        .find(|(hamlib, _)| hamlib.eq_ignore_ascii_case(name))
+        .map(|(_, ours)| *ours)
+}
+
+/// Map our mode name to its Hamlib equivalent.
+pub fn mode_to_hamlib(name: &str) -> Option<&'static str> {
+    HAMLIB_MODE_TABLE
+        .iter()
+        .find(|(_, ours)| *ours == name)
+        .map(|(hamlib, _)| *hamlib)
+}
+
+fn rprt(code: i32) -> String {
+    format!("RPRT {}\n", -code)
+}
+
+fn rprt_result(result: Result<(), Psk31Error>) -> String {
+    match result {
+        Ok(()) => rprt(RIG_OK),
+        Err(e) => {
+            log::warn!("rigctld: radio error: {e}");
+            rprt(RIG_EIO)
+        }
+    }
+}
+
+/// Minimal `\dump_state` reply (protocol version 0).
+///
+/// Advertises HF–70cm RX/TX ranges for the modes we support and no
+/// functions/levels — enough for Hamlib clients to accept the connection.
+pub fn dump_state() -> String {
+    // Hamlib RIG_MODE_* bitmask for AM|CW|USB|LSB|RTTY|FM|CWR|RTTYR|PKTLSB|PKTUSB|PKTFM
+    const MODES: u32 = 0x1 | 0x2 | 0x4 | 0x8 | 0x10 | 0x20 | 0x80 | 0x100 | 0x400 | 0x800 | 0x1000;
+    // VFO_A | VFO_B
+    const VFOS: u32 = 0x1 | 0x2;
+
+    let mut s = String::new();
+    s.push_str("0\n"); // protocol version
+    s.push_str(&format!("{HAMLIB_MODEL_FT991}\n"));
+    s.push_str("2\n"); // ITU region
+    // RX ranges: start end modes low_power high_power vfo ant
+    s.push_str(&format!("30000 470000000 0x{MODES:x} -1 -1 0x{VFOS:x} 0x1\n"));
+    s.push_str("0 0 0 0 0 0 0\n");
+    // TX ranges (power in mW)
+    s.push_str(&format!("1800000 450000000 0x{MODES:x} 5000 100000 0x{VFOS:x} 0x1\n"));
+    s.push_str("0 0 0 0 0 0 0\n");
+    // Tuning steps: modes step
+    s.push_str(&format!("0x{MODES:x} 10\n"));
+    s.push_str("0 0\n");
+    // Filters: modes width
+    s.push_str(&format!("0x{MODES:x} {DEFAULT_PASSBAND_HZ}\n"));
+    s.push_str("0 0\n");
+    s.push_str("9999\n"); // max RIT
+    s.push_str("9999\n"); // max XIT
+    s.push_str("0\n"); // max IF shift
+    s.push_str("0\n"); // announces
+    s.push('\n'); // preamp list
+    s.push('\n'); // attenuator list
+    // has_get_func, has_set_func, has_get_level, has_set_level, has_get_parm, has_set_parm
+    for _ in 0..6 {
+        s.push_str("0x0\n");
+    }
+    s
+}
+
+/// Short capabilities reply for `1` / `\dump_caps`.
+fn dump_caps() -> String {
+    format!("Caps dump for model: {HAMLIB_MODEL_FT991}\nModel name:\tFT-991\nMfg name:\tYaesu\n")
+}
+
+/// Reply for a line that never reached the radio (e.g. none connected).
+pub fn radio_unavailable_reply() -> String {
+    rprt(RIG_EIO)
+}
+
+/// Handle one protocol line. Returns the reply to send, or `None` when the
+/// client asked to close the connection (`q`).
+pub fn handle_line(line: &str, radio: &mut dyn RadioControl) -> Option<String> {
+    let mut parts = line.split_whitespace();
+    let Some(cmd) = parts.next() else {
+        return Some(String::new());
+    };
+    let args: Vec<&str> = parts.collect();
+
+    let reply = match cmd {
+        "q" | "Q" => return None,
+        "\\dump_state" => dump_state(),
+        "1" | "\\dump_caps" => dump_caps(),
+        "\\chk_vfo" => "0\n".to_string(),
+        "v" | "\\get_vfo" => match radio.get_active_vfo() {
+            Ok(vfo) => format!("{}\n", vfo.unwrap_or_default().hamlib_name()),
+            Err(e) => rprt_result(Err(e)),
+        },
+        "f" | "\\get_freq" => match radio.get_frequency() {
+            Ok(f) => format!("{}\n", f.as_hz() as u64),
+            Err(e) => rprt_result(Err(e)),
+        },
+        "F" | "\\set_freq" => match args.first().and_then(|a| a.parse::<f64>().ok()) {
+            Some(hz) => rprt_result(radio.set_frequency(Frequency::hz(hz))),
+            None => rprt(RIG_EINVAL),
+        },
+        "m" | "\\get_mode" => match radio.get_mode() {
+            Ok(mode) => {
+                let hamlib = mode_to_hamlib(&mode).unwrap_or("USB");
+                format!("{hamlib}\n{DEFAULT_PASSBAND_HZ}\n")
+            }
+            Err(e) => rprt_result(Err(e)),
+        },
+        "M" | "\\set_mode" => match args.first().and_then(|m| mode_from_hamlib(m)) {
+            Some(mode) => rprt_result(radio.set_mode(mode)),
+            None => rprt(RIG_EINVAL),
+        },
+        "t" | "\\get_ptt" => format!("{}\n", u8::from(radio.is_transmitting())),
+        "T" | "\\set_ptt" => match args.first() {
+            Some(&"0") => rprt_result(radio.ptt_off()),
+            // 1 = PTT, 2 = mic, 3 = data; all key the rig over CAT
+            Some(&("1" | "2" | "3")) => rprt_result(radio.ptt_on()),
+            _ => rprt(RIG_EINVAL),
+        },
+        _ => rprt(RIG_ENIMPL),
+    };
+    Some(reply)
+}
+
+/// Serve one client connection until EOF or `q`.
+pub fn serve_connection<R: BufRead, W: Write>(
+    reader: R,
+    writer: W,
+    radio: &mut dyn RadioControl,
+) -> std::io::Result<()> {
+    serve_connection_with(reader, writer, |line| handle_line(line, radio))
+}
+
+/// Serve one client connection until EOF or `q`, passing each line to
+/// `handle` (normally `handle_line` against a radio it locks per line, so
+/// an idle client doesn't hold the radio).
+pub fn serve_connection_with<R: BufRead, W: Write>(
+    reader: R,
+    mut writer: W,
+    mut handle: impl FnMut(&str) -> Option<String>,
+) -> std::io::Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        match handle(line.trim()) {
+            Some(reply) => {
+                writer.write_all(reply.as_bytes())?;
+                writer.flush()?;
+            }
+            None => break,
+        }
+    }
+    Ok(())
+}
+
+/// Accept clients on `listener` until `abort` is set, serving each on its
+/// own thread with `serve_connection_with`. Stopping shuts down the open
+/// client sockets too, so their threads end with the server.
+pub fn run_server<H>(listener: TcpListener, abort: Arc<AtomicBool>, handle: H) -> std::io::Result<()>
+where
+    H: Fn(&str) -> Option<String> + Clone + Send + 'static,
+{
+    listener.set_nonblocking(true)?;
+    let mut clients: Vec<TcpStream> = Vec::new();
+    while !abort.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                log::info!("rigctld: client {peer} connected");
+                stream.set_nonblocking(false)?;
+                clients.push(stream.try_clone()?);
+                let handle = handle.clone();
+                thread::spawn(move || {
+                    let reader = match stream.try_clone() {
+                        Ok(s) => BufReader::new(s),
+                        Err(e) => {
+                            log::warn!("rigctld: client {peer}: {e}");
+                            return;
+                        }
+                    };
+                    if let Err(e) = serve_connection_with(reader, &stream, handle) {
+                        log::warn!("rigctld: client {peer}: {e}");
+                    }
+                    log::info!("rigctld: client {peer} disconnected");
+                });
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(ACCEPT_POLL),
+            Err(e) => log::warn!("rigctld: accept failed: {e}"),
+        }
+    }
+    for client in clients {
+        let _ = client.shutdown(std::net::Shutdown::Both);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::mock_radio::MockRadio;
+    use crate::cat::MODE_TABLE;
+
+    #[test]
+    fn dump_state_parses() {
+        let out = dump_state();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "0", "protocol version");
+        assert_eq!(lines[1].parse::<u32>().unwrap(), HAMLIB_MODEL_FT991);
+        assert_eq!(lines[2], "2", "ITU region");
+
+        // RX range, terminator, TX range, terminator — each 7 numeric fields
+        for line in &lines[3..7] {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            assert_eq!(fields.len(), 7, "range line: {line}");
+            for f in fields {
+                let ok = f.parse::<i64>().is_ok() || i64::from_str_radix(f.trim_start_matches("0x"), 16).is_ok();
+                assert!(ok, "non-numeric field {f:?} in {line}");
+            }
+        }
+        assert_eq!(lines[4], "0 0 0 0 0 0 0");
+        assert_eq!(lines[6], "0 0 0 0 0 0 0");
+        assert!(out.ends_with("0x0\n"));
+    }
+
+    #[test]
+    fn set_mode_usb_maps_to_radio_set_mode() {
+        let mut radio = MockRadio::new();
+        let reply = handle_line("M USB 0", &mut radio).unwrap();
+        assert_eq!(reply, "RPRT 0\n");
+        assert_eq!(radio.get_mode().unwrap(), "USB");
+    }
+
+    #[test]
+    fn get_mode_reports_hamlib_name() {
+        let mut radio = MockRadio::new(); // DATA-USB
+        assert_eq!(handle_line("m", &mut radio).unwrap(), "PKTUSB\n3000\n");
+    }
+
+    #[test]
+    fn frequency_round_trip() {
+        let mut radio = MockRadio::new();
+        assert_eq!(handle_line("F 7035000", &mut radio).unwrap(), "RPRT 0\n");
+        assert_eq!(handle_line("f", &mut radio).unwrap(), "7035000\n");
+    }
+
+    #[test]
+    fn get_vfo_defaults_to_vfo_a_when_adapter_cannot_tell() {
+        let mut radio = MockRadio::new();
+        assert_eq!(handle_line("v", &mut radio).unwrap(), "VFOA\n");
+    }
+
+    #[test]
+    fn unknown_mode_and_command_are_rejected() {
+        let mut radio = MockRadio::new();
+        assert_eq!(handle_line("M WFM 0", &mut radio).unwrap(), "RPRT -1\n");
+        assert_eq!(handle_line("\\get_level STRENGTH", &mut radio).unwrap(), "RPRT -4\n");
+    }
+
+    #[test]
+    fn set_ptt_rejects_unknown_argument_without_keying() {
+        let mut radio = MockRadio::new();
+        for arg in ["off", "on", "01x", "9", ""] {
+            assert_eq!(handle_line(&format!("T {arg}"), &mut radio).unwrap(), "RPRT -1\n");
+            assert!(!radio.is_transmitting(), "'T {arg}' keyed the radio");
+        }
+        assert_eq!(handle_line("T 1", &mut radio).unwrap(), "RPRT 0\n");
+        assert!(radio.is_transmitting());
+        assert_eq!(handle_line("T 0", &mut radio).unwrap(), "RPRT 0\n");
+        assert!(!radio.is_transmitting());
+    }
+
+    #[test]
+    fn hamlib_table_uses_mode_table_names() {
+        for (_, ours) in HAMLIB_MODE_TABLE {
+            assert!(
+                MODE_TABLE.iter().any(|(_, name)| name == ours),
+                "{ours} is not in MODE_TABLE"
+            );
+        }
+    }
+
+    #[test]
+    fn serve_connection_stops_on_quit() {
+        let mut radio = MockRadio::new();
+        let input = b"f\nq\nf\n";
+        let mut out = Vec::new();
+        serve_connection(&input[..], &mut out, &mut radio).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "14070000\n");
+    }
+}
@@ -6,8 +6,8 @@
 
 use std::time::Duration;
 
-use crate::domain::{Psk31Error, Psk31Result, SerialPortInfo};
-use crate::ports::{SerialConnection, SerialFactory};
+use crate::domain::{Psk31Error, Psk31Result, SerialParams, SerialPortInfo};
+use crate::ports::{OpenedSerial, SerialConnection, SerialFactory};
 
 /// Return a human-readable device label for a known USB VID:PID pair.
 fn known_device(vid: u16, pid: u16) -> Option<&'static str> {
@@ -21,6 +21,22 @@ fn known_device(vid: u16, pid: u16) -> Option<&'static str> {
     }
 }
 
+/// Windows only opens `COM10` and above through the device namespace
+/// (`\\.\COM10`); `COM1`–`COM9`, already-prefixed and non-COM names
+/// (e.g. `/dev/ttyUSB0`) pass through unchanged.
+#[cfg(any(windows, test))]
+fn normalize_port_name(port: &str) -> String {
+    let is_high_com = port
+        .get(..3)
+        .is_some_and(|p| p.eq_ignore_ascii_case("COM"))
+        && port[3..].parse::<u32>().is_ok_and(|n| n >= 10);
+    if is_high_com {
+        format!(r"\\.\{port}")
+    } else {
+        port.to_string()
+    }
+}
+
 /// Zero-sized factory for creating serial port connections.
 pub struct SerialPortFactory;
 
@@ -52,8 +68,17 @@ impl SerialFactory for SerialPortFactory {
     }
 
     fn open(port: &str, baud_rate: u32) -> Psk31Result<Box<dyn SerialConnection>> {
-        let serial = serialport::new(port, baud_rate)
-            .timeout(Duration::from_millis(100))
+        Self::open_with_params(port, &SerialParams::new(baud_rate)).map(|o| o.connection)
+    }
+
+    fn open_with_params(port: &str, params: &SerialParams) -> Psk31Result<OpenedSerial> {
+        #[cfg(windows)]
+        let device = normalize_port_name(port);
+        #[cfg(not(windows))]
+        let device = port;
+
+        let serial = serialport::new(device, params.baud_rate)
+            .timeout(Duration::from_millis(params.read_timeout_ms))
             .data_bits(serialport::DataBits::Eight)
             .stop_bits(serialport::StopBits::One)
             .parity(serialport::Parity::None)
@@ -61,10 +86,21 @@ impl SerialFactory for SerialPortFactory {
             .open()
             .map_err(|e| Psk31Error::Serial(format!("Failed to open {port}: {e}")))?;
 
-        Ok(Box::new(SerialPortConnection {
-            port: serial,
-            connected: true,
-        }))
+        // Read back what the driver applied; some silently substitute rates
+        let baud_rate = serial.baud_rate().unwrap_or_else(|e| {
+            log::warn!("Cannot read back baud rate of {port}: {e}");
+            params.baud_rate
+        });
+        let read_timeout = serial.timeout();
+
+        Ok(OpenedSerial {
+            connection: Box::new(SerialPortConnection {
+                port: serial,
+                connected: true,
+            }),
+            baud_rate,
+            read_timeout,
+        })
     }
 }
 
@@ -89,6 +125,13 @@ impl SerialConnection for SerialPortConnection {
             .map_err(|e| Psk31Error::Serial(format!("Read failed: {e}")))
     }
 
+    fn bytes_available(&self) -> Psk31Result<usize> {
+        self.port
+            .bytes_to_read()
+            .map(|n| n as usize)
+            .map_err(|e| Psk31Error::Serial(format!("Read failed: {e}")))
+    }
+
     fn close(&mut self) -> Psk31Result<()> {
         self.connected = false;
         Ok(())
@@ -98,3 +141,36 @@ impl SerialConnection for SerialPortConnection {
         self.connected
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_prefixes_only_high_com_ports() {
+        assert_eq!(normalize_port_name("COM3"), "COM3");
+        assert_eq!(normalize_port_name("COM10"), r"\\.\COM10");
+        assert_eq!(normalize_port_name(r"\\.\COM12"), r"\\.\COM12");
+        assert_eq!(normalize_port_name("/dev/ttyUSB0"), "/dev/ttyUSB0");
+    }
+
+    /// Open the far end of a pseudo-terminal pair through the factory, so
+    /// the driver actually applies (and reports) the settings.
+    #[cfg(unix)]
+    #[test]
+    fn opened_params_match_request() {
+        let (_master, slave) = serialport::TTYPort::pair().expect("pty pair");
+        let name = serialport::SerialPort::name(&slave).expect("pty name");
+
+        let params = SerialParams { read_timeout_ms: 250, ..SerialParams::new(19200) };
+        let opened = SerialPortFactory::open_with_params(&name, &params).unwrap();
+        assert_eq!(opened.baud_rate, 19200);
+        assert_eq!(opened.read_timeout, Duration::from_millis(250));
+        assert!(opened.mismatches(&params).is_empty());
+
+        let asked_faster = SerialParams { baud_rate: 38400, ..params };
+        let mismatches = opened.mismatches(&asked_faster);
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches[0].contains("19200 baud"), "{mismatches:?}");
+    }
+}
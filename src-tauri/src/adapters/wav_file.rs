@@ -0,0 +1,443 @@
+//! WAV file reading and writing
+//!
+//! Writes mono audio through `hound`, either 16-bit PCM or 32-bit IEEE
+//! float. Used to export encoder output as test vectors for other PSK
+//! software.
+//!
+//! Decode snapshots add a `json` chunk after the samples carrying the
+//! decoder settings; WAV readers skip chunks they don't know.
+//!
+//! `WavStream` reads any integer or 32-bit float WAV back through `hound` a
+//! block at a time, mixed down to mono, for decoding recordings offline.
+//! Streaming recorders that never fill in the `data` size (0xFFFFFFFF) are
+//! read until the file ends.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Chain, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+
+use crate::domain::{AudioSample, Psk31Error, Psk31Result, WavSampleFormat};
+
+/// Chunk ID of the JSON metadata chunk
+pub const METADATA_CHUNK_ID: &[u8; 4] = b"json";
+
+/// Frames per `read_block` when reading a whole file
+const READ_BLOCK_FRAMES: usize = 4096;
+
+/// Room for the RIFF, `fmt ` and `data` headers, extensible format included
+const MAX_HEADER_LEN: u64 = 80;
+
+fn wav_spec(sample_rate: u32, format: WavSampleFormat) -> WavSpec {
+    let (bits_per_sample, sample_format) = match format {
+        WavSampleFormat::Int16 => (16, SampleFormat::Int),
+        WavSampleFormat::Float32 => (32, SampleFormat::Float),
+    };
+    WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample,
+        sample_format,
+    }
+}
+
+/// Fail up front if the file would outgrow the 32-bit RIFF chunk sizes.
+fn check_length(
+    sample_count: usize,
+    format: WavSampleFormat,
+    metadata_len: usize,
+) -> std::io::Result<()> {
+    let bytes_per_sample = match format {
+        WavSampleFormat::Int16 => 2,
+        WavSampleFormat::Float32 => 4,
+    };
+    let total = (sample_count as u64)
+        .saturating_mul(bytes_per_sample)
+        .saturating_add(metadata_len as u64 + 9)
+        .saturating_add(MAX_HEADER_LEN);
+    if total > u64::from(u32::MAX) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{sample_count} samples are too long for a WAV file"),
+        ));
+    }
+    Ok(())
+}
+
+/// Write mono `samples` as a WAV stream.
+pub fn write_wav<W: Write + Seek>(
+    writer: W,
+    samples: &[AudioSample],
+    sample_rate: u32,
+    format: WavSampleFormat,
+) -> hound::Result<()> {
+    check_length(samples.len(), format, 0)?;
+    let mut wav = WavWriter::new(writer, wav_spec(sample_rate, format))?;
+    for &s in samples {
+        match format {
+            WavSampleFormat::Int16 => {
+                wav.write_sample((s.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16)?
+            }
+            WavSampleFormat::Float32 => wav.write_sample(s)?,
+        }
+    }
+    wav.finalize()
+}
+
+/// Write mono `samples` as a WAV stream followed by a `json` chunk.
+pub fn write_wav_with_metadata<W: Write + Seek>(
+    mut writer: W,
+    samples: &[AudioSample],
+    sample_rate: u32,
+    format: WavSampleFormat,
+    metadata_json: &str,
+) -> hound::Result<()> {
+    let metadata = metadata_json.as_bytes();
+    check_length(samples.len(), format, metadata.len())?;
+    write_wav(&mut writer, samples, sample_rate, format)?;
+
+    writer.seek(SeekFrom::End(0))?;
+    writer.write_all(METADATA_CHUNK_ID)?;
+    writer.write_all(&(metadata.len() as u32).to_le_bytes())?;
+    writer.write_all(metadata)?;
+    // Chunks are word-aligned: odd-length bodies get a pad byte
+    if metadata.len() % 2 == 1 {
+        writer.write_all(&[0])?;
+    }
+    // hound sized the RIFF chunk for the samples alone
+    let riff_len = writer.stream_position()? - 8;
+    writer.seek(SeekFrom::Start(4))?;
+    writer.write_all(&(riff_len as u32).to_le_bytes())?;
+    writer.seek(SeekFrom::End(0))?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write mono `samples` to a WAV file at `path`, replacing any existing file.
+pub fn write_wav_file(
+    path: &Path,
+    samples: &[AudioSample],
+    sample_rate: u32,
+    format: WavSampleFormat,
+) -> Psk31Result<()> {
+    let file = File::create(path)
+        .map_err(|e| Psk31Error::Audio(format!("Failed to create {}: {e}", path.display())))?;
+    write_wav(BufWriter::new(file), samples, sample_rate, format)
+        .map_err(|e| Psk31Error::Audio(format!("Failed to write {}: {e}", path.display())))
+}
+
+/// Like `write_wav_file`, with a `json` metadata chunk.
+pub fn write_wav_file_with_metadata(
+    path: &Path,
+    samples: &[AudioSample],
+    sample_rate: u32,
+    format: WavSampleFormat,
+    metadata_json: &str,
+) -> Psk31Result<()> {
+    let file = File::create(path)
+        .map_err(|e| Psk31Error::Audio(format!("Failed to create {}: {e}", path.display())))?;
+    write_wav_with_metadata(BufWriter::new(file), samples, sample_rate, format, metadata_json)
+        .map_err(|e| Psk31Error::Audio(format!("Failed to write {}: {e}", path.display())))
+}
+
+/// `data` chunk size left by recorders that stream without seeking back
+const UNKNOWN_DATA_LEN: u32 = u32::MAX;
+
+/// Read the RIFF header and every chunk before the samples, replacing a
+/// streaming recorder's unknown `data` size with the largest whole-frame
+/// size so `hound` reads on until the file ends. Returns the bytes read and
+/// whether the size was unknown.
+fn read_up_to_data<R: Read>(reader: &mut R) -> std::io::Result<(Vec<u8>, bool)> {
+    let mut header = vec![0; 12];
+    reader.read_exact(&mut header)?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        // Let hound report it
+        return Ok((header, false));
+    }
+    let mut block_align = 1;
+    loop {
+        let mut chunk = [0; 8];
+        reader.read_exact(&mut chunk)?;
+        let len = u32::from_le_bytes(chunk[4..8].try_into().unwrap());
+        if &chunk[0..4] == b"data" {
+            let streamed = len == UNKNOWN_DATA_LEN;
+            if streamed {
+                let len = len - len % block_align;
+                chunk[4..8].copy_from_slice(&len.to_le_bytes());
+            }
+            header.extend_from_slice(&chunk);
+            return Ok((header, streamed));
+        }
+        header.extend_from_slice(&chunk);
+        let body = header.len();
+        reader.take(u64::from(len)).read_to_end(&mut header)?;
+        if header.len() - body < len as usize {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        if &chunk[0..4] == b"fmt " && len >= 14 {
+            let align = u16::from_le_bytes([header[body + 12], header[body + 13]]);
+            block_align = u32::from(align).max(1);
+        }
+    }
+}
+
+/// Notes when the wrapped reader runs dry, so the end of a streamed
+/// recording can be told apart from a read error
+struct EofFlag<R> {
+    inner: R,
+    eof: Arc<AtomicBool>,
+}
+
+impl<R: Read> Read for EofFlag<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n == 0 && !buf.is_empty() {
+            self.eof.store(true, Ordering::Relaxed);
+        }
+        Ok(n)
+    }
+}
+
+/// WAV audio read a block at a time, mixed down to mono `f32` in ±1
+pub struct WavStream<R: Read> {
+    reader: WavReader<Chain<Cursor<Vec<u8>>, EofFlag<R>>>,
+    /// The `data` size was unknown: the samples end wherever the file does
+    streamed: bool,
+    eof: Arc<AtomicBool>,
+    /// Scale from an integer sample to ±1
+    int_scale: f32,
+}
+
+impl<R: Read> WavStream<R> {
+    /// Read the header of a WAV stream. Any channel count is accepted;
+    /// samples may be 8–32-bit integer or 32-bit float.
+    pub fn new(mut reader: R) -> Psk31Result<Self> {
+        let invalid = |msg: String| Psk31Error::Audio(format!("Not a supported WAV file: {msg}"));
+        let (header, streamed) =
+            read_up_to_data(&mut reader).map_err(|e| invalid(e.to_string()))?;
+        let eof = Arc::new(AtomicBool::new(false));
+        let reader = EofFlag {
+            inner: reader,
+            eof: eof.clone(),
+        };
+        let reader = WavReader::new(Cursor::new(header).chain(reader))
+            .map_err(|e| invalid(e.to_string()))?;
+        let spec = reader.spec();
+        match (spec.sample_format, spec.bits_per_sample) {
+            (SampleFormat::Int, 8..=32) | (SampleFormat::Float, 32) => {}
+            (format, bits) => return Err(invalid(format!("{format:?}, {bits}-bit"))),
+        }
+        Ok(Self {
+            reader,
+            streamed,
+            eof,
+            int_scale: 1.0 / ((1u64 << (spec.bits_per_sample - 1)) - 1) as f32,
+        })
+    }
+
+    /// Sample rate from the `fmt ` chunk
+    pub fn sample_rate(&self) -> u32 {
+        self.reader.spec().sample_rate
+    }
+
+    fn next_sample(&mut self) -> Option<hound::Result<f32>> {
+        let sample = match self.reader.spec().sample_format {
+            SampleFormat::Float => self.reader.samples::<f32>().next(),
+            SampleFormat::Int => {
+                let scale = self.int_scale;
+                self.reader
+                    .samples::<i32>()
+                    .next()
+                    .map(|s| s.map(|v| v as f32 * scale))
+            }
+        };
+        match sample {
+            Some(Err(_)) if self.streamed && self.eof.load(Ordering::Relaxed) => None,
+            sample => sample,
+        }
+    }
+
+    /// Read up to `max` frames, each the mean of its channels. Returns an
+    /// empty block at end of file.
+    pub fn read_block(&mut self, max: usize) -> Psk31Result<Vec<AudioSample>> {
+        let channels = usize::from(self.reader.spec().channels);
+        let mut block = Vec::with_capacity(max);
+        'frames: while block.len() < max {
+            let mut sum = 0.0;
+            for _ in 0..channels {
+                match self.next_sample() {
+                    Some(sample) => {
+                        sum += sample
+                            .map_err(|e| Psk31Error::Audio(format!("Failed to read WAV: {e}")))?
+                    }
+                    // A streamed recording can stop mid-frame
+                    None => break 'frames,
+                }
+            }
+            block.push(sum / channels as f32);
+        }
+        Ok(block)
+    }
+}
+
+/// Open a WAV file for streaming.
+pub fn open_wav_file(path: &Path) -> Psk31Result<WavStream<BufReader<File>>> {
+    let file = File::open(path)
+        .map_err(|e| Psk31Error::Audio(format!("Failed to open {}: {e}", path.display())))?;
+    WavStream::new(BufReader::new(file))
+}
+
+/// Read a whole WAV stream. Returns the sample rate and the samples mixed
+/// down to mono.
+pub fn read_wav<R: Read>(reader: R) -> Psk31Result<(u32, Vec<AudioSample>)> {
+    let mut stream = WavStream::new(reader)?;
+    let mut samples = Vec::new();
+    loop {
+        let block = stream.read_block(READ_BLOCK_FRAMES)?;
+        if block.is_empty() {
+            return Ok((stream.sample_rate(), samples));
+        }
+        samples.extend(block);
+    }
+}
+
+/// Read a whole WAV file written by `write_wav_file` (or any other tool).
+pub fn read_wav_file(path: &Path) -> Psk31Result<(u32, Vec<AudioSample>)> {
+    let file = File::open(path)
+        .map_err(|e| Psk31Error::Audio(format!("Failed to open {}: {e}", path.display())))?;
+    read_wav(BufReader::new(file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16_at(b: &[u8], i: usize) -> u16 {
+        u16::from_le_bytes([b[i], b[i + 1]])
+    }
+
+    fn u32_at(b: &[u8], i: usize) -> u32 {
+        u32::from_le_bytes(b[i..i + 4].try_into().unwrap())
+    }
+
+    fn written(samples: &[AudioSample], sample_rate: u32, format: WavSampleFormat) -> Vec<u8> {
+        let mut out = Cursor::new(Vec::new());
+        write_wav(&mut out, samples, sample_rate, format).unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn int16_header_and_samples() {
+        let out = written(&[0.0, 1.0, -1.0], 48000, WavSampleFormat::Int16);
+
+        assert_eq!(out.len(), 44 + 6);
+        assert_eq!(&out[0..4], b"RIFF");
+        assert_eq!(u32_at(&out, 4), 36 + 6);
+        assert_eq!(&out[8..16], b"WAVEfmt ");
+        assert_eq!(u16_at(&out, 20), 1); // PCM
+        assert_eq!(u16_at(&out, 22), 1);
+        assert_eq!(u32_at(&out, 24), 48000);
+        assert_eq!(u32_at(&out, 28), 96000);
+        assert_eq!(u16_at(&out, 34), 16);
+        assert_eq!(&out[36..40], b"data");
+        assert_eq!(u32_at(&out, 40), 6);
+        assert_eq!(u16_at(&out, 44) as i16, 0);
+        assert_eq!(u16_at(&out, 46) as i16, i16::MAX);
+        assert_eq!(u16_at(&out, 48) as i16, -i16::MAX);
+    }
+
+    #[test]
+    fn float32_keeps_samples_exact() {
+        let out = written(&[0.25, -0.5], 8000, WavSampleFormat::Float32);
+
+        let reader = WavReader::new(out.as_slice()).unwrap();
+        let spec = reader.spec();
+        assert_eq!(spec.sample_format, SampleFormat::Float);
+        assert_eq!(spec.bits_per_sample, 32);
+        let samples: Vec<f32> = reader.into_samples().map(Result::unwrap).collect();
+        assert_eq!(samples, [0.25, -0.5]);
+    }
+
+    #[test]
+    fn metadata_chunk_follows_data_and_is_padded() {
+        let mut out = Cursor::new(Vec::new());
+        write_wav_with_metadata(&mut out, &[0.5], 8000, WavSampleFormat::Int16, "{\"a\":1}")
+            .unwrap();
+        let out = out.into_inner();
+
+        // 44 header + 2 data + 8 chunk header + 7 body + 1 pad
+        assert_eq!(out.len(), 62);
+        assert_eq!(u32_at(&out, 4) as usize, out.len() - 8);
+        assert_eq!(u32_at(&out, 40), 2);
+        assert_eq!(&out[46..50], METADATA_CHUNK_ID);
+        assert_eq!(u32_at(&out, 50), 7);
+        assert_eq!(&out[54..61], b"{\"a\":1}");
+    }
+
+    #[test]
+    fn rejects_more_samples_than_a_wav_can_hold() {
+        assert!(check_length(1 << 20, WavSampleFormat::Float32, 100).is_ok());
+        assert!(check_length(u32::MAX as usize / 4, WavSampleFormat::Float32, 0).is_err());
+        assert!(check_length(u32::MAX as usize / 2 - 40, WavSampleFormat::Int16, 0).is_err());
+        assert!(check_length(usize::MAX, WavSampleFormat::Int16, 0).is_err());
+    }
+
+    #[test]
+    fn read_returns_what_was_written() {
+        let samples = [0.0, 0.5, -0.25, 1.0];
+        for format in [WavSampleFormat::Int16, WavSampleFormat::Float32] {
+            let mut out = Cursor::new(Vec::new());
+            write_wav_with_metadata(&mut out, &samples, 8000, format, "{}").unwrap();
+            let (rate, read) = read_wav(out.get_ref().as_slice()).unwrap();
+            assert_eq!(rate, 8000);
+            assert_eq!(read.len(), samples.len());
+            for (r, s) in read.iter().zip(samples) {
+                assert!((r - s).abs() < 1e-4, "{format:?}: {r} vs {s}");
+            }
+        }
+    }
+
+    #[test]
+    fn read_mixes_channels_down_to_mono() {
+        let spec = WavSpec {
+            channels: 2,
+            sample_rate: 48000,
+            bits_per_sample: 24,
+            sample_format: SampleFormat::Int,
+        };
+        let mut out = Cursor::new(Vec::new());
+        let mut wav = WavWriter::new(&mut out, spec).unwrap();
+        for (left, right) in [(0x7F_FFFF, 0), (0x7F_FFFF, -0x7F_FFFF)] {
+            wav.write_sample(left).unwrap();
+            wav.write_sample(right).unwrap();
+        }
+        wav.finalize().unwrap();
+
+        let (rate, read) = read_wav(out.get_ref().as_slice()).unwrap();
+        assert_eq!(rate, 48000);
+        assert_eq!(read, [0.5, 0.0]);
+    }
+
+    #[test]
+    fn reads_a_streamed_file_until_it_ends() {
+        let mut out = written(&[0.5; 5], 48000, WavSampleFormat::Int16);
+        out[40..44].copy_from_slice(&UNKNOWN_DATA_LEN.to_le_bytes());
+        // The recorder stopped halfway through a sample
+        out.push(0);
+
+        let mut stream = WavStream::new(out.as_slice()).unwrap();
+        assert_eq!(stream.read_block(3).unwrap().len(), 3);
+        assert_eq!(stream.read_block(3).unwrap().len(), 2);
+        assert!(stream.read_block(3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn read_rejects_truncated_files_and_garbage() {
+        let out = written(&[0.5; 4], 48000, WavSampleFormat::Int16);
+        assert!(read_wav(&out[..out.len() - 2]).is_err());
+        assert!(read_wav(&b"not a wav file"[..]).is_err());
+    }
+}
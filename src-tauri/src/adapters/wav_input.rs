@@ -0,0 +1,201 @@
+//! WAV file audio input
+//!
+//! `WavFileInput` plays a recording into the RX chain in place of a
+//! soundcard. The "device id" passed to `start` is the file path. A worker
+//! thread reads the file in fixed chunks and feeds each to the callback,
+//! either paced at roughly real time or as fast as the callback takes them,
+//! and the input stops by itself at end of file.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::adapters::wav_file::open_wav_file;
+use crate::domain::{AudioDeviceInfo, AudioSample, Psk31Error, Psk31Result};
+use crate::ports::AudioInput;
+
+/// Rate the RX chain runs at; files at other rates are rejected
+pub const WAV_INPUT_SAMPLE_RATE: u32 = 48000;
+
+/// Samples per callback, 10 ms at 48 kHz
+const CHUNK_SAMPLES: usize = 480;
+
+/// Audio input that reads a 48 kHz WAV file
+pub struct WavFileInput {
+    realtime: bool,
+    running: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl WavFileInput {
+    /// `realtime` paces chunks at the file's sample rate; otherwise the file
+    /// is streamed as fast as the callback consumes it.
+    pub fn new(realtime: bool) -> Self {
+        Self {
+            realtime,
+            running: Arc::new(AtomicBool::new(false)),
+            worker: None,
+        }
+    }
+}
+
+impl AudioInput for WavFileInput {
+    /// Files aren't enumerable devices
+    fn list_devices(&self) -> Psk31Result<Vec<AudioDeviceInfo>> {
+        Ok(Vec::new())
+    }
+
+    fn start(
+        &mut self,
+        device_id: &str,
+        mut callback: Box<dyn FnMut(&[AudioSample]) + Send + 'static>,
+    ) -> Psk31Result<()> {
+        if self.running.load(Ordering::SeqCst) {
+            return Err(Psk31Error::Audio("Audio input already running".into()));
+        }
+        // A previous file that ran to EOF leaves a finished worker behind
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+
+        let mut wav = open_wav_file(Path::new(device_id))?;
+        let rate = wav.sample_rate();
+        if rate != WAV_INPUT_SAMPLE_RATE {
+            return Err(Psk31Error::Audio(format!(
+                "WAV file is {rate} Hz, expected {WAV_INPUT_SAMPLE_RATE} Hz"
+            )));
+        }
+
+        let chunk_time =
+            Duration::from_secs_f64(CHUNK_SAMPLES as f64 / WAV_INPUT_SAMPLE_RATE as f64);
+        let realtime = self.realtime;
+        let running = self.running.clone();
+        running.store(true, Ordering::SeqCst);
+
+        self.worker = Some(std::thread::spawn(move || {
+            while running.load(Ordering::SeqCst) {
+                let chunk = match wav.read_block(CHUNK_SAMPLES) {
+                    Ok(chunk) if chunk.is_empty() => break,
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        log::warn!("Stopping WAV input: {e}");
+                        break;
+                    }
+                };
+                callback(&chunk);
+                if realtime {
+                    std::thread::sleep(chunk_time);
+                }
+            }
+            running.store(false, Ordering::SeqCst);
+        }));
+
+        log::info!("Audio input started from WAV file {device_id}");
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Psk31Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+        Ok(())
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn sample_rate(&self) -> Option<u32> {
+        Some(WAV_INPUT_SAMPLE_RATE)
+    }
+}
+
+impl Drop for WavFileInput {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::wav_file::write_wav_file;
+    use crate::domain::WavSampleFormat;
+    use crate::modem::encoder::Psk31Encoder;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Instant;
+
+    fn wait_until_stopped(input: &WavFileInput) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while input.is_running() {
+            assert!(Instant::now() < deadline, "WAV input never reached EOF");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn streams_every_sample_then_stops() {
+        let samples = Psk31Encoder::new(WAV_INPUT_SAMPLE_RATE, 1000.0).encode("CQ");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cq.wav");
+        write_wav_file(&path, &samples, WAV_INPUT_SAMPLE_RATE, WavSampleFormat::Float32)
+            .unwrap();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let seen = count.clone();
+        let mut input = WavFileInput::new(false);
+        input
+            .start(
+                path.to_str().unwrap(),
+                Box::new(move |chunk| {
+                    seen.fetch_add(chunk.len(), Ordering::SeqCst);
+                }),
+            )
+            .unwrap();
+        wait_until_stopped(&input);
+
+        assert_eq!(count.load(Ordering::SeqCst), samples.len());
+    }
+
+    #[test]
+    fn plays_a_file_with_an_unknown_data_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("streamed.wav");
+        write_wav_file(&path, &[0.25; 1000], WAV_INPUT_SAMPLE_RATE, WavSampleFormat::Int16)
+            .unwrap();
+        // As left by a recorder that never seeks back to fill in the size
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(&path, bytes).unwrap();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let seen = count.clone();
+        let mut input = WavFileInput::new(false);
+        input
+            .start(
+                path.to_str().unwrap(),
+                Box::new(move |chunk| {
+                    seen.fetch_add(chunk.len(), Ordering::SeqCst);
+                }),
+            )
+            .unwrap();
+        wait_until_stopped(&input);
+
+        assert_eq!(count.load(Ordering::SeqCst), 1000);
+    }
+
+    #[test]
+    fn rejects_other_sample_rates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("8k.wav");
+        write_wav_file(&path, &[0.0; 100], 8000, WavSampleFormat::Int16).unwrap();
+
+        let mut input = WavFileInput::new(false);
+        let err = input.start(path.to_str().unwrap(), Box::new(|_| {})).unwrap_err();
+        assert!(err.to_string().contains("8000 Hz"), "{err}");
+        assert!(!input.is_running());
+    }
+}
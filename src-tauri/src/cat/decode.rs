@@ -4,7 +4,7 @@
 //! to expect in the response — the FT-991A uses the same prefix for
 //! queries and replies so we need the context to know what we're parsing.
 
-use crate::domain::{Psk31Error, Psk31Result, RadioStatus};
+use crate::domain::{Psk31Error, Psk31Result, RadioStatus, Vfo};
 
 use super::{CatCommand, CatResponse, MODE_TABLE};
 
@@ -25,28 +25,59 @@ pub fn decode(response: &str, cmd: &CatCommand) -> Psk31Result<CatResponse> {
     }
 
     match cmd {
-        GetFrequencyA => parse_frequency(response),
+        GetFrequencyA => parse_frequency(response, "FA"),
         SetFrequencyA(_) => expect_ack(response, cmd),
+        GetFrequencyB => parse_frequency(response, "FB"),
+        SetFrequencyB(_) | SwapVfo | SetSplit(_) => expect_ack(response, cmd),
         GetMode => parse_mode(response),
         SetMode(_) => expect_ack(response, cmd),
         PttOn | PttOff => expect_ack(response, cmd),
+        GetTxState => parse_tx_state(response),
         GetTxPower => parse_tx_power(response),
         SetTxPower(_) => expect_ack(response, cmd),
-        GetSignalStrength => parse_signal_strength(response),
+        GetSMeter => parse_smeter(response),
+        GetSwr => parse_swr(response),
         GetStatus => parse_status(response),
-        // BandSelect is write-only — never decoded, but must be covered for exhaustiveness.
-        BandSelect(_) => expect_ack(response, cmd),
+        GetMemoryChannel => parse_memory_channel(response),
+        ReadMemoryChannel(_) => parse_memory_read(response),
+        GetNarrowFilter => parse_narrow_filter(response),
+        GetFilterWidth => parse_filter_width(response),
+        GetScanStatus => parse_scan_status(response),
+        // Write-only — never decoded, but must be covered for exhaustiveness.
+        BandSelect(_)
+        | SetAutoInformation(_)
+        | SetRitEnabled(_)
+        | SetXitEnabled(_)
+        | SetRitOffsetHz(_)
+        | ClearRit => expect_ack(response, cmd),
     }
 }
 
+/// Decode a frame the radio sent without being asked (auto-information
+/// mode): `FA…;` → `FrequencyHz`, `MD0…;` → `Mode`. `None` for anything
+/// else, which is dropped.
+pub fn decode_unsolicited(frame: &str) -> Option<CatResponse> {
+    let cmd = if frame.starts_with("FA") {
+        CatCommand::GetFrequencyA
+    } else if frame.starts_with("MD0") {
+        CatCommand::GetMode
+    } else {
+        log::debug!("Ignoring unsolicited CAT frame '{frame}'");
+        return None;
+    };
+    decode(frame, &cmd)
+        .inspect_err(|e| log::warn!("Bad unsolicited CAT frame '{frame}': {e}"))
+        .ok()
+}
+
 /// Parse `"FA00014070000;"` or `"FA007073900;"` → `FrequencyHz(N)`
 ///
 /// The FT-991A returns variable-width frequency strings (9 or 11 digits depending
-/// on firmware/band).  We parse all digits after the `FA` prefix rather than
-/// assuming a fixed width.
-fn parse_frequency(response: &str) -> Psk31Result<CatResponse> {
+/// on firmware/band).  We parse all digits after the prefix (`FA` for VFO-A,
+/// `FB` for VFO-B) rather than assuming a fixed width.
+fn parse_frequency(response: &str, prefix: &str) -> Psk31Result<CatResponse> {
     let trimmed = response.trim().trim_end_matches(';');
-    if !trimmed.starts_with("FA") || trimmed.len() < 3 {
+    if !trimmed.starts_with(prefix) || trimmed.len() < 3 {
         return Err(Psk31Error::Cat(format!(
             "Invalid frequency response: '{response}'"
         )));
@@ -89,21 +120,140 @@ fn parse_tx_power(response: &str) -> Psk31Result<CatResponse> {
     Ok(CatResponse::TxPower(watts))
 }
 
-/// Parse `"SM00015;"` → `SignalStrength(0.5)`  (15 / 30 = 0.5)
+/// Parse `"TX0;"` / `"TX1;"` / `"TX2;"` → `TxState(n)`
+fn parse_tx_state(response: &str) -> Psk31Result<CatResponse> {
+    let trimmed = response.trim().trim_end_matches(';');
+    match trimmed.strip_prefix("TX") {
+        Some(state @ ("0" | "1" | "2")) => Ok(CatResponse::TxState(state.as_bytes()[0] - b'0')),
+        _ => Err(Psk31Error::Cat(format!("Invalid TX state response: '{response}'"))),
+    }
+}
+
+/// Parse `"MC001;"` → `MemoryChannel(1)`
+fn parse_memory_channel(response: &str) -> Psk31Result<CatResponse> {
+    let trimmed = response.trim().trim_end_matches(';');
+    trimmed
+        .strip_prefix("MC")
+        .filter(|digits| digits.len() == 3)
+        .and_then(|digits| digits.parse::<u16>().ok())
+        .map(CatResponse::MemoryChannel)
+        .ok_or_else(|| Psk31Error::Cat(format!("Invalid memory channel response: '{response}'")))
+}
+
+/// Parse `"MR001014070000+000000C00000;"` → `MemoryChannelFrequency`
 ///
-/// Format: `"SM0"` + 4-digit value (0000–0030) + `";"`
-fn parse_signal_strength(response: &str) -> Psk31Result<CatResponse> {
+/// Format: `"MR"` + 3-digit channel + 9-digit frequency, followed by the
+/// same clarifier/mode fields as the compact `IF;` body (ignored here).
+fn parse_memory_read(response: &str) -> Psk31Result<CatResponse> {
+    let trimmed = response.trim().trim_end_matches(';');
+    let invalid = || Psk31Error::Cat(format!("Invalid memory read response: '{response}'"));
+    let body = trimmed.strip_prefix("MR").filter(|b| b.len() >= 12).ok_or_else(invalid)?;
+    let channel = body[0..3].parse::<u16>().map_err(|_| invalid())?;
+    let frequency_hz = body[3..12].parse::<u64>().map_err(|_| invalid())?;
+    if frequency_hz == 0 {
+        return Err(Psk31Error::Cat(format!("Memory channel {channel} is empty")));
+    }
+    Ok(CatResponse::MemoryChannelFrequency { channel, frequency_hz })
+}
+
+/// IF; VFO/memory field: 0 = VFO, 1–6 = memory, memory tune, QMB, PMS, HOME
+fn parse_vfo_field(byte: Option<&u8>) -> Vfo {
+    match byte {
+        Some(b'1'..=b'6') => Vfo::Memory,
+        _ => Vfo::A,
+    }
+}
+
+/// Parse `"NA00;"` / `"NA01;"` → `NarrowFilter(false/true)`
+fn parse_narrow_filter(response: &str) -> Psk31Result<CatResponse> {
     let trimmed = response.trim().trim_end_matches(';');
-    if !trimmed.starts_with("SM0") || trimmed.len() < 7 {
+    match trimmed.strip_prefix("NA0") {
+        Some("0") => Ok(CatResponse::NarrowFilter(false)),
+        Some("1") => Ok(CatResponse::NarrowFilter(true)),
+        _ => Err(Psk31Error::Cat(format!("Invalid narrow filter response: '{response}'"))),
+    }
+}
+
+/// Parse `"SC0;"` → `Scanning(false)`, `"SC1;"` / `"SC2;"` (up/down) → `Scanning(true)`
+fn parse_scan_status(response: &str) -> Psk31Result<CatResponse> {
+    let trimmed = response.trim().trim_end_matches(';');
+    match trimmed.strip_prefix("SC") {
+        Some("0") => Ok(CatResponse::Scanning(false)),
+        Some("1" | "2") => Ok(CatResponse::Scanning(true)),
+        _ => Err(Psk31Error::Cat(format!("Invalid scan status response: '{response}'"))),
+    }
+}
+
+/// Parse `"SH013;"` → `FilterWidth(13)`
+///
+/// Format: `"SH0"` + 2-digit width index (00–21) + `";"`
+fn parse_filter_width(response: &str) -> Psk31Result<CatResponse> {
+    let trimmed = response.trim().trim_end_matches(';');
+    let width = trimmed
+        .strip_prefix("SH0")
+        .filter(|digits| digits.len() == 2)
+        .and_then(|digits| digits.parse::<u8>().ok())
+        .filter(|&code| code <= 21)
+        .ok_or_else(|| Psk31Error::Cat(format!("Invalid filter width response: '{response}'")))?;
+    Ok(CatResponse::FilterWidth(width))
+}
+
+/// Parse `"SM0130;"` → `SMeter(130)`
+///
+/// Format: `"SM0"` + 3-digit raw reading (000–255) + `";"`
+fn parse_smeter(response: &str) -> Psk31Result<CatResponse> {
+    let trimmed = response.trim().trim_end_matches(';');
+    if !trimmed.starts_with("SM0") || trimmed.len() != 6 {
         return Err(Psk31Error::Cat(format!(
             "Invalid S-meter response: '{response}'"
         )));
     }
-    let digits = &trimmed[3..7];
-    let raw: u32 = digits.parse().map_err(|e| {
-        Psk31Error::Cat(format!("Failed to parse S-meter value '{digits}': {e}"))
-    })?;
-    Ok(CatResponse::SignalStrength(raw.min(30) as f32 / 30.0))
+    let digits = &trimmed[3..6];
+    let raw = digits
+        .parse::<u8>()
+        .map_err(|e| Psk31Error::Cat(format!("Failed to parse S-meter '{digits}': {e}")))?;
+    Ok(CatResponse::SMeter(raw))
+}
+
+/// Points on the FT-991A's SWR meter scale: (raw 0–255 reading, SWR).
+/// Readings in between are interpolated linearly; the top of the scale
+/// means "off the scale" rather than a precise ratio.
+const SWR_SCALE: [(u8, f32); 8] = [
+    (0, 1.0),
+    (26, 1.2),
+    (52, 1.5),
+    (89, 2.0),
+    (126, 3.0),
+    (173, 4.0),
+    (236, 5.0),
+    (255, 25.0),
+];
+
+/// Approximate SWR ratio for a raw `RM6;` meter reading, per `SWR_SCALE`
+pub fn swr_from_raw(raw: u8) -> f32 {
+    let upper = SWR_SCALE.iter().position(|&(r, _)| r >= raw).unwrap_or(SWR_SCALE.len() - 1);
+    if upper == 0 {
+        return SWR_SCALE[0].1;
+    }
+    let (r0, s0) = SWR_SCALE[upper - 1];
+    let (r1, s1) = SWR_SCALE[upper];
+    s0 + (s1 - s0) * f32::from(raw - r0) / f32::from(r1 - r0)
+}
+
+/// Parse `"RM6045;"` (some firmware appends a second 3-digit field, ignored)
+/// → `Swr(1.37)`
+fn parse_swr(response: &str) -> Psk31Result<CatResponse> {
+    let trimmed = response.trim().trim_end_matches(';');
+    if !trimmed.starts_with("RM6") || !matches!(trimmed.len(), 6 | 9) {
+        return Err(Psk31Error::Cat(format!(
+            "Invalid SWR meter response: '{response}'"
+        )));
+    }
+    let digits = &trimmed[3..6];
+    let raw = digits
+        .parse::<u8>()
+        .map_err(|e| Psk31Error::Cat(format!("Failed to parse SWR meter '{digits}': {e}")))?;
+    Ok(CatResponse::Swr(swr_from_raw(raw)))
 }
 
 /// Parse `"IF{body};"` → `Status(RadioStatus)`
@@ -177,6 +327,7 @@ fn parse_status_full(body: &str, _response: &str) -> Psk31Result<CatResponse> {
     let mode = lookup_mode(mode_code, mode_code_padded);
 
     let split = body.as_bytes().get(31).map(|&b| b != b'0').unwrap_or(false);
+    let vfo = parse_vfo_field(body.as_bytes().get(25));
 
     Ok(CatResponse::Status(RadioStatus {
         frequency_hz,
@@ -185,6 +336,7 @@ fn parse_status_full(body: &str, _response: &str) -> Psk31Result<CatResponse> {
         rit_offset_hz,
         rit_enabled,
         split,
+        vfo,
     }))
 }
 
@@ -209,6 +361,8 @@ fn parse_status_compact(body: &str, _response: &str) -> Psk31Result<CatResponse>
     let mode = lookup_mode(mode_code, mode_code);
 
     let split = body.as_bytes().get(24).map(|&b| b != b'0').unwrap_or(false);
+    // VFO/memory: [20], right after the mode
+    let vfo = parse_vfo_field(body.as_bytes().get(20));
 
     Ok(CatResponse::Status(RadioStatus {
         frequency_hz,
@@ -217,6 +371,7 @@ fn parse_status_compact(body: &str, _response: &str) -> Psk31Result<CatResponse>
         rit_offset_hz,
         rit_enabled,
         split,
+        vfo,
     }))
 }
 
@@ -266,6 +421,20 @@ fn expect_ack(response: &str, cmd: &CatCommand) -> Psk31Result<CatResponse> {
 mod tests {
     use super::*;
     use CatCommand::*;
+    use crate::domain::SMeterReading;
+
+    // --- Unsolicited (auto-information) ---
+
+    #[test]
+    fn unsolicited_frequency_and_mode_frames() {
+        assert_eq!(
+            decode_unsolicited("FA00014075000;"),
+            Some(CatResponse::FrequencyHz(14_075_000))
+        );
+        assert_eq!(decode_unsolicited("MD0C;"), Some(CatResponse::Mode("DATA-USB".into())));
+        assert_eq!(decode_unsolicited("SM0012;"), None);
+        assert_eq!(decode_unsolicited("FAxyz;"), None);
+    }
 
     // --- NAK ---
 
@@ -314,6 +483,108 @@ mod tests {
         assert!(decode("FA;", &GetFrequencyA).is_err());
     }
 
+    // --- GetSMeter ---
+
+    fn s_meter(response: &str) -> SMeterReading {
+        match decode(response, &GetSMeter).unwrap() {
+            CatResponse::SMeter(raw) => SMeterReading::from_raw(raw),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decode_smeter_s0() {
+        assert_eq!(s_meter("SM0000;"), SMeterReading { raw: 0, s_units: 0, over_s9_db: 0 });
+    }
+
+    #[test]
+    fn decode_smeter_s9() {
+        assert_eq!(s_meter("SM0130;"), SMeterReading { raw: 130, s_units: 9, over_s9_db: 0 });
+    }
+
+    #[test]
+    fn decode_smeter_over_s9() {
+        assert_eq!(s_meter("SM0190;"), SMeterReading { raw: 190, s_units: 9, over_s9_db: 30 });
+    }
+
+    #[test]
+    fn decode_smeter_full_scale() {
+        assert_eq!(decode("SM0255;", &GetSMeter).unwrap(), CatResponse::SMeter(255));
+    }
+
+    #[test]
+    fn decode_smeter_rejects_malformed() {
+        assert!(decode("SM0;", &GetSMeter).is_err());
+        assert!(decode("SM0256;", &GetSMeter).is_err());
+        assert!(decode("SM00015;", &GetSMeter).is_err());
+        assert!(decode("PC050;", &GetSMeter).is_err());
+    }
+
+    // --- GetSwr ---
+
+    fn swr(response: &str) -> f32 {
+        match decode(response, &GetSwr).unwrap() {
+            CatResponse::Swr(ratio) => ratio,
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decode_swr_matched_antenna() {
+        assert_eq!(swr("RM6000;"), 1.0);
+    }
+
+    #[test]
+    fn decode_swr_on_scale_points_and_between() {
+        assert_eq!(swr("RM6052;"), 1.5);
+        assert_eq!(swr("RM6089000;"), 2.0);
+        let between = swr("RM6107;");
+        assert!((between - 2.5).abs() < 0.05, "{between}");
+    }
+
+    #[test]
+    fn decode_swr_full_scale() {
+        assert_eq!(swr("RM6255;"), 25.0);
+    }
+
+    #[test]
+    fn decode_swr_rejects_malformed() {
+        assert!(decode("RM6;", &GetSwr).is_err());
+        assert!(decode("RM5045;", &GetSwr).is_err());
+        assert!(decode("RM6300;", &GetSwr).is_err());
+    }
+
+    #[test]
+    fn swr_rises_with_the_meter() {
+        let ratios: Vec<f32> = (0..=255).map(swr_from_raw).collect();
+        assert!(ratios.windows(2).all(|w| w[1] >= w[0]));
+    }
+
+    // --- VFO-B ---
+
+    #[test]
+    fn decode_frequency_b_40m() {
+        assert_eq!(
+            decode("FB007070000;", &GetFrequencyB).unwrap(),
+            CatResponse::FrequencyHz(7_070_000)
+        );
+    }
+
+    #[test]
+    fn decode_frequency_b_rejects_vfo_a_reply() {
+        assert!(decode("FA007070000;", &GetFrequencyB).is_err());
+    }
+
+    #[test]
+    fn decode_split_ack() {
+        assert_eq!(decode(";", &SetSplit(true)).unwrap(), CatResponse::Ack);
+    }
+
+    #[test]
+    fn decode_swap_vfo_ack() {
+        assert_eq!(decode(";", &SwapVfo).unwrap(), CatResponse::Ack);
+    }
+
     // --- SetFrequencyA ---
 
     #[test]
@@ -413,45 +684,56 @@ mod tests {
         assert_eq!(decode(";", &SetTxPower(25)).unwrap(), CatResponse::Ack);
     }
 
-    // --- GetSignalStrength ---
+    // --- GetTxState ---
 
     #[test]
-    fn decode_signal_strength_half() {
-        // 15 / 30 = 0.5
-        assert_eq!(
-            decode("SM00015;", &GetSignalStrength).unwrap(),
-            CatResponse::SignalStrength(0.5)
-        );
+    fn decode_tx_state_receive_and_keyed() {
+        assert_eq!(decode("TX0;", &GetTxState).unwrap(), CatResponse::TxState(0));
+        assert_eq!(decode("TX1;", &GetTxState).unwrap(), CatResponse::TxState(1));
+        assert_eq!(decode("TX2;", &GetTxState).unwrap(), CatResponse::TxState(2));
     }
 
+    // --- GetNarrowFilter / GetFilterWidth ---
+
     #[test]
-    fn decode_signal_strength_zero() {
-        assert_eq!(
-            decode("SM00000;", &GetSignalStrength).unwrap(),
-            CatResponse::SignalStrength(0.0)
-        );
+    fn decode_narrow_filter_on_and_off() {
+        assert_eq!(decode("NA00;", &GetNarrowFilter).unwrap(), CatResponse::NarrowFilter(false));
+        assert_eq!(decode("NA01;", &GetNarrowFilter).unwrap(), CatResponse::NarrowFilter(true));
+        assert!(decode("NA02;", &GetNarrowFilter).is_err());
+        assert!(decode("NA0;", &GetNarrowFilter).is_err());
     }
 
+    // --- GetScanStatus (TX inhibit) ---
+
     #[test]
-    fn decode_signal_strength_max() {
-        // 30 / 30 = 1.0
-        assert_eq!(
-            decode("SM00030;", &GetSignalStrength).unwrap(),
-            CatResponse::SignalStrength(1.0)
-        );
+    fn decode_scan_status() {
+        assert_eq!(decode("SC0;", &GetScanStatus).unwrap(), CatResponse::Scanning(false));
+        assert_eq!(decode("SC1;", &GetScanStatus).unwrap(), CatResponse::Scanning(true));
+        assert_eq!(decode("SC2;", &GetScanStatus).unwrap(), CatResponse::Scanning(true));
+        assert!(decode("SC3;", &GetScanStatus).is_err());
+        assert!(decode("SC;", &GetScanStatus).is_err());
+        assert!(decode("?;", &GetScanStatus).is_err());
+    }
+
+    #[test]
+    fn decode_filter_width() {
+        assert_eq!(decode("SH000;", &GetFilterWidth).unwrap(), CatResponse::FilterWidth(0));
+        assert_eq!(decode("SH013;", &GetFilterWidth).unwrap(), CatResponse::FilterWidth(13));
+        assert!(decode("SH022;", &GetFilterWidth).is_err());
+        assert!(decode("SH01;", &GetFilterWidth).is_err());
+        assert!(decode("NA01;", &GetFilterWidth).is_err());
     }
 
     #[test]
-    fn decode_signal_strength_clamps_above_30() {
-        // Any value > 30 is clamped to 30 → 1.0
-        // (shouldn't happen in practice, but be defensive)
-        let r = decode("SM00030;", &GetSignalStrength).unwrap();
-        assert_eq!(r, CatResponse::SignalStrength(1.0));
+    fn decode_filter_query_nak() {
+        assert!(decode("?;", &GetFilterWidth).is_err());
     }
 
     #[test]
-    fn decode_signal_strength_too_short() {
-        assert!(decode("SM0;", &GetSignalStrength).is_err());
+    fn decode_tx_state_rejects_garbage() {
+        assert!(decode("TX;", &GetTxState).is_err());
+        assert!(decode("TX7;", &GetTxState).is_err());
+        assert!(decode("FA014070000;", &GetTxState).is_err());
     }
 
     // --- GetStatus (IF;) ---
@@ -575,6 +857,44 @@ mod tests {
         assert!(!s.is_transmitting);
     }
 
+    #[test]
+    fn decode_if_reports_vfo_or_memory() {
+        let vfo_of = |response: &str| match decode(response, &GetStatus).unwrap() {
+            CatResponse::Status(s) => s.vfo,
+            _ => panic!("expected Status"),
+        };
+        // Compact: VFO/memory field at body[20]
+        assert_eq!(vfo_of("IF001007073900+000000C00000;"), Vfo::A);
+        assert_eq!(vfo_of("IF005007073900+000000C10000;"), Vfo::Memory);
+        // Full: VFO/memory field at body[25]
+        let vfo = make_if_response(14_070_000, "DATA-USB", false, false, 0, false);
+        assert_eq!(vfo_of(&vfo), Vfo::A);
+        let mut memory = vfo.into_bytes();
+        memory[2 + 25] = b'1';
+        assert_eq!(vfo_of(&String::from_utf8(memory).unwrap()), Vfo::Memory);
+    }
+
+    // --- GetMemoryChannel / ReadMemoryChannel ---
+
+    #[test]
+    fn decode_memory_channel() {
+        assert_eq!(decode("MC001;", &GetMemoryChannel).unwrap(), CatResponse::MemoryChannel(1));
+        assert_eq!(decode("MC117;", &GetMemoryChannel).unwrap(), CatResponse::MemoryChannel(117));
+        assert!(decode("MC1;", &GetMemoryChannel).is_err());
+        assert!(decode("MCabc;", &GetMemoryChannel).is_err());
+    }
+
+    #[test]
+    fn decode_memory_read_frequency() {
+        assert_eq!(
+            decode("MR005014070000+000000C00000;", &ReadMemoryChannel(5)).unwrap(),
+            CatResponse::MemoryChannelFrequency { channel: 5, frequency_hz: 14_070_000 }
+        );
+        assert!(decode("MR005000000000+000000C00000;", &ReadMemoryChannel(5)).is_err());
+        assert!(decode("MR00514070;", &ReadMemoryChannel(5)).is_err());
+        assert!(decode("MR00501407x000+000000C00000;", &ReadMemoryChannel(5)).is_err());
+    }
+
     #[test]
     fn decode_if_too_short() {
         assert!(decode("IF12345;", &GetStatus).is_err());
@@ -6,6 +6,7 @@
 //! command value and get back the exact bytes to send to the radio.
 
 use super::{CatCommand, MODE_TABLE};
+use crate::domain::normalize_mode;
 
 /// Encode a CatCommand into the FT-991A wire string (including the `;` terminator).
 pub fn encode(cmd: &CatCommand) -> String {
@@ -13,11 +14,15 @@ pub fn encode(cmd: &CatCommand) -> String {
     match cmd {
         GetFrequencyA => "FA;".into(),
         SetFrequencyA(hz) => format!("FA{hz:09};"),
+        GetFrequencyB => "FB;".into(),
+        SetFrequencyB(hz) => format!("FB{hz:09};"),
+        SwapVfo => "SV;".into(),
+        SetSplit(on) => format!("ST{};", u8::from(*on)),
         GetMode => "MD0;".into(),
         SetMode(name) => {
-            let code = MODE_TABLE
-                .iter()
-                .find(|(_, n)| *n == name.as_str())
+            let code = normalize_mode(name)
+                .ok()
+                .and_then(|mode| MODE_TABLE.iter().find(|(_, n)| *n == mode))
                 .map(|(c, _)| *c)
                 .unwrap_or_else(|| {
                     log::warn!("encode: unknown mode '{name}', falling back to DATA-USB");
@@ -27,11 +32,24 @@ pub fn encode(cmd: &CatCommand) -> String {
         }
         PttOff => "TX0;".into(),
         PttOn => "TX1;".into(),
+        GetTxState => "TX;".into(),
         GetTxPower => "PC;".into(),
         SetTxPower(w) => format!("PC{w:03};"),
-        GetSignalStrength => "SM0;".into(),
+        GetSMeter => "SM0;".into(),
+        GetSwr => "RM6;".into(),
         GetStatus => "IF;".into(),
+        GetMemoryChannel => "MC;".into(),
+        ReadMemoryChannel(ch) => format!("MR{ch:03};"),
+        GetNarrowFilter => "NA0;".into(),
+        GetFilterWidth => "SH0;".into(),
+        GetScanStatus => "SC;".into(),
         BandSelect(code) => format!("BS{code:02};"),
+        SetAutoInformation(on) => format!("AI{};", u8::from(*on)),
+        SetRitEnabled(on) => format!("RT{};", u8::from(*on)),
+        SetXitEnabled(on) => format!("XT{};", u8::from(*on)),
+        SetRitOffsetHz(hz) if *hz < 0 => format!("RD{:04};", hz.unsigned_abs()),
+        SetRitOffsetHz(hz) => format!("RU{hz:04};"),
+        ClearRit => "RC;".into(),
     }
 }
 
@@ -61,6 +79,19 @@ mod tests {
         assert_eq!(encode(&SetFrequencyA(1_800_000)), "FA001800000;");
     }
 
+    #[test]
+    fn encode_vfo_b_and_swap() {
+        assert_eq!(encode(&GetFrequencyB), "FB;");
+        assert_eq!(encode(&SetFrequencyB(7_070_000)), "FB007070000;");
+        assert_eq!(encode(&SwapVfo), "SV;");
+    }
+
+    #[test]
+    fn encode_split() {
+        assert_eq!(encode(&SetSplit(true)), "ST1;");
+        assert_eq!(encode(&SetSplit(false)), "ST0;");
+    }
+
     #[test]
     fn encode_get_mode() {
         assert_eq!(encode(&GetMode), "MD0;");
@@ -118,8 +149,60 @@ mod tests {
     }
 
     #[test]
-    fn encode_get_signal_strength() {
-        assert_eq!(encode(&GetSignalStrength), "SM0;");
+    fn encode_get_smeter() {
+        assert_eq!(encode(&GetSMeter), "SM0;");
+    }
+
+    #[test]
+    fn encode_get_swr() {
+        assert_eq!(encode(&GetSwr), "RM6;");
+    }
+
+    #[test]
+    fn encode_get_tx_state() {
+        assert_eq!(encode(&GetTxState), "TX;");
+    }
+
+    #[test]
+    fn encode_memory_channel_queries() {
+        assert_eq!(encode(&GetMemoryChannel), "MC;");
+        assert_eq!(encode(&ReadMemoryChannel(7)), "MR007;");
+        assert_eq!(encode(&ReadMemoryChannel(117)), "MR117;");
+    }
+
+    #[test]
+    fn encode_filter_queries() {
+        assert_eq!(encode(&GetNarrowFilter), "NA0;");
+        assert_eq!(encode(&GetFilterWidth), "SH0;");
+    }
+
+    #[test]
+    fn encode_get_scan_status() {
+        assert_eq!(encode(&GetScanStatus), "SC;");
+    }
+
+    #[test]
+    fn encode_auto_information() {
+        assert_eq!(encode(&SetAutoInformation(true)), "AI1;");
+        assert_eq!(encode(&SetAutoInformation(false)), "AI0;");
+    }
+
+    #[test]
+    fn encode_rit_and_xit_switches() {
+        assert_eq!(encode(&SetRitEnabled(true)), "RT1;");
+        assert_eq!(encode(&SetRitEnabled(false)), "RT0;");
+        assert_eq!(encode(&SetXitEnabled(true)), "XT1;");
+        assert_eq!(encode(&SetXitEnabled(false)), "XT0;");
+        assert_eq!(encode(&ClearRit), "RC;");
+    }
+
+    #[test]
+    fn encode_rit_offsets() {
+        assert_eq!(encode(&SetRitOffsetHz(150)), "RU0150;");
+        assert_eq!(encode(&SetRitOffsetHz(-20)), "RD0020;");
+        assert_eq!(encode(&SetRitOffsetHz(9999)), "RU9999;");
+        assert_eq!(encode(&SetRitOffsetHz(-9999)), "RD9999;");
+        assert_eq!(encode(&SetRitOffsetHz(0)), "RU0000;");
     }
 
     #[test]
@@ -136,4 +219,20 @@ mod tests {
             assert_eq!(encoded, format!("MD0{code};"), "Roundtrip failed for mode '{name}'");
         }
     }
+
+    #[test]
+    fn encode_set_mode_accepts_alias_spellings() {
+        assert_eq!(encode(&SetMode("usb".into())), "MD02;");
+        assert_eq!(encode(&SetMode("PKTLSB".into())), "MD08;");
+    }
+
+    #[test]
+    fn mode_table_names_are_canonical() {
+        use crate::domain::MODE_NAMES;
+        use super::super::MODE_TABLE;
+        assert_eq!(MODE_TABLE.len(), MODE_NAMES.len());
+        for (_, name) in MODE_TABLE {
+            assert!(MODE_NAMES.contains(name), "{name}");
+        }
+    }
 }
@@ -12,7 +12,7 @@ pub mod decode;
 pub mod encode;
 pub mod session;
 
-pub use decode::decode;
+pub use decode::{decode, decode_unsolicited};
 pub use encode::encode;
 pub use session::CatSession;
 
@@ -37,12 +37,22 @@ pub const MODE_TABLE: &[(&str, &str)] = &[
     ("E", "C4FM"),
 ];
 
+/// Largest clarifier (RIT/XIT) offset the FT-991A accepts, either direction
+pub const RIT_MAX_OFFSET_HZ: i32 = 9_999;
+
 /// High-level CAT commands understood by the FT-991A.
 #[derive(Debug, PartialEq, Clone)]
 pub enum CatCommand {
     // VFO-A frequency
     GetFrequencyA,
     SetFrequencyA(u64),
+    // VFO-B frequency, for split
+    GetFrequencyB,
+    SetFrequencyB(u64),
+    /// Exchange VFO-A and VFO-B (SV;)
+    SwapVfo,
+    /// Split on/off (ST1;/ST0;): receive on VFO-A, transmit on VFO-B
+    SetSplit(bool),
     // Operating mode
     GetMode,
     /// Mode name e.g. "DATA-USB"
@@ -50,17 +60,44 @@ pub enum CatCommand {
     // PTT control
     PttOff,
     PttOn,
+    /// Query transmit state (TX; → TX0/TX1/TX2)
+    GetTxState,
     // TX power
     GetTxPower,
     /// Watts, 0–100
     SetTxPower(u32),
-    // S-meter
-    GetSignalStrength,
+    /// Raw S-meter reading (SM0; → SM0000–SM0255)
+    GetSMeter,
+    /// SWR meter (RM6; → RM6nnn…), meaningful only while transmitting
+    GetSwr,
     // Comprehensive status (IF; command)
     GetStatus,
+    /// Query the selected memory channel (MC; → MC001–MC117)
+    GetMemoryChannel,
+    /// Read a memory channel's contents (MRnnn;), for its frequency
+    ReadMemoryChannel(u16),
+    /// Query the narrow filter switch (NA0; → NA00/NA01)
+    GetNarrowFilter,
+    /// Query the filter width index (SH0; → SH000–SH021)
+    GetFilterWidth,
+    /// Query memory/VFO scan (SC; → SC0 off, SC1 up, SC2 down)
+    GetScanStatus,
     /// Select band group (BS; command).  Code 0–10=HF/6m, 12=2m, 13=70cm.
     /// The FT-991A executes BS; silently — no ack is returned.
     BandSelect(u8),
+    /// Auto-information on/off (AI1;/AI0;). While on, the radio pushes
+    /// `FA`/`MD` frames by itself when the front panel changes them.
+    SetAutoInformation(bool),
+    /// RIT on/off (RT1;/RT0;)
+    SetRitEnabled(bool),
+    /// XIT on/off (XT1;/XT0;). Shares the clarifier offset with RIT.
+    SetXitEnabled(bool),
+    /// Move the clarifier by a signed offset (RUnnnn; up, RDnnnn; down).
+    /// The radio steps from the current offset — send `ClearRit` first to
+    /// land on an absolute value.
+    SetRitOffsetHz(i32),
+    /// Zero the clarifier offset (RC;)
+    ClearRit,
 }
 
 /// Parsed responses from the FT-991A.
@@ -71,10 +108,25 @@ pub enum CatResponse {
     Mode(String),
     /// TX power in watts
     TxPower(u32),
-    /// S-meter level, normalised 0.0–1.0 from the 0–30 SM0 scale
-    SignalStrength(f32),
+    /// Transmit state from `TX;`: 0 = receiving, 1 = keyed by CAT,
+    /// 2 = keyed at the radio (mic PTT / rear-panel line)
+    TxState(u8),
+    /// Raw S-meter reading 0–255 from `SM0;`
+    SMeter(u8),
+    /// Approximate SWR ratio (1.0 = matched) from `RM6;`
+    Swr(f32),
     /// Full radio status from the IF; command
     Status(RadioStatus),
+    /// Memory channel number from `MC;`
+    MemoryChannel(u16),
+    /// Stored frequency of a memory channel from `MRnnn;`
+    MemoryChannelFrequency { channel: u16, frequency_hz: u64 },
+    /// Narrow filter on/off from `NA0;`
+    NarrowFilter(bool),
+    /// Width-table index from `SH0;`
+    FilterWidth(u8),
+    /// Scan running (either direction) from `SC;`
+    Scanning(bool),
     /// Command accepted; radio returned just ";"
     Ack,
 }
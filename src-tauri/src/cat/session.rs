@@ -8,13 +8,10 @@
 
 use std::time::{Duration, Instant};
 
-use crate::domain::{Psk31Error, Psk31Result};
-use crate::ports::SerialConnection;
+use crate::domain::{Psk31Error, Psk31Result, DEFAULT_COMMAND_DELAY_MS};
+use crate::ports::{Clock, SerialConnection, SystemClock};
 
-use super::{decode, encode, CatCommand, CatResponse};
-
-/// Minimum delay between CAT commands (FT-991A firmware requirement)
-const COMMAND_DELAY_MS: u64 = 50;
+use super::{decode, decode_unsolicited, encode, CatCommand, CatResponse};
 
 /// Settling delay after opening a serial port before sending the first command.
 /// Some USB-serial adapters (e.g. CP2105) need a moment to become ready.
@@ -23,6 +20,10 @@ const PORT_SETTLE_MS: u64 = 200;
 /// Chunk size for each serial read call
 const READ_CHUNK_SIZE: usize = 64;
 
+/// Longest response we'll accumulate waiting for `;`. Real FT-991A replies
+/// are under 64 bytes; anything this long is garbage, usually a baud mismatch.
+const MAX_RESPONSE_BYTES: usize = 512;
+
 /// Max read attempts before giving up (~100ms per attempt → ~1000ms total)
 const RESPONSE_TIMEOUT_READS: usize = 10;
 
@@ -30,36 +31,83 @@ const RESPONSE_TIMEOUT_READS: usize = 10;
 pub struct CatSession {
     serial: Box<dyn SerialConnection>,
     last_command_time: Option<Instant>,
+    /// Minimum delay between commands
+    command_delay: Duration,
+    clock: Box<dyn Clock>,
+    /// Expect unsolicited frames between commands (auto-information mode)
+    listening: bool,
+    /// Bytes of an unsolicited frame still waiting for its `;`
+    unsolicited: Vec<u8>,
+    /// Unsolicited frames decoded but not yet collected
+    pushes: Vec<CatResponse>,
 }
 
 impl CatSession {
     pub fn new(serial: Box<dyn SerialConnection>) -> Self {
+        Self::with_clock(serial, Box::new(SystemClock))
+    }
+
+    /// Time the settle and inter-command delays against `clock`.
+    pub fn with_clock(serial: Box<dyn SerialConnection>, clock: Box<dyn Clock>) -> Self {
         // Give the USB-serial adapter time to settle before the first command.
-        std::thread::sleep(Duration::from_millis(PORT_SETTLE_MS));
+        clock.sleep(Duration::from_millis(PORT_SETTLE_MS));
         Self {
             serial,
             last_command_time: None,
+            command_delay: Duration::from_millis(DEFAULT_COMMAND_DELAY_MS),
+            clock,
+            listening: false,
+            unsolicited: Vec::new(),
+            pushes: Vec::new(),
+        }
+    }
+
+    /// Expect the radio to send frames on its own (after `AI1;`). While
+    /// listening, input waiting before each command is collected as
+    /// unsolicited frames rather than mistaken for the command's reply.
+    pub fn set_listening(&mut self, listening: bool) {
+        self.listening = listening;
+        if !listening {
+            self.unsolicited.clear();
+            self.pushes.clear();
         }
     }
 
+    /// Unsolicited `FA`/`MD` frames received since the last call, oldest
+    /// first. Only reads what is already waiting, so it never blocks.
+    pub fn poll_unsolicited(&mut self) -> Psk31Result<Vec<CatResponse>> {
+        self.drain_unsolicited()?;
+        Ok(std::mem::take(&mut self.pushes))
+    }
+
+    /// Override the minimum delay between commands (default 50 ms).
+    pub fn set_command_delay(&mut self, delay: Duration) {
+        self.command_delay = delay;
+    }
+
+    pub fn command_delay(&self) -> Duration {
+        self.command_delay
+    }
+
     /// Send a CAT command and return the parsed response.
     ///
-    /// Enforces the 50ms inter-command delay, writes the wire string,
+    /// Enforces the inter-command delay, writes the wire string,
     /// reads bytes until the `;` terminator, strips any command echo,
     /// then delegates to `decode()`.
     pub fn execute(&mut self, cmd: &CatCommand) -> Psk31Result<CatResponse> {
+        self.drain_unsolicited()?;
         self.ensure_command_delay();
 
         let wire = encode(cmd);
         log::debug!("CAT TX: {wire}");
 
         self.serial
-            .write(wire.as_bytes())
+            .write_all(wire.as_bytes())
             .map_err(|e| Psk31Error::Cat(format!("Command '{wire}' write failed: {e}")))?;
 
         let raw = self.read_until_semicolon(&wire);
         // Update timestamp even on error so the next command still respects the delay
-        self.last_command_time = Some(Instant::now());
+        self.last_command_time = Some(self.clock.now());
         let raw = raw?;
 
         log::debug!("CAT RX: {raw}");
@@ -79,16 +127,17 @@ impl CatSession {
     ///
     /// Returns an error only if the serial write itself fails.
     pub fn execute_write_only(&mut self, cmd: &CatCommand) -> Psk31Result<()> {
+        self.drain_unsolicited()?;
         self.ensure_command_delay();
 
         let wire = encode(cmd);
         log::debug!("CAT TX: {wire}");
 
         self.serial
-            .write(wire.as_bytes())
+            .write_all(wire.as_bytes())
             .map_err(|e| Psk31Error::Cat(format!("Command '{wire}' write failed: {e}")))?;
 
-        self.last_command_time = Some(Instant::now());
+        self.last_command_time = Some(self.clock.now());
         Ok(())
     }
 
@@ -96,7 +145,8 @@ impl CatSession {
     ///
     /// Each serial.read() has a 100ms hardware timeout. We retry up to
     /// RESPONSE_TIMEOUT_READS times to handle slow USB-serial adapters
-    /// that may return partial responses.
+    /// that may return partial responses. The accumulated bytes are capped at
+    /// `MAX_RESPONSE_BYTES` so a babbling port fails fast with a framing error.
     fn read_until_semicolon(&mut self, cmd_wire: &str) -> Psk31Result<String> {
         let mut buf: Vec<u8> = Vec::with_capacity(READ_CHUNK_SIZE);
         let mut chunk = [0u8; READ_CHUNK_SIZE];
@@ -108,6 +158,12 @@ impl CatSession {
                     if buf.contains(&b';') {
                         break;
                     }
+                    if buf.len() > MAX_RESPONSE_BYTES {
+                        return Err(Psk31Error::Cat(format!(
+                            "Command '{cmd_wire}': response too long (over {MAX_RESPONSE_BYTES} \
+                             bytes without ';') — framing error, check the baud rate"
+                        )));
+                    }
                 }
                 Ok(_) => {} // Zero bytes: read timed out, try again
                 Err(_) => {
@@ -124,27 +180,60 @@ impl CatSession {
             )));
         }
 
-        std::str::from_utf8(&buf)
-            .map(|s| s.to_string())
-            .map_err(|e| Psk31Error::Cat(format!("Invalid UTF-8 response: {e}")))
+        Ok(ascii_response(&buf, cmd_wire))
+    }
+
+    /// When listening, read whatever input is already waiting and decode
+    /// each complete frame into `pushes`; a trailing partial frame is kept
+    /// for next time.
+    fn drain_unsolicited(&mut self) -> Psk31Result<()> {
+        if !self.listening {
+            return Ok(());
+        }
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        while self.serial.bytes_available()? > 0 {
+            match self.serial.read(&mut chunk)? {
+                0 => break,
+                n => self.unsolicited.extend_from_slice(&chunk[..n]),
+            }
+        }
+        while let Some(end) = self.unsolicited.iter().position(|&b| b == b';') {
+            let frame: Vec<u8> = self.unsolicited.drain(..=end).collect();
+            let frame = ascii_response(&frame, "unsolicited");
+            self.pushes.extend(decode_unsolicited(frame.trim_start()));
+        }
+        if self.unsolicited.len() > MAX_RESPONSE_BYTES {
+            log::warn!("Dropping {} bytes of unterminated CAT input", self.unsolicited.len());
+            self.unsolicited.clear();
+        }
+        Ok(())
     }
 
     /// Sleep if needed to maintain the minimum inter-command delay.
     fn ensure_command_delay(&self) {
         if let Some(last) = self.last_command_time {
-            let elapsed = last.elapsed();
-            let min_delay = Duration::from_millis(COMMAND_DELAY_MS);
-            if elapsed < min_delay {
-                std::thread::sleep(min_delay - elapsed);
+            let elapsed = self.clock.now().saturating_duration_since(last);
+            if elapsed < self.command_delay {
+                self.clock.sleep(self.command_delay - elapsed);
             }
         }
     }
 }
 
+/// CAT is plain ASCII: drop any other byte (line noise) rather than failing
+/// the whole command over one corrupt byte.
+fn ascii_response(buf: &[u8], cmd_wire: &str) -> String {
+    let noise: Vec<u8> = buf.iter().copied().filter(|b| !b.is_ascii()).collect();
+    if !noise.is_empty() {
+        log::warn!("Command '{cmd_wire}': dropped non-ASCII bytes {noise:02X?} from response");
+    }
+    buf.iter().filter(|b| b.is_ascii()).map(|&b| char::from(b)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ports::SerialConnection;
+    use crate::ports::{FakeClock, SerialConnection};
     use std::sync::{Arc, Mutex};
 
     // ---------------------------------------------------------------------------
@@ -153,7 +242,7 @@ mod tests {
 
     struct MockSerial {
         log: Arc<Mutex<Vec<String>>>,
-        response: String,
+        response: Vec<u8>,
     }
 
     impl SerialConnection for MockSerial {
@@ -165,7 +254,7 @@ mod tests {
             Ok(data.len())
         }
         fn read(&mut self, buf: &mut [u8]) -> Psk31Result<usize> {
-            let bytes = self.response.as_bytes();
+            let bytes = &self.response;
             let n = bytes.len().min(buf.len());
             buf[..n].copy_from_slice(&bytes[..n]);
             Ok(n)
@@ -179,12 +268,44 @@ mod tests {
     }
 
     fn make_session(response: &str) -> (CatSession, Arc<Mutex<Vec<String>>>) {
+        make_session_bytes(response.as_bytes())
+    }
+
+    fn make_session_bytes(response: &[u8]) -> (CatSession, Arc<Mutex<Vec<String>>>) {
         let log = Arc::new(Mutex::new(Vec::new()));
         let mock = MockSerial {
             log: Arc::clone(&log),
-            response: response.to_string(),
+            response: response.to_vec(),
         };
-        (CatSession::new(Box::new(mock)), log)
+        (CatSession::with_clock(Box::new(mock), Box::<FakeClock>::default()), log)
+    }
+
+    // --- Timing ---
+
+    #[test]
+    fn command_delay_sleeps_only_for_the_remainder() {
+        let clock = FakeClock::new();
+        let mock = MockSerial { log: Arc::new(Mutex::new(Vec::new())), response: b";".to_vec() };
+        let mut session = CatSession::with_clock(Box::new(mock), Box::new(clock.clone()));
+        assert_eq!(clock.take_sleeps(), vec![Duration::from_millis(PORT_SETTLE_MS)]);
+
+        // First command: nothing to wait for
+        session.execute(&CatCommand::PttOn).unwrap();
+        assert!(clock.take_sleeps().is_empty());
+
+        // 20 ms later the next command waits out the other 30 ms
+        clock.advance(Duration::from_millis(20));
+        session.execute(&CatCommand::PttOff).unwrap();
+        assert_eq!(clock.take_sleeps(), vec![Duration::from_millis(30)]);
+
+        // Once the delay has passed on its own, no sleep
+        clock.advance(Duration::from_millis(DEFAULT_COMMAND_DELAY_MS));
+        session.execute_write_only(&CatCommand::PttOn).unwrap();
+        assert!(clock.take_sleeps().is_empty());
+
+        session.set_command_delay(Duration::from_millis(80));
+        session.execute(&CatCommand::PttOff).unwrap();
+        assert_eq!(clock.take_sleeps(), vec![Duration::from_millis(80)]);
     }
 
     // --- Basic I/O ---
@@ -238,6 +359,15 @@ mod tests {
         assert_eq!(resp, CatResponse::FrequencyHz(14_070_000));
     }
 
+    // --- Line noise ---
+
+    #[test]
+    fn stray_non_ascii_byte_is_dropped() {
+        let (mut session, _) = make_session_bytes(b"FA000140\xFF70000;");
+        let resp = session.execute(&CatCommand::GetFrequencyA).unwrap();
+        assert_eq!(resp, CatResponse::FrequencyHz(14_070_000));
+    }
+
     // --- TX power ---
 
     #[test]
@@ -292,7 +422,7 @@ mod tests {
     struct SilentMockSerial;
 
     impl SerialConnection for SilentMockSerial {
-        fn write(&mut self, _data: &[u8]) -> Psk31Result<usize> { Ok(0) }
+        fn write(&mut self, data: &[u8]) -> Psk31Result<usize> { Ok(data.len()) }
         fn read(&mut self, _buf: &mut [u8]) -> Psk31Result<usize> { Ok(0) }
         fn close(&mut self) -> Psk31Result<()> { Ok(()) }
         fn is_connected(&self) -> bool { true }
@@ -315,7 +445,7 @@ mod tests {
     }
 
     impl SerialConnection for ErroringMockSerial {
-        fn write(&mut self, _data: &[u8]) -> Psk31Result<usize> { Ok(0) }
+        fn write(&mut self, data: &[u8]) -> Psk31Result<usize> { Ok(data.len()) }
         fn read(&mut self, buf: &mut [u8]) -> Psk31Result<usize> {
             self.calls += 1;
             if self.calls <= self.error_count {
@@ -355,6 +485,78 @@ mod tests {
         assert_eq!(log.lock().unwrap()[0], "BS03;");
     }
 
+    /// A MockSerial that streams garbage forever and never sends `;` —
+    /// what a port at the wrong baud rate looks like.
+    struct BabblingMockSerial {
+        bytes_read: Arc<Mutex<usize>>,
+    }
+
+    impl SerialConnection for BabblingMockSerial {
+        fn write(&mut self, data: &[u8]) -> Psk31Result<usize> { Ok(data.len()) }
+        fn read(&mut self, buf: &mut [u8]) -> Psk31Result<usize> {
+            buf.fill(0xE6);
+            *self.bytes_read.lock().unwrap() += buf.len();
+            Ok(buf.len())
+        }
+        fn close(&mut self) -> Psk31Result<()> { Ok(()) }
+        fn is_connected(&self) -> bool { true }
+    }
+
+    #[test]
+    fn endless_response_without_terminator_is_a_framing_error() {
+        let bytes_read = Arc::new(Mutex::new(0));
+        let serial = BabblingMockSerial { bytes_read: Arc::clone(&bytes_read) };
+        let mut session = CatSession::new(Box::new(serial));
+
+        let err = session.execute(&CatCommand::GetFrequencyA).unwrap_err().to_string();
+        assert!(err.contains("too long"), "got: {err}");
+        assert!(err.contains("baud"), "got: {err}");
+        // Gave up at the cap rather than reading the whole retry budget
+        assert!(*bytes_read.lock().unwrap() <= MAX_RESPONSE_BYTES + READ_CHUNK_SIZE);
+    }
+
+    /// A MockSerial that accepts one byte per write() — a slow or full
+    /// transmit buffer. Records everything that reached the wire.
+    struct TrickleWriteMockSerial {
+        written: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl SerialConnection for TrickleWriteMockSerial {
+        fn write(&mut self, data: &[u8]) -> Psk31Result<usize> {
+            self.written.lock().unwrap().push(data[0]);
+            Ok(1)
+        }
+        fn read(&mut self, _buf: &mut [u8]) -> Psk31Result<usize> { Ok(0) }
+        fn close(&mut self) -> Psk31Result<()> { Ok(()) }
+        fn is_connected(&self) -> bool { true }
+    }
+
+    #[test]
+    fn short_writes_still_send_the_whole_command() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let serial = TrickleWriteMockSerial { written: Arc::clone(&written) };
+        let mut session = CatSession::new(Box::new(serial));
+        session.execute_write_only(&CatCommand::SetFrequencyA(14_070_000)).unwrap();
+        assert_eq!(written.lock().unwrap().as_slice(), b"FA014070000;");
+    }
+
+    /// A MockSerial whose write() never accepts anything.
+    struct StalledWriteMockSerial;
+
+    impl SerialConnection for StalledWriteMockSerial {
+        fn write(&mut self, _data: &[u8]) -> Psk31Result<usize> { Ok(0) }
+        fn read(&mut self, _buf: &mut [u8]) -> Psk31Result<usize> { Ok(0) }
+        fn close(&mut self) -> Psk31Result<()> { Ok(()) }
+        fn is_connected(&self) -> bool { true }
+    }
+
+    #[test]
+    fn stalled_write_is_an_error() {
+        let mut session = CatSession::new(Box::new(StalledWriteMockSerial));
+        let err = session.execute_write_only(&CatCommand::PttOn).unwrap_err().to_string();
+        assert!(err.contains("stalled"), "got: {err}");
+    }
+
     /// A MockSerial whose write() always fails.
     struct FailingWriteMockSerial;
 
@@ -414,4 +616,79 @@ mod tests {
             Ok(_) => {} // If it somehow parses, that's fine too
         }
     }
+
+    // --- Unsolicited frames (auto-information) ---
+
+    /// Radio that has `pending` bytes waiting on its own, ahead of the reply
+    /// to whatever is asked next
+    struct PushingMockSerial {
+        pending: Arc<Mutex<Vec<u8>>>,
+        response: Vec<u8>,
+    }
+
+    impl SerialConnection for PushingMockSerial {
+        fn write(&mut self, data: &[u8]) -> Psk31Result<usize> { Ok(data.len()) }
+        fn read(&mut self, buf: &mut [u8]) -> Psk31Result<usize> {
+            let mut pending = self.pending.lock().unwrap();
+            let source = if pending.is_empty() { &mut self.response } else { &mut *pending };
+            let n = source.len().min(buf.len());
+            buf[..n].copy_from_slice(&source[..n]);
+            source.drain(..n);
+            Ok(n)
+        }
+        fn bytes_available(&self) -> Psk31Result<usize> { Ok(self.pending.lock().unwrap().len()) }
+        fn close(&mut self) -> Psk31Result<()> { Ok(()) }
+        fn is_connected(&self) -> bool { true }
+    }
+
+    fn make_listening_session(response: &str) -> (CatSession, Arc<Mutex<Vec<u8>>>) {
+        let pending = Arc::new(Mutex::new(Vec::new()));
+        let serial = PushingMockSerial {
+            pending: Arc::clone(&pending),
+            response: response.as_bytes().to_vec(),
+        };
+        let mut session = CatSession::with_clock(Box::new(serial), Box::<FakeClock>::default());
+        session.set_listening(true);
+        (session, pending)
+    }
+
+    #[test]
+    fn unsolicited_frames_are_collected_between_commands() {
+        let (mut session, pending) = make_listening_session("");
+        pending.lock().unwrap().extend_from_slice(b"FA00014075000;MD0C;");
+        assert_eq!(
+            session.poll_unsolicited().unwrap(),
+            vec![CatResponse::FrequencyHz(14_075_000), CatResponse::Mode("DATA-USB".into())]
+        );
+        assert!(session.poll_unsolicited().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unsolicited_frame_split_across_reads_is_reassembled() {
+        let (mut session, pending) = make_listening_session("");
+        pending.lock().unwrap().extend_from_slice(b"FA000140");
+        assert!(session.poll_unsolicited().unwrap().is_empty());
+        pending.lock().unwrap().extend_from_slice(b"75000;");
+        assert_eq!(session.poll_unsolicited().unwrap(), vec![CatResponse::FrequencyHz(14_075_000)]);
+    }
+
+    #[test]
+    fn push_waiting_before_a_command_is_not_taken_as_its_reply() {
+        let (mut session, pending) = make_listening_session("MD02;");
+        pending.lock().unwrap().extend_from_slice(b"FA00014075000;");
+        assert_eq!(
+            session.execute(&CatCommand::GetMode).unwrap(),
+            CatResponse::Mode("USB".into())
+        );
+        assert_eq!(session.poll_unsolicited().unwrap(), vec![CatResponse::FrequencyHz(14_075_000)]);
+    }
+
+    #[test]
+    fn not_listening_leaves_input_alone() {
+        let (mut session, pending) = make_listening_session("");
+        session.set_listening(false);
+        pending.lock().unwrap().extend_from_slice(b"FA00014075000;");
+        assert!(session.poll_unsolicited().unwrap().is_empty());
+        assert_eq!(pending.lock().unwrap().len(), 14);
+    }
 }
@@ -1,7 +1,65 @@
 //! Application-level Tauri commands
 
+use tauri::{AppHandle, State};
+
+use crate::adapters::cpal_audio::list_devices_grouped;
+use crate::commands::audio::selected_audio_host;
+use crate::commands::config::config_dir;
+use crate::domain::{Frequency, FrequencyStyle, SelfTestReport};
+use crate::events::EVENT_SCHEMA_VERSION;
+use crate::modem::dsp_info::DspInfo;
+use crate::modem::self_test::run_loopback_self_test;
+use crate::state::AppState;
+
 /// Exit the application cleanly.
 #[tauri::command]
-pub fn exit_app(app: tauri::AppHandle) {
+pub fn exit_app(app: AppHandle) {
     app.exit(0);
 }
+
+/// Format a frequency for display so every panel renders it the same way.
+#[tauri::command]
+pub fn format_frequency(hz: f64, style: FrequencyStyle) -> String {
+    Frequency::hz(hz).format(style)
+}
+
+/// Version of the event payload schema (see `events`), so the frontend can
+/// detect a backend whose events it doesn't understand.
+#[tauri::command]
+pub fn get_event_schema_version() -> u32 {
+    EVENT_SCHEMA_VERSION
+}
+
+/// DSP parameters and version of this build, for attaching to bug reports.
+#[tauri::command]
+pub fn get_dsp_info() -> DspInfo {
+    DspInfo::current()
+}
+
+/// "Does my install work" check that needs no radio or audio stream: an
+/// in-memory modem loopback, audio device enumeration and config dir access.
+#[tauri::command]
+pub fn run_self_test(app: AppHandle, state: State<AppState>) -> Result<SelfTestReport, String> {
+    let sample_rate = state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .sample_rate;
+    let mut report = run_loopback_self_test(sample_rate);
+
+    report.add_check(
+        "Audio device enumeration",
+        list_devices_grouped(selected_audio_host(&state).as_deref())
+            .map(|d| format!("{} inputs, {} outputs", d.inputs.len(), d.outputs.len()))
+            .map_err(|e| e.to_string()),
+    );
+    report.add_check(
+        "Config directory",
+        config_dir(&app).and_then(|dir| {
+            std::fs::read_dir(&dir)
+                .map(|_| dir.display().to_string())
+                .map_err(|e| format!("Cannot read {}: {e}", dir.display()))
+        }),
+    );
+    Ok(report)
+}
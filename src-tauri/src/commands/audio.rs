@@ -7,66 +7,170 @@
 //!
 //! The RX decoder runs inside the same audio thread — when `rx_running` is true,
 //! each audio sample is fed to the Psk31Decoder alongside FFT processing.
+//!
+//! `inject_test_signal` queues encoder output that the cpal callback pushes
+//! into the ring buffer instead of the soundcard's samples, so the whole RX
+//! chain can be checked without a radio.
+//!
+//! `capture_decode_snapshot` has the audio thread copy the next N seconds of
+//! raw input, then writes it with the decoder settings as a float WAV with a
+//! `json` chunk, for "won't decode this" reports.
+//!
+//! With `rx_record_mode` set to auto, the audio thread also records each
+//! transmission it hears to its own WAV in `recordings/` (see
+//! `modem::auto_record`), for unattended monitoring.
+//!
+//! `clear_rx_session` wipes the RX transcript and cached indicators and has
+//! the audio thread reset the decoder, for a clean slate between QSOs.
+//!
+//! The latest FFT frame is cached in `AppState.latest_spectrum` so
+//! `export_spectrum_csv` can dump a snapshot for interference reports.
 
-use ringbuf::HeapRb;
-use ringbuf::traits::{Consumer, Producer, Split};
-use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
-use tauri::{AppHandle, Emitter};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
 
-use crate::adapters::cpal_audio::CpalAudioInput;
-use crate::domain::AudioDeviceInfo;
-use crate::dsp::fft::FftProcessor;
+use crate::adapters::cpal_audio::{list_devices_grouped, open_host, CpalAudioInput};
+use crate::adapters::wav_file::{write_wav_file, write_wav_file_with_metadata};
+use crate::commands::config::config_dir;
+use crate::domain::{
+    AudioDeviceInfo, AudioDevices, FftFormat, RxMode, RxRecordMode, VfoTracker, WavSampleFormat,
+};
+use crate::dsp::fft::{bin_range, quantize_db, tuning_error_hz, FftProcessor};
+use crate::dsp::input_trim::{apply_trim, trim_gain};
+use crate::dsp::sample_ring::{sample_ring, SampleConsumer, SampleProducer};
+use crate::events::{
+    self, AfcFrequencyPayload, AudioOverrunPayload, AudioStatusPayload, FftMagnitudes, FftPayload,
+    RxBitsPayload, RxCarrierChangedPayload, RxRatePayload, RxTextPayload,
+    SignalLevelPayload, SuggestRetunePayload, SymbolTimingPayload, TuningErrorPayload,
+};
+use crate::modem::acquire::{AcquireStep, CarrierAcquirer};
+use crate::modem::auto_record::{
+    recording_file_name, AutoRecordTrigger, AutoRecorder, FinishedRecording, MAX_RECORDING_SECS,
+};
+use crate::modem::decode_snapshot::SnapshotCapture;
 use crate::modem::decoder::Psk31Decoder;
-use crate::ports::AudioInput;
+use crate::modem::rx_channels::{
+    decode_channels, find_channel, new_channel, RxChannel, MAX_RX_CHANNELS,
+};
+use crate::modem::encoder::Psk31Encoder;
+use crate::modem::carrier_throttle::{CarrierThrottle, CarrierUpdate};
+use crate::modem::retune::RetuneAdvisor;
+use crate::modem::rx_rate::RxRateEstimator;
+use crate::modem::rx_text::filter_control_chars;
+use crate::ports::{AudioInput, RadioControl};
 use crate::state::AppState;
 
-/// Payload for the `fft-data` event sent to the frontend
-#[derive(Clone, Serialize)]
-struct FftPayload {
-    magnitudes: Vec<f32>,
+/// Read the radio's dial and return the RX carrier that keeps the current
+/// station centred, if the dial moved. Radio errors just skip this poll —
+/// the periodic UI sync is what reports a lost radio.
+pub fn poll_vfo_tracking(
+    tracker: &mut VfoTracker,
+    radio: &mut dyn RadioControl,
+    carrier_hz: f64,
+) -> Option<f64> {
+    let status = radio.get_status().ok()?;
+    tracker.update(status.frequency_hz as f64, &status.mode, carrier_hz)
 }
 
-/// Payload for the `audio-status` event
-#[derive(Clone, Serialize)]
-struct AudioStatusPayload {
-    status: String,
-}
+/// How often the VFO tracking thread reads the dial
+const VFO_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
-/// Payload for the `rx-text` event — decoded characters from the RX decoder
-#[derive(Clone, Serialize)]
-struct RxTextPayload {
-    text: String,
+/// Poll the dial while `track_vfo` is on and RX is decoding, sending each
+/// new RX carrier to the DSP loop. Runs on its own thread for the life of
+/// the audio thread, so slow CAT I/O never holds up the DSP loop.
+fn run_vfo_poll_thread(
+    app: AppHandle,
+    running: Arc<AtomicBool>,
+    rx_running: Arc<AtomicBool>,
+    rx_carrier_freq: Arc<Mutex<f64>>,
+    carrier_tx: Sender<f64>,
+) {
+    let mut tracker = VfoTracker::new();
+    let mut last_poll = Instant::now();
+    while running.load(Ordering::SeqCst) {
+        thread::sleep(Duration::from_millis(50));
+        if last_poll.elapsed() < VFO_POLL_INTERVAL {
+            continue;
+        }
+        last_poll = Instant::now();
+        let app_state = app.state::<AppState>();
+        let track = app_state.config.lock().map(|c| c.track_vfo).unwrap_or(false);
+        if !track || !rx_running.load(Ordering::Relaxed) {
+            continue;
+        }
+        let carrier = *rx_carrier_freq.lock().unwrap();
+        let new_carrier = match app_state.radio.lock() {
+            Ok(mut guard) => guard
+                .as_mut()
+                .and_then(|radio| poll_vfo_tracking(&mut tracker, radio.as_mut(), carrier)),
+            Err(_) => None,
+        };
+        if let Some(freq) = new_carrier {
+            if carrier_tx.send(freq).is_err() {
+                break;
+            }
+        }
+    }
 }
 
-/// Payload for the `signal-level` event — normalized AGC-derived signal strength
-#[derive(Clone, Serialize)]
-struct SignalLevelPayload {
-    level: f32,
+/// Selected audio host (see `set_audio_host`); `None` is the system default.
+pub(crate) fn selected_audio_host(state: &AppState) -> Option<String> {
+    state.config.lock().ok().and_then(|c| c.audio_host.clone())
 }
 
 #[tauri::command]
-pub fn list_audio_devices() -> Result<Vec<AudioDeviceInfo>, String> {
-    let input = CpalAudioInput::new();
+pub fn list_audio_devices(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<AudioDeviceInfo>, String> {
+    let input = CpalAudioInput::new().with_host(selected_audio_host(&state));
     input.list_devices().map_err(|e| e.to_string())
 }
 
+/// Input and output devices as two separate lists, defaults flagged per role.
+#[tauri::command]
+pub fn list_audio_devices_grouped(
+    state: tauri::State<'_, AppState>,
+) -> Result<AudioDevices, String> {
+    list_devices_grouped(selected_audio_host(&state).as_deref()).map_err(|e| e.to_string())
+}
+
+/// Audio hosts (APIs) available on this machine, system default first.
+#[tauri::command]
+pub fn list_audio_hosts() -> Vec<String> {
+    crate::adapters::cpal_audio::list_audio_hosts()
+}
+
+/// Select the audio host used to list devices and open streams from the
+/// next start on; `None` goes back to the system default.
+#[tauri::command]
+pub fn set_audio_host(
+    state: tauri::State<'_, AppState>,
+    host: Option<String>,
+) -> Result<(), String> {
+    open_host(host.as_deref()).map_err(|e| e.to_string())?;
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .audio_host = host;
+    Ok(())
+}
+
 #[tauri::command]
 pub fn start_audio_stream(
     app: AppHandle,
     state: tauri::State<'_, AppState>,
     device_id: String,
 ) -> Result<(), String> {
-    // Check if already running
-    if state.audio_running.load(Ordering::SeqCst) {
-        return Err("Audio stream already running".into());
-    }
-
+    state.begin_audio_start()?;
     let running = state.audio_running.clone();
-    running.store(true, Ordering::SeqCst);
 
     if let Err(e) = state
         .audio_device_name
@@ -74,45 +178,50 @@ pub fn start_audio_stream(
         .map(|mut name| *name = Some(device_id.clone()))
         .map_err(|_| "Audio state corrupted".to_string())
     {
-        running.store(false, Ordering::SeqCst);
-        let _ = app.emit("audio-status", AudioStatusPayload {
+        state.abort_audio_start()?;
+        let _ = app.emit(events::AUDIO_STATUS, AudioStatusPayload {
             status: format!("error: {e}"),
         });
         return Err(e);
     }
 
-    let rx_running = state.rx_running.clone();
-    let rx_carrier_freq = state.rx_carrier_freq.clone();
+    let rx = RxShared {
+        rx_running: state.rx_running.clone(),
+        decoder_reset_requested: state.decoder_reset_requested.clone(),
+        dsp_reset_requested: state.dsp_reset_requested.clone(),
+        acquire_requested: state.acquire_requested.clone(),
+        rx_carrier_freq: state.rx_carrier_freq.clone(),
+        rx_notch_freq: state.rx_notch_freq.clone(),
+        rx_bandpass: state.rx_bandpass.clone(),
+        rx_squelch: state.rx_squelch.clone(),
+        rx_channels: state.rx_channels.clone(),
+        rx_mode_requested: state.rx_mode_requested.clone(),
+        test_signal_active: state.test_signal_active.clone(),
+        test_signal: state.test_signal.clone(),
+        decode_snapshot: state.decode_snapshot.clone(),
+    };
     let audio_device_name = state.audio_device_name.clone();
-    let sample_rate = state.config.lock().unwrap().sample_rate;
+    let audio_sample_rate = state.audio_sample_rate.clone();
+    let preferred_rate = state.config.lock().unwrap().sample_rate;
 
     let handle = thread::spawn(move || {
-        run_audio_thread(app, running, rx_running, rx_carrier_freq, audio_device_name, device_id, sample_rate);
+        run_audio_thread(
+            app,
+            running,
+            rx,
+            audio_device_name,
+            audio_sample_rate,
+            device_id,
+            preferred_rate,
+        );
     });
 
-    state
-        .audio_thread
-        .lock()
-        .unwrap()
-        .replace(handle);
-
-    Ok(())
+    state.finish_audio_start(handle)
 }
 
 #[tauri::command]
 pub fn stop_audio_stream(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    // Stop RX decoder first (it runs inside the audio thread)
-    state.rx_running.store(false, Ordering::SeqCst);
-
-    // Signal the thread to stop
-    state.audio_running.store(false, Ordering::SeqCst);
-
-    // Join the thread — it clears audio_device_name on exit
-    if let Some(handle) = state.audio_thread.lock().unwrap().take() {
-        handle.join().map_err(|_| "Audio thread panicked".to_string())?;
-    }
-
-    Ok(())
+    state.stop_audio()
 }
 
 #[tauri::command]
@@ -120,6 +229,8 @@ pub fn start_rx(state: tauri::State<'_, AppState>) -> Result<(), String> {
     if !state.audio_running.load(Ordering::SeqCst) {
         return Err("Audio stream not running. Start audio first.".into());
     }
+    let acquire = state.config.lock().map_err(|_| "config lock poisoned".to_string())?.rx_acquire;
+    state.acquire_requested.store(acquire, Ordering::SeqCst);
     state.rx_running.store(true, Ordering::SeqCst);
     Ok(())
 }
@@ -130,6 +241,354 @@ pub fn stop_rx(state: tauri::State<'_, AppState>) -> Result<(), String> {
     Ok(())
 }
 
+/// Ask the audio thread to reset the RX decoder on its next iteration.
+///
+/// Recovers from a decoder stuck in the wrong bit-inversion sense or a garbled
+/// Varicode state without retuning or restarting the audio stream.
+#[tauri::command]
+pub fn reset_decoder(state: tauri::State<'_, AppState>) -> Result<(), String> {
+    state.decoder_reset_requested.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// RX display text received so far (bounded; oldest text dropped first).
+#[tauri::command]
+pub fn get_rx_transcript(state: tauri::State<'_, AppState>) -> Result<String, String> {
+    Ok(state
+        .rx_transcript
+        .lock()
+        .map_err(|_| "RX transcript corrupted".to_string())?
+        .text())
+}
+
+/// Start over between QSOs: clear the RX transcript, reset the decoder and
+/// zero the signal indicators, then emit `rx-cleared`.
+#[tauri::command]
+pub fn clear_rx_session(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    clear_rx_state(&state)?;
+    let _ = app.emit(events::RX_CLEARED, ());
+    Ok(())
+}
+
+/// Everything `clear_rx_session` does short of emitting the event. The
+/// audio thread resets the decoder and RX rate on its next iteration.
+fn clear_rx_state(state: &AppState) -> Result<(), String> {
+    state.rx_transcript.lock().map_err(|_| "RX transcript corrupted".to_string())?.clear();
+    state.decoder_reset_requested.store(true, Ordering::SeqCst);
+    *state.latest_spectrum.lock().map_err(|_| "spectrum cache corrupted".to_string())? = None;
+    state.status.lock().map_err(|_| "status lock poisoned".to_string())?.signal_level = 0.0;
+    Ok(())
+}
+
+/// Ask the audio thread to rebuild all DSP state (FFT, decoder, sample
+/// buffer) on its next iteration, without stopping the audio stream.
+///
+/// For after a band or config change, when stale AGC/Costas/clock state
+/// would otherwise cause a transient.
+#[tauri::command]
+pub fn reset_dsp(state: tauri::State<'_, AppState>) -> Result<(), String> {
+    state.dsp_reset_requested.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// If a DSP reset was requested, replace the FFT processor and decoder with
+/// fresh ones (same FFT size, decoder on `carrier`) and drop the samples
+/// waiting for the next FFT frame, clearing the request. Returns true if it did.
+fn apply_dsp_reset_request(
+    requested: &AtomicBool,
+    fft: &mut FftProcessor,
+    decoder: &mut Psk31Decoder,
+    sample_buf: &mut Vec<f32>,
+    carrier: f64,
+    sample_rate: u32,
+) -> bool {
+    if !requested.swap(false, Ordering::SeqCst) {
+        return false;
+    }
+    *fft = FftProcessor::new(fft.fft_size());
+    *decoder = Psk31Decoder::for_mode(carrier, sample_rate, decoder.rx_mode());
+    sample_buf.clear();
+    true
+}
+
+/// Switch the decoder to a different baud rate / modulation without
+/// stopping the audio stream. The change is picked up by the audio thread
+/// before its next batch of samples; the carrier is kept.
+#[tauri::command]
+pub fn set_rx_mode(state: tauri::State<'_, AppState>, rx_mode: RxMode) -> Result<(), String> {
+    rx_mode.validate().map_err(|e| e.to_string())?;
+    *state.rx_mode_requested.lock().map_err(|_| "RX state corrupted".to_string())? = Some(rx_mode);
+    Ok(())
+}
+
+/// If a new RX mode was requested, replace `decoder` with one built for it
+/// on `carrier`, clearing the request. Returns the mode applied, if any.
+fn apply_rx_mode_request(
+    requested: &Mutex<Option<RxMode>>,
+    decoder: &mut Psk31Decoder,
+    carrier: f64,
+    sample_rate: u32,
+) -> Option<RxMode> {
+    let rx_mode = requested.lock().ok()?.take()?;
+    *decoder = Psk31Decoder::for_mode(carrier, sample_rate, rx_mode);
+    Some(rx_mode)
+}
+
+/// Reset `decoder` if a reset was requested, clearing the request.
+/// Returns true if a reset happened.
+fn apply_decoder_reset_request(requested: &AtomicBool, decoder: &mut Psk31Decoder) -> bool {
+    if requested.swap(false, Ordering::SeqCst) {
+        decoder.reset();
+        true
+    } else {
+        false
+    }
+}
+
+/// Push one capture callback's samples into the ring buffer. When it is
+/// full the oldest samples are overwritten, so the newest audio always wins.
+///
+/// While a test signal is queued (`injecting` set), its samples replace the
+/// soundcard's one for one, so injected audio arrives at the device's own
+/// rate. Never blocks: if the queue is busy this callback passes real audio.
+/// `injecting` is only cleared with the queue lock held, and
+/// `inject_test_signal` sets it under the same lock, so a signal queued just
+/// as the last one runs out can't be stranded behind a cleared flag.
+fn push_input(
+    ring: &mut SampleProducer,
+    samples: &[f32],
+    injecting: &AtomicBool,
+    injected: &Mutex<VecDeque<f32>>,
+) {
+    let mut queue = if injecting.load(Ordering::Acquire) {
+        injected.try_lock().ok()
+    } else {
+        None
+    };
+    match queue.as_mut() {
+        Some(q) => ring.push_iter_overwrite(
+            samples.iter().map(|&sample| q.pop_front().unwrap_or(sample)),
+        ),
+        None => ring.push_slice_overwrite(samples),
+    }
+    if let Some(queue) = &queue {
+        if queue.is_empty() {
+            injecting.store(false, Ordering::Release);
+        }
+    }
+}
+
+/// Feed a generated PSK-31 transmission of `text` at `carrier_freq` into the
+/// audio input as if it came from the soundcard, to check the waterfall and
+/// decoder without a radio. Queues behind any signal still being injected.
+#[tauri::command]
+pub fn inject_test_signal(
+    state: tauri::State<'_, AppState>,
+    text: String,
+    carrier_freq: f64,
+) -> Result<(), String> {
+    if !state.audio_running.load(Ordering::SeqCst) {
+        return Err("Audio stream not running".into());
+    }
+    if !(200.0..=3500.0).contains(&carrier_freq) {
+        return Err("Carrier frequency must be between 200-3500 Hz".into());
+    }
+    let sample_rate = input_sample_rate(&state)?;
+    let baud = state.config.lock().map_err(|_| "config lock poisoned".to_string())?.modem_baud;
+    let samples = Psk31Encoder::new(sample_rate, carrier_freq).with_baud(baud).encode(&text);
+    let mut queue = state
+        .test_signal
+        .lock()
+        .map_err(|_| "test signal queue corrupted".to_string())?;
+    queue.extend(samples);
+    // Under the queue lock, so the capture callback can't clear it in between
+    state.test_signal_active.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Longest decode snapshot `capture_decode_snapshot` will record
+const MAX_SNAPSHOT_SECS: f64 = 60.0;
+
+/// Write a completed snapshot as a lossless float WAV with its metadata chunk.
+fn write_decode_snapshot(path: &Path, capture: &SnapshotCapture) -> Result<(), String> {
+    let meta = capture.meta().ok_or("Snapshot is not complete")?;
+    let json = serde_json::to_string(meta).map_err(|e| format!("Serialization error: {e}"))?;
+    write_wav_file_with_metadata(
+        path,
+        capture.samples(),
+        meta.sample_rate,
+        WavSampleFormat::Float32,
+        &json,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Record the next `seconds` of raw RX audio with the current decoder
+/// settings to `snapshots/` in the config dir. Returns the file path.
+/// Runs off the main thread since it waits for the audio to arrive.
+#[tauri::command(async)]
+pub fn capture_decode_snapshot(app: AppHandle, seconds: f64) -> Result<String, String> {
+    if !(seconds > 0.0 && seconds <= MAX_SNAPSHOT_SECS) {
+        return Err(format!("Snapshot length must be 0-{MAX_SNAPSHOT_SECS} seconds"));
+    }
+    let state = app.state::<AppState>();
+    if !state.audio_running.load(Ordering::SeqCst) {
+        return Err("Audio stream not running".into());
+    }
+    let sample_rate = input_sample_rate(&state)?;
+    {
+        let mut snapshot = state
+            .decode_snapshot
+            .lock()
+            .map_err(|_| "snapshot state corrupted".to_string())?;
+        if snapshot.is_some() {
+            return Err("A decode snapshot is already being captured".into());
+        }
+        *snapshot = Some(SnapshotCapture::new(seconds, sample_rate));
+    }
+
+    // Generous deadline in case the input stalls
+    let deadline = Instant::now() + Duration::from_secs_f64(seconds + 5.0);
+    let capture = loop {
+        thread::sleep(Duration::from_millis(50));
+        let mut snapshot = state
+            .decode_snapshot
+            .lock()
+            .map_err(|_| "snapshot state corrupted".to_string())?;
+        if snapshot.as_ref().is_some_and(SnapshotCapture::is_complete) {
+            break snapshot.take().unwrap();
+        }
+        if !state.audio_running.load(Ordering::SeqCst) {
+            *snapshot = None;
+            return Err("Audio stream stopped during snapshot".into());
+        }
+        if Instant::now() > deadline {
+            *snapshot = None;
+            return Err("Timed out waiting for snapshot audio".into());
+        }
+    };
+
+    let dir = config_dir(&app)?.join("snapshots");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create snapshots dir: {e}"))?;
+    let unix_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = dir.join(recording_file_name(unix_secs).replacen("rx-", "snapshot-", 1));
+    write_decode_snapshot(&path, &capture)?;
+    Ok(path.display().to_string())
+}
+
+/// Feed one batch to the auto-recorder while `rx_record_mode` is auto,
+/// creating it when auto mode comes on and closing its recording when it
+/// goes off. Finished recordings are saved by `save_auto_recording`.
+fn auto_record(
+    app: &AppHandle,
+    recorder: &mut Option<AutoRecorder>,
+    samples: &[f32],
+    lock_quality: f32,
+    sample_rate: u32,
+) {
+    let settings = app.state::<AppState>().config.lock().ok().and_then(|c| {
+        (c.rx_record_mode == RxRecordMode::Auto)
+            .then_some((c.rx_record_threshold, c.rx_record_trailing_ms))
+    });
+    let finished = match settings {
+        Some((threshold, trailing_ms)) => {
+            let recorder = recorder.get_or_insert_with(|| {
+                let trailing = (sample_rate as u64 * trailing_ms / 1000) as usize;
+                let max_samples = (sample_rate as u64 * MAX_RECORDING_SECS) as usize;
+                AutoRecorder::new(AutoRecordTrigger::new(threshold, trailing), max_samples)
+            });
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            recorder.push(samples, lock_quality, now)
+        }
+        None => recorder.take().and_then(AutoRecorder::finish),
+    };
+    if let Some(recording) = finished {
+        save_auto_recording(app, recording, sample_rate);
+    }
+}
+
+/// Write an auto-recording to `recordings/` in the config dir, on its own
+/// thread so the DSP loop never waits on the disk.
+fn save_auto_recording(app: &AppHandle, recording: FinishedRecording, sample_rate: u32) {
+    let dir = match config_dir(app) {
+        Ok(dir) => dir.join("recordings"),
+        Err(e) => {
+            log::warn!("Failed to save auto-recording: {e}");
+            return;
+        }
+    };
+    thread::spawn(move || {
+        let path = dir.join(recording_file_name(recording.started_unix_secs));
+        let result = std::fs::create_dir_all(&dir)
+            .map_err(|e| e.to_string())
+            .and_then(|()| {
+                write_wav_file(&path, &recording.samples, sample_rate, WavSampleFormat::Float32)
+                    .map_err(|e| e.to_string())
+            });
+        match result {
+            Ok(()) => log::info!("Auto-recorded {}", path.display()),
+            Err(e) => log::warn!("Failed to save auto-recording: {e}"),
+        }
+    });
+}
+
+/// Minimum time between `suggest-retune` events
+const RETUNE_HOLDOFF_SECS: f64 = 5.0;
+
+/// How long acquire mode searches for a signal before leaving the carrier be
+const ACQUIRE_TIMEOUT_SECS: f64 = 5.0;
+
+/// Minimum time between carrier changes reaching the decoder
+const CARRIER_THROTTLE_SECS: f64 = 0.1;
+
+/// Backlog (in drain budgets) beyond which the DSP loop drops old input
+const MAX_BACKLOG_BUDGETS: usize = 3;
+
+/// Move up to `budget` samples from the input ring buffer into `out`. If
+/// more than `MAX_BACKLOG_BUDGETS` budgets are waiting (the loop stalled),
+/// the oldest are dropped first so only the newest budget's worth remains.
+/// Returns the number of samples dropped.
+fn drain_input(consumer: &mut SampleConsumer, budget: usize, out: &mut Vec<f32>) -> usize {
+    let backlog = consumer.occupied_len();
+    let dropped = if backlog > budget * MAX_BACKLOG_BUDGETS {
+        consumer.skip(backlog - budget)
+    } else {
+        0
+    };
+    consumer.pop_into(budget, out);
+    dropped
+}
+
+/// Feed `samples` to the decoder, appending decoded characters to `out` and,
+/// when given, each decided bit to `bits` as `'0'`/`'1'`.
+/// While `muted` (transmitting) the decoder sees nothing, so its AGC holds
+/// the pre-TX gain rather than winding up to maximum on a silent input.
+fn decode_samples(
+    decoder: &mut Psk31Decoder,
+    samples: &[f32],
+    muted: bool,
+    out: &mut String,
+    mut bits: Option<&mut String>,
+) {
+    if muted {
+        return;
+    }
+    for &sample in samples {
+        if let Some(ch) = decoder.process(sample) {
+            out.push(ch);
+        }
+        if let (Some(bits), Some(bit)) = (bits.as_deref_mut(), decoder.last_bit()) {
+            bits.push(if bit { '1' } else { '0' });
+        }
+    }
+}
+
 #[tauri::command]
 pub fn set_carrier_frequency(
     state: tauri::State<'_, AppState>,
@@ -144,68 +603,279 @@ pub fn set_carrier_frequency(
     Ok(())
 }
 
+/// Nearest FFT bin centre to `freq_hz`, for snapping waterfall clicks.
+#[tauri::command]
+pub fn snap_carrier_to_bin(freq_hz: f64, fft_size: usize, sample_rate: u32) -> Result<f64, String> {
+    if fft_size == 0 || sample_rate == 0 {
+        return Err("FFT size and sample rate must be non-zero".into());
+    }
+    Ok(crate::dsp::fft::snap_carrier_to_bin(freq_hz, fft_size, sample_rate as f64))
+}
+
+/// Notch a steady interferer (heterodyne, tune carrier) out of the RX audio
+/// ahead of the decoder, or `None` to turn the notch off.
+#[tauri::command]
+pub fn set_rx_notch(state: tauri::State<'_, AppState>, freq_hz: Option<f64>) -> Result<(), String> {
+    if freq_hz.is_some_and(|f| !(200.0..=3500.0).contains(&f)) {
+        return Err("Notch frequency must be between 200-3500 Hz".into());
+    }
+    *state.rx_notch_freq.lock().map_err(|_| "RX state corrupted".to_string())? = freq_hz;
+    Ok(())
+}
+
+/// Pass only ±100 Hz around the RX carrier to the decoder, so strong
+/// neighbours don't desensitize the AGC or pull the Costas loop.
+#[tauri::command]
+pub fn set_rx_bandpass(state: tauri::State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    state.rx_bandpass.store(enabled, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Rate the audio input is running at: the negotiated device rate while
+/// streaming, otherwise the configured one
+fn input_sample_rate(state: &AppState) -> Result<u32, String> {
+    let negotiated =
+        *state.audio_sample_rate.lock().map_err(|_| "Audio state corrupted".to_string())?;
+    if let Some(rate) = negotiated {
+        return Ok(rate);
+    }
+    Ok(state.config.lock().map_err(|_| "config lock poisoned".to_string())?.sample_rate)
+}
+
+/// Open an extra RX channel on `freq_hz`, decoded alongside the main
+/// decoder at the configured baud (see `modem::rx_channels`). A no-op if the
+/// channel is already open.
+#[tauri::command]
+pub fn start_rx_channel(state: tauri::State<'_, AppState>, freq_hz: f64) -> Result<(), String> {
+    if !(200.0..=3500.0).contains(&freq_hz) {
+        return Err("Channel frequency must be between 200-3500 Hz".into());
+    }
+    let sample_rate = input_sample_rate(&state)?;
+    let baud = state.config.lock().map_err(|_| "config lock poisoned".to_string())?.modem_baud;
+    let mut channels = state.rx_channels.lock().map_err(|_| "RX state corrupted".to_string())?;
+    if find_channel(&channels, freq_hz).is_some() {
+        return Ok(());
+    }
+    if channels.len() >= MAX_RX_CHANNELS {
+        return Err(format!("At most {MAX_RX_CHANNELS} RX channels can be open"));
+    }
+    let rx_mode = RxMode { baud, ..RxMode::default() };
+    channels.push(new_channel(freq_hz, sample_rate, rx_mode));
+    Ok(())
+}
+
+/// Close the extra RX channel on `freq_hz`.
+#[tauri::command]
+pub fn stop_rx_channel(state: tauri::State<'_, AppState>, freq_hz: f64) -> Result<(), String> {
+    let mut channels = state.rx_channels.lock().map_err(|_| "RX state corrupted".to_string())?;
+    let index = find_channel(&channels, freq_hz)
+        .ok_or_else(|| format!("No RX channel open on {freq_hz} Hz"))?;
+    channels.remove(index);
+    Ok(())
+}
+
+/// Symbol squelch for the RX decoder: symbols weaker than `level` (on the
+/// AGC's 0.5 scale) decide no bits. Raise it when noise prints garbage.
+#[tauri::command]
+pub fn set_rx_squelch(state: tauri::State<'_, AppState>, level: f32) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&level) {
+        return Err("Squelch level must be between 0 and 1".into());
+    }
+    *state.rx_squelch.lock().map_err(|_| "RX state corrupted".to_string())? = level;
+    Ok(())
+}
+
+/// Format FFT magnitudes (positive bins, as from `FftProcessor::compute`) as a
+/// `frequency_hz,magnitude_db` CSV, one row per bin.
+pub fn spectrum_to_csv(magnitudes_db: &[f32], sample_rate: u32) -> String {
+    let bin_hz = sample_rate as f64 / (2 * magnitudes_db.len().max(1)) as f64;
+    let mut csv = String::from("frequency_hz,magnitude_db\n");
+    for (i, db) in magnitudes_db.iter().enumerate() {
+        let _ = writeln!(csv, "{},{db:.2}", i as f64 * bin_hz);
+    }
+    csv
+}
+
+/// Write the most recent waterfall spectrum to `path` as CSV.
+#[tauri::command]
+pub fn export_spectrum_csv(state: tauri::State<'_, AppState>, path: String) -> Result<(), String> {
+    if !state.audio_running.load(Ordering::SeqCst) {
+        return Err("Audio is not running — start the audio stream to capture a spectrum".into());
+    }
+    let magnitudes = state
+        .latest_spectrum
+        .lock()
+        .map_err(|_| "spectrum cache corrupted".to_string())?
+        .clone()
+        .ok_or("No spectrum captured yet — wait for the waterfall to start")?;
+    let sample_rate = input_sample_rate(&state)?;
+    std::fs::write(Path::new(&path), spectrum_to_csv(&magnitudes, sample_rate))
+        .map_err(|e| format!("Failed to write {path}: {e}"))
+}
+
+/// Recent waterfall rows (full span, downsampled), oldest first, for
+/// repainting after the frontend reloads.
+#[tauri::command]
+pub fn get_waterfall_history(state: tauri::State<'_, AppState>) -> Result<Vec<Vec<f32>>, String> {
+    Ok(state
+        .waterfall_history
+        .lock()
+        .map_err(|_| "waterfall history corrupted".to_string())?
+        .rows())
+}
+
+/// Limit `fft-data` to a `[low_hz, high_hz]` sub-band, or `None` for the
+/// full 0–Nyquist span.
+#[tauri::command]
+pub fn set_waterfall_span(
+    state: tauri::State<'_, AppState>,
+    span: Option<(f64, f64)>,
+) -> Result<(), String> {
+    if let Some((low, high)) = span {
+        if !(low >= 0.0 && low < high) {
+            return Err(format!("Invalid waterfall span {low}-{high} Hz"));
+        }
+    }
+    *state.waterfall_span.lock().map_err(|_| "waterfall state corrupted".to_string())? = span;
+    Ok(())
+}
+
+/// RX decoder controls and test-signal queue shared between AppState and the audio thread
+struct RxShared {
+    rx_running: Arc<AtomicBool>,
+    decoder_reset_requested: Arc<AtomicBool>,
+    dsp_reset_requested: Arc<AtomicBool>,
+    acquire_requested: Arc<AtomicBool>,
+    rx_carrier_freq: Arc<Mutex<f64>>,
+    rx_notch_freq: Arc<Mutex<Option<f64>>>,
+    rx_bandpass: Arc<AtomicBool>,
+    rx_squelch: Arc<Mutex<f32>>,
+    rx_channels: Arc<Mutex<Vec<RxChannel>>>,
+    rx_mode_requested: Arc<Mutex<Option<RxMode>>>,
+    test_signal_active: Arc<AtomicBool>,
+    test_signal: Arc<Mutex<VecDeque<f32>>>,
+    decode_snapshot: Arc<Mutex<Option<SnapshotCapture>>>,
+}
+
 /// The main audio processing loop, runs on its own thread.
 ///
 /// Flow: cpal callback → ring buffer → DSP loop → FFT + RX decoder → emit events
 fn run_audio_thread(
     app: AppHandle,
     running: Arc<AtomicBool>,
-    rx_running: Arc<AtomicBool>,
-    rx_carrier_freq: Arc<Mutex<f64>>,
+    rx: RxShared,
     audio_device_name: Arc<Mutex<Option<String>>>,
+    audio_sample_rate: Arc<Mutex<Option<u32>>>,
     device_id: String,
-    sample_rate: u32,
+    preferred_rate: u32,
 ) {
+    let RxShared {
+        rx_running,
+        decoder_reset_requested,
+        dsp_reset_requested,
+        acquire_requested,
+        rx_carrier_freq,
+        rx_notch_freq,
+        rx_bandpass,
+        rx_squelch,
+        rx_channels,
+        rx_mode_requested,
+        test_signal_active,
+        test_signal,
+        decode_snapshot,
+    } = rx;
+
     // Emit status
-    let _ = app.emit("audio-status", AudioStatusPayload { status: "running".into() });
+    let _ = app.emit(events::AUDIO_STATUS, AudioStatusPayload { status: "running".into() });
 
     // Create ring buffer — 8192 samples gives ~170ms buffer at 48kHz
-    // Think of it like a Python `collections.deque(maxlen=8192)` but lock-free
-    let rb = HeapRb::<f32>::new(8192);
-    let (mut producer, mut consumer) = rb.split();
+    // Think of it like a Python `collections.deque(maxlen=8192)`: when full,
+    // the oldest samples go. It is lock-free (see `dsp::sample_ring`): the
+    // capture callback owns the write end and never waits on this loop.
+    let (mut capture_ring, mut input_ring) = sample_ring(8192);
+
+    // Samples lost to overruns (overwritten or dropped catching up) since
+    // the last audio-overrun event
+    let mut overrun: usize = 0;
 
     // Create audio input and start capture
-    let mut audio_input = CpalAudioInput::new();
+    let mut audio_input = CpalAudioInput::new()
+        .with_host(selected_audio_host(&app.state::<AppState>()))
+        .with_sample_rate(preferred_rate);
     let capture_result = audio_input.start(
         &device_id,
         Box::new(move |samples: &[f32]| {
-            // Push samples into ring buffer; try_push drops the newest sample if full
-            // (oldest data stays). For real-time audio, push_overwrite (drop oldest)
-            // would be preferable — consider switching if latency becomes an issue.
-            for &sample in samples {
-                let _ = producer.try_push(sample);
-            }
+            push_input(&mut capture_ring, samples, &test_signal_active, &test_signal);
         }),
     );
 
     if let Err(e) = capture_result {
         log::error!("Failed to start audio capture: {e}");
         running.store(false, Ordering::SeqCst);
-        let _ = app.emit("audio-status", AudioStatusPayload {
+        let _ = app.emit(events::AUDIO_STATUS, AudioStatusPayload {
             status: format!("error: {e}"),
         });
         return;
     }
 
+    // The device may not offer the configured rate; everything below runs at
+    // the rate it was actually opened at
+    let sample_rate = audio_input.sample_rate().unwrap_or(preferred_rate);
+    *audio_sample_rate.lock().unwrap() = Some(sample_rate);
+    log::info!("Audio input running at {sample_rate} Hz");
+
     // DSP loop: pull samples, compute FFT + RX decode, emit to frontend
     let fft_size = 4096;
     let hop_size = 2048; // 50% overlap for smooth waterfall scrolling
     let mut fft = FftProcessor::new(fft_size);
     let mut sample_buf: Vec<f32> = Vec::with_capacity(fft_size);
 
-    // RX decoder — created with configured sample rate and initial carrier freq
+    // RX decoder — created with the negotiated sample rate, configured baud and initial carrier
     let initial_carrier = *rx_carrier_freq.lock().unwrap();
-    let mut decoder = Psk31Decoder::new(initial_carrier, sample_rate);
+    let baud = app.state::<AppState>().config.lock().map_or(31.25, |c| c.modem_baud);
+    let rx_mode = RxMode { baud, ..RxMode::default() };
+    let mut decoder = Psk31Decoder::for_mode(initial_carrier, sample_rate, rx_mode);
+    let mut applied_notch: Option<f64> = None;
 
     // Buffer decoded chars to emit in batches (reduces event overhead)
     let mut rx_text_buf = String::new();
+    let mut rx_bits_buf = String::new();
+
+    // Retune suggestions: at most one per RETUNE_HOLDOFF_SECS
+    let mut retune_advisor =
+        RetuneAdvisor::new((sample_rate as f64 * RETUNE_HOLDOFF_SECS) as usize);
+
+    // Marker drags: apply the latest carrier at most once per CARRIER_THROTTLE_SECS
+    let mut carrier_throttle =
+        CarrierThrottle::new((sample_rate as f64 * CARRIER_THROTTLE_SECS) as usize);
+
+    // Acquire mode: searching for a signal to settle on, after start_rx
+    let mut acquirer: Option<CarrierAcquirer> = None;
+
+    // Received typing speed, clocked by the samples fed to the decoder
+    let mut rx_rate = RxRateEstimator::new(sample_rate);
 
     // Throttle signal-level events to ~500ms (100 iterations × 5ms sleep)
     let mut signal_emit_counter: u32 = 0;
+    // Any trimmed input at full scale since the last signal-level event
+    let mut clipped = false;
+
+    // VFO tracking: a side thread polls the dial and sends the new carrier here
+    let (vfo_carrier_tx, vfo_carrier_rx) = mpsc::channel();
+    let vfo_thread = {
+        let (app, running) = (app.clone(), running.clone());
+        let (rx_running, rx_carrier_freq) = (rx_running.clone(), rx_carrier_freq.clone());
+        thread::spawn(move || {
+            run_vfo_poll_thread(app, running, rx_running, rx_carrier_freq, vfo_carrier_tx)
+        })
+    };
 
     // Set when cpal error callback fires (device removed mid-stream)
     let mut device_lost = false;
 
+    // Auto-record: one WAV per transmission while `rx_record_mode` is auto
+    let mut auto_recorder: Option<AutoRecorder> = None;
+
     while running.load(Ordering::SeqCst) {
         // Check if cpal silently killed the stream (e.g. USB device removed)
         if !audio_input.is_running() {
@@ -213,29 +883,133 @@ fn run_audio_thread(
             device_lost = true;
             break;
         }
-        // Drain available samples from ring buffer into a temporary vec
-        // so we can use them for both FFT and RX decoding
-        let mut new_samples: Vec<f32> = Vec::new();
-        while let Some(sample) = consumer.try_pop() {
-            new_samples.push(sample);
+        // Drain up to one budget of samples into a temporary vec so we can
+        // use them for both FFT and RX decoding; after a stall, catch up by
+        // dropping the oldest rather than processing one long burst
+        let budget_ms = app.state::<AppState>().config.lock().map_or(40, |c| c.rx_drain_budget_ms);
+        let budget = (sample_rate as u64 * budget_ms / 1000) as usize;
+        let mut new_samples: Vec<f32> = Vec::with_capacity(budget);
+        let dropped = drain_input(&mut input_ring, budget, &mut new_samples);
+        if dropped > 0 {
+            log::warn!("RX audio backlog: dropped {dropped} samples to catch up");
+        }
+        overrun += dropped + input_ring.take_overwritten();
+        let trim_db = app.state::<AppState>().config.lock().map_or(0.0, |c| c.input_trim_db);
+        clipped |= apply_trim(&mut new_samples, trim_gain(trim_db)) > 0;
+
+        // Honour pending reset_dsp / reset_decoder requests before feeding new samples
+        let carrier = *rx_carrier_freq.lock().unwrap();
+        if apply_dsp_reset_request(
+            &dsp_reset_requested,
+            &mut fft,
+            &mut decoder,
+            &mut sample_buf,
+            carrier,
+            sample_rate,
+        ) {
+            // The fresh decoder has no notch; re-apply it below
+            applied_notch = None;
+            log::info!("DSP state reset");
+        }
+        if let Some(rx_mode) =
+            apply_rx_mode_request(&rx_mode_requested, &mut decoder, carrier, sample_rate)
+        {
+            applied_notch = None;
+            log::info!("RX decoder rebuilt for {} baud {:?}", rx_mode.baud, rx_mode.modulation);
+        }
+        if apply_decoder_reset_request(&decoder_reset_requested, &mut decoder) {
+            rx_rate.reset();
+            log::info!("RX decoder reset");
+        }
+        if acquire_requested.swap(false, Ordering::SeqCst) {
+            let frames = (sample_rate as f64 * ACQUIRE_TIMEOUT_SECS) as usize / hop_size;
+            acquirer = Some(CarrierAcquirer::new(frames));
         }
 
-        // RX decoding: feed every new sample to the decoder when enabled
+        // Decode snapshot: raw input, stamped with the decoder settings once full
+        if let Some(capture) = decode_snapshot.lock().unwrap().as_mut() {
+            if capture.push(&new_samples) {
+                capture.finish(&decoder, sample_rate, applied_notch);
+            }
+        }
+
+        // RX decoding: feed every new sample to the decoder when enabled,
+        // unless RX is muted because we're transmitting
         if rx_running.load(Ordering::SeqCst) {
+            let app_state = app.state::<AppState>();
+            let muted = app_state.config.lock().map(|c| c.rx_mute_during_tx).unwrap_or(true)
+                && app_state.status.lock().map(|s| s.tx_running).unwrap_or(false);
             // Check if carrier frequency changed (click-to-tune)
-            decoder.update_carrier_if_changed(*rx_carrier_freq.lock().unwrap());
+            let requested = *rx_carrier_freq.lock().unwrap();
+            match carrier_throttle.update(decoder.carrier_freq(), requested, new_samples.len()) {
+                Some(CarrierUpdate::Smooth(freq)) => decoder.retune_smooth(freq),
+                Some(CarrierUpdate::Reset(freq)) => decoder.set_carrier_freq(freq),
+                None => {}
+            }
+            decoder.set_dc_blocker(app_state.config.lock().map_or(true, |c| c.rx_dc_blocker));
+            decoder.set_bandpass(rx_bandpass.load(Ordering::Relaxed));
+            decoder.set_squelch(*rx_squelch.lock().unwrap());
+            let notch = *rx_notch_freq.lock().unwrap();
+            if notch != applied_notch {
+                decoder.set_notch(notch);
+                applied_notch = notch;
+            }
 
-            for &sample in &new_samples {
-                if let Some(ch) = decoder.process(sample) {
-                    rx_text_buf.push(ch);
-                }
+            let bits_debug = app_state.config.lock().map(|c| c.rx_bits_debug).unwrap_or(false);
+            let bits = bits_debug.then_some(&mut rx_bits_buf);
+            decode_samples(&mut decoder, &new_samples, muted, &mut rx_text_buf, bits);
+            if !rx_bits_buf.is_empty() {
+                let bits = std::mem::take(&mut rx_bits_buf);
+                let _ = app.emit(events::RX_BITS, RxBitsPayload { bits });
             }
+            rx_rate.update(new_samples.len(), rx_text_buf.chars().count());
+            let lock_quality = decoder.lock_quality();
+            auto_record(&app, &mut auto_recorder, &new_samples, lock_quality, sample_rate);
 
-            // Emit any decoded text as a batch
+            // Emit any decoded text as a batch: raw for debugging, and the
+            // display stream with control characters stripped unless disabled
+            let filter = app_state.config.lock().map(|c| c.rx_filter_control).unwrap_or(true);
+            let channel_freq = decoder.carrier_freq();
             if !rx_text_buf.is_empty() {
-                let _ = app.emit("rx-text", RxTextPayload { text: rx_text_buf.clone() });
+                let text = rx_text_buf.clone();
+                let _ = app.emit(events::RX_TEXT_RAW, RxTextPayload { text, channel_freq });
+                let text = if filter {
+                    filter_control_chars(&rx_text_buf)
+                } else {
+                    rx_text_buf.clone()
+                };
+                if !text.is_empty() {
+                    if let Ok(mut transcript) = app_state.rx_transcript.lock() {
+                        transcript.push(&text);
+                    }
+                    let _ = app.emit(events::RX_TEXT, RxTextPayload { text, channel_freq });
+                }
                 rx_text_buf.clear();
             }
+
+            // Extra channels see the same samples; their text is tagged with the
+            // channel and skips the transcript. Decode from a snapshot of the list so
+            // opening or closing a channel doesn't wait on the decode.
+            let channels = match rx_channels.lock() {
+                Ok(channels) if !muted => channels.clone(),
+                _ => Vec::new(),
+            };
+            let channel_text = decode_channels(&channels, &new_samples);
+            for (channel_freq, raw) in channel_text {
+                let text = raw.clone();
+                let _ = app.emit(events::RX_TEXT_RAW, RxTextPayload { text, channel_freq });
+                let text = if filter { filter_control_chars(&raw) } else { raw };
+                if !text.is_empty() {
+                    let _ = app.emit(events::RX_TEXT, RxTextPayload { text, channel_freq });
+                }
+            }
+        }
+
+        // RX stopped: close any auto-recording in progress
+        if !rx_running.load(Ordering::SeqCst) {
+            if let Some(recording) = auto_recorder.take().and_then(AutoRecorder::finish) {
+                save_auto_recording(&app, recording, sample_rate);
+            }
         }
 
         // Accumulate samples for FFT processing
@@ -244,22 +1018,139 @@ fn run_audio_thread(
         // When we have enough samples, compute FFT with 50% overlap
         while sample_buf.len() >= fft_size {
             let magnitudes = fft.compute(&sample_buf[..fft_size]);
-            let _ = app.emit("fft-data", FftPayload { magnitudes });
+
+            // Acquire mode: settle the decoder on the strongest signal found
+            if let Some(search) = acquirer.as_mut().filter(|_| rx_running.load(Ordering::Relaxed)) {
+                match search.update(&magnitudes, sample_rate as f64) {
+                    AcquireStep::Searching => {}
+                    AcquireStep::Found(freq) => {
+                        decoder.set_carrier_freq(freq);
+                        *rx_carrier_freq.lock().unwrap() = freq;
+                        if let Ok(mut config) = app.state::<AppState>().config.lock() {
+                            config.carrier_freq = freq;
+                        }
+                        let _ = app.emit(
+                            events::RX_CARRIER_CHANGED,
+                            RxCarrierChangedPayload { carrier_freq: freq },
+                        );
+                        log::info!("RX acquired signal at {freq:.1} Hz");
+                        acquirer = None;
+                    }
+                    AcquireStep::GaveUp => {
+                        log::info!("RX acquire found no signal; carrier unchanged");
+                        acquirer = None;
+                    }
+                }
+            }
+
+            // Tuning indicator: how far the RX carrier is from the signal's centre
+            if rx_running.load(Ordering::Relaxed) {
+                let carrier = *rx_carrier_freq.lock().unwrap();
+                if let Some(offset_hz) = tuning_error_hz(&magnitudes, carrier, sample_rate as f64) {
+                    let _ = app.emit(events::TUNING_ERROR, TuningErrorPayload { offset_hz });
+                }
+                if let Some(freq_hz) = retune_advisor.update(
+                    decoder.lock_quality(),
+                    &magnitudes,
+                    carrier,
+                    sample_rate as f64,
+                    hop_size,
+                ) {
+                    let _ = app.emit(events::SUGGEST_RETUNE, SuggestRetunePayload { freq_hz });
+                }
+            }
+
+            let app_state = app.state::<AppState>();
+            if let Ok(mut latest) = app_state.latest_spectrum.lock() {
+                *latest = Some(magnitudes.clone());
+            }
+            if let Ok(mut history) = app_state.waterfall_history.lock() {
+                history.push(&magnitudes);
+            }
+
+            // Only ship the zoomed sub-band, if one is set
+            let bin_hz = sample_rate as f64 / fft_size as f64;
+            let span = app_state.waterfall_span.lock().ok().and_then(|s| *s);
+            let bins = match span {
+                Some((low, high)) => bin_range(magnitudes.len(), low, high, sample_rate as f64),
+                None => 0..magnitudes.len(),
+            };
+            let start_hz = bins.start as f64 * bin_hz;
+            let format = app_state
+                .config
+                .lock()
+                .map(|c| (c.fft_format, c.fft_min_db, c.fft_max_db))
+                .unwrap_or((FftFormat::Float, 0.0, 0.0));
+            let magnitudes = match format {
+                (FftFormat::Float, ..) => {
+                    FftMagnitudes::Float { magnitudes: magnitudes[bins].to_vec() }
+                }
+                (FftFormat::U8, min_db, max_db) => FftMagnitudes::U8 {
+                    quantized: quantize_db(&magnitudes[bins], min_db, max_db),
+                    min_db,
+                    max_db,
+                },
+            };
+            let _ = app.emit(events::FFT_DATA, FftPayload { magnitudes, start_hz, bin_hz });
 
             // Advance by hop_size (keep the overlap portion)
             sample_buf.drain(..hop_size);
         }
 
-        // Emit signal level every ~500ms (100 iterations)
+        // Follow the VFO: shift the carrier by the dial delta so the station stays put
+        if let Some(freq) = vfo_carrier_rx.try_iter().last() {
+            if rx_running.load(Ordering::Relaxed) {
+                decoder.retune_smooth(freq);
+                *rx_carrier_freq.lock().unwrap() = freq;
+                let _ = app.emit(
+                    events::RX_CARRIER_CHANGED,
+                    RxCarrierChangedPayload { carrier_freq: freq },
+                );
+            }
+        }
+
+        // Emit signal level (and timing/AFC while decoding) every ~500ms (100 iterations)
         signal_emit_counter += 1;
         if signal_emit_counter >= 100 {
             signal_emit_counter = 0;
-            let level = if rx_running.load(Ordering::Relaxed) {
-                decoder.signal_strength()
+            let (level, snr_db) = if rx_running.load(Ordering::Relaxed) {
+                (decoder.signal_strength(), decoder.snr_db())
             } else {
-                0.0
+                (0.0, 0.0)
             };
-            let _ = app.emit("signal-level", SignalLevelPayload { level });
+            if let Ok(mut status) = app.state::<AppState>().status.lock() {
+                status.signal_level = level;
+            }
+            let _ = app.emit(events::SIGNAL_LEVEL, SignalLevelPayload { level, clipped, snr_db });
+            clipped = false;
+
+            let dropped_samples = std::mem::take(&mut overrun);
+            if dropped_samples > 0 {
+                let _ = app.emit(events::AUDIO_OVERRUN, AudioOverrunPayload { dropped_samples });
+            }
+
+            if rx_running.load(Ordering::Relaxed) {
+                let _ = app.emit(
+                    events::SYMBOL_TIMING,
+                    SymbolTimingPayload {
+                        samples_per_symbol: decoder.current_sps(),
+                        drift_ppm: decoder.timing_drift_ppm(),
+                    },
+                );
+                let _ = app.emit(
+                    events::AFC_FREQUENCY,
+                    AfcFrequencyPayload { frequency_hz: decoder.tracked_frequency() },
+                );
+                let _ = app.emit(
+                    events::RX_RATE,
+                    RxRatePayload {
+                        chars_per_minute: rx_rate.chars_per_minute(),
+                        wpm: rx_rate.wpm(),
+                    },
+                );
+            } else {
+                rx_rate.reset();
+            }
         }
 
         // Sleep to avoid busy-waiting (~5ms = well within 42ms frame budget)
@@ -268,12 +1159,267 @@ fn run_audio_thread(
 
     // Clean shutdown
     let _ = audio_input.stop();
+    let _ = vfo_thread.join();
+    if let Some(recording) = auto_recorder.take().and_then(AutoRecorder::finish) {
+        save_auto_recording(&app, recording, sample_rate);
+    }
     *audio_device_name.lock().unwrap() = None;
+    *audio_sample_rate.lock().unwrap() = None;
+    if let Ok(mut latest) = app.state::<AppState>().latest_spectrum.lock() {
+        *latest = None;
+    }
+    if let Ok(mut status) = app.state::<AppState>().status.lock() {
+        status.signal_level = 0.0;
+    }
 
     let status = if device_lost {
         "error: audio device lost".to_string()
     } else {
         "stopped".to_string()
     };
-    let _ = app.emit("audio-status", AudioStatusPayload { status });
+    let _ = app.emit(events::AUDIO_STATUS, AudioStatusPayload { status });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_request_resets_decoder_and_clears_flag() {
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        let fresh_level = decoder.signal_strength();
+        // Loud input pulls AGC gain down, raising the signal strength
+        for i in 0..10_000 {
+            decoder.process((i as f32 * 0.13).sin());
+        }
+        assert!(decoder.signal_strength() > fresh_level);
+
+        let requested = AtomicBool::new(true);
+        assert!(apply_decoder_reset_request(&requested, &mut decoder));
+        assert!(!requested.load(Ordering::SeqCst), "request should be consumed");
+        assert_eq!(decoder.signal_strength(), fresh_level);
+    }
+
+    #[test]
+    fn clearing_rx_session_empties_transcript_and_requests_decoder_reset() {
+        let state = AppState::new();
+        state.rx_transcript.lock().unwrap().push("CQ CQ DE W1AW K");
+        *state.latest_spectrum.lock().unwrap() = Some(vec![-40.0; 16]);
+        state.status.lock().unwrap().signal_level = 0.7;
+
+        clear_rx_state(&state).unwrap();
+
+        assert!(state.rx_transcript.lock().unwrap().is_empty());
+        assert!(state.decoder_reset_requested.load(Ordering::SeqCst));
+        assert!(state.latest_spectrum.lock().unwrap().is_none());
+        assert_eq!(state.status.lock().unwrap().signal_level, 0.0);
+    }
+
+    #[test]
+    fn dsp_reset_request_rebuilds_decoder_and_drops_samples() {
+        let mut fft = FftProcessor::new(4096);
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        let fresh_level = decoder.signal_strength();
+        for i in 0..10_000 {
+            decoder.process((i as f32 * 0.13).sin());
+        }
+        let mut sample_buf = vec![0.5; 3000];
+
+        let requested = AtomicBool::new(true);
+        assert!(apply_dsp_reset_request(
+            &requested,
+            &mut fft,
+            &mut decoder,
+            &mut sample_buf,
+            1500.0,
+            48000
+        ));
+        assert!(!requested.load(Ordering::SeqCst), "request should be consumed");
+        assert_eq!(decoder.signal_strength(), fresh_level, "decoder should be fresh");
+        assert!(sample_buf.is_empty());
+        assert_eq!(fft.fft_size(), 4096);
+
+        // No request: nothing is touched
+        sample_buf.push(0.1);
+        assert!(!apply_dsp_reset_request(
+            &requested,
+            &mut fft,
+            &mut decoder,
+            &mut sample_buf,
+            1500.0,
+            48000
+        ));
+        assert_eq!(sample_buf.len(), 1);
+    }
+
+    #[test]
+    fn rx_mode_request_rebuilds_decoder_on_same_carrier() {
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        let requested = Mutex::new(None);
+        assert_eq!(apply_rx_mode_request(&requested, &mut decoder, 1500.0, 48000), None);
+        assert_eq!(decoder.carrier_freq(), 1000.0, "no request leaves the decoder alone");
+
+        let rx_mode = RxMode::default();
+        *requested.lock().unwrap() = Some(rx_mode);
+        assert_eq!(apply_rx_mode_request(&requested, &mut decoder, 1500.0, 48000), Some(rx_mode));
+        assert!(requested.lock().unwrap().is_none(), "request should be consumed");
+        assert_eq!(decoder.rx_mode(), rx_mode);
+        assert_eq!(decoder.carrier_freq(), 1500.0);
+        assert_eq!(decoder.current_sps(), 48000.0 / rx_mode.baud);
+    }
+
+    #[test]
+    fn no_reset_without_request() {
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        for i in 0..10_000 {
+            decoder.process((i as f32 * 0.13).sin());
+        }
+        let level = decoder.signal_strength();
+
+        let requested = AtomicBool::new(false);
+        assert!(!apply_decoder_reset_request(&requested, &mut decoder));
+        assert_eq!(decoder.signal_strength(), level);
+    }
+
+    #[test]
+    fn muting_holds_agc_across_transmit_window() {
+        let signal: Vec<f32> = (0..20_000).map(|i| 0.3 * (i as f32 * 0.13).sin()).collect();
+        // Two seconds of the near-silence a shared soundcard hears during TX
+        let tx_window = vec![0.0001; 96_000];
+        let mut text = String::new();
+
+        let mut muted = Psk31Decoder::new(1000.0, 48000);
+        decode_samples(&mut muted, &signal, false, &mut text, None);
+        let before = muted.signal_strength();
+        decode_samples(&mut muted, &tx_window, true, &mut text, None);
+        assert_eq!(muted.signal_strength(), before, "AGC gain should be held while muted");
+
+        // Unmuted, the same window drives the gain to its maximum
+        let mut unmuted = Psk31Decoder::new(1000.0, 48000);
+        decode_samples(&mut unmuted, &signal, false, &mut text, None);
+        decode_samples(&mut unmuted, &tx_window, false, &mut text, None);
+        assert_eq!(unmuted.signal_strength(), 0.0);
+    }
+
+    #[test]
+    fn spectrum_csv_has_header_and_one_row_per_bin() {
+        let csv = spectrum_to_csv(&[-100.0, -42.5, -3.0, -80.125], 48000);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "frequency_hz,magnitude_db");
+        // 4 positive bins → FFT size 8 → 6000 Hz per bin
+        assert_eq!(&lines[1..], ["0,-100.00", "6000,-42.50", "12000,-3.00", "18000,-80.12"]);
+    }
+
+    #[test]
+    fn drain_input_caps_at_budget_and_drops_stale_backlog() {
+        let (mut producer, mut ring) = sample_ring(1024);
+
+        // Within the backlog limit: take one budget, leave the rest queued
+        producer.push_iter_overwrite((0..250).map(|i| i as f32));
+        let mut out = Vec::new();
+        assert_eq!(drain_input(&mut ring, 100, &mut out), 0);
+        assert_eq!(out, (0..100).map(|i| i as f32).collect::<Vec<_>>());
+        assert_eq!(ring.occupied_len(), 150);
+
+        // Past it: drop the oldest, process the newest budget
+        producer.push_iter_overwrite((250..700).map(|i| i as f32));
+        out.clear();
+        assert_eq!(drain_input(&mut ring, 100, &mut out), 500);
+        assert_eq!(out, (600..700).map(|i| i as f32).collect::<Vec<_>>());
+        assert_eq!(ring.occupied_len(), 0);
+    }
+
+    #[test]
+    fn push_input_overwrites_oldest_when_full() {
+        let (mut ring, mut consumer) = sample_ring(8);
+        let (injecting, injected) = (AtomicBool::new(false), Mutex::new(VecDeque::new()));
+        let samples: Vec<f32> = (0..12).map(|i| i as f32).collect();
+
+        push_input(&mut ring, &samples[..6], &injecting, &injected);
+        push_input(&mut ring, &samples[6..], &injecting, &injected);
+        let mut held = Vec::new();
+        consumer.pop_into(usize::MAX, &mut held);
+        assert_eq!(held, &samples[4..]);
+        assert_eq!(consumer.take_overwritten(), 4);
+    }
+
+    #[test]
+    fn injected_test_signal_replaces_input_and_decodes() {
+        let (sample_rate, carrier) = (48000, 1500.0);
+        let injected = Mutex::new(VecDeque::from(
+            Psk31Encoder::new(sample_rate, carrier).encode("CQ CQ DE W1AW"),
+        ));
+        let injecting = AtomicBool::new(true);
+        let (mut ring, mut consumer) = sample_ring(8192);
+        let mut pop = || {
+            let mut held = Vec::new();
+            consumer.pop_into(usize::MAX, &mut held);
+            held
+        };
+        let mut decoder = Psk31Decoder::new(carrier, sample_rate);
+        let mut decoded = String::new();
+
+        // Silent 480-sample callbacks, drained by the "DSP loop" each time
+        let silence = [0.0f32; 480];
+        while injecting.load(Ordering::Acquire) {
+            push_input(&mut ring, &silence, &injecting, &injected);
+            let chunk = pop();
+            decode_samples(&mut decoder, &chunk, false, &mut decoded, None);
+        }
+        assert!(decoded.contains("Q DE W1AW"), "got: '{decoded}'");
+        assert!(injected.lock().unwrap().is_empty());
+
+        // Once drained, the soundcard's own samples flow again
+        push_input(&mut ring, &[0.25; 4], &injecting, &injected);
+        assert_eq!(pop(), [0.25; 4]);
+
+        // A signal queued after the queue ran dry plays in full
+        injected.lock().unwrap().extend([0.5; 4]);
+        injecting.store(true, Ordering::Release);
+        push_input(&mut ring, &[0.0; 8], &injecting, &injected);
+        assert_eq!(pop(), [0.5, 0.5, 0.5, 0.5, 0.0, 0.0, 0.0, 0.0]);
+        assert!(!injecting.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn decode_snapshot_file_holds_samples_and_decoder_settings() {
+        let mut capture = SnapshotCapture::new(0.25, 8000);
+        let tone: Vec<f32> = (0..3000).map(|i| (i as f32 * 0.1).sin()).collect();
+        capture.push(&tone);
+        let mut decoder = Psk31Decoder::new(1500.0, 8000);
+        decoder.set_decimation(2);
+        capture.finish(&decoder, 8000, Some(600.0));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.wav");
+        write_decode_snapshot(&path, &capture).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+
+        let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+        assert_eq!(u32_at(24), 8000);
+        let data_len = u32_at(40) as usize;
+        assert_eq!(data_len, 2000 * 4, "float32 samples");
+        assert_eq!(f32::from_le_bytes(bytes[48..52].try_into().unwrap()), tone[1]);
+
+        let chunk = 44 + data_len;
+        assert_eq!(&bytes[chunk..chunk + 4], b"json");
+        let json_len = u32_at(chunk + 4) as usize;
+        let meta: serde_json::Value =
+            serde_json::from_slice(&bytes[chunk + 8..chunk + 8 + json_len]).unwrap();
+        assert_eq!(meta["sampleRate"], 8000);
+        assert_eq!(meta["sampleCount"], 2000);
+        assert_eq!(meta["carrierFreq"], 1500.0);
+        assert_eq!(meta["rxMode"]["baud"], 31.25);
+        assert_eq!(meta["decimation"], 2);
+        assert_eq!(meta["notchFreq"], 600.0);
+        assert_eq!(meta["dsp"]["version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn incomplete_snapshot_is_not_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.wav");
+        assert!(write_decode_snapshot(&path, &SnapshotCapture::new(1.0, 8000)).is_err());
+        assert!(!path.exists());
+    }
 }
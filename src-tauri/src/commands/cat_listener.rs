@@ -0,0 +1,146 @@
+//! CAT listener — follow front-panel changes live
+//!
+//! `set_cat_listener(true)` puts the radio in auto-information mode (`AI1;`
+//! on the FT-991A), where it reports frequency and mode changes by itself,
+//! and spawns a thread that collects those reports between commands and
+//! emits `radio-frequency-changed` / `radio-mode-changed`. The thread ends
+//! on `set_cat_listener(false)` or when the radio goes away.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::commands::radio::with_radio;
+use crate::domain::RadioPush;
+use crate::events::{self, RadioFrequencyChangedPayload, RadioModeChangedPayload};
+use crate::state::AppState;
+
+/// How often the listener checks for unsolicited reports
+const LISTEN_POLL: Duration = Duration::from_millis(200);
+
+/// The event (name, payload) announcing `push`
+fn push_event(push: RadioPush) -> (&'static str, Value) {
+    match push {
+        RadioPush::Frequency(freq) => (
+            events::RADIO_FREQUENCY_CHANGED,
+            json!(RadioFrequencyChangedPayload { frequency_hz: freq.as_hz() }),
+        ),
+        RadioPush::Mode(mode) => {
+            (events::RADIO_MODE_CHANGED, json!(RadioModeChangedPayload { mode }))
+        }
+    }
+}
+
+/// Turn live tracking of the radio's front panel on or off.
+#[tauri::command]
+pub fn set_cat_listener(app: AppHandle, state: State<AppState>, enabled: bool) -> Result<(), String> {
+    stop_listener(&state)?;
+    if !enabled {
+        // Nothing to switch off on a radio that is already gone
+        if state.radio.lock().map(|r| r.is_none()).unwrap_or(true) {
+            return Ok(());
+        }
+        return with_radio(&state, &app, |r| r.set_auto_information(false));
+    }
+
+    with_radio(&state, &app, |r| r.set_auto_information(true))?;
+    let abort = state.cat_listener_abort.clone();
+    abort.store(false, Ordering::SeqCst);
+    let handle = thread::spawn(move || run_listener_thread(app, abort));
+    state
+        .cat_listener_thread
+        .lock()
+        .map_err(|_| "CAT listener state corrupted")?
+        .replace(handle);
+    Ok(())
+}
+
+fn stop_listener(state: &AppState) -> Result<(), String> {
+    state.cat_listener_abort.store(true, Ordering::SeqCst);
+    let handle = state
+        .cat_listener_thread
+        .lock()
+        .map_err(|_| "CAT listener state corrupted")?
+        .take();
+    if let Some(handle) = handle {
+        handle.join().map_err(|_| "CAT listener thread panicked".to_string())?;
+    }
+    Ok(())
+}
+
+fn run_listener_thread(app: AppHandle, abort: Arc<AtomicBool>) {
+    let state = app.state::<AppState>();
+    while !abort.load(Ordering::SeqCst) {
+        thread::sleep(LISTEN_POLL);
+        if state.radio.lock().map(|r| r.is_none()).unwrap_or(true) {
+            log::info!("CAT listener stopped: radio disconnected");
+            break;
+        }
+        match with_radio(&state, &app, |r| r.poll_unsolicited()) {
+            Ok(pushes) => {
+                for push in pushes {
+                    let (event, payload) = push_event(push);
+                    let _ = app.emit(event, payload);
+                }
+            }
+            Err(e) => log::warn!("CAT listener: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::ft991a::Ft991aRadio;
+    use crate::domain::{Frequency, Psk31Result};
+    use crate::ports::{RadioControl, SerialConnection};
+    use std::sync::Mutex;
+
+    /// Port where the radio has sent `pending` by itself and acks nothing
+    struct PushingSerial {
+        pending: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl SerialConnection for PushingSerial {
+        fn write(&mut self, data: &[u8]) -> Psk31Result<usize> { Ok(data.len()) }
+        fn read(&mut self, buf: &mut [u8]) -> Psk31Result<usize> {
+            let mut pending = self.pending.lock().unwrap();
+            let n = pending.len().min(buf.len());
+            buf[..n].copy_from_slice(&pending[..n]);
+            pending.drain(..n);
+            Ok(n)
+        }
+        fn bytes_available(&self) -> Psk31Result<usize> { Ok(self.pending.lock().unwrap().len()) }
+        fn close(&mut self) -> Psk31Result<()> { Ok(()) }
+        fn is_connected(&self) -> bool { true }
+    }
+
+    #[test]
+    fn unsolicited_frequency_frame_becomes_frequency_event() {
+        let pending = Arc::new(Mutex::new(Vec::new()));
+        let mut radio = Ft991aRadio::new(Box::new(PushingSerial { pending: pending.clone() }));
+        radio.set_auto_information(true).unwrap();
+
+        pending.lock().unwrap().extend_from_slice(b"FA00014075000;");
+        let pushes = radio.poll_unsolicited().unwrap();
+        assert_eq!(pushes, vec![RadioPush::Frequency(Frequency::hz(14_075_000.0))]);
+
+        let events: Vec<_> = pushes.into_iter().map(push_event).collect();
+        assert_eq!(
+            events,
+            vec![(events::RADIO_FREQUENCY_CHANGED, json!({ "frequencyHz": 14_075_000.0 }))]
+        );
+    }
+
+    #[test]
+    fn mode_push_becomes_mode_event() {
+        assert_eq!(
+            push_event(RadioPush::Mode("DATA-USB".into())),
+            (events::RADIO_MODE_CHANGED, json!({ "mode": "DATA-USB" }))
+        );
+    }
+}
@@ -4,15 +4,22 @@
 //! in the platform-appropriate app data directory.
 
 use crate::commands::radio::with_radio;
-use crate::domain::Configuration;
+use crate::domain::{
+    is_amateur_frequency, Band, Configuration, FftFormat, Frequency, IdlePattern, ModemConfig,
+    Psk31Result, QsoRecord, RxMode, RxRecordMode, FIRST_SERIAL, MAX_DUTY_WINDOW_SECS,
+};
+use crate::dsp::input_trim::MAX_INPUT_TRIM_DB;
+use crate::events::{self, ConfigurationAppliedPayload};
+use crate::ports::RadioControl;
 use crate::state::AppState;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 /// Get (and create if needed) the configs directory.
 /// Think of this like Python's `os.makedirs(path, exist_ok=True)` — it ensures
 /// the directory exists and returns the path.
-fn config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+pub(crate) fn config_dir(app: &AppHandle) -> Result<PathBuf, String> {
     let base = app
         .path()
         .app_data_dir()
@@ -22,6 +29,18 @@ fn config_dir(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(dir)
 }
 
+/// File holding each configuration's contest serial, keyed by name. It lives
+/// beside `configs/`, not in it: saving a profile from the frontend must not
+/// roll its serial back.
+fn serial_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let base = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {e}"))?;
+    std::fs::create_dir_all(&base).map_err(|e| format!("Failed to create app data dir: {e}"))?;
+    Ok(base.join("contest_serial.json"))
+}
+
 /// Sanitize a configuration name to prevent path traversal.
 /// Like Python's `os.path.basename()` check — rejects anything with
 /// path separators, "..", or empty strings.
@@ -65,6 +84,21 @@ pub fn save_configuration(app: AppHandle, config: Configuration) -> Result<(), S
     write_config_to_disk(&app, &config)
 }
 
+/// `radio_type` of the applied profile. Before any profile is applied,
+/// that of the saved "Default" profile, if there is one.
+pub(crate) fn active_radio_type(app: &AppHandle, state: &AppState) -> Result<String, String> {
+    let dir = config_dir(app)?;
+    let name = state
+        .active_configuration
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .clone();
+    match name {
+        Some(name) => load_config_from_dir(&dir, &name).map(|profile| profile.radio_type),
+        None => Ok(load_config_from_dir(&dir, "Default").unwrap_or_default().radio_type),
+    }
+}
+
 fn load_config_from_dir(dir: &std::path::Path, name: &str) -> Result<Configuration, String> {
     let name = sanitize_name(name)?;
     let path = dir.join(format!("{name}.json"));
@@ -151,6 +185,472 @@ pub fn set_tx_power_config(
     Ok(())
 }
 
+/// Enable or disable automatic QSO logging on TX completion.
+#[tauri::command]
+pub fn set_auto_log(state: State<AppState>, enabled: bool) -> Result<(), String> {
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .auto_log = enabled;
+    Ok(())
+}
+
+/// Enable or disable shifting the RX carrier to follow VFO changes.
+#[tauri::command]
+pub fn set_track_vfo(state: State<AppState>, enabled: bool) -> Result<(), String> {
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .track_vfo = enabled;
+    Ok(())
+}
+
+/// Enable or disable stripping control characters from decoded RX text.
+#[tauri::command]
+pub fn set_rx_filter_control(state: State<AppState>, enabled: bool) -> Result<(), String> {
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .rx_filter_control = enabled;
+    Ok(())
+}
+
+/// Enable or disable muting RX (and holding its AGC) while transmitting.
+#[tauri::command]
+pub fn set_rx_mute_during_tx(state: State<AppState>, enabled: bool) -> Result<(), String> {
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .rx_mute_during_tx = enabled;
+    Ok(())
+}
+
+/// Enable or disable the DC blocker ahead of the RX decoder.
+#[tauri::command]
+pub fn set_rx_dc_blocker(state: State<AppState>, enabled: bool) -> Result<(), String> {
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .rx_dc_blocker = enabled;
+    Ok(())
+}
+
+/// Search for a signal and settle the decoder on it each time RX starts.
+#[tauri::command]
+pub fn set_rx_acquire(state: State<AppState>, enabled: bool) -> Result<(), String> {
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .rx_acquire = enabled;
+    Ok(())
+}
+
+/// Set the software input trim in dB (±20), applied to captured audio
+/// ahead of the waterfall and decoder.
+#[tauri::command]
+pub fn set_input_trim(state: State<AppState>, trim_db: f32) -> Result<(), String> {
+    if !(-MAX_INPUT_TRIM_DB..=MAX_INPUT_TRIM_DB).contains(&trim_db) {
+        return Err(format!("Input trim must be within ±{MAX_INPUT_TRIM_DB} dB"));
+    }
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .input_trim_db = trim_db;
+    Ok(())
+}
+
+/// Set how much audio (ms) the DSP loop processes per iteration; a larger
+/// backlog is dropped down to one budget's worth.
+#[tauri::command]
+pub fn set_rx_drain_budget(state: State<AppState>, budget_ms: u64) -> Result<(), String> {
+    if !(5..=100).contains(&budget_ms) {
+        return Err("RX drain budget must be 5-100 ms".into());
+    }
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .rx_drain_budget_ms = budget_ms;
+    Ok(())
+}
+
+/// Enable or disable the `rx-bits` raw bit stream.
+#[tauri::command]
+pub fn set_rx_bits_debug(state: State<AppState>, enabled: bool) -> Result<(), String> {
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .rx_bits_debug = enabled;
+    Ok(())
+}
+
+/// Check signal-present detector settings: levels in 0–1, off ≤ on.
+fn validate_signal_detector(on: f32, off: f32, min_dwell_ms: u64) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&on) || !(0.0..=1.0).contains(&off) {
+        return Err("Signal thresholds must be between 0 and 1".into());
+    }
+    if off > on {
+        return Err(format!("Off threshold {off} is above on threshold {on}"));
+    }
+    if min_dwell_ms > 10_000 {
+        return Err("Signal minimum dwell must be 0-10000 ms".into());
+    }
+    Ok(())
+}
+
+/// Set the signal-present detector's on/off thresholds and minimum dwell.
+#[tauri::command]
+pub fn set_signal_detector(
+    state: State<AppState>,
+    on_threshold: f32,
+    off_threshold: f32,
+    min_dwell_ms: u64,
+) -> Result<(), String> {
+    validate_signal_detector(on_threshold, off_threshold, min_dwell_ms)?;
+    let mut config = state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?;
+    config.signal_on_threshold = on_threshold;
+    config.signal_off_threshold = off_threshold;
+    config.signal_min_dwell_ms = min_dwell_ms;
+    Ok(())
+}
+
+/// Check auto-record settings: lock quality in 0–1, trailing silence
+/// 0.1–60 s.
+fn validate_rx_record(threshold: f32, trailing_silence_ms: u64) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("Record threshold must be between 0 and 1".into());
+    }
+    if !(100..=60_000).contains(&trailing_silence_ms) {
+        return Err("Trailing silence must be 100-60000 ms".into());
+    }
+    Ok(())
+}
+
+/// Select RX recording: `Auto` writes one WAV per transmission to
+/// `recordings/` in the config dir, starting when lock quality reaches
+/// `threshold` and closing after `trailing_silence_ms` without it.
+#[tauri::command]
+pub fn set_rx_record_mode(
+    state: State<AppState>,
+    mode: RxRecordMode,
+    threshold: f32,
+    trailing_silence_ms: u64,
+) -> Result<(), String> {
+    validate_rx_record(threshold, trailing_silence_ms)?;
+    let mut config = state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?;
+    config.rx_record_mode = mode;
+    config.rx_record_threshold = threshold;
+    config.rx_record_trailing_ms = trailing_silence_ms;
+    Ok(())
+}
+
+/// Set the PTT lead (PTT ON → audio) and tail (audio end → PTT OFF) delays.
+#[tauri::command]
+pub fn set_ptt_delays(state: State<AppState>, lead_ms: u64, tail_ms: u64) -> Result<(), String> {
+    if lead_ms > 1000 || tail_ms > 1000 {
+        return Err("PTT delays must be 0-1000 ms".into());
+    }
+    let mut config = state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?;
+    config.ptt_lead_ms = lead_ms;
+    config.ptt_tail_ms = tail_ms;
+    Ok(())
+}
+
+/// Set the transmit duty-cycle limit: at most `max_percent` of any
+/// `window_secs` spent transmitting (100 = no limit).
+#[tauri::command]
+pub fn set_tx_duty_cycle_limit(
+    state: State<AppState>,
+    max_percent: u32,
+    window_secs: u64,
+) -> Result<(), String> {
+    if !(1..=100).contains(&max_percent) {
+        return Err("Duty-cycle limit must be 1-100%".into());
+    }
+    if !(60..=MAX_DUTY_WINDOW_SECS).contains(&window_secs) {
+        return Err(format!("Duty-cycle window must be 60-{MAX_DUTY_WINDOW_SECS} s"));
+    }
+    let mut config = state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?;
+    config.max_tx_duty_cycle_percent = max_percent;
+    config.tx_duty_window_secs = window_secs;
+    Ok(())
+}
+
+/// Select how `fft-data` frames carry magnitudes; `min_db`/`max_db` set the
+/// range mapped onto 0–255 for `FftFormat::U8`.
+#[tauri::command]
+pub fn set_fft_format(
+    state: State<AppState>,
+    format: FftFormat,
+    min_db: f32,
+    max_db: f32,
+) -> Result<(), String> {
+    if !min_db.is_finite() || !max_db.is_finite() || min_db >= max_db {
+        return Err(format!("FFT range {min_db}..{max_db} dB is empty"));
+    }
+    let mut config = state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?;
+    config.fft_format = format;
+    config.fft_min_db = min_db;
+    config.fft_max_db = max_db;
+    Ok(())
+}
+
+/// Select the TX idle pattern (preamble/postamble) used by subsequent transmissions.
+#[tauri::command]
+pub fn set_idle_pattern(state: State<AppState>, pattern: IdlePattern) -> Result<(), String> {
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .idle_pattern = pattern;
+    Ok(())
+}
+
+/// Select the symbol rate (31.25, 62.5 or 125 baud) for subsequent
+/// transmissions, and switch the RX decoder to it.
+#[tauri::command]
+pub fn set_modem_baud(state: State<AppState>, baud: f64) -> Result<(), String> {
+    let rx_mode = RxMode { baud, ..RxMode::default() };
+    rx_mode.validate().map_err(|e| e.to_string())?;
+    state.config.lock().map_err(|_| "config lock poisoned".to_string())?.modem_baud = baud;
+    *state.rx_mode_requested.lock().map_err(|_| "RX state corrupted".to_string())? = Some(rx_mode);
+    Ok(())
+}
+
+/// Return the QSOs logged this session, oldest first.
+#[tauri::command]
+pub fn get_qso_log(state: State<AppState>) -> Result<Vec<QsoRecord>, String> {
+    state
+        .qso_log
+        .lock()
+        .map(|log| log.clone())
+        .map_err(|_| "QSO log lock poisoned".to_string())
+}
+
+/// Read every configuration's persisted contest serial (path-based, testable
+/// without AppHandle). No file yet means no configuration has logged a QSO.
+fn load_serials_from(path: &std::path::Path) -> Result<BTreeMap<String, u32>, String> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let json =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read contest serial: {e}"))?;
+    serde_json::from_str(&json).map_err(|e| format!("Invalid contest serial: {e}"))
+}
+
+/// Persist `serial` as configuration `name`'s contest serial (path-based,
+/// testable without AppHandle).
+fn store_serial_to(path: &std::path::Path, name: &str, serial: u32) -> Result<(), String> {
+    let mut serials = load_serials_from(path)?;
+    serials.insert(name.to_string(), serial);
+    let json =
+        serde_json::to_string_pretty(&serials).map_err(|e| format!("Serialization error: {e}"))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write contest serial: {e}"))
+}
+
+/// Name of the applied configuration ("Default" until one is applied)
+fn active_configuration_name(state: &AppState) -> Result<String, String> {
+    Ok(state
+        .active_configuration
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .clone()
+        .unwrap_or_else(|| "Default".to_string()))
+}
+
+/// Make the active configuration's persisted serial the live one; a
+/// configuration that never logged a QSO starts at `FIRST_SERIAL`.
+fn restore_serial_from(state: &AppState, path: &std::path::Path) -> Result<(), String> {
+    let name = active_configuration_name(state)?;
+    let serial = load_serials_from(path)?.get(&name).copied().unwrap_or(FIRST_SERIAL);
+    *state
+        .contest_serial
+        .lock()
+        .map_err(|_| "contest serial lock poisoned".to_string())? = serial;
+    Ok(())
+}
+
+/// Set the live contest serial and persist it for the active configuration.
+fn store_contest_serial_to(
+    state: &AppState,
+    path: &std::path::Path,
+    serial: u32,
+) -> Result<(), String> {
+    *state
+        .contest_serial
+        .lock()
+        .map_err(|_| "contest serial lock poisoned".to_string())? = serial;
+    store_serial_to(path, &active_configuration_name(state)?, serial)
+}
+
+/// Load the active configuration's contest serial into `AppState`. Called at
+/// startup and whenever a configuration is applied.
+pub(crate) fn restore_contest_serial(app: &AppHandle) -> Result<(), String> {
+    restore_serial_from(&app.state::<AppState>(), &serial_path(app)?)
+}
+
+/// Set the live contest serial and persist it.
+fn store_contest_serial(app: &AppHandle, state: &AppState, serial: u32) -> Result<(), String> {
+    store_contest_serial_to(state, &serial_path(app)?, serial)
+}
+
+/// The serial after the current one, without changing anything.
+fn next_contest_serial(state: &AppState) -> Result<u32, String> {
+    state
+        .contest_serial
+        .lock()
+        .map(|serial| serial.saturating_add(1))
+        .map_err(|_| "contest serial lock poisoned".to_string())
+}
+
+/// Advance the contest serial by one and persist it; returns the new serial.
+/// Called when a QSO is logged.
+pub(crate) fn advance_contest_serial(app: &AppHandle, state: &AppState) -> Result<u32, String> {
+    let serial = next_contest_serial(state)?;
+    store_contest_serial(app, state, serial)?;
+    Ok(serial)
+}
+
+/// Contest serial number that `<NR>` will send next.
+#[tauri::command]
+pub fn get_serial(state: State<AppState>) -> Result<u32, String> {
+    state
+        .contest_serial
+        .lock()
+        .map(|serial| *serial)
+        .map_err(|_| "contest serial lock poisoned".to_string())
+}
+
+/// Set the contest serial (e.g. to restart at 1 for a new contest).
+#[tauri::command]
+pub fn set_serial(app: AppHandle, state: State<AppState>, n: u32) -> Result<(), String> {
+    if n < FIRST_SERIAL {
+        return Err(format!("Serial number must be at least {FIRST_SERIAL}"));
+    }
+    store_contest_serial(&app, &state, n)
+}
+
+/// Advance the contest serial by hand (e.g. a QSO that wasn't auto-logged).
+#[tauri::command]
+pub fn increment_serial(app: AppHandle, state: State<AppState>) -> Result<u32, String> {
+    advance_contest_serial(&app, &state)
+}
+
+/// Resolve a profile's band, validating its PSK-31 dial frequency is in-band.
+/// Returns `None` when the profile doesn't specify a band.
+fn profile_band(profile: &Configuration) -> Result<Option<&'static Band>, String> {
+    let Some(name) = profile.band.as_deref() else {
+        return Ok(None);
+    };
+    let band = Band::by_name(name).ok_or_else(|| format!("Unknown band '{name}'"))?;
+    if !is_amateur_frequency(band.psk31_hz) {
+        return Err(format!("{} Hz is outside US amateur bands", band.psk31_hz));
+    }
+    Ok(Some(band))
+}
+
+/// TX power a profile asks for: the band's suggested power when
+/// `use_band_defaults` is set and a band is named, else `tx_power_watts`.
+fn profile_tx_power(profile: &Configuration) -> u32 {
+    match profile_band(profile) {
+        Ok(Some(band)) if profile.use_band_defaults => band.max_power_watts,
+        _ => profile.tx_power_watts,
+    }
+}
+
+/// Validate every value in a profile before anything is written.
+fn validate_profile(profile: &Configuration) -> Result<(), String> {
+    validate_tx_power(profile.tx_power_watts)?;
+    if !(200.0..=3500.0).contains(&profile.carrier_freq) {
+        return Err("Carrier frequency must be between 200-3500 Hz".into());
+    }
+    profile_band(profile)?;
+    Ok(())
+}
+
+/// Copy a profile's modem settings (carrier, TX power) into the live config.
+pub fn apply_profile_to_modem(config: &mut ModemConfig, profile: &Configuration) {
+    config.carrier_freq = profile.carrier_freq;
+    config.tx_power_watts = profile_tx_power(profile);
+}
+
+/// Push a profile to a connected radio: QSY to the band's PSK-31 frequency
+/// (if the profile names a band), select the band's DATA mode, and set TX power.
+///
+/// Stops at the first radio error.
+pub fn apply_profile_to_radio(radio: &mut dyn RadioControl, profile: &Configuration) -> Psk31Result<()> {
+    // validate_profile has already run, so an invalid band is unreachable here
+    if let Ok(Some(band)) = profile_band(profile) {
+        radio.set_frequency(Frequency::hz(band.psk31_hz as f64))?;
+        radio.set_mode(band.default_mode)?;
+    }
+    radio.set_tx_power(profile_tx_power(profile))
+}
+
+/// Load a profile and apply it to the live modem and (if connected) the radio.
+///
+/// All values are validated before anything changes. Emits `configuration-applied`
+/// on success and returns the applied profile so the frontend can refresh its UI.
+#[tauri::command]
+pub fn apply_configuration(
+    app: AppHandle,
+    state: State<AppState>,
+    name: String,
+) -> Result<Configuration, String> {
+    let dir = config_dir(&app)?;
+    let profile = load_config_from_dir(&dir, &name)?;
+    validate_profile(&profile)?;
+
+    {
+        let mut cfg = state
+            .config
+            .lock()
+            .map_err(|_| "config lock poisoned".to_string())?;
+        apply_profile_to_modem(&mut cfg, &profile);
+    }
+    *state.rx_carrier_freq.lock().map_err(|_| "RX state corrupted".to_string())? =
+        profile.carrier_freq;
+    *state
+        .active_configuration
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())? = Some(profile.name.clone());
+    restore_serial_from(&state, &serial_path(&app)?)?;
+
+    let connected = state.radio.lock().map(|r| r.is_some()).unwrap_or(false);
+    if connected {
+        with_radio(&state, &app, |radio| apply_profile_to_radio(radio.as_mut(), &profile))?;
+    }
+
+    let _ = app.emit(
+        events::CONFIGURATION_APPLIED,
+        ConfigurationAppliedPayload { name: profile.name.clone() },
+    );
+    Ok(profile)
+}
+
 #[tauri::command]
 pub fn load_configuration(app: AppHandle, name: String) -> Result<Configuration, String> {
     let dir = config_dir(&app)?;
@@ -214,6 +714,58 @@ mod tests {
         assert!(validate_tx_power(100).is_ok());
     }
 
+    #[test]
+    fn signal_detector_validation() {
+        assert!(validate_signal_detector(0.65, 0.55, 200).is_ok());
+        assert!(validate_signal_detector(0.6, 0.6, 0).is_ok());
+        assert!(validate_signal_detector(0.5, 0.6, 200).is_err());
+        assert!(validate_signal_detector(1.2, 0.5, 200).is_err());
+        assert!(validate_signal_detector(0.6, -0.1, 200).is_err());
+        assert!(validate_signal_detector(0.6, 0.5, 60_000).is_err());
+    }
+
+    #[test]
+    fn rx_record_settings_are_range_checked() {
+        assert!(validate_rx_record(0.7, 3000).is_ok());
+        assert!(validate_rx_record(1.5, 3000).is_err());
+        assert!(validate_rx_record(0.7, 50).is_err());
+        assert!(validate_rx_record(0.7, 120_000).is_err());
+    }
+
+    #[test]
+    fn validate_profile_rejects_unknown_band() {
+        let profile = Configuration { band: Some("11m".into()), ..Configuration::default() };
+        assert!(validate_profile(&profile).is_err());
+    }
+
+    #[test]
+    fn validate_profile_rejects_out_of_range_values() {
+        let loud = Configuration { tx_power_watts: 150, ..Configuration::default() };
+        assert!(validate_profile(&loud).is_err());
+        let low_carrier = Configuration { carrier_freq: 50.0, ..Configuration::default() };
+        assert!(validate_profile(&low_carrier).is_err());
+        assert!(validate_profile(&Configuration::default()).is_ok());
+    }
+
+    #[test]
+    fn band_defaults_override_profile_power_only_when_enabled() {
+        let profile = Configuration {
+            band: Some("30m".into()),
+            tx_power_watts: 50,
+            ..Configuration::default()
+        };
+        assert_eq!(profile_tx_power(&profile), 50);
+
+        let with_defaults = Configuration { use_band_defaults: true, ..profile };
+        let mut cfg = ModemConfig::default();
+        apply_profile_to_modem(&mut cfg, &with_defaults);
+        assert_eq!(cfg.tx_power_watts, Band::by_name("30m").unwrap().max_power_watts);
+
+        // No band named: nothing to take defaults from
+        let no_band = Configuration { band: None, ..with_defaults };
+        assert_eq!(profile_tx_power(&no_band), 50);
+    }
+
     #[test]
     fn modem_config_tx_power_can_be_updated() {
         use crate::domain::ModemConfig;
@@ -227,6 +779,71 @@ mod tests {
 
     // --- I/O path tests using temp directories ---
 
+    #[test]
+    fn contest_serial_survives_profile_save_and_reload() {
+        let base = tempfile::tempdir().unwrap();
+        let configs = base.path().join("configs");
+        std::fs::create_dir_all(&configs).unwrap();
+        let serial_file = base.path().join("contest_serial.json");
+
+        write_config_to_dir(&configs, &sample_config("Default")).unwrap();
+        store_serial_to(&serial_file, "Default", 42).unwrap();
+
+        // Reload and save the profile again, as the frontend does
+        let profile = load_config_from_dir(&configs, "Default").unwrap();
+        write_config_to_dir(&configs, &profile).unwrap();
+
+        assert_eq!(load_serials_from(&serial_file).unwrap()["Default"], 42);
+        assert_eq!(list_configs_in_dir(&configs).unwrap(), vec!["Default".to_string()]);
+    }
+
+    #[test]
+    fn missing_serial_file_starts_at_first_serial() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = AppState::new();
+        *state.contest_serial.lock().unwrap() = 12;
+        restore_serial_from(&state, &dir.path().join("contest_serial.json")).unwrap();
+        assert_eq!(*state.contest_serial.lock().unwrap(), FIRST_SERIAL);
+    }
+
+    #[test]
+    fn switching_configurations_keeps_separate_serials() {
+        let dir = tempfile::tempdir().unwrap();
+        let serial_file = dir.path().join("contest_serial.json");
+        let state = AppState::new();
+        let switch_to = |name: &str| {
+            *state.active_configuration.lock().unwrap() = Some(name.to_string());
+            restore_serial_from(&state, &serial_file).unwrap();
+            *state.contest_serial.lock().unwrap()
+        };
+
+        assert_eq!(switch_to("Default"), FIRST_SERIAL);
+        store_contest_serial_to(&state, &serial_file, 42).unwrap();
+        assert_eq!(switch_to("Contest"), FIRST_SERIAL);
+        store_contest_serial_to(&state, &serial_file, next_contest_serial(&state).unwrap())
+            .unwrap();
+
+        assert_eq!(switch_to("Default"), 42);
+        assert_eq!(switch_to("Contest"), FIRST_SERIAL + 1);
+    }
+
+    #[test]
+    fn next_contest_serial_counts_up_from_current() {
+        let state = AppState::new();
+        assert_eq!(*state.contest_serial.lock().unwrap(), FIRST_SERIAL);
+        assert_eq!(next_contest_serial(&state).unwrap(), FIRST_SERIAL + 1);
+        *state.contest_serial.lock().unwrap() = 99;
+        assert_eq!(next_contest_serial(&state).unwrap(), 100);
+    }
+
+    #[test]
+    fn profile_saved_with_contest_serial_still_loads() {
+        let json = r#"{"name":"Old","audio_input":null,"audio_output":null,"serial_port":null,
+            "baud_rate":38400,"radio_type":"FT-991A","carrier_freq":1000.0,"contest_serial":7}"#;
+        let profile: Configuration = serde_json::from_str(json).unwrap();
+        assert_eq!(profile.name, "Old");
+    }
+
     fn sample_config(name: &str) -> Configuration {
         Configuration { name: name.to_string(), ..Configuration::default() }
     }
@@ -0,0 +1,194 @@
+//! Memory channel commands — save/recall/list stored frequencies
+//!
+//! Each slot is persisted as `<slot>.json` in a `memories/` folder inside the
+//! configs directory, using the same path-based helpers as `config.rs` so the
+//! I/O can be tested against a temp directory.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, State};
+
+use crate::commands::config::config_dir;
+use crate::commands::radio::with_radio;
+use crate::domain::{normalize_mode, Band, Frequency, Memory};
+use crate::state::AppState;
+
+/// Get (and create if needed) the memories directory.
+fn memories_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = config_dir(app)?.join("memories");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create memories dir: {e}"))?;
+    Ok(dir)
+}
+
+/// Check a memory is safe to apply: frequency inside its named band, a mode
+/// the radio knows, carrier inside the audio passband.
+fn validate_memory(memory: &Memory) -> Result<(), String> {
+    let band = Band::by_name(&memory.band)
+        .ok_or_else(|| format!("Unknown band '{}'", memory.band))?;
+    let hz = memory.frequency_hz as u64;
+    if memory.frequency_hz < 0.0 || !band.contains(hz) {
+        return Err(format!(
+            "Memory '{}': {hz} Hz is outside the {} band",
+            memory.name, band.name
+        ));
+    }
+    if normalize_mode(&memory.mode).is_err() {
+        return Err(format!("Memory '{}': unknown mode '{}'", memory.name, memory.mode));
+    }
+    if !(200.0..=3500.0).contains(&memory.carrier_freq) {
+        return Err("Carrier frequency must be between 200-3500 Hz".into());
+    }
+    Ok(())
+}
+
+fn write_memory_to_dir(dir: &Path, slot: u32, memory: &Memory) -> Result<(), String> {
+    let json =
+        serde_json::to_string_pretty(memory).map_err(|e| format!("Serialization error: {e}"))?;
+    std::fs::write(dir.join(format!("{slot}.json")), json)
+        .map_err(|e| format!("Failed to write memory {slot}: {e}"))
+}
+
+fn load_memory_from_dir(dir: &Path, slot: u32) -> Result<Memory, String> {
+    let json = std::fs::read_to_string(dir.join(format!("{slot}.json")))
+        .map_err(|_| format!("Memory slot {slot} is empty"))?;
+    serde_json::from_str(&json).map_err(|e| format!("Failed to parse memory {slot}: {e}"))
+}
+
+/// Load a slot and validate it before it is applied to the radio.
+fn recall_memory_from_dir(dir: &Path, slot: u32) -> Result<Memory, String> {
+    let memory = load_memory_from_dir(dir, slot)?;
+    validate_memory(&memory)?;
+    Ok(memory)
+}
+
+fn list_memories_in_dir(dir: &Path) -> Result<BTreeMap<u32, Memory>, String> {
+    let mut memories = BTreeMap::new();
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read memories dir: {e}"))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(slot) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        // Skip unreadable slots rather than failing the whole listing
+        if let Ok(memory) = load_memory_from_dir(dir, slot) {
+            memories.insert(slot, memory);
+        }
+    }
+    Ok(memories)
+}
+
+#[tauri::command]
+pub fn save_memory(app: AppHandle, slot: u32, memory: Memory) -> Result<(), String> {
+    validate_memory(&memory)?;
+    let dir = memories_dir(&app)?;
+    write_memory_to_dir(&dir, slot, &memory)
+}
+
+/// Recall a memory: QSY the radio (if connected) and retune the RX carrier.
+#[tauri::command]
+pub fn recall_memory(app: AppHandle, state: State<AppState>, slot: u32) -> Result<Memory, String> {
+    let dir = memories_dir(&app)?;
+    let memory = recall_memory_from_dir(&dir, slot)?;
+
+    let connected = state.radio.lock().map(|r| r.is_some()).unwrap_or(false);
+    if connected {
+        with_radio(&state, &app, |radio| {
+            radio.set_frequency(Frequency::hz(memory.frequency_hz))?;
+            radio.set_mode(&memory.mode)
+        })?;
+    }
+
+    *state.rx_carrier_freq.lock().map_err(|_| "RX state corrupted".to_string())? =
+        memory.carrier_freq;
+    state
+        .config
+        .lock()
+        .map_err(|_| "config lock poisoned".to_string())?
+        .carrier_freq = memory.carrier_freq;
+
+    Ok(memory)
+}
+
+#[tauri::command]
+pub fn list_memories(app: AppHandle) -> Result<BTreeMap<u32, Memory>, String> {
+    let dir = memories_dir(&app)?;
+    list_memories_in_dir(&dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_memory(name: &str, band: &str, frequency_hz: f64) -> Memory {
+        Memory {
+            name: name.to_string(),
+            band: band.to_string(),
+            frequency_hz,
+            mode: "DATA-USB".to_string(),
+            carrier_freq: 1000.0,
+        }
+    }
+
+    #[test]
+    fn save_and_recall_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let memory = sample_memory("20m calling", "20m", 14_070_000.0);
+        write_memory_to_dir(dir.path(), 1, &memory).unwrap();
+        assert_eq!(recall_memory_from_dir(dir.path(), 1).unwrap(), memory);
+    }
+
+    #[test]
+    fn list_returns_slots_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        write_memory_to_dir(dir.path(), 7, &sample_memory("17m", "17m", 18_100_000.0)).unwrap();
+        write_memory_to_dir(dir.path(), 2, &sample_memory("30m", "30m", 10_142_000.0)).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let memories = list_memories_in_dir(dir.path()).unwrap();
+        let slots: Vec<u32> = memories.keys().copied().collect();
+        assert_eq!(slots, vec![2, 7]);
+        assert_eq!(memories[&7].name, "17m");
+    }
+
+    #[test]
+    fn recall_empty_slot_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = recall_memory_from_dir(dir.path(), 3).unwrap_err();
+        assert!(err.contains("empty"), "got: {err}");
+    }
+
+    #[test]
+    fn recall_rejects_out_of_band_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        // Hand-edited file: 20m label but a frequency outside the band
+        write_memory_to_dir(dir.path(), 4, &sample_memory("bad", "20m", 14_400_000.0)).unwrap();
+        let err = recall_memory_from_dir(dir.path(), 4).unwrap_err();
+        assert!(err.contains("outside"), "got: {err}");
+    }
+
+    #[test]
+    fn validate_rejects_bad_carrier_and_unknown_band() {
+        let mut memory = sample_memory("x", "20m", 14_070_000.0);
+        memory.carrier_freq = 5000.0;
+        assert!(validate_memory(&memory).is_err());
+        assert!(validate_memory(&sample_memory("x", "11m", 27_185_000.0)).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_mode() {
+        let mut memory = sample_memory("x", "20m", 14_070_000.0);
+        memory.mode = "PKTUSB".into();
+        assert!(validate_memory(&memory).is_ok());
+        memory.mode = "OLIVIA".into();
+        let err = validate_memory(&memory).unwrap_err();
+        assert!(err.contains("unknown mode 'OLIVIA'"), "got: {err}");
+    }
+}
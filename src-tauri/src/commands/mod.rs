@@ -2,8 +2,13 @@
 
 pub mod app;
 pub mod audio;
+pub mod cat_listener;
 pub mod config;
+pub mod memory;
+pub mod operator;
 pub mod radio;
+pub mod rigctld;
+pub mod scan;
 pub mod serial;
 pub mod status;
 pub mod tx;
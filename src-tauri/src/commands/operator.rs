@@ -0,0 +1,112 @@
+//! Operator profile commands
+//!
+//! The profile is persisted as `operator/operator.json` under the configs
+//! directory, using path-based helpers like `config.rs` so the I/O can be
+//! tested against a temp directory. It sits in a subdirectory so the profile
+//! scan, which lists every `*.json` in the configs directory, doesn't
+//! offer it as a configuration.
+
+use std::path::Path;
+
+use tauri::AppHandle;
+
+use crate::commands::config::config_dir;
+use crate::domain::OperatorProfile;
+
+const OPERATOR_DIR: &str = "operator";
+const OPERATOR_FILE: &str = "operator.json";
+
+/// Read the profile from under the configs `dir`; an empty profile if none
+/// has been saved.
+fn load_operator_from_dir(dir: &Path) -> Result<OperatorProfile, String> {
+    let path = dir.join(OPERATOR_DIR).join(OPERATOR_FILE);
+    if !path.exists() {
+        return Ok(OperatorProfile::default());
+    }
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read operator profile: {e}"))?;
+    serde_json::from_str(&json).map_err(|e| format!("Failed to parse operator profile: {e}"))
+}
+
+/// Normalize and validate `profile`, then write it under the configs `dir`.
+/// Returns the profile as stored.
+fn save_operator_to_dir(dir: &Path, profile: &OperatorProfile) -> Result<OperatorProfile, String> {
+    let profile = profile.normalized();
+    profile.validate().map_err(|e| e.to_string())?;
+    let json =
+        serde_json::to_string_pretty(&profile).map_err(|e| format!("Serialization error: {e}"))?;
+    let dir = dir.join(OPERATOR_DIR);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create operator dir: {e}"))?;
+    std::fs::write(dir.join(OPERATOR_FILE), json)
+        .map_err(|e| format!("Failed to write operator profile: {e}"))?;
+    Ok(profile)
+}
+
+/// The saved operator profile, for TX macro expansion.
+pub(crate) fn load_operator_profile(app: &AppHandle) -> Result<OperatorProfile, String> {
+    load_operator_from_dir(&config_dir(app)?)
+}
+
+#[tauri::command]
+pub fn get_operator_profile(app: AppHandle) -> Result<OperatorProfile, String> {
+    load_operator_profile(&app)
+}
+
+/// Save the operator profile. The callsign is required; the grid, if set,
+/// must be a 4- or 6-character Maidenhead locator.
+#[tauri::command]
+pub fn set_operator_profile(
+    app: AppHandle,
+    profile: OperatorProfile,
+) -> Result<OperatorProfile, String> {
+    save_operator_to_dir(&config_dir(&app)?, &profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let profile = OperatorProfile {
+            callsign: "w1aw".into(),
+            grid: "FN31PR".into(),
+            name: "Hiram".into(),
+            qth: "Newington, CT".into(),
+        };
+        let saved = save_operator_to_dir(dir.path(), &profile).unwrap();
+        assert_eq!(saved.callsign, "W1AW");
+        assert_eq!(saved.grid, "FN31pr");
+        assert_eq!(load_operator_from_dir(dir.path()).unwrap(), saved);
+    }
+
+    #[test]
+    fn profile_is_not_saved_among_configurations() {
+        let dir = tempfile::tempdir().unwrap();
+        let profile = OperatorProfile { callsign: "W1AW".into(), ..Default::default() };
+        save_operator_to_dir(dir.path(), &profile).unwrap();
+        let top_level_json = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .count();
+        assert_eq!(top_level_json, 0);
+    }
+
+    #[test]
+    fn missing_file_loads_empty_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load_operator_from_dir(dir.path()).unwrap(), OperatorProfile::default());
+    }
+
+    #[test]
+    fn invalid_callsign_is_rejected_and_not_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let profile = OperatorProfile { callsign: "599".into(), ..Default::default() };
+        let err = save_operator_to_dir(dir.path(), &profile).unwrap_err();
+        assert!(err.contains("callsign"), "{err}");
+        assert!(!dir.path().join(OPERATOR_DIR).join(OPERATOR_FILE).exists());
+    }
+}
@@ -7,20 +7,16 @@
 //! with_radio() detects these, nulls out AppState.radio, and emits a
 //! `serial-disconnected` event so the frontend can reset its UI automatically.
 
-use serde::Serialize;
 use tauri::{AppHandle, Emitter, State};
 
-use crate::domain::{Frequency, Psk31Error, Psk31Result, RadioStatus};
+use crate::domain::{
+    Band, BandPlanEntry, Frequency, Psk31Error, Psk31Result, RadioFilterState, RadioMemoryChannel,
+    RadioStatus, SMeterReading, Vfo,
+};
+use crate::events::{self, FrequencyCorrectedPayload, SerialDisconnectedPayload};
 use crate::ports::RadioControl;
 use crate::state::AppState;
 
-/// Payload for the `serial-disconnected` event
-#[derive(Clone, Serialize)]
-struct SerialDisconnectedPayload {
-    reason: String,
-    port: String,
-}
-
 /// Lock the radio mutex, check it's connected, and run `f` on it.
 ///
 /// On `Psk31Error::Serial` (physical I/O failure), automatically:
@@ -46,7 +42,7 @@ pub(crate) fn with_radio<T>(
             drop(guard); // Release radio mutex before acquiring port-name mutex
             let port = state.serial_port_name.lock().unwrap().take().unwrap_or_default();
             let _ = app.emit(
-                "serial-disconnected",
+                events::SERIAL_DISCONNECTED,
                 SerialDisconnectedPayload { reason: e.to_string(), port },
             );
             Err(e.to_string())
@@ -70,9 +66,145 @@ pub fn get_frequency(app: AppHandle, state: State<AppState>) -> Result<f64, Stri
     with_radio(&state, &app, |r| r.get_frequency().map(|f| f.as_hz()))
 }
 
+/// Set the VFO, then read back where the radio actually landed — it may
+/// round to its tuning step. Returns the read-back frequency.
+pub fn set_frequency_with_readback(
+    radio: &mut dyn RadioControl,
+    freq: Frequency,
+) -> Psk31Result<Frequency> {
+    radio.set_frequency(freq)?;
+    radio.get_frequency()
+}
+
+/// Set the VFO. With `read_back`, also read the frequency back (one extra
+/// round-trip), emit `frequency-corrected` if the radio rounded it, and
+/// return the actual frequency; otherwise returns `None`.
+#[tauri::command]
+pub fn set_frequency(
+    app: AppHandle,
+    state: State<AppState>,
+    freq_hz: f64,
+    read_back: Option<bool>,
+) -> Result<Option<f64>, String> {
+    let freq = Frequency::hz(freq_hz);
+    if !read_back.unwrap_or(false) {
+        return with_radio(&state, &app, |r| r.set_frequency(freq)).map(|()| None);
+    }
+    let actual = with_radio(&state, &app, |r| set_frequency_with_readback(r.as_mut(), freq))?;
+    if actual.as_hz() as u64 != freq_hz as u64 {
+        let _ = app.emit(
+            events::FREQUENCY_CORRECTED,
+            FrequencyCorrectedPayload { requested_hz: freq_hz, actual_hz: actual.as_hz() },
+        );
+    }
+    Ok(Some(actual.as_hz()))
+}
+
 #[tauri::command]
-pub fn set_frequency(app: AppHandle, state: State<AppState>, freq_hz: f64) -> Result<(), String> {
-    with_radio(&state, &app, |r| r.set_frequency(Frequency::hz(freq_hz)))
+pub fn get_frequency_b(app: AppHandle, state: State<AppState>) -> Result<f64, String> {
+    with_radio(&state, &app, |r| r.get_frequency_b().map(|f| f.as_hz()))
+}
+
+/// Set VFO-B (the split TX VFO). Rejected outside the amateur bands.
+#[tauri::command]
+pub fn set_frequency_b(app: AppHandle, state: State<AppState>, freq_hz: f64) -> Result<(), String> {
+    with_radio(&state, &app, |r| r.set_frequency_b(Frequency::hz(freq_hz)))
+}
+
+/// Exchange VFO-A and VFO-B
+#[tauri::command]
+pub fn swap_vfo(app: AppHandle, state: State<AppState>) -> Result<(), String> {
+    with_radio(&state, &app, |r| r.swap_vfo())
+}
+
+/// Turn split on or off (receive on VFO-A, transmit on VFO-B)
+#[tauri::command]
+pub fn set_split(app: AppHandle, state: State<AppState>, on: bool) -> Result<(), String> {
+    with_radio(&state, &app, |r| r.set_split(on))
+}
+
+/// Step the VFO by `delta_hz` from its current frequency.
+///
+/// The step must stay within the amateur band the radio is currently on;
+/// a step that would cross a band edge is rejected without touching the
+/// radio, so the operator never ends up on a frequency they didn't choose.
+/// Returns the new frequency.
+pub fn step_frequency_by(radio: &mut dyn RadioControl, delta_hz: i32) -> Psk31Result<Frequency> {
+    let current = radio.get_frequency()?.as_hz() as u64;
+    let band = Band::containing(current).ok_or_else(|| {
+        Psk31Error::Cat(format!("Current frequency {current} Hz is outside US amateur bands"))
+    })?;
+    let target = current as i64 + delta_hz as i64;
+    if target < 0 || !band.contains(target as u64) {
+        return Err(Psk31Error::Cat(format!(
+            "Step of {delta_hz} Hz from {current} Hz leaves the {} band ({}–{} Hz)",
+            band.name, band.low_hz, band.high_hz
+        )));
+    }
+    let freq = Frequency::hz(target as f64);
+    radio.set_frequency(freq)?;
+    Ok(freq)
+}
+
+/// QSY to a band's PSK-31 dial frequency. With `apply_band_defaults`, also
+/// select the band's DATA mode and set its suggested TX power.
+///
+/// Stops at the first radio error. Returns the new frequency.
+pub fn qsy_to_band(
+    radio: &mut dyn RadioControl,
+    band: &Band,
+    apply_band_defaults: bool,
+) -> Psk31Result<Frequency> {
+    let plan = BandPlanEntry::for_band(band, apply_band_defaults);
+    radio.apply_band_plan(&plan)?;
+    Ok(plan.frequency())
+}
+
+/// QSY to a band by name (e.g. "30m"), optionally applying its default mode and power.
+#[tauri::command]
+pub fn qsy(
+    app: AppHandle,
+    state: State<AppState>,
+    band: String,
+    apply_band_defaults: bool,
+) -> Result<f64, String> {
+    let band = Band::by_name(&band).ok_or_else(|| format!("Unknown band '{band}'"))?;
+    let freq =
+        with_radio(&state, &app, |r| qsy_to_band(r.as_mut(), band, apply_band_defaults))?;
+    if apply_band_defaults {
+        // start_tx re-applies the configured power before PTT, so keep it in step
+        state
+            .config
+            .lock()
+            .map_err(|_| "config lock poisoned".to_string())?
+            .tx_power_watts = band.max_power_watts;
+    }
+    Ok(freq.as_hz())
+}
+
+/// Apply a band-change macro (frequency, then mode, then power), stopping at
+/// the first failure. Keeps the configured TX power in step like `qsy`.
+#[tauri::command]
+pub fn apply_band_plan(
+    app: AppHandle,
+    state: State<AppState>,
+    plan: BandPlanEntry,
+) -> Result<(), String> {
+    with_radio(&state, &app, |r| r.apply_band_plan(&plan))?;
+    if let Some(watts) = plan.tx_power_watts {
+        state
+            .config
+            .lock()
+            .map_err(|_| "config lock poisoned".to_string())?
+            .tx_power_watts = watts;
+    }
+    Ok(())
+}
+
+/// Step the VFO by a fixed increment (e.g. ±100 Hz, ±1 kHz) within the current band.
+#[tauri::command]
+pub fn step_frequency(app: AppHandle, state: State<AppState>, delta_hz: i32) -> Result<f64, String> {
+    with_radio(&state, &app, |r| step_frequency_by(r.as_mut(), delta_hz).map(|f| f.as_hz()))
 }
 
 #[tauri::command]
@@ -90,12 +222,104 @@ pub fn get_signal_strength(app: AppHandle, state: State<AppState>) -> Result<f32
     with_radio(&state, &app, |r| r.get_signal_strength())
 }
 
+/// The radio's own S-meter, raw and in S-units
+#[tauri::command]
+pub fn read_s_meter(app: AppHandle, state: State<AppState>) -> Result<SMeterReading, String> {
+    with_radio(&state, &app, |r| r.read_s_meter().map(SMeterReading::from_raw))
+}
+
+/// Read the approximate SWR while transmitting. Refused when not keyed,
+/// since the meter then reads a meaningless value.
+pub fn read_swr_while_keyed(radio: &mut dyn RadioControl) -> Psk31Result<f32> {
+    if !radio.is_transmitting() {
+        return Err(Psk31Error::Cat("SWR can only be read while transmitting".into()));
+    }
+    radio.read_swr()
+}
+
+#[tauri::command]
+pub fn read_swr(app: AppHandle, state: State<AppState>) -> Result<f32, String> {
+    with_radio(&state, &app, |r| read_swr_while_keyed(r.as_mut()))
+}
+
 /// Returns frequency + mode in one IF; round-trip, used for periodic UI sync.
 #[tauri::command]
 pub fn get_radio_state(app: AppHandle, state: State<AppState>) -> Result<RadioStatus, String> {
     with_radio(&state, &app, |r| r.get_status())
 }
 
+/// Read back the radio's receive filter, to check it suits PSK-31.
+/// `None` if the connected radio can't report it.
+#[tauri::command]
+pub fn get_radio_filter_state(
+    app: AppHandle,
+    state: State<AppState>,
+) -> Result<Option<RadioFilterState>, String> {
+    with_radio(&state, &app, |r| r.get_filter_state())
+}
+
+/// Which VFO (or memory channel) the radio is on. `None` if the
+/// connected radio can't report it.
+#[tauri::command]
+pub fn get_active_vfo(app: AppHandle, state: State<AppState>) -> Result<Option<Vfo>, String> {
+    with_radio(&state, &app, |r| r.get_active_vfo())
+}
+
+/// The memory channel the radio is tuned to and its stored frequency.
+/// `None` when the radio is on a VFO or can't report memory channels.
+pub fn active_memory_channel(
+    radio: &mut dyn RadioControl,
+) -> Psk31Result<Option<RadioMemoryChannel>> {
+    if radio.get_active_vfo()? != Some(Vfo::Memory) {
+        return Ok(None);
+    }
+    radio.memory_channel_frequency()
+}
+
+#[tauri::command]
+pub fn get_memory_channel_frequency(
+    app: AppHandle,
+    state: State<AppState>,
+) -> Result<Option<RadioMemoryChannel>, String> {
+    with_radio(&state, &app, |r| active_memory_channel(r.as_mut()))
+}
+
+/// Turn the radio's RIT on or off
+#[tauri::command]
+pub fn set_rit_enabled(
+    app: AppHandle,
+    state: State<AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    with_radio(&state, &app, |r| r.set_rit_enabled(enabled))
+}
+
+/// Turn the radio's XIT on or off
+#[tauri::command]
+pub fn set_xit_enabled(
+    app: AppHandle,
+    state: State<AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    with_radio(&state, &app, |r| r.set_xit_enabled(enabled))
+}
+
+/// Set the RIT/XIT clarifier offset. The radio adapter rejects offsets
+/// outside its range (±9999 Hz on the FT-991A) without sending anything.
+#[tauri::command]
+pub fn set_rit_offset(
+    app: AppHandle,
+    state: State<AppState>,
+    offset_hz: i32,
+) -> Result<(), String> {
+    with_radio(&state, &app, |r| r.set_rit_offset(offset_hz))
+}
+
+#[tauri::command]
+pub fn clear_rit(app: AppHandle, state: State<AppState>) -> Result<(), String> {
+    with_radio(&state, &app, |r| r.clear_rit())
+}
+
 #[tauri::command]
 pub fn get_tx_power(app: AppHandle, state: State<AppState>) -> Result<u32, String> {
     with_radio(&state, &app, |r| r.get_tx_power()).map_err(|e| e.to_string())
@@ -104,7 +328,7 @@ pub fn get_tx_power(app: AppHandle, state: State<AppState>) -> Result<u32, Strin
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{Frequency, Psk31Result, RadioStatus};
+    use crate::domain::{Frequency, Psk31Result, RadioStatus, Vfo};
     use crate::ports::RadioControl;
 
     /// Minimal mock radio whose tx_power field can be set for testing.
@@ -134,6 +358,7 @@ mod tests {
                 rit_offset_hz: 0,
                 rit_enabled: false,
                 split: false,
+                vfo: Vfo::A,
             })
         }
     }
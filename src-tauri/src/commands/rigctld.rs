@@ -0,0 +1,76 @@
+//! rigctld server — let Hamlib clients share the radio
+//!
+//! `start_rigctld_server` listens on localhost (port 4532 unless told
+//! otherwise) and serves the Hamlib `rigctld` protocol, so tools such as
+//! fldigi or a logger can read and set the rig through our CAT connection.
+//! Each command line takes the radio lock on its own, so connected clients
+//! never block the UI between commands.
+
+use std::net::{Ipv4Addr, TcpListener};
+use std::sync::atomic::Ordering;
+use std::thread;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::adapters::rigctld::{self, DEFAULT_RIGCTLD_PORT};
+use crate::commands::radio::with_radio;
+use crate::state::AppState;
+
+/// Start serving rigctld on `127.0.0.1:port` (default 4532), replacing any
+/// server already running. Returns the port actually bound.
+#[tauri::command]
+pub fn start_rigctld_server(
+    app: AppHandle,
+    state: State<AppState>,
+    port: Option<u16>,
+) -> Result<u16, String> {
+    stop_server(&state)?;
+
+    let port = port.unwrap_or(DEFAULT_RIGCTLD_PORT);
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .map_err(|e| format!("Could not listen on port {port}: {e}"))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+
+    let abort = state.rigctld_abort.clone();
+    abort.store(false, Ordering::SeqCst);
+    let handle_line = move |line: &str| {
+        let state = app.state::<AppState>();
+        with_radio(&state, &app, |r| Ok(rigctld::handle_line(line, r.as_mut()))).unwrap_or_else(
+            |e| {
+                log::warn!("rigctld: {e}");
+                Some(rigctld::radio_unavailable_reply())
+            },
+        )
+    };
+    let handle = thread::spawn(move || {
+        if let Err(e) = rigctld::run_server(listener, abort, handle_line) {
+            log::error!("rigctld server stopped: {e}");
+        }
+    });
+    state
+        .rigctld_thread
+        .lock()
+        .map_err(|_| "rigctld server state corrupted")?
+        .replace(handle);
+    log::info!("rigctld server listening on 127.0.0.1:{port}");
+    Ok(port)
+}
+
+/// Stop the rigctld server and disconnect its clients (no-op when not running).
+#[tauri::command]
+pub fn stop_rigctld_server(state: State<AppState>) -> Result<(), String> {
+    stop_server(&state)
+}
+
+fn stop_server(state: &AppState) -> Result<(), String> {
+    state.rigctld_abort.store(true, Ordering::SeqCst);
+    let handle = state
+        .rigctld_thread
+        .lock()
+        .map_err(|_| "rigctld server state corrupted")?
+        .take();
+    if let Some(handle) = handle {
+        handle.join().map_err(|_| "rigctld server thread panicked".to_string())?;
+    }
+    Ok(())
+}
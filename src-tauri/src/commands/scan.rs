@@ -0,0 +1,199 @@
+//! Band scanner — hop between PSK-31 calling frequencies looking for activity
+//!
+//! `start_band_scan` spawns a thread that cycles through the requested bands:
+//! QSY the radio to each band's PSK-31 frequency (DATA mode, power untouched),
+//! park the RX carrier at the configured audio offset, reset the decoder and
+//! listen for `dwell_seconds`. The signal level published by the audio thread
+//! feeds an `AutoRecordTrigger` built from the configured signal detector
+//! thresholds (counting in ms); the first start edge emits `scan-hit` and
+//! ends the scan with the radio left on that band. `scan-progress` is emitted
+//! on every hop. `stop_band_scan` (or a radio error) cancels it.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::commands::radio::with_radio;
+use crate::domain::{Band, BandPlanEntry};
+use crate::events::{self, ScanHitPayload, ScanProgressPayload};
+use crate::modem::auto_record::{AutoRecordTrigger, TriggerEvent};
+use crate::state::AppState;
+
+/// How often the scan thread samples the signal level and the cancel flag
+const SCAN_POLL: Duration = Duration::from_millis(100);
+
+/// Resolve band names into the ordered list of scan stops. Names are
+/// case-insensitive; repeats are dropped so each band is visited once per pass.
+fn scan_steps(bands: &[String]) -> Result<Vec<&'static Band>, String> {
+    let mut steps: Vec<&'static Band> = Vec::new();
+    for name in bands {
+        let band = Band::by_name(name).ok_or_else(|| format!("Unknown band '{name}'"))?;
+        if !steps.contains(&band) {
+            steps.push(band);
+        }
+    }
+    if steps.is_empty() {
+        return Err("No bands to scan".into());
+    }
+    Ok(steps)
+}
+
+/// Start scanning `bands` in order, repeating until a signal is found or
+/// the scan is cancelled. Needs a connected radio and RX running.
+#[tauri::command]
+pub fn start_band_scan(
+    app: AppHandle,
+    state: State<AppState>,
+    bands: Vec<String>,
+    dwell_seconds: f64,
+) -> Result<(), String> {
+    if !(0.5..=60.0).contains(&dwell_seconds) {
+        return Err("Dwell must be between 0.5 and 60 seconds".into());
+    }
+    let steps = scan_steps(&bands)?;
+    if state.scan_thread.lock().map_err(|_| "scan state corrupted")?.is_some() {
+        return Err("Already scanning".into());
+    }
+    if state.tx_thread.lock().map_err(|_| "TX state corrupted")?.is_some() {
+        return Err("Cannot scan while transmitting".into());
+    }
+    if !state.rx_running.load(Ordering::SeqCst) {
+        return Err("RX is not running — start receiving before scanning".into());
+    }
+    if state.radio.lock().map(|r| r.is_none()).unwrap_or(true) {
+        return Err("Radio not connected".into());
+    }
+
+    let abort = state.scan_abort.clone();
+    abort.store(false, Ordering::SeqCst);
+    let dwell = Duration::from_secs_f64(dwell_seconds);
+    let handle = thread::spawn(move || run_scan_thread(app, abort, steps, dwell));
+    state
+        .scan_thread
+        .lock()
+        .map_err(|_| "scan state corrupted")?
+        .replace(handle);
+    Ok(())
+}
+
+/// Cancel a running band scan. The radio stays on the band it was on.
+#[tauri::command]
+pub fn stop_band_scan(state: State<AppState>) -> Result<(), String> {
+    state.scan_abort.store(true, Ordering::SeqCst);
+    if let Some(handle) = state.scan_thread.lock().map_err(|_| "scan state corrupted")?.take() {
+        handle.join().map_err(|_| "Scan thread panicked".to_string())?;
+    }
+    Ok(())
+}
+
+fn run_scan_thread(
+    app: AppHandle,
+    abort: Arc<AtomicBool>,
+    steps: Vec<&'static Band>,
+    dwell: Duration,
+) {
+    let state = app.state::<AppState>();
+    let total_steps = steps.len();
+
+    'scan: for (step, band) in steps.iter().cycle().enumerate() {
+        if abort.load(Ordering::SeqCst) {
+            break;
+        }
+
+        // QSY in DATA mode; leave TX power alone, we're only listening
+        let plan = BandPlanEntry {
+            mode: Some(band.default_mode.to_string()),
+            ..BandPlanEntry::for_band(band, false)
+        };
+        if let Err(e) = with_radio(&state, &app, |r| r.apply_band_plan(&plan)) {
+            log::warn!("Band scan stopped: {e}");
+            break;
+        }
+        let Ok((carrier, mut detector)) = state.config.lock().map(|c| {
+            let detector = AutoRecordTrigger::new(c.signal_on_threshold, 0)
+                .with_hysteresis(c.signal_on_threshold, c.signal_off_threshold)
+                .with_min_dwell(c.signal_min_dwell_ms as usize);
+            (c.carrier_freq, detector)
+        }) else {
+            log::warn!("Band scan stopped: config lock poisoned");
+            break;
+        };
+        if let Ok(mut rx_carrier) = state.rx_carrier_freq.lock() {
+            *rx_carrier = carrier;
+        }
+        // Don't let the last band's AGC level count as a hit here
+        state.decoder_reset_requested.store(true, Ordering::SeqCst);
+        if let Ok(mut status) = state.status.lock() {
+            status.signal_level = 0.0;
+        }
+
+        let _ = app.emit(
+            events::SCAN_PROGRESS,
+            ScanProgressPayload {
+                band: band.name.to_string(),
+                frequency_hz: band.psk31_hz,
+                step: step % total_steps + 1,
+                total_steps,
+            },
+        );
+
+        let started = Instant::now();
+        while started.elapsed() < dwell {
+            if abort.load(Ordering::SeqCst) {
+                break 'scan;
+            }
+            thread::sleep(SCAN_POLL);
+            let level = state.status.lock().map(|s| s.signal_level).unwrap_or(0.0);
+            let poll_ms = SCAN_POLL.as_millis() as usize;
+            if detector.update(level, poll_ms) == Some(TriggerEvent::Start) {
+                let _ = app.emit(
+                    events::SCAN_HIT,
+                    ScanHitPayload {
+                        band: band.name.to_string(),
+                        frequency_hz: band.psk31_hz,
+                        level,
+                    },
+                );
+                break 'scan;
+            }
+        }
+    }
+
+    // Self-clear like the TX thread; stop_band_scan joins instead if it holds the lock
+    if let Ok(mut guard) = state.scan_thread.try_lock() {
+        let _ = guard.take();
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(bands: &[&str]) -> Vec<String> {
+        bands.iter().map(|b| b.to_string()).collect()
+    }
+
+    #[test]
+    fn scan_steps_follow_requested_order() {
+        let steps = scan_steps(&names(&["20m", "40M", "17m"])).unwrap();
+        let hops: Vec<(&str, u64)> = steps.iter().map(|b| (b.name, b.psk31_hz)).collect();
+        assert_eq!(hops, vec![("20m", 14_070_000), ("40m", 7_035_000), ("17m", 18_100_000)]);
+    }
+
+    #[test]
+    fn scan_steps_visit_each_band_once_per_pass() {
+        let steps = scan_steps(&names(&["30m", "20m", "30M", "20m"])).unwrap();
+        let hops: Vec<&str> = steps.iter().map(|b| b.name).collect();
+        assert_eq!(hops, vec!["30m", "20m"]);
+    }
+
+    #[test]
+    fn scan_steps_reject_unknown_or_empty_lists() {
+        let err = scan_steps(&names(&["20m", "11m"])).unwrap_err();
+        assert!(err.contains("11m"), "got: {err}");
+        assert!(scan_steps(&[]).is_err());
+    }
+}
@@ -1,12 +1,14 @@
 //! Serial port commands — list, connect, disconnect
 
-use crate::adapters::ft991a::Ft991aRadio;
 use crate::adapters::mock_radio::MockRadio;
+use crate::adapters::radio_registry::radio_factory;
 use crate::adapters::serial_port::SerialPortFactory;
-use crate::domain::{data_mode_for_frequency, RadioInfo, SerialPortInfo};
-use crate::ports::{RadioControl, SerialFactory};
+use crate::commands::config::active_radio_type;
+use crate::domain::{data_mode_for_frequency, RadioInfo, SerialParams, SerialPortInfo};
+use crate::events::{self, RecoveredFromKeyedPayload};
+use crate::ports::{RadioControl, SerialFactory, SystemClock};
 use crate::state::AppState;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
 #[tauri::command]
 pub fn list_serial_ports() -> Result<Vec<SerialPortInfo>, String> {
@@ -22,58 +24,154 @@ pub fn list_serial_ports() -> Result<Vec<SerialPortInfo>, String> {
 
 #[tauri::command]
 pub fn connect_serial(
+    app: AppHandle,
     state: State<AppState>,
     port: String,
     baud_rate: u32,
+) -> Result<RadioInfo, String> {
+    connect_serial_advanced(app, state, port, SerialParams::new(baud_rate))
+}
+
+/// `connect_serial` with a custom CAT command delay and read timeout, for
+/// USB-serial adapters that need slower timing.
+#[tauri::command]
+pub fn connect_serial_advanced(
+    app: AppHandle,
+    state: State<AppState>,
+    port: String,
+    params: SerialParams,
 ) -> Result<RadioInfo, String> {
     let mock_mode = std::env::var("MOCK_RADIO").is_ok();
 
+    // What the driver reports it applied, where that differs from the request
+    let mut baud_rate = params.baud_rate;
+    let mut read_timeout_ms = None;
+    let mut mismatches = Vec::new();
+
     let (mut radio, display_port): (Box<dyn RadioControl>, String) = if mock_mode {
         log::info!("[MOCK RADIO] MOCK_RADIO=1: skipping serial, using mock adapter");
         (Box::new(MockRadio::new()), "mock".to_string())
     } else {
-        // Open real serial connection and wrap in FT-991A adapter
-        let connection = SerialPortFactory::open(&port, baud_rate).map_err(|e| e.to_string())?;
-        (Box::new(Ft991aRadio::new(connection)), port.clone())
+        // Pick the adapter for the profile's radio before touching the port
+        let factory =
+            radio_factory(&active_radio_type(&app, &state)?).map_err(|e| e.to_string())?;
+        let opened =
+            SerialPortFactory::open_with_params(&port, &params).map_err(|e| e.to_string())?;
+        mismatches = opened.mismatches(&params);
+        baud_rate = opened.baud_rate;
+        read_timeout_ms = Some(opened.read_timeout.as_millis() as u64);
+        (factory(opened.connection, &params, Box::new(SystemClock)), port.clone())
+    };
+
+    let mut info = probe_radio(radio.as_mut(), display_port.clone(), baud_rate);
+    for mismatch in &mismatches {
+        log::warn!("connect: {mismatch}");
+    }
+    info.warnings.extend(mismatches);
+    info.read_timeout_ms = read_timeout_ms;
+    if info.recovered_from_keyed {
+        let _ = app.emit(
+            events::RECOVERED_FROM_KEYED,
+            RecoveredFromKeyedPayload { port: display_port.clone() },
+        );
+    }
+
+    // Store radio and port name in app state
+    let mut radio_slot = state.radio.lock().map_err(|_| "Radio state corrupted".to_string())?;
+    *radio_slot = Some(radio);
+    *state.serial_port_name.lock().map_err(|_| "Serial port state corrupted".to_string())? =
+        Some(display_port);
+
+    Ok(info)
+}
+
+/// Query the freshly-opened radio for its current frequency and mode.
+///
+/// First, if the adapter can report transmit state and the radio is keyed
+/// (a previous session crashed mid-TX), PTT is released and
+/// `recovered_from_keyed` is set.
+///
+/// Probes are best-effort: a radio that answers `FA;` but NAKs `MD0;` (some
+/// firmware revisions do) should still connect. Each failed probe leaves its
+/// field as `None` and adds a human-readable entry to `RadioInfo.warnings`.
+pub fn probe_radio(radio: &mut dyn RadioControl, port: String, baud_rate: u32) -> RadioInfo {
+    let mut warnings = Vec::new();
+
+    // Un-key a radio left transmitting. Radios that can't answer TX; just skip this.
+    let recovered_from_keyed = match radio.is_transmitting_remote() {
+        Ok(Some(true)) => {
+            log::warn!("connect: radio is transmitting — releasing PTT");
+            match radio.ptt_off() {
+                Ok(()) => {
+                    warnings.push("Radio was left transmitting; PTT released".into());
+                    true
+                }
+                Err(e) => {
+                    warnings.push(format!("Radio is transmitting and PTT release failed: {e}"));
+                    false
+                }
+            }
+        }
+        Ok(_) => false,
+        Err(e) => {
+            log::debug!("connect: TX state query failed: {e}");
+            false
+        }
     };
 
     // Auto-detect current state with separate FA; and MD0; queries.
     // Using FA; + MD0; avoids the firmware-variant ambiguity in IF; response parsing,
     // and FA; has no amateur-band restriction on read (unlike set_frequency).
-    let frequency_hz = radio.get_frequency().map_err(|e| e.to_string())?.as_hz();
-    let current_mode = radio.get_mode().map_err(|e| e.to_string())?;
+    let frequency_hz = match radio.get_frequency() {
+        Ok(freq) => Some(freq.as_hz()),
+        Err(e) => {
+            log::warn!("connect: frequency probe failed: {e}");
+            warnings.push(format!("Could not read frequency: {e}"));
+            None
+        }
+    };
+    let current_mode = match radio.get_mode() {
+        Ok(mode) => Some(mode),
+        Err(e) => {
+            log::warn!("connect: mode probe failed: {e}");
+            warnings.push(format!("Could not read mode: {e}"));
+            None
+        }
+    };
 
     // Ensure the radio is in the correct DATA mode for this frequency.
     // BS; (used by set_frequency) recalls the band's stored mode, which may be a phone
     // mode (e.g. LSB) rather than DATA-LSB. Correct it here at connect time.
-    let required_mode = data_mode_for_frequency(frequency_hz);
-    let mode = if current_mode != required_mode {
-        log::info!("connect: correcting mode {current_mode} → {required_mode} for {frequency_hz} Hz");
-        if let Err(e) = radio.set_mode(required_mode) {
-            log::warn!("connect: set_mode failed (continuing with current mode): {e}");
-            current_mode
-        } else {
-            required_mode.to_string()
+    // Only attempted when both probes succeeded — we won't guess at an unknown mode.
+    let mode = match (frequency_hz, current_mode) {
+        (Some(hz), Some(current_mode)) => {
+            let required_mode = data_mode_for_frequency(hz);
+            if current_mode != required_mode {
+                log::info!("connect: correcting mode {current_mode} → {required_mode} for {hz} Hz");
+                if let Err(e) = radio.set_mode(required_mode) {
+                    log::warn!("connect: set_mode failed (continuing with current mode): {e}");
+                    warnings.push(format!("Could not set {required_mode}: {e}"));
+                    Some(current_mode)
+                } else {
+                    Some(required_mode.to_string())
+                }
+            } else {
+                Some(current_mode)
+            }
         }
-    } else {
-        current_mode
+        (_, current_mode) => current_mode,
     };
 
-    let info = RadioInfo {
-        port: display_port.clone(),
+    RadioInfo {
+        port,
         baud_rate,
         frequency_hz,
         mode,
         connected: true,
-    };
-
-    // Store radio and port name in app state
-    let mut radio_slot = state.radio.lock().map_err(|_| "Radio state corrupted".to_string())?;
-    *radio_slot = Some(radio);
-    *state.serial_port_name.lock().map_err(|_| "Serial port state corrupted".to_string())? =
-        Some(display_port);
-
-    Ok(info)
+        warnings,
+        recovered_from_keyed,
+        read_timeout_ms: None,
+    }
 }
 
 #[tauri::command]
@@ -3,26 +3,71 @@
 //! The TX pipeline:
 //! 1. Encode text to BPSK-31 samples (upfront, not streaming)
 //! 2. Spawn a TX thread that:
+//!    - Sets `ModemStatus.tx_running` until it exits (RX mutes while set)
 //!    - Activates PTT (if radio connected)
-//!    - Waits 50ms for PTT settle
-//!    - Plays the samples via CpalAudioOutput
+//!    - Waits the configured PTT lead delay (default 50ms) for relays to settle
+//!    - Plays the samples via CpalAudioOutput (`play_tx`, which takes any
+//!      `AudioOutput` and `Clock`, so tests drive it with `MockAudioOutput`)
 //!    - Emits progress events to the frontend
-//!    - Deactivates PTT on both abort and complete paths
+//!    - On completion, waits until the last sample has actually left the
+//!      device (`completion_wait`: sample count / rate plus output latency)
+//!    - Deactivates PTT on both abort and complete paths (after the tail
+//!      delay on completion, immediately on abort)
 //!    - Emits a `tx-status: complete` or `tx-status: aborted` event
 //! 3. stop_tx signals abort and calls PTT OFF as a belt-and-suspenders safety net
+//!
+//! An optional `monitor_device_id` plays the same audio at `MONITOR_GAIN` on
+//! a second output (e.g. the built-in speakers) from its own cursor, since
+//! the two devices run on separate clocks. If the monitor can't be opened TX
+//! carries on without it.
+//!
+//! An optional `record_path` tees what the output callback hands the rig into
+//! a mono 16-bit WAV, through a lock-free `sample_ring` so the real-time
+//! callback never waits. The file is written as soon as the stream stops and
+//! its path comes back in the final `tx-status` (`complete` or `aborted`).
+//!
+//! The playback position is a `TxCursor` shared with the output callback. On
+//! abort the TX thread freezes it before stopping the stream, so the progress
+//! it reports is exactly what the callback handed to the device.
+//!
+//! `start_tx` refuses up front if a connected radio reports TX inhibited
+//! (see `RadioControl::get_tx_inhibit`). It and `start_tune` also refuse an
+//! output device that is no longer listed, before encoding or keying —
+//! otherwise the rig would key with no audio.
+//!
+//! Macros such as `<NR>` (contest serial) are expanded before encoding.
+//!
+//! When `auto_log` is enabled, a completed transmission whose text contains a
+//! callsign and RST is appended to `AppState.qso_log` (and `qso-logged` emitted),
+//! and the contest serial advances.
+//!
+//! `encode_to_wav` runs the same encoder offline and writes the result to a
+//! WAV file, for interoperability test vectors.
 
-use serde::Serialize;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Emitter, Manager};
 
-use crate::adapters::cpal_audio::CpalAudioOutput;
+use crate::adapters::cpal_audio::{match_device_name, CpalAudioOutput};
+use crate::adapters::wav_file::write_wav_file;
+use crate::commands::audio::selected_audio_host;
+use crate::commands::config::advance_contest_serial;
+use crate::commands::operator::load_operator_profile;
 use crate::commands::radio::with_radio;
-use crate::domain::data_mode_for_frequency;
-use crate::modem::encoder::Psk31Encoder;
-use crate::ports::{AudioOutput, RadioControl};
+use crate::domain::{
+    data_mode_for_frequency, expand_tx_macro, parse_exchange, uses_operator_tokens,
+    DutyCycleTracker, MacroContext, ModemStatus, OperatorProfile, Psk31Result, QsoRecord,
+    TxDutyCycle, WavSampleFormat,
+};
+use crate::dsp::sample_ring::{sample_ring, SampleConsumer, SampleProducer};
+use crate::events::{self, TxStatusPayload};
+use crate::modem::encoder::{validate_message_length, Psk31Encoder};
+use crate::modem::tune::TuneGenerator;
+use crate::modem::varicode::Varicode;
+use crate::ports::{AudioOutput, Clock, RadioControl, SystemClock};
 use crate::state::AppState;
 
 /// Query the radio's current frequency and mode; if the mode is not the correct
@@ -59,11 +104,135 @@ fn ensure_data_mode(radio: &mut dyn RadioControl) {
     }
 }
 
-/// Payload for `tx-status` events sent to the frontend
-#[derive(Clone, Serialize)]
-struct TxStatusPayload {
-    status: String,
-    progress: f32,
+/// Refuse to key a radio that reports TX inhibited. A failed query is
+/// logged and doesn't block TX, like the other pre-TX radio checks.
+pub fn check_tx_permitted(radio: &mut dyn RadioControl) -> Result<(), String> {
+    match radio.get_tx_inhibit() {
+        Ok(Some(reason)) => Err(format!("Radio is inhibiting TX: {reason}")),
+        Ok(None) => Ok(()),
+        Err(e) => {
+            log::warn!("TX inhibit check failed (continuing): {e}");
+            Ok(())
+        }
+    }
+}
+
+/// Refuse to transmit into an output device that is no longer listed, using
+/// the same name matching as playback.
+pub fn check_output_device(output: &dyn AudioOutput, device_id: &str) -> Result<(), String> {
+    let names: Vec<String> = output
+        .list_devices()
+        .map_err(|e| format!("Cannot list audio outputs: {e}"))?
+        .into_iter()
+        .filter(|d| d.is_output)
+        .map(|d| d.name)
+        .collect();
+    match_device_name(&names, device_id)
+        .map(|_| ())
+        .map_err(|e| format!("TX output unavailable: {e}"))
+}
+
+/// start_tx's pre-flight, run before anything touches the radio: the output
+/// device must still be listed, then a connected radio must not be
+/// inhibiting TX. Keys nothing.
+pub fn check_tx_preflight(
+    output: &dyn AudioOutput,
+    device_id: &str,
+    radio: Option<&mut dyn RadioControl>,
+) -> Result<(), String> {
+    check_output_device(output, device_id)?;
+    radio.map_or(Ok(()), check_tx_permitted)
+}
+
+/// Refuse TX that would take the share of the duty-cycle window spent
+/// transmitting past the configured limit, counting `planned` more airtime.
+fn check_duty_cycle(state: &AppState, now: Instant, planned: Duration) -> Result<(), String> {
+    let (limit, window_secs) = state
+        .config
+        .lock()
+        .map(|c| (c.max_tx_duty_cycle_percent, c.tx_duty_window_secs))
+        .map_err(|_| "config lock poisoned".to_string())?;
+    if limit >= 100 {
+        return Ok(());
+    }
+    let window = Duration::from_secs(window_secs);
+    let percent = state
+        .tx_duty
+        .lock()
+        .map_err(|_| "TX duty state corrupted".to_string())?
+        .projected_duty_cycle(now, planned, window)
+        * 100.0;
+    if percent > f64::from(limit) {
+        return Err(format!(
+            "TX duty cycle would reach {percent:.0}% of the last {window_secs} s \
+             (limit {limit}%) — let the rig cool down"
+        ));
+    }
+    Ok(())
+}
+
+/// Share of the duty-cycle window spent transmitting, against its limit.
+#[tauri::command]
+pub fn get_tx_duty_cycle(state: tauri::State<'_, AppState>) -> Result<TxDutyCycle, String> {
+    let (limit_percent, window_secs) = state
+        .config
+        .lock()
+        .map(|c| (c.max_tx_duty_cycle_percent, c.tx_duty_window_secs))
+        .map_err(|_| "config lock poisoned".to_string())?;
+    let duty = state
+        .tx_duty
+        .lock()
+        .map_err(|_| "TX duty state corrupted".to_string())?
+        .projected_duty_cycle(Instant::now(), Duration::ZERO, Duration::from_secs(window_secs));
+    Ok(TxDutyCycle { percent: duty * 100.0, limit_percent, window_secs })
+}
+
+/// Build the auto-log record for a completed transmission.
+///
+/// Returns `None` when auto-log is off or the text has no recognisable
+/// callsign + RST exchange.
+fn auto_log_record(
+    enabled: bool,
+    text: &str,
+    frequency_hz: Option<f64>,
+    mode: Option<String>,
+    timestamp_utc: u64,
+) -> Option<QsoRecord> {
+    if !enabled {
+        return None;
+    }
+    let (callsign, rst_sent) = parse_exchange(text)?;
+    Some(QsoRecord { callsign, rst_sent, frequency_hz, mode, timestamp_utc })
+}
+
+/// Log the QSO for a completed transmission, reading the rig's current
+/// frequency/mode (best-effort) for the record.
+fn log_completed_tx(app: &AppHandle, state: &AppState, text: &str) {
+    let (frequency_hz, mode) = match state.radio.lock() {
+        Ok(mut guard) => match guard.as_mut() {
+            Some(radio) => (
+                radio.get_frequency().ok().map(|f| f.as_hz()),
+                radio.get_mode().ok(),
+            ),
+            None => (None, None),
+        },
+        Err(_) => (None, None),
+    };
+    let timestamp_utc = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    if let Some(record) = auto_log_record(true, text, frequency_hz, mode, timestamp_utc) {
+        log::info!("Auto-logged QSO with {} ({})", record.callsign, record.rst_sent);
+        if let Ok(mut qso_log) = state.qso_log.lock() {
+            qso_log.push(record.clone());
+        }
+        if let Err(e) = advance_contest_serial(app, state) {
+            log::warn!("Could not advance contest serial: {e}");
+        }
+        let _ = app.emit(events::QSO_LOGGED, record);
+    }
 }
 
 #[tauri::command]
@@ -72,23 +241,61 @@ pub fn start_tx(
     state: tauri::State<'_, AppState>,
     text: String,
     device_id: String,
+    monitor_device_id: Option<String>,
+    record_path: Option<String>,
 ) -> Result<(), String> {
     // Check if already transmitting
     if state.tx_thread.lock().unwrap().is_some() {
         return Err("Already transmitting".into());
     }
+    if monitor_device_id.as_deref() == Some(device_id.as_str()) {
+        return Err("Monitor device must differ from the rig output device".into());
+    }
+    // Respect the rig's TX inhibit before touching its mode or power
+    let output = CpalAudioOutput::new().with_host(selected_audio_host(&state));
+    {
+        let mut radio = state.radio.lock().map_err(|_| "Radio state corrupted")?;
+        let radio = radio.as_mut().map(|r| r.as_mut() as &mut dyn RadioControl);
+        check_tx_preflight(&output, &device_id, radio)?;
+    }
 
     // Read carrier frequency from config
     let carrier_freq = state.config.lock().unwrap().carrier_freq;
     let sample_rate = state.config.lock().unwrap().sample_rate;
+    let idle_pattern = state.config.lock().unwrap().idle_pattern;
+    let baud = state.config.lock().unwrap().modem_baud;
+
+    let serial = *state
+        .contest_serial
+        .lock()
+        .map_err(|_| "contest serial lock poisoned".to_string())?;
+    // Only macros need the operator profile; an unreadable one must not block TX
+    let operator = if uses_operator_tokens(&text) {
+        load_operator_profile(&app).unwrap_or_else(|e| {
+            log::warn!("Operator profile unavailable, expanding macros empty: {e}");
+            OperatorProfile::default()
+        })
+    } else {
+        OperatorProfile::default()
+    };
+    let text = expand_tx_macro(&text, &MacroContext { serial, operator: &operator });
+
+    // Keep the text for the auto-log hook only when the operator opted in
+    let auto_log_text = state.config.lock().unwrap().auto_log.then(|| text.clone());
+
+    validate_message_length(&text).map_err(|e| e.to_string())?;
 
     // Encode the entire message upfront
-    let encoder = Psk31Encoder::new(sample_rate, carrier_freq);
+    let encoder = Psk31Encoder::new(sample_rate, carrier_freq)
+        .with_baud(baud)
+        .with_idle_pattern(idle_pattern);
     let samples = encoder.encode(&text);
 
     if samples.is_empty() {
         return Err("Nothing to transmit".into());
     }
+    let airtime = Duration::from_secs_f64(samples.len() as f64 / f64::from(sample_rate));
+    check_duty_cycle(&state, Instant::now(), airtime)?;
 
     // Reset abort flag
     let abort = state.tx_abort.clone();
@@ -104,16 +311,13 @@ pub fn start_tx(
         Ok(())
     });
 
-    // Shared playback position for progress tracking
-    let play_pos = Arc::new(AtomicUsize::new(0));
-    let total_samples = samples.len();
-
     let handle = {
         let abort = abort.clone();
-        let play_pos = play_pos.clone();
 
         thread::spawn(move || {
-            run_tx_thread(app, abort, play_pos, samples, device_id, total_samples);
+            let job = TxJob { samples, sample_rate };
+            let outputs = TxOutputs { device_id, monitor_device_id };
+            run_tx_thread(app, abort, job, outputs, record_path, auto_log_text);
         })
     };
 
@@ -156,6 +360,11 @@ pub fn start_tune(
         return Err("Already transmitting".into());
     }
 
+    let output = CpalAudioOutput::new().with_host(selected_audio_host(&state));
+    check_output_device(&output, &device_id)?;
+    // Tune runs until stopped, so only the airtime already used counts
+    check_duty_cycle(&state, Instant::now(), Duration::ZERO)?;
+
     let carrier_freq = state.config.lock().unwrap().carrier_freq;
     let sample_rate = state.config.lock().unwrap().sample_rate;
 
@@ -203,7 +412,122 @@ pub fn stop_tune(state: tauri::State<'_, AppState>) -> Result<(), String> {
     Ok(())
 }
 
+/// PTT ON/OFF sequencing with lead and tail delays around the audio.
+///
+/// `sleep` is a seam so tests can observe the delays without waiting them out.
+pub struct PttSequencer<S: FnMut(Duration)> {
+    lead: Duration,
+    tail: Duration,
+    sleep: S,
+}
+
+impl PttSequencer<fn(Duration)> {
+    pub fn new(lead_ms: u64, tail_ms: u64) -> Self {
+        Self::with_sleep(lead_ms, tail_ms, thread::sleep)
+    }
+}
+
+impl<S: FnMut(Duration)> PttSequencer<S> {
+    pub fn with_sleep(lead_ms: u64, tail_ms: u64, sleep: S) -> Self {
+        Self {
+            lead: Duration::from_millis(lead_ms),
+            tail: Duration::from_millis(tail_ms),
+            sleep,
+        }
+    }
+
+    /// PTT ON (if a radio is connected), then wait out the lead delay.
+    /// The radio lock is released before sleeping.
+    pub fn key(&mut self, radio: &Mutex<Option<Box<dyn RadioControl>>>) {
+        if let Ok(mut guard) = radio.lock() {
+            if let Some(radio) = guard.as_mut() {
+                if let Err(e) = radio.ptt_on() {
+                    log::warn!("PTT ON failed (continuing without PTT): {e}");
+                }
+            }
+        }
+        (self.sleep)(self.lead);
+    }
+
+    /// Wait out the tail delay, then PTT OFF.
+    pub fn unkey(&mut self, radio: &Mutex<Option<Box<dyn RadioControl>>>) {
+        (self.sleep)(self.tail);
+        self.unkey_now(radio);
+    }
+
+    /// PTT OFF without the tail delay (abort path).
+    pub fn unkey_now(&mut self, radio: &Mutex<Option<Box<dyn RadioControl>>>) {
+        if let Ok(mut guard) = radio.lock() {
+            if let Some(radio) = guard.as_mut() {
+                if let Err(e) = radio.ptt_off() {
+                    log::warn!("PTT OFF failed: {e}");
+                }
+            }
+        }
+    }
+}
+
+/// Sequencer using the lead/tail delays from the current modem config.
+fn ptt_sequencer(state: &AppState) -> PttSequencer<fn(Duration)> {
+    let (lead_ms, tail_ms) = state
+        .config
+        .lock()
+        .map(|c| (c.ptt_lead_ms, c.ptt_tail_ms))
+        .unwrap_or((50, 50));
+    PttSequencer::new(lead_ms, tail_ms)
+}
+
+/// Output latency assumed when the device doesn't report one.
+const FALLBACK_OUTPUT_LATENCY: Duration = Duration::from_millis(50);
+
+/// How much longer to wait after `elapsed` since playback started before the
+/// last of `total_samples` has been played: the buffer's duration at
+/// `sample_rate` plus the device's output latency. Zero if already past.
+pub fn completion_wait(
+    total_samples: usize,
+    sample_rate: u32,
+    latency: Duration,
+    elapsed: Duration,
+) -> Duration {
+    let playback = Duration::from_secs_f64(total_samples as f64 / f64::from(sample_rate.max(1)));
+    (playback + latency).saturating_sub(elapsed)
+}
+
+/// Sets `ModemStatus.tx_running` for the life of a TX or tune thread, on
+/// every exit path, so the audio thread knows when to mute RX. The same
+/// span is recorded as airtime for the duty-cycle limit.
+struct TxRunningGuard<'a> {
+    status: &'a Mutex<ModemStatus>,
+    duty: &'a Mutex<DutyCycleTracker>,
+}
+
+impl<'a> TxRunningGuard<'a> {
+    fn new(status: &'a Mutex<ModemStatus>, duty: &'a Mutex<DutyCycleTracker>) -> Self {
+        if let Ok(mut s) = status.lock() {
+            s.tx_running = true;
+        }
+        if let Ok(mut d) = duty.lock() {
+            d.key(Instant::now());
+        }
+        Self { status, duty }
+    }
+}
+
+impl Drop for TxRunningGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut s) = self.status.lock() {
+            s.tx_running = false;
+        }
+        if let Ok(mut d) = self.duty.lock() {
+            d.unkey(Instant::now());
+        }
+    }
+}
+
 /// Tune thread: transmits a continuous sine wave at the carrier frequency until aborted.
+///
+/// The carrier ramps up on start and, on abort, ramps down before the stream
+/// stops and PTT drops, so the rig never sees a hard on/off edge.
 fn run_tune_thread(
     app: AppHandle,
     abort: Arc<std::sync::atomic::AtomicBool>,
@@ -212,76 +536,63 @@ fn run_tune_thread(
     sample_rate: f64,
 ) {
     let radio_state = app.state::<AppState>();
-
-    // PTT ON
-    if let Ok(mut guard) = radio_state.radio.lock() {
-        if let Some(radio) = guard.as_mut() {
-            if let Err(e) = radio.ptt_on() {
-                log::warn!("PTT ON failed (continuing without PTT): {e}");
-            }
-        }
-    }
-
-    thread::sleep(Duration::from_millis(50));
+    let _transmitting = TxRunningGuard::new(&radio_state.status, &radio_state.tx_duty);
+    let mut ptt = ptt_sequencer(&radio_state);
+    ptt.key(&radio_state.radio);
 
     let _ = app.emit(
-        "tx-status",
+        events::TX_STATUS,
         TxStatusPayload {
             status: "tuning".into(),
             progress: 0.0,
+            record_path: None,
         },
     );
 
-    let phase_inc = 2.0 * std::f64::consts::PI * carrier_freq / sample_rate;
     let abort_for_cb = abort.clone();
+    let released = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let released_for_cb = released.clone();
 
-    let mut audio_output = CpalAudioOutput::new();
-    let mut phase: f64 = 0.0;
+    let mut audio_output =
+        CpalAudioOutput::new().with_host(selected_audio_host(&radio_state));
+    let mut tone = TuneGenerator::new(carrier_freq, sample_rate);
 
     let start_result = audio_output.start(
         &device_id,
         Box::new(move |output_buf: &mut [f32]| {
             if abort_for_cb.load(Ordering::SeqCst) {
-                for s in output_buf.iter_mut() {
-                    *s = 0.0;
-                }
-                return;
+                tone.release();
             }
-            for s in output_buf.iter_mut() {
-                *s = phase.sin() as f32;
-                phase += phase_inc;
-                if phase > 2.0 * std::f64::consts::PI {
-                    phase -= 2.0 * std::f64::consts::PI;
-                }
+            tone.fill(output_buf);
+            if tone.is_finished() {
+                released_for_cb.store(true, Ordering::SeqCst);
             }
         }),
     );
 
     if let Err(e) = start_result {
         log::error!("Failed to start audio output for tune: {e}");
-        if let Ok(mut guard) = radio_state.radio.lock() {
-            if let Some(radio) = guard.as_mut() {
-                let _ = radio.ptt_off();
-            }
-        }
+        ptt.unkey_now(&radio_state.radio);
         return;
     }
 
     loop {
         if abort.load(Ordering::SeqCst) {
-            let _ = audio_output.stop();
-            if let Ok(mut guard) = radio_state.radio.lock() {
-                if let Some(radio) = guard.as_mut() {
-                    if let Err(e) = radio.ptt_off() {
-                        log::warn!("PTT OFF failed: {e}");
-                    }
+            // Let the release ramp play out (bounded in case the stream stalls)
+            for _ in 0..40 {
+                if released.load(Ordering::SeqCst) {
+                    break;
                 }
+                thread::sleep(Duration::from_millis(5));
             }
+            let _ = audio_output.stop();
+            ptt.unkey_now(&radio_state.radio);
             let _ = app.emit(
-                "tx-status",
+                events::TX_STATUS,
                 TxStatusPayload {
                     status: "aborted".into(),
                     progress: 0.0,
+                    record_path: None,
                 },
             );
             return;
@@ -290,123 +601,306 @@ fn run_tune_thread(
     }
 }
 
-/// TX thread: plays encoded samples through the audio output device.
-fn run_tx_thread(
-    app: AppHandle,
-    abort: Arc<std::sync::atomic::AtomicBool>,
-    play_pos: Arc<AtomicUsize>,
-    samples: Vec<f32>,
-    device_id: String,
-    total_samples: usize,
-) {
-    // Activate PTT at the top of the thread (before the settle delay)
-    let radio_state = app.state::<AppState>();
-    if let Ok(mut guard) = radio_state.radio.lock() {
-        if let Some(radio) = guard.as_mut() {
-            if let Err(e) = radio.ptt_on() {
-                log::warn!("PTT ON failed (continuing without PTT): {e}");
+/// Bit of `TxCursor::state` set once playback is stopped
+const CURSOR_STOPPED: usize = 1 << (usize::BITS - 1);
+
+/// Playback position shared by the TX thread and the output callback.
+///
+/// The position and a stopped bit share one atomic word. The callback only
+/// advances it with a compare-exchange, so once `stop` has set the bit no
+/// callback still in flight can move it: the position `stop` returns is final
+/// and matches the samples actually handed to the device.
+struct TxCursor {
+    state: AtomicUsize,
+    total: usize,
+}
+
+impl TxCursor {
+    fn new(total: usize) -> Self {
+        Self { state: AtomicUsize::new(0), total }
+    }
+
+    /// Output-callback side: copy the next samples into `out`, padding with
+    /// silence. Returns how many samples were handed over; 0 once finished
+    /// or stopped.
+    fn fill(&self, samples: &[f32], out: &mut [f32]) -> usize {
+        let state = self.state.load(Ordering::Acquire);
+        if state & CURSOR_STOPPED != 0 {
+            out.fill(0.0);
+            return 0;
+        }
+        let copy_len = out.len().min(self.total.saturating_sub(state));
+        out[..copy_len].copy_from_slice(&samples[state..state + copy_len]);
+        out[copy_len..].fill(0.0);
+        if copy_len == 0 {
+            return 0;
+        }
+        // Lost the race with `stop`: that position is final, so send silence
+        match self.state.compare_exchange(
+            state,
+            state + copy_len,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => copy_len,
+            Err(_) => {
+                out.fill(0.0);
+                0
             }
         }
     }
 
-    // Brief delay after PTT to let the radio switch to TX
-    thread::sleep(Duration::from_millis(50));
+    /// Samples handed to the device so far
+    fn position(&self) -> usize {
+        self.state.load(Ordering::Acquire) & !CURSOR_STOPPED
+    }
 
-    let _ = app.emit(
-        "tx-status",
-        TxStatusPayload {
-            status: "transmitting".into(),
-            progress: 0.0,
-        },
-    );
+    /// True once the callback has handed over the last sample
+    fn is_done(&self) -> bool {
+        self.position() >= self.total
+    }
 
-    // Set up audio output with a callback that pulls from our sample buffer
-    let mut audio_output = CpalAudioOutput::new();
-    let samples_arc = Arc::new(samples);
-    let samples_for_callback = samples_arc.clone();
-    let pos_for_callback = play_pos.clone();
-    let done_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
-    let done_for_callback = done_flag.clone();
+    /// Freeze the cursor and return the final position.
+    fn stop(&self) -> usize {
+        self.state.fetch_or(CURSOR_STOPPED, Ordering::AcqRel) & !CURSOR_STOPPED
+    }
 
-    let start_result = audio_output.start(
-        &device_id,
-        Box::new(move |output_buf: &mut [f32]| {
-            let current_pos = pos_for_callback.load(Ordering::Relaxed);
-            let remaining = total_samples.saturating_sub(current_pos);
+    fn progress(position: usize, total: usize) -> f32 {
+        position as f32 / total.max(1) as f32
+    }
+}
 
-            if remaining == 0 {
-                // Fill with silence — we're done
-                for sample in output_buf.iter_mut() {
-                    *sample = 0.0;
-                }
-                done_for_callback.store(true, Ordering::SeqCst);
-                return;
-            }
+/// Level of the monitor copy of TX audio relative to the rig output
+pub const MONITOR_GAIN: f32 = 0.25;
 
-            let copy_len = output_buf.len().min(remaining);
-            output_buf[..copy_len]
-                .copy_from_slice(&samples_for_callback[current_pos..current_pos + copy_len]);
+type OutputCallback = Box<dyn FnMut(&mut [f32]) + Send + 'static>;
 
-            // Fill any remaining buffer with silence
-            for sample in output_buf[copy_len..].iter_mut() {
-                *sample = 0.0;
-            }
+/// Output callback playing `samples` from `cursor`, scaled by `gain`.
+fn tx_output_callback(samples: Arc<Vec<f32>>, cursor: Arc<TxCursor>, gain: f32) -> OutputCallback {
+    Box::new(move |output_buf: &mut [f32]| {
+        cursor.fill(&samples, output_buf);
+        if gain != 1.0 {
+            output_buf.iter_mut().for_each(|s| *s *= gain);
+        }
+    })
+}
 
-            pos_for_callback.store(current_pos + copy_len, Ordering::Relaxed);
-        }),
-    );
+/// Like `tx_output_callback` at unity gain, also pushing every sample the
+/// cursor hands over into `recording` — the padding silence after the last
+/// sample isn't part of the transmission and is left out. The ring must hold
+/// the whole transmission, since nothing drains it until the stream stops.
+fn recording_output_callback(
+    samples: Arc<Vec<f32>>,
+    cursor: Arc<TxCursor>,
+    mut recording: SampleProducer,
+) -> OutputCallback {
+    Box::new(move |output_buf: &mut [f32]| {
+        let copied = cursor.fill(&samples, output_buf);
+        recording.push_slice_overwrite(&output_buf[..copied]);
+    })
+}
+
+/// Write what TX played, drained from `recording`, to `path` as 16-bit PCM.
+/// Returns the path for the `tx-status` event, or `None` (logged) if the
+/// file couldn't be written.
+fn save_tx_recording(
+    path: &Path,
+    mut recording: SampleConsumer,
+    sample_rate: u32,
+) -> Option<String> {
+    let mut samples = Vec::with_capacity(recording.occupied_len());
+    recording.pop_into(usize::MAX, &mut samples);
+    match write_wav_file(path, &samples, sample_rate, WavSampleFormat::Int16) {
+        Ok(()) => Some(path.display().to_string()),
+        Err(e) => {
+            log::warn!("Failed to save TX recording: {e}");
+            None
+        }
+    }
+}
+
+/// Start the monitor copy of `samples` on `output`. Returns its cursor, or
+/// `None` if the device can't be opened — TX goes ahead without a monitor.
+fn start_monitor(
+    output: &mut dyn AudioOutput,
+    device_id: &str,
+    samples: Arc<Vec<f32>>,
+) -> Option<Arc<TxCursor>> {
+    let cursor = Arc::new(TxCursor::new(samples.len()));
+    match output.start(device_id, tx_output_callback(samples, cursor.clone(), MONITOR_GAIN)) {
+        Ok(()) => Some(cursor),
+        Err(e) => {
+            log::warn!("Monitor output unavailable, transmitting without it: {e}");
+            None
+        }
+    }
+}
+
+/// Encoded audio for one transmission, at the rate it was encoded for
+struct TxJob {
+    samples: Vec<f32>,
+    sample_rate: u32,
+}
+
+/// The devices a transmission plays on: the rig, and an optional monitor copy
+struct TxOutputs {
+    device_id: String,
+    monitor_device_id: Option<String>,
+}
+
+/// An output and the device to open on it
+struct TxDevice<'a> {
+    output: &'a mut dyn AudioOutput,
+    device_id: &'a str,
+}
+
+/// How `play_tx` ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TxOutcome {
+    /// Every sample played; PTT can drop after the tail delay
+    Complete,
+    /// Stopped by the abort flag
+    Aborted,
+    /// The output device wouldn't start
+    Failed,
+}
+
+/// Play `job` on the rig output (and a monitor copy, if given) until every
+/// sample has left the device or `abort` is set, reporting `tx-status`
+/// payloads through `emit`. With `record_path`, what the rig output played
+/// is saved there before the final status, which carries the path. PTT is
+/// the caller's business: keyed before, dropped after according to the
+/// outcome.
+fn play_tx(
+    rig: TxDevice,
+    monitor: Option<TxDevice>,
+    TxJob { samples, sample_rate }: TxJob,
+    record_path: Option<&Path>,
+    abort: &AtomicBool,
+    clock: &dyn Clock,
+    emit: &mut dyn FnMut(TxStatusPayload),
+) -> TxOutcome {
+    emit(TxStatusPayload { status: "transmitting".into(), progress: 0.0, record_path: None });
+
+    // Set up audio output with a callback that pulls from our sample buffer
+    let total_samples = samples.len();
+    let samples = Arc::new(samples);
+    let cursor = Arc::new(TxCursor::new(total_samples));
+    let (callback, mut recording) = match record_path {
+        Some(path) => {
+            let (producer, consumer) = sample_ring(total_samples);
+            let callback = recording_output_callback(samples.clone(), cursor.clone(), producer);
+            (callback, Some((path, consumer)))
+        }
+        None => (tx_output_callback(samples.clone(), cursor.clone(), 1.0), None),
+    };
+    let TxDevice { output, device_id } = rig;
+    let start_result = output.start(device_id, callback);
 
     if let Err(e) = start_result {
         log::error!("Failed to start audio output: {e}");
-        let _ = app.emit(
-            "tx-status",
-            TxStatusPayload {
-                status: format!("error: {e}"),
-                progress: 0.0,
-            },
-        );
-        return;
+        emit(TxStatusPayload { status: format!("error: {e}"), progress: 0.0, record_path: None });
+        return TxOutcome::Failed;
     }
+    // Only called once the stream has stopped, so the recording is final
+    let mut save_recording = || {
+        recording
+            .take()
+            .and_then(|(path, consumer)| save_tx_recording(path, consumer, sample_rate))
+    };
+    let playback_started = clock.now();
+
+    let mut monitor = monitor.and_then(|TxDevice { output: monitor_output, device_id: id }| {
+        start_monitor(monitor_output, id, samples.clone()).map(|cursor| (monitor_output, cursor))
+    });
 
     // Wait for playback to finish or abort
     loop {
         if abort.load(Ordering::SeqCst) {
-            let _ = audio_output.stop();
-            let _ = app.emit(
-                "tx-status",
-                TxStatusPayload {
-                    status: "aborted".into(),
-                    progress: play_pos.load(Ordering::Relaxed) as f32 / total_samples as f32,
-                },
-            );
-
-            // PTT OFF — deactivate before returning
-            if let Ok(mut guard) = radio_state.radio.lock() {
-                if let Some(radio) = guard.as_mut() {
-                    if let Err(e) = radio.ptt_off() {
-                        log::warn!("PTT OFF failed: {e}");
-                    }
-                }
+            // Freeze the cursor first: the callback may still run until the
+            // stream is stopped, but can no longer advance it
+            let played = cursor.stop();
+            let _ = output.stop();
+            if let Some((monitor_output, monitor_cursor)) = monitor.as_mut() {
+                monitor_cursor.stop();
+                let _ = monitor_output.stop();
             }
-            return;
+            emit(TxStatusPayload {
+                status: "aborted".into(),
+                progress: TxCursor::progress(played, total_samples),
+                record_path: save_recording(),
+            });
+            return TxOutcome::Aborted;
         }
 
-        if done_flag.load(Ordering::SeqCst) {
-            // Brief wait for the audio device to clock out its current buffer
-            thread::sleep(Duration::from_millis(30));
-            let _ = audio_output.stop();
+        if cursor.is_done() {
+            // The callback has handed over the last sample, but it is still
+            // queued in the device — wait until it has actually been played
+            let latency = output.output_latency().unwrap_or(FALLBACK_OUTPUT_LATENCY);
+            clock.sleep(completion_wait(
+                total_samples,
+                sample_rate,
+                latency,
+                clock.now().saturating_duration_since(playback_started),
+            ));
+            let _ = output.stop();
+            if let Some((monitor_output, _)) = monitor.as_mut() {
+                let _ = monitor_output.stop();
+            }
 
             // Emit complete BEFORE PTT OFF — UI resets with zero IPC latency.
             // The frontend onComplete handler needs no follow-up invoke() call
-            // because we self-clear the thread handle here with try_lock.
-            let _ = app.emit(
-                "tx-status",
-                TxStatusPayload {
-                    status: "complete".into(),
-                    progress: 1.0,
-                },
-            );
+            // because the TX thread self-clears its handle.
+            emit(TxStatusPayload {
+                status: "complete".into(),
+                progress: 1.0,
+                record_path: save_recording(),
+            });
+            return TxOutcome::Complete;
+        }
+
+        clock.sleep(Duration::from_millis(5));
+    }
+}
+
+/// TX thread: plays encoded samples through the audio output device.
+fn run_tx_thread(
+    app: AppHandle,
+    abort: Arc<AtomicBool>,
+    job: TxJob,
+    TxOutputs { device_id, monitor_device_id }: TxOutputs,
+    record_path: Option<String>,
+    auto_log_text: Option<String>,
+) {
+    // Activate PTT at the top of the thread, then let the radio switch to TX
+    let radio_state = app.state::<AppState>();
+    let _transmitting = TxRunningGuard::new(&radio_state.status, &radio_state.tx_duty);
+    let mut ptt = ptt_sequencer(&radio_state);
+    ptt.key(&radio_state.radio);
+
+    let audio_host = selected_audio_host(&radio_state);
+    let mut audio_output = CpalAudioOutput::new().with_host(audio_host.clone());
+    let mut monitor_output = CpalAudioOutput::new().with_host(audio_host);
+    let monitor = monitor_device_id
+        .as_deref()
+        .map(|device_id| TxDevice { output: &mut monitor_output, device_id });
+
+    let outcome = play_tx(
+        TxDevice { output: &mut audio_output, device_id: &device_id },
+        monitor,
+        job,
+        record_path.as_deref().map(Path::new),
+        &abort,
+        &SystemClock,
+        &mut |payload| {
+            let _ = app.emit(events::TX_STATUS, payload);
+        },
+    );
 
+    match outcome {
+        // PTT OFF — deactivate before returning, no tail on abort or error
+        TxOutcome::Aborted | TxOutcome::Failed => ptt.unkey_now(&radio_state.radio),
+        TxOutcome::Complete => {
             // Self-clear our handle from AppState so start_tx works immediately.
             // Use try_lock to avoid deadlock if stop_tx holds the lock concurrently
             // (in that case stop_tx will clear the handle itself via join).
@@ -414,19 +908,441 @@ fn run_tx_thread(
                 let _ = guard.take();
             }
 
-            // PTT OFF after the emit — audio is already silent, holding key
-            // for ~50–100ms more is harmless for PSK-31.
-            if let Ok(mut guard) = radio_state.radio.lock() {
-                if let Some(radio) = guard.as_mut() {
-                    if let Err(e) = radio.ptt_off() {
-                        log::warn!("PTT OFF failed: {e}");
+            // PTT OFF after the emit and the tail delay — audio is already
+            // silent, holding key a little longer is harmless for PSK-31.
+            ptt.unkey(&radio_state.radio);
+
+            if let Some(text) = auto_log_text {
+                log_completed_tx(&app, &radio_state, &text);
+            }
+        }
+    }
+}
+
+/// Characters in `text` that Varicode can't encode and TX would drop, so
+/// the UI can warn before sending. Empty when everything is transmittable.
+#[tauri::command]
+pub fn is_transmittable(text: String) -> Vec<char> {
+    Varicode::untransmittable_chars(&text)
+}
+
+/// Encode `text` and write it to `path` as a mono WAV. Returns the duration in seconds.
+pub fn encode_to_wav_file(
+    text: &str,
+    carrier_freq: f64,
+    sample_rate: u32,
+    path: &Path,
+    format: WavSampleFormat,
+) -> Psk31Result<f64> {
+    validate_message_length(text)?;
+    let encoder = Psk31Encoder::new(sample_rate, carrier_freq);
+    let samples = encoder.encode(text);
+    write_wav_file(path, &samples, sample_rate, format)?;
+    Ok(encoder.estimated_duration(text))
+}
+
+/// Export `text` as a PSK-31 WAV test vector at the configured sample rate.
+/// `format` defaults to 16-bit PCM.
+#[tauri::command]
+pub fn encode_to_wav(
+    state: tauri::State<'_, AppState>,
+    text: String,
+    carrier_freq: f64,
+    path: String,
+    format: Option<WavSampleFormat>,
+) -> Result<f64, String> {
+    if !(200.0..=3500.0).contains(&carrier_freq) {
+        return Err("Carrier frequency must be between 200-3500 Hz".into());
+    }
+    let sample_rate = state.config.lock().map_err(|_| "config lock poisoned")?.sample_rate;
+    encode_to_wav_file(
+        &text,
+        carrier_freq,
+        sample_rate,
+        Path::new(&path),
+        format.unwrap_or_default(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::mock_audio::{MockAudioOutput, MockPlayer};
+    use crate::adapters::wav_file::read_wav_file;
+    use crate::ports::FakeClock;
+
+    #[test]
+    fn auto_log_records_call_and_rst() {
+        let record = auto_log_record(
+            true,
+            "W1AW 599",
+            Some(14_070_000.0),
+            Some("DATA-USB".into()),
+            1_700_000_000,
+        )
+        .expect("exchange should be logged");
+        assert_eq!(record.callsign, "W1AW");
+        assert_eq!(record.rst_sent, "599");
+        assert_eq!(record.frequency_hz, Some(14_070_000.0));
+        assert_eq!(record.mode.as_deref(), Some("DATA-USB"));
+        assert_eq!(record.timestamp_utc, 1_700_000_000);
+    }
+
+    #[test]
+    fn auto_log_disabled_produces_nothing() {
+        assert!(auto_log_record(false, "W1AW 599", None, None, 0).is_none());
+    }
+
+    #[test]
+    fn auto_log_ignores_text_without_exchange() {
+        assert!(auto_log_record(true, "CQ CQ DE W1AW", None, None, 0).is_none());
+    }
+
+    /// Read back the data chunk of a 16-bit mono WAV written by `write_wav`.
+    fn read_int16_wav(path: &Path) -> (u32, Vec<f32>) {
+        let bytes = std::fs::read(path).unwrap();
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u16::from_le_bytes([bytes[34], bytes[35]]), 16);
+        let sample_rate = u32::from_le_bytes(bytes[24..28].try_into().unwrap());
+        let samples = bytes[44..]
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / i16::MAX as f32)
+            .collect();
+        (sample_rate, samples)
+    }
+
+    #[test]
+    fn encode_to_wav_round_trips_through_decoder() {
+        use crate::modem::decoder::Psk31Decoder;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.wav");
+        let duration =
+            encode_to_wav_file("TEST", 1000.0, 48000, &path, WavSampleFormat::Int16).unwrap();
+
+        let (sample_rate, samples) = read_int16_wav(&path);
+        assert_eq!(sample_rate, 48000);
+        assert_eq!(samples.len(), (duration * 48000.0).round() as usize);
+
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        let decoded: String = samples.iter().filter_map(|&s| decoder.process(s)).collect();
+        assert!(decoded.contains("TEST"), "decoded: {decoded:?}");
+    }
+
+    #[test]
+    fn tx_cursor_plays_to_the_end_then_pads_silence() {
+        let samples: Vec<f32> = (1..=10).map(|i| i as f32).collect();
+        let cursor = TxCursor::new(samples.len());
+        let mut out = [9.0; 4];
+        assert_eq!(cursor.fill(&samples, &mut out), 4);
+        assert_eq!(out, [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(cursor.fill(&samples, &mut out), 4);
+        assert!(!cursor.is_done());
+        assert_eq!(cursor.fill(&samples, &mut out), 2);
+        assert_eq!(out, [9.0, 10.0, 0.0, 0.0]);
+        assert!(cursor.is_done());
+        assert_eq!(cursor.fill(&samples, &mut out), 0);
+        assert_eq!(out, [0.0; 4]);
+    }
+
+    #[test]
+    fn tx_abort_stress_freezes_progress_at_samples_played() {
+        use std::sync::atomic::AtomicBool;
+
+        let samples = vec![0.5; 48_000];
+        for cycle in 0..200 {
+            let cursor = Arc::new(TxCursor::new(samples.len()));
+            let running = Arc::new(AtomicBool::new(true));
+            // Stand-in for the output callback, with varying buffer sizes
+            let callback = {
+                let (cursor, running, samples) = (cursor.clone(), running.clone(), samples.clone());
+                thread::spawn(move || {
+                    let mut delivered = 0;
+                    let mut out = vec![0.0; 64 + cycle % 7 * 100];
+                    while running.load(Ordering::Acquire) {
+                        delivered += cursor.fill(&samples, &mut out);
                     }
-                }
+                    delivered
+                })
+            };
+
+            thread::sleep(Duration::from_micros((cycle % 20) as u64 * 50));
+            let played = cursor.stop();
+            // Callbacks racing the stop must not move the cursor
+            thread::sleep(Duration::from_micros(200));
+            running.store(false, Ordering::Release);
+            let delivered = callback.join().expect("callback panicked");
+
+            assert_eq!(cursor.position(), played, "cycle {cycle}");
+            assert_eq!(delivered, played, "cycle {cycle}");
+            let progress = TxCursor::progress(played, samples.len());
+            assert!((0.0..=1.0).contains(&progress), "cycle {cycle}: {progress}");
+        }
+    }
+
+    #[test]
+    fn monitor_output_plays_scaled_copy_of_rig_audio() {
+        let samples = Arc::new((0..1000).map(|i| (i as f32 * 0.01).sin()).collect::<Vec<_>>());
+        let cursor = Arc::new(TxCursor::new(samples.len()));
+        let mut rig = MockAudioOutput::with_devices(&["rig"]);
+        rig.start("rig", tx_output_callback(samples.clone(), cursor.clone(), 1.0)).unwrap();
+        let mut speakers = MockAudioOutput::with_devices(&["speakers"]);
+        let monitor = start_monitor(&mut speakers, "speakers", samples.clone()).unwrap();
+
+        // Different buffer sizes: the monitor keeps its own position
+        let (rig, speakers) = (rig.player(), speakers.player());
+        let rig_audio: Vec<f32> = (0..4).flat_map(|_| rig.pull(250).unwrap()).collect();
+        let monitor_audio: Vec<f32> = (0..10).flat_map(|_| speakers.pull(100).unwrap()).collect();
+        assert_eq!(rig_audio, *samples);
+        for (m, s) in monitor_audio.iter().zip(samples.iter()) {
+            assert_eq!(*m, s * MONITOR_GAIN);
+        }
+        assert!(cursor.is_done() && monitor.is_done());
+    }
+
+    #[test]
+    fn unavailable_monitor_is_skipped() {
+        let mut missing = MockAudioOutput::new().failing();
+        assert!(start_monitor(&mut missing, "gone", Arc::new(vec![0.5; 10])).is_none());
+    }
+
+    /// Run `play_tx` for `samples` on a mock output while `device` pulls
+    /// buffers from it; returns the outcome and the `tx-status` sequence.
+    fn play_on_mock(
+        output: MockAudioOutput,
+        samples: Vec<f32>,
+        abort: &AtomicBool,
+        device: impl FnOnce(MockPlayer),
+    ) -> (TxOutcome, Vec<(String, f32)>) {
+        let player = output.player();
+        thread::scope(|scope| {
+            let tx = scope.spawn(move || {
+                let mut output = output;
+                let mut statuses = Vec::new();
+                let clock = FakeClock::new();
+                let outcome = play_tx(
+                    TxDevice { output: &mut output, device_id: "Mock Output" },
+                    None,
+                    TxJob { samples, sample_rate: 48000 },
+                    None,
+                    abort,
+                    &clock,
+                    &mut |p| statuses.push((p.status, p.progress)),
+                );
+                (outcome, statuses)
+            });
+            device(player);
+            tx.join().expect("TX thread panicked")
+        })
+    }
+
+    /// Pull `frames`-sample buffers until the TX side stops the output.
+    fn pull_until_stopped(player: &MockPlayer, frames: usize) {
+        let mut started = false;
+        loop {
+            match player.pull(frames) {
+                Some(_) => started = true,
+                None if started => return,
+                None => thread::yield_now(),
             }
+        }
+    }
 
-            return;
+    #[test]
+    fn tx_plays_encoder_samples_in_order_then_silence() {
+        let samples = Psk31Encoder::new(48000, 1000.0).encode("TEST");
+        assert_ne!(samples.len() % 500, 0, "last buffer should need padding");
+        let output = MockAudioOutput::new();
+        let player = output.player();
+        let abort = AtomicBool::new(false);
+
+        let (outcome, statuses) =
+            play_on_mock(output, samples.clone(), &abort, |p| pull_until_stopped(&p, 500));
+
+        assert_eq!(outcome, TxOutcome::Complete);
+        let played = player.captured();
+        assert_eq!(played[..samples.len()], samples[..]);
+        let tail = &played[samples.len()..];
+        assert!(!tail.is_empty() && tail.iter().all(|&s| s == 0.0), "tail: {} samples", tail.len());
+        assert_eq!(statuses, vec![("transmitting".into(), 0.0), ("complete".into(), 1.0)]);
+    }
+
+    #[test]
+    fn tx_recording_holds_exactly_what_was_sent() {
+        let samples = Psk31Encoder::new(48000, 1000.0).encode("TEST");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tx.wav");
+        let mut output = MockAudioOutput::new();
+        let player = output.player();
+        let abort = AtomicBool::new(false);
+
+        let mut last = None;
+        let outcome = thread::scope(|scope| {
+            scope.spawn(|| pull_until_stopped(&player, 500));
+            play_tx(
+                TxDevice { output: &mut output, device_id: "Mock Output" },
+                None,
+                TxJob { samples: samples.clone(), sample_rate: 48000 },
+                Some(&path),
+                &abort,
+                &FakeClock::new(),
+                &mut |p| last = Some(p),
+            )
+        });
+
+        assert_eq!(outcome, TxOutcome::Complete);
+        // The file is already written when `complete` names it
+        let last = last.unwrap();
+        assert_eq!(last.status, "complete");
+        assert_eq!(last.record_path, Some(path.display().to_string()));
+        let (rate, recorded) = read_wav_file(&path).unwrap();
+        assert_eq!(rate, 48000);
+        assert_eq!(recorded.len(), samples.len());
+        for (r, s) in recorded.iter().zip(&samples) {
+            assert!((r - s).abs() <= 1.0 / i16::MAX as f32, "{r} vs {s}");
         }
+    }
+
+    #[test]
+    fn tx_abort_stops_output_where_it_was() {
+        let samples = Psk31Encoder::new(48000, 1000.0).encode("CQ CQ DE W1AW");
+        let output = MockAudioOutput::new();
+        let player = output.player();
+        let abort = AtomicBool::new(false);
+
+        let (outcome, statuses) = play_on_mock(output, samples.clone(), &abort, |p| {
+            let mut pulled = 0;
+            while pulled < 10 {
+                pulled += usize::from(p.pull(512).is_some());
+            }
+            abort.store(true, Ordering::SeqCst);
+        });
+
+        assert_eq!(outcome, TxOutcome::Aborted);
+        assert_eq!(player.captured(), samples[..5120]);
+        assert!(player.pull(512).is_none(), "output should be stopped");
+        let progress = 5120.0 / samples.len() as f32;
+        assert_eq!(statuses, vec![("transmitting".into(), 0.0), ("aborted".into(), progress)]);
+    }
+
+    #[test]
+    fn tx_reports_output_that_fails_to_start() {
+        let abort = AtomicBool::new(false);
+        let (outcome, statuses) =
+            play_on_mock(MockAudioOutput::new().failing(), vec![0.5; 100], &abort, |_| {});
+        assert_eq!(outcome, TxOutcome::Failed);
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses[1].0.starts_with("error: "), "got {:?}", statuses[1]);
+    }
+
+    #[test]
+    fn encode_to_wav_rejects_overlong_message() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("long.wav");
+        let text = "A".repeat(crate::modem::encoder::MAX_MESSAGE_CHARS + 1);
+        assert!(encode_to_wav_file(&text, 1000.0, 48000, &path, WavSampleFormat::Int16).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn ptt_sequencer_honors_lead_and_tail_delays() {
+        use crate::adapters::mock_radio::MockRadio;
+
+        let radio: Mutex<Option<Box<dyn RadioControl>>> =
+            Mutex::new(Some(Box::new(MockRadio::new())));
+        // Record each delay with the PTT state at the time it is taken
+        let slept = std::cell::RefCell::new(Vec::new());
+        let keyed = || radio.lock().unwrap().as_ref().unwrap().is_transmitting();
+
+        let mut ptt = PttSequencer::with_sleep(120, 80, |d: Duration| {
+            slept.borrow_mut().push((d.as_millis(), keyed()));
+        });
+        ptt.key(&radio);
+        assert!(keyed(), "PTT should be on once the lead delay has elapsed");
+        ptt.unkey(&radio);
+        assert!(!keyed());
+
+        // Lead taken while keyed before audio; tail taken while still keyed after it
+        assert_eq!(*slept.borrow(), vec![(120, true), (80, true)]);
+    }
+
+    #[test]
+    fn completion_wait_covers_playback_plus_latency() {
+        let latency = Duration::from_millis(40);
+        // 1.5 s of audio at 48 kHz
+        assert_eq!(
+            completion_wait(72_000, 48000, latency, Duration::ZERO),
+            Duration::from_millis(1540)
+        );
+        // Callback finished 1.4 s in: still 140 ms of audio in flight
+        assert_eq!(
+            completion_wait(72_000, 48000, latency, Duration::from_millis(1400)),
+            Duration::from_millis(140)
+        );
+        // Rate matters: the same buffer at 8 kHz takes 9 s
+        assert_eq!(
+            completion_wait(72_000, 8000, Duration::ZERO, Duration::from_secs(8)),
+            Duration::from_secs(1)
+        );
+        // Already past the end: no wait
+        assert_eq!(
+            completion_wait(72_000, 48000, latency, Duration::from_secs(2)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn tx_running_guard_clears_flag_on_drop() {
+        let status = Mutex::new(ModemStatus::default());
+        let duty = Mutex::new(DutyCycleTracker::default());
+        {
+            let _guard = TxRunningGuard::new(&status, &duty);
+            assert!(status.lock().unwrap().tx_running);
+        }
+        assert!(!status.lock().unwrap().tx_running);
+    }
+
+    #[test]
+    fn duty_cycle_limit_refuses_tx_until_cooled_down() {
+        let state = AppState::new();
+        {
+            let mut config = state.config.lock().unwrap();
+            config.max_tx_duty_cycle_percent = 50;
+            config.tx_duty_window_secs = 600;
+        }
+        // Near-continuous TX: 9 of the last 10 minutes
+        let t0 = Instant::now();
+        let min = Duration::from_secs(60);
+        {
+            let mut duty = state.tx_duty.lock().unwrap();
+            duty.key(t0);
+            duty.unkey(t0 + 9 * min);
+        }
+        let now = t0 + 10 * min;
+        let message = Duration::from_secs(20);
+        let err = check_duty_cycle(&state, now, message).unwrap_err();
+        assert!(err.contains("duty cycle"), "{err}");
+
+        // Unlimited: never refused
+        state.config.lock().unwrap().max_tx_duty_cycle_percent = 100;
+        assert!(check_duty_cycle(&state, now, message).is_ok());
+        state.config.lock().unwrap().max_tx_duty_cycle_percent = 50;
+
+        // After a cool-down only 4 of the last 10 minutes were TX
+        assert!(check_duty_cycle(&state, t0 + 15 * min, message).is_ok());
+    }
+
+    #[test]
+    fn ptt_sequencer_abort_skips_tail() {
+        use crate::adapters::mock_radio::MockRadio;
 
-        thread::sleep(Duration::from_millis(5));
+        let radio: Mutex<Option<Box<dyn RadioControl>>> =
+            Mutex::new(Some(Box::new(MockRadio::new())));
+        let slept = std::cell::RefCell::new(Vec::new());
+        let mut ptt = PttSequencer::with_sleep(50, 50, |d: Duration| slept.borrow_mut().push(d));
+        ptt.key(&radio);
+        ptt.unkey_now(&radio);
+        assert_eq!(*slept.borrow(), vec![Duration::from_millis(50)]);
     }
 }
@@ -0,0 +1,302 @@
+//! Amateur band table
+//!
+//! Single source of truth for US amateur band edges (FCC Part 97), the
+//! conventional PSK-31 operating frequency on each band, and the per-band
+//! defaults applied on QSY (DATA sideband, suggested TX power). Mirrors
+//! `BAND_PLAN` in `serial-panel.ts`.
+//!
+//! A `BandPlanEntry` is the ordered set of radio settings for one band
+//! change, applied by `RadioControl::apply_band_plan`.
+
+use serde::{Deserialize, Serialize};
+
+use super::{normalize_mode, Frequency, Psk31Error, Psk31Result};
+
+/// A US amateur band
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Band {
+    /// Band name as shown in the UI (e.g. "20m")
+    pub name: &'static str,
+    /// Lower band edge in Hz (inclusive)
+    pub low_hz: u64,
+    /// Upper band edge in Hz (inclusive)
+    pub high_hz: u64,
+    /// Conventional PSK-31 dial frequency in Hz
+    pub psk31_hz: u64,
+    /// DATA mode to select on this band (see `data_mode_for_frequency`)
+    pub default_mode: &'static str,
+    /// Suggested maximum TX power in watts — lower on the narrow WARC bands
+    pub max_power_watts: u32,
+}
+
+/// All supported bands, lowest first.
+pub const BANDS: &[Band] = &[
+    Band {
+        name: "160m",
+        low_hz: 1_800_000,
+        high_hz: 2_000_000,
+        psk31_hz: 1_838_000,
+        default_mode: "DATA-LSB",
+        max_power_watts: 25,
+    },
+    Band {
+        name: "80m",
+        low_hz: 3_500_000,
+        high_hz: 4_000_000,
+        psk31_hz: 3_580_000,
+        default_mode: "DATA-LSB",
+        max_power_watts: 25,
+    },
+    Band {
+        name: "60m",
+        low_hz: 5_332_000,
+        high_hz: 5_405_000,
+        psk31_hz: 5_357_000,
+        default_mode: "DATA-USB",
+        max_power_watts: 25,
+    },
+    Band {
+        name: "40m",
+        low_hz: 7_000_000,
+        high_hz: 7_300_000,
+        psk31_hz: 7_035_000,
+        default_mode: "DATA-LSB",
+        max_power_watts: 25,
+    },
+    Band {
+        name: "30m",
+        low_hz: 10_100_000,
+        high_hz: 10_150_000,
+        psk31_hz: 10_142_000,
+        default_mode: "DATA-USB",
+        max_power_watts: 20,
+    },
+    Band {
+        name: "20m",
+        low_hz: 14_000_000,
+        high_hz: 14_350_000,
+        psk31_hz: 14_070_000,
+        default_mode: "DATA-USB",
+        max_power_watts: 25,
+    },
+    Band {
+        name: "17m",
+        low_hz: 18_068_000,
+        high_hz: 18_168_000,
+        psk31_hz: 18_100_000,
+        default_mode: "DATA-USB",
+        max_power_watts: 20,
+    },
+    Band {
+        name: "15m",
+        low_hz: 21_000_000,
+        high_hz: 21_450_000,
+        psk31_hz: 21_080_000,
+        default_mode: "DATA-USB",
+        max_power_watts: 25,
+    },
+    Band {
+        name: "12m",
+        low_hz: 24_890_000,
+        high_hz: 24_990_000,
+        psk31_hz: 24_920_000,
+        default_mode: "DATA-USB",
+        max_power_watts: 20,
+    },
+    Band {
+        name: "10m",
+        low_hz: 28_000_000,
+        high_hz: 29_700_000,
+        psk31_hz: 28_120_000,
+        default_mode: "DATA-USB",
+        max_power_watts: 25,
+    },
+    Band {
+        name: "6m",
+        low_hz: 50_000_000,
+        high_hz: 54_000_000,
+        psk31_hz: 50_290_000,
+        default_mode: "DATA-USB",
+        max_power_watts: 25,
+    },
+    Band {
+        name: "2m",
+        low_hz: 144_000_000,
+        high_hz: 148_000_000,
+        psk31_hz: 144_144_000,
+        default_mode: "DATA-USB",
+        max_power_watts: 25,
+    },
+    Band {
+        name: "70cm",
+        low_hz: 420_000_000,
+        high_hz: 450_000_000,
+        psk31_hz: 432_100_000,
+        default_mode: "DATA-USB",
+        max_power_watts: 25,
+    },
+];
+
+impl Band {
+    /// Look up a band by name (case-insensitive, e.g. "20m" or "20M").
+    pub fn by_name(name: &str) -> Option<&'static Band> {
+        BANDS.iter().find(|b| b.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The band containing `hz`, or `None` outside every US allocation.
+    pub fn containing(hz: u64) -> Option<&'static Band> {
+        BANDS.iter().find(|b| b.contains(hz))
+    }
+
+    /// True if `hz` lies within this band's edges (inclusive).
+    pub fn contains(&self, hz: u64) -> bool {
+        hz >= self.low_hz && hz <= self.high_hz
+    }
+
+    /// Conventional PSK-31 calling frequency in Hz, as a dial frequency.
+    pub fn psk31_calling_hz(&self) -> f64 {
+        self.psk31_hz as f64
+    }
+}
+
+/// PSK-31 calling frequency for a band name (case-insensitive), or `None`
+/// for an unknown band. Never returns a frequency outside the band.
+pub fn psk31_frequency_for_band(band: &str) -> Option<f64> {
+    Band::by_name(band)
+        .filter(|b| b.contains(b.psk31_hz))
+        .map(Band::psk31_calling_hz)
+}
+
+/// Settings for one band change, applied in order: frequency, mode, TX power.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BandPlanEntry {
+    /// Dial frequency in Hz
+    pub frequency_hz: u64,
+    /// Mode to select (e.g. "DATA-USB"); `None` leaves the mode alone
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// TX power in watts (0–100); `None` leaves the power alone
+    #[serde(default)]
+    pub tx_power_watts: Option<u32>,
+}
+
+impl BandPlanEntry {
+    /// QSY to the band's PSK-31 frequency, with its default mode and
+    /// suggested power when `apply_band_defaults` is set.
+    pub fn for_band(band: &Band, apply_band_defaults: bool) -> Self {
+        Self {
+            frequency_hz: band.psk31_hz,
+            mode: apply_band_defaults.then(|| band.default_mode.to_string()),
+            tx_power_watts: apply_band_defaults.then_some(band.max_power_watts),
+        }
+    }
+
+    pub fn frequency(&self) -> Frequency {
+        Frequency::hz(self.frequency_hz as f64)
+    }
+
+    /// Reject a plan before any of it is sent to the radio.
+    pub fn validate(&self) -> Psk31Result<()> {
+        if Band::containing(self.frequency_hz).is_none() {
+            return Err(Psk31Error::Config(format!(
+                "Band plan frequency {} Hz is outside US amateur bands",
+                self.frequency_hz
+            )));
+        }
+        if let Some(mode) = &self.mode {
+            normalize_mode(mode)?;
+        }
+        if let Some(watts) = self.tx_power_watts.filter(|&w| w > 100) {
+            return Err(Psk31Error::Config(format!(
+                "Band plan TX power {watts} W exceeds maximum (100 W)"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::data_mode_for_frequency;
+
+    #[test]
+    fn psk31_frequency_is_inside_every_band() {
+        for band in BANDS {
+            assert!(band.contains(band.psk31_hz), "{} PSK-31 freq out of band", band.name);
+        }
+    }
+
+    #[test]
+    fn calling_frequency_for_band_name() {
+        // Same table as BAND_PLAN[...].psk31Hz in serial-panel.ts
+        assert_eq!(psk31_frequency_for_band("20m"), Some(14_070_000.0));
+        assert_eq!(psk31_frequency_for_band("40m"), Some(7_035_000.0));
+        assert_eq!(psk31_frequency_for_band("80M"), Some(3_580_000.0));
+        assert_eq!(psk31_frequency_for_band("30m"), Some(10_142_000.0));
+        assert_eq!(psk31_frequency_for_band("6m"), Some(50_290_000.0));
+        assert_eq!(psk31_frequency_for_band("11m"), None);
+        for band in BANDS {
+            assert_eq!(psk31_frequency_for_band(band.name), Some(band.psk31_calling_hz()));
+        }
+    }
+
+    #[test]
+    fn default_mode_matches_sideband_convention() {
+        for band in BANDS {
+            assert_eq!(
+                band.default_mode,
+                data_mode_for_frequency(band.psk31_hz as f64),
+                "{} default mode",
+                band.name
+            );
+        }
+        assert_eq!(Band::by_name("30m").unwrap().default_mode, "DATA-USB");
+        assert_eq!(Band::by_name("40m").unwrap().default_mode, "DATA-LSB");
+    }
+
+    #[test]
+    fn warc_bands_suggest_lower_power() {
+        let twenty = Band::by_name("20m").unwrap().max_power_watts;
+        for name in ["30m", "17m", "12m"] {
+            assert!(Band::by_name(name).unwrap().max_power_watts < twenty, "{name}");
+        }
+    }
+
+    #[test]
+    fn lookup_by_name_is_case_insensitive() {
+        assert_eq!(Band::by_name("20M").unwrap().psk31_hz, 14_070_000);
+        assert!(Band::by_name("11m").is_none());
+    }
+
+    #[test]
+    fn containing_finds_band_by_frequency() {
+        assert_eq!(Band::containing(7_035_000).unwrap().name, "40m");
+        assert!(Band::containing(10_000_000).is_none());
+    }
+
+    #[test]
+    fn band_plan_for_band_applies_defaults_only_when_asked() {
+        let band = Band::by_name("40m").unwrap();
+        let plan = BandPlanEntry::for_band(band, true);
+        assert_eq!(plan.frequency_hz, 7_035_000);
+        assert_eq!(plan.mode.as_deref(), Some("DATA-LSB"));
+        assert_eq!(plan.tx_power_watts, Some(25));
+
+        let plan = BandPlanEntry::for_band(band, false);
+        assert_eq!((plan.mode, plan.tx_power_watts), (None, None));
+    }
+
+    #[test]
+    fn band_plan_validation() {
+        let ok = BandPlanEntry::for_band(Band::by_name("20m").unwrap(), true);
+        assert!(ok.validate().is_ok());
+        let out_of_band = BandPlanEntry { frequency_hz: 27_185_000, ..ok.clone() };
+        assert!(out_of_band.validate().is_err());
+        let bad_mode = BandPlanEntry { mode: Some("PSK31".into()), ..ok.clone() };
+        assert!(bad_mode.validate().is_err());
+        let too_hot = BandPlanEntry { tx_power_watts: Some(150), ..ok };
+        assert!(too_hot.validate().is_err());
+    }
+}
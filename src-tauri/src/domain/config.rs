@@ -50,6 +50,12 @@ pub struct Configuration {
     /// TX power in watts applied before PTT ON (0–100)
     #[serde(default = "default_tx_power_watts")]
     pub tx_power_watts: u32,
+    /// Band to QSY to when this profile is applied (e.g. "20m"); None leaves the VFO alone
+    #[serde(default)]
+    pub band: Option<String>,
+    /// Use the band's default mode and suggested TX power instead of `tx_power_watts`
+    #[serde(default)]
+    pub use_band_defaults: bool,
 }
 
 impl Default for Configuration {
@@ -66,6 +72,8 @@ impl Default for Configuration {
             waterfall_noise_floor: default_waterfall_noise_floor(),
             waterfall_zoom: default_waterfall_zoom(),
             tx_power_watts: default_tx_power_watts(),
+            band: None,
+            use_band_defaults: false,
         }
     }
 }
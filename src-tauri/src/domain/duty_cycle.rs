@@ -0,0 +1,114 @@
+//! Transmit duty-cycle accounting
+//!
+//! Long PSK sessions are hard on a rig's finals, which spend most of the
+//! time keyed at a steady output. `DutyCycleTracker` records
+//! when the transmitter was keyed so TX can be refused once the share of
+//! a rolling window spent transmitting would pass the configured limit.
+//!
+//! Times are passed in rather than read from the clock so the accounting
+//! can be tested without waiting.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+/// Default rolling window for the duty-cycle limit
+pub const DEFAULT_DUTY_WINDOW_SECS: u64 = 600;
+
+/// Longest window accepted; history older than this is discarded
+pub const MAX_DUTY_WINDOW_SECS: u64 = 3600;
+
+/// Keyed periods within the last `MAX_DUTY_WINDOW_SECS`
+#[derive(Debug, Default)]
+pub struct DutyCycleTracker {
+    /// Completed transmissions `(keyed, unkeyed)`, oldest first
+    spans: VecDeque<(Instant, Instant)>,
+    /// Start of the transmission in progress, if any
+    keyed_since: Option<Instant>,
+}
+
+impl DutyCycleTracker {
+    pub fn key(&mut self, now: Instant) {
+        self.keyed_since.get_or_insert(now);
+    }
+
+    pub fn unkey(&mut self, now: Instant) {
+        if let Some(start) = self.keyed_since.take() {
+            self.spans.push_back((start, now));
+        }
+        let max_window = Duration::from_secs(MAX_DUTY_WINDOW_SECS);
+        while self.spans.front().is_some_and(|&(_, end)| now.duration_since(end) > max_window) {
+            self.spans.pop_front();
+        }
+    }
+
+    /// Time spent keyed in the `window` ending at `now`, including any
+    /// transmission still in progress.
+    pub fn tx_time(&self, now: Instant, window: Duration) -> Duration {
+        let window_start = now.checked_sub(window);
+        self.spans
+            .iter()
+            .copied()
+            .chain(self.keyed_since.map(|start| (start, now)))
+            .map(|(start, end)| {
+                let start = window_start.map_or(start, |w| start.max(w));
+                end.min(now).saturating_duration_since(start)
+            })
+            .sum()
+    }
+
+    /// Share of `window` (0.0–1.0) spent keyed once a further `planned`
+    /// of transmitting starting at `now` is added.
+    pub fn projected_duty_cycle(&self, now: Instant, planned: Duration, window: Duration) -> f64 {
+        if window.is_zero() {
+            return 0.0;
+        }
+        let keyed = (self.tx_time(now, window) + planned).min(window);
+        keyed.as_secs_f64() / window.as_secs_f64()
+    }
+}
+
+/// Current duty cycle against its limit, for `get_tx_duty_cycle`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxDutyCycle {
+    /// Percentage of the window spent transmitting
+    pub percent: f64,
+    /// Configured maximum (100 = no limit)
+    pub limit_percent: u32,
+    pub window_secs: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn counts_only_the_part_inside_the_window() {
+        let t0 = Instant::now();
+        let mut tracker = DutyCycleTracker::default();
+        tracker.key(t0);
+        tracker.unkey(t0 + 4 * MIN);
+        tracker.key(t0 + 8 * MIN);
+        tracker.unkey(t0 + 9 * MIN);
+
+        // 10-minute window ending at 12 min covers 2..12: 2 of the first, 1 of the second
+        assert_eq!(tracker.tx_time(t0 + 12 * MIN, 10 * MIN), 3 * MIN);
+        let duty = tracker.projected_duty_cycle(t0 + 12 * MIN, MIN, 10 * MIN);
+        assert!((duty - 0.4).abs() < 1e-9, "{duty}");
+    }
+
+    #[test]
+    fn transmission_in_progress_counts() {
+        let t0 = Instant::now();
+        let mut tracker = DutyCycleTracker::default();
+        tracker.key(t0);
+        assert_eq!(tracker.tx_time(t0 + 2 * MIN, 10 * MIN), 2 * MIN);
+        tracker.key(t0 + MIN); // already keyed: keeps the original start
+        tracker.unkey(t0 + 3 * MIN);
+        assert_eq!(tracker.tx_time(t0 + 5 * MIN, 10 * MIN), 3 * MIN);
+    }
+}
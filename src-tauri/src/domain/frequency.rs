@@ -1,5 +1,12 @@
 //! Frequency-related domain helpers
 
+use super::band::Band;
+
+/// Check if a frequency falls within a US amateur band.
+pub fn is_amateur_frequency(hz: u64) -> bool {
+    Band::containing(hz).is_some()
+}
+
 /// Determine the correct PSK-31 DATA mode for a given radio frequency.
 ///
 /// By HF convention:
@@ -19,6 +26,48 @@ pub fn data_mode_for_frequency(hz: f64) -> &'static str {
     }
 }
 
+/// How far a fixed RF signal moves in the audio passband when the dial
+/// moves by `dial_delta_hz`, or `None` for modes without a single sideband.
+///
+/// On USB audio = RF − dial, so the signal moves down as the dial goes up;
+/// on LSB audio = dial − RF, so it moves up.
+pub fn audio_shift_for_dial_change(dial_delta_hz: f64, mode: &str) -> Option<f64> {
+    if mode.ends_with("LSB") {
+        Some(dial_delta_hz)
+    } else if mode.ends_with("USB") {
+        Some(-dial_delta_hz)
+    } else {
+        None
+    }
+}
+
+/// Follows the radio's dial and reports where the RX carrier should move so
+/// a station stays under the decoder when the operator turns the VFO.
+#[derive(Debug, Default)]
+pub struct VfoTracker {
+    last_dial_hz: Option<f64>,
+}
+
+impl VfoTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the latest dial reading. Returns the new carrier when the dial
+    /// moved and the shifted carrier is still inside the 200–3500 Hz passband.
+    ///
+    /// The first reading only records the dial position.
+    pub fn update(&mut self, dial_hz: f64, mode: &str, carrier_hz: f64) -> Option<f64> {
+        let last = self.last_dial_hz.replace(dial_hz)?;
+        let delta = dial_hz - last;
+        if delta == 0.0 {
+            return None;
+        }
+        let shifted = carrier_hz + audio_shift_for_dial_change(delta, mode)?;
+        (200.0..=3500.0).contains(&shifted).then_some(shifted)
+    }
+}
+
 #[cfg(test)]
 mod sideband_tests {
     use super::*;
@@ -42,6 +91,26 @@ mod sideband_tests {
         assert_eq!(data_mode_for_frequency(5_405_000.0), "DATA-USB"); // upper edge
     }
 
+    #[test]
+    fn audio_shift_follows_sideband() {
+        assert_eq!(audio_shift_for_dial_change(500.0, "DATA-USB"), Some(-500.0));
+        assert_eq!(audio_shift_for_dial_change(500.0, "DATA-LSB"), Some(500.0));
+        assert_eq!(audio_shift_for_dial_change(500.0, "FM"), None);
+    }
+
+    #[test]
+    fn vfo_tracker_ignores_first_reading_and_passband_overflow() {
+        let mut tracker = VfoTracker::new();
+        assert_eq!(tracker.update(14_070_000.0, "DATA-USB", 1000.0), None);
+        assert_eq!(tracker.update(14_070_000.0, "DATA-USB", 1000.0), None);
+        // 1 kHz up on USB would put a 1000 Hz carrier at 0 Hz
+        assert_eq!(tracker.update(14_071_000.0, "DATA-USB", 1000.0), None);
+        assert_eq!(
+            tracker.update(14_070_900.0, "DATA-USB", 1000.0),
+            Some(1100.0)
+        );
+    }
+
     #[test]
     fn boundary_at_10mhz_is_usb() {
         assert_eq!(data_mode_for_frequency(10_000_000.0), "DATA-USB"); // 30m lower edge
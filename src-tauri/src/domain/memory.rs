@@ -0,0 +1,22 @@
+//! Memory channels
+//!
+//! Like a rig's memory banks: a named (band, frequency, mode, carrier)
+//! snapshot the operator can store in a numbered slot and recall later.
+
+use serde::{Deserialize, Serialize};
+
+/// A stored memory channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Memory {
+    /// Operator label (e.g. "20m PSK calling")
+    pub name: String,
+    /// Band name from the band table (e.g. "20m")
+    pub band: String,
+    /// Radio dial frequency in Hz
+    pub frequency_hz: f64,
+    /// Radio mode name (e.g. "DATA-USB")
+    pub mode: String,
+    /// Audio carrier frequency in Hz for the RX decoder / TX encoder
+    pub carrier_freq: f64,
+}
@@ -3,12 +3,26 @@
 //! Pure types with no I/O dependencies. These represent the core concepts
 //! of the PSK-31 application.
 
+pub mod band;
 pub mod config;
+pub mod duty_cycle;
 pub mod error;
 pub mod frequency;
+pub mod memory;
+pub mod mode;
+pub mod operator;
+pub mod qso;
+pub mod tx_macro;
 pub mod types;
 
+pub use band::*;
 pub use config::*;
+pub use duty_cycle::*;
 pub use error::*;
 pub use frequency::*;
+pub use memory::*;
+pub use mode::*;
+pub use operator::*;
+pub use qso::*;
+pub use tx_macro::*;
 pub use types::*;
@@ -0,0 +1,85 @@
+//! Operating mode names
+//!
+//! Canonical names are the FT-991A's, as listed in `cat::MODE_TABLE`. Users
+//! and other programs spell them many ways ("usb", "Data-USB", "USB-D",
+//! "PKTUSB"); `normalize_mode` maps those onto the canonical name before the
+//! CAT code lookup.
+
+use super::{Psk31Error, Psk31Result};
+
+/// Canonical mode names, one per FT-991A mode code
+pub const MODE_NAMES: &[&str] = &[
+    "LSB", "USB", "CW", "FM", "AM", "RTTY-LSB", "CW-R", "DATA-LSB", "RTTY-USB", "DATA-FM",
+    "FM-N", "DATA-USB", "AM-N", "C4FM",
+];
+
+/// Common alternative spellings (already upper-cased, `_`/space → `-`)
+const MODE_ALIASES: &[(&str, &str)] = &[
+    ("USB-D", "DATA-USB"),
+    ("DATA-U", "DATA-USB"),
+    ("PKTUSB", "DATA-USB"),
+    ("DIGU", "DATA-USB"),
+    ("LSB-D", "DATA-LSB"),
+    ("DATA-L", "DATA-LSB"),
+    ("PKTLSB", "DATA-LSB"),
+    ("DIGL", "DATA-LSB"),
+    ("PKTFM", "DATA-FM"),
+    ("FM-D", "DATA-FM"),
+    ("CWR", "CW-R"),
+    ("NFM", "FM-N"),
+    ("RTTY", "RTTY-LSB"),
+    ("RTTYR", "RTTY-USB"),
+];
+
+/// Map a mode name in any common spelling to its canonical name.
+///
+/// Case-insensitive; `_` and spaces are treated as `-`. Unknown modes are an
+/// error rather than a silent fallback, so a typo never changes the rig's mode.
+pub fn normalize_mode(name: &str) -> Psk31Result<&'static str> {
+    let key = name.trim().to_ascii_uppercase().replace(['_', ' '], "-");
+    MODE_NAMES
+        .iter()
+        .copied()
+        .find(|&m| m == key)
+        .or_else(|| MODE_ALIASES.iter().find(|(a, _)| *a == key).map(|(_, m)| *m))
+        .ok_or_else(|| Psk31Error::Cat(format!("Unknown mode '{name}'")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_and_separator_variants_normalize() {
+        for spelling in ["usb", "Usb", " USB "] {
+            assert_eq!(normalize_mode(spelling).unwrap(), "USB");
+        }
+        for spelling in ["Data-USB", "data_usb", "DATA USB"] {
+            assert_eq!(normalize_mode(spelling).unwrap(), "DATA-USB");
+        }
+    }
+
+    #[test]
+    fn aliases_normalize_to_canonical_mode() {
+        for spelling in ["USB-D", "pktusb", "DIGU", "data-u"] {
+            assert_eq!(normalize_mode(spelling).unwrap(), "DATA-USB", "{spelling}");
+        }
+        for spelling in ["LSB-D", "PKTLSB", "digl"] {
+            assert_eq!(normalize_mode(spelling).unwrap(), "DATA-LSB", "{spelling}");
+        }
+        assert_eq!(normalize_mode("cwr").unwrap(), "CW-R");
+    }
+
+    #[test]
+    fn aliases_point_at_canonical_names() {
+        for (alias, mode) in MODE_ALIASES {
+            assert!(MODE_NAMES.contains(mode), "{alias} → {mode}");
+        }
+    }
+
+    #[test]
+    fn unknown_mode_errors() {
+        assert!(normalize_mode("PSK31").is_err());
+        assert!(normalize_mode("").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! Operator profile
+//!
+//! The station's own details — callsign, grid locator, name and QTH — for
+//! TX macros (`<MYCALL>` etc.) and anything else that needs to say who we
+//! are. Persisted once per install, separately from the configuration
+//! profiles.
+
+use serde::{Deserialize, Serialize};
+
+use super::{is_callsign, Psk31Error, Psk31Result};
+
+/// The operator's own station info
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperatorProfile {
+    /// Our callsign (e.g. "W1AW")
+    pub callsign: String,
+    /// Maidenhead locator, 4 or 6 characters (e.g. "FN31pr"); empty if unset
+    #[serde(default)]
+    pub grid: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub qth: String,
+}
+
+impl OperatorProfile {
+    /// Trim every field and put the callsign and grid in their usual case
+    /// ("W1AW", "FN31pr").
+    pub fn normalized(&self) -> Self {
+        Self {
+            callsign: self.callsign.trim().to_ascii_uppercase(),
+            grid: normalize_grid(self.grid.trim()),
+            name: self.name.trim().to_string(),
+            qth: self.qth.trim().to_string(),
+        }
+    }
+
+    pub fn validate(&self) -> Psk31Result<()> {
+        if !is_callsign(&self.callsign) {
+            return Err(Psk31Error::Config(format!(
+                "'{}' is not a valid callsign",
+                self.callsign
+            )));
+        }
+        if !self.grid.is_empty() && !is_grid_locator(&self.grid) {
+            return Err(Psk31Error::Config(format!(
+                "'{}' is not a valid grid locator",
+                self.grid
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Check a Maidenhead locator: field (A–R ×2), square (0–9 ×2) and an
+/// optional subsquare (A–X ×2), case-insensitive.
+pub fn is_grid_locator(grid: &str) -> bool {
+    let b = grid.to_ascii_uppercase().into_bytes();
+    let field = |c: u8| (b'A'..=b'R').contains(&c);
+    let subsquare = |c: u8| (b'A'..=b'X').contains(&c);
+    match b.len() {
+        4 | 6 => {
+            field(b[0])
+                && field(b[1])
+                && b[2].is_ascii_digit()
+                && b[3].is_ascii_digit()
+                && b[4..].iter().all(|&c| subsquare(c))
+        }
+        _ => false,
+    }
+}
+
+/// Field and square upper case, subsquare lower case ("fn31PR" → "FN31pr")
+fn normalize_grid(grid: &str) -> String {
+    let split = grid.len().min(4);
+    match (grid.get(..split), grid.get(split..)) {
+        (Some(head), Some(tail)) => head.to_ascii_uppercase() + &tail.to_ascii_lowercase(),
+        _ => grid.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(callsign: &str, grid: &str) -> OperatorProfile {
+        OperatorProfile { callsign: callsign.into(), grid: grid.into(), ..Default::default() }
+    }
+
+    #[test]
+    fn grid_locator_validation() {
+        for grid in ["FN31", "FN31pr", "fn31PR", "JO01", "RR99xx"] {
+            assert!(is_grid_locator(grid), "{grid} should be valid");
+        }
+        for grid in ["", "FN3", "FN31p", "SN31", "FN3A", "FN31pz", "FN31pr00"] {
+            assert!(!is_grid_locator(grid), "{grid} should be invalid");
+        }
+    }
+
+    #[test]
+    fn normalized_fixes_case_and_whitespace() {
+        let p = OperatorProfile {
+            callsign: " w1aw ".into(),
+            grid: "fn31PR".into(),
+            name: " Hiram ".into(),
+            qth: "Newington".into(),
+        };
+        let n = p.normalized();
+        assert_eq!((n.callsign.as_str(), n.grid.as_str()), ("W1AW", "FN31pr"));
+        assert_eq!(n.name, "Hiram");
+    }
+
+    #[test]
+    fn validation_rejects_bad_callsign_and_grid() {
+        assert!(profile("W1AW", "FN31pr").validate().is_ok());
+        assert!(profile("W1AW", "").validate().is_ok(), "grid is optional");
+        assert!(profile("", "FN31").validate().is_err());
+        assert!(profile("HELLO", "FN31").validate().is_err());
+        assert!(profile("W1AW", "ZZ99").validate().is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//! QSO log records and exchange parsing
+//!
+//! A QSO (contact) record captures who we worked, the report we sent, and the
+//! rig state at the time. `parse_exchange` pulls the callsign and RST out of
+//! free-form TX text like "W1AW DE K2ABC UR 599 599" so completed
+//! transmissions can be logged automatically.
+
+use serde::{Deserialize, Serialize};
+
+/// A single logged contact
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QsoRecord {
+    /// The other station's callsign
+    pub callsign: String,
+    /// Signal report we sent (RST, e.g. "599")
+    pub rst_sent: String,
+    /// Rig dial frequency at log time (`None` if the radio couldn't be read)
+    pub frequency_hz: Option<f64>,
+    /// Rig mode at log time (`None` if the radio couldn't be read)
+    pub mode: Option<String>,
+    /// Seconds since the Unix epoch (UTC)
+    pub timestamp_utc: u64,
+}
+
+/// Words that look like callsigns to a naive parser but are QSO boilerplate
+const NOT_CALLSIGNS: &[&str] = &["DE", "CQ", "UR", "RST", "QSL", "TU", "73"];
+
+/// Check if a token looks like an amateur callsign (e.g. W1AW, K2ABC, VE3XYZ/P).
+///
+/// Heuristic: 3–10 characters of A–Z, 0–9 and '/', containing at least one
+/// letter and at least one digit, with the last character of the base call
+/// being a letter (so "599" and "14070" are rejected).
+pub fn is_callsign(token: &str) -> bool {
+    let upper = token.to_ascii_uppercase();
+    if !(3..=10).contains(&upper.len()) || NOT_CALLSIGNS.contains(&upper.as_str()) {
+        return false;
+    }
+    if !upper.chars().all(|c| c.is_ascii_alphanumeric() || c == '/') {
+        return false;
+    }
+    // Portable suffixes/prefixes: take the longest '/'-separated part as the base call
+    let base = upper.split('/').max_by_key(|p| p.len()).unwrap_or("");
+    base.chars().any(|c| c.is_ascii_digit())
+        && base.chars().any(|c| c.is_ascii_alphabetic())
+        && base.chars().last().is_some_and(|c| c.is_ascii_alphabetic())
+}
+
+/// Check if a token is a valid RST report (R 1–5, S 1–9, T 1–9).
+pub fn is_rst(token: &str) -> bool {
+    let b = token.as_bytes();
+    b.len() == 3
+        && (b'1'..=b'5').contains(&b[0])
+        && (b'1'..=b'9').contains(&b[1])
+        && (b'1'..=b'9').contains(&b[2])
+}
+
+/// Extract `(callsign, rst)` from an exchange, or `None` if either is missing.
+///
+/// By convention the first callsign in "THEM DE ME" is the station being
+/// worked, so the first callsign-like token wins.
+pub fn parse_exchange(text: &str) -> Option<(String, String)> {
+    let tokens: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+        .collect();
+    let callsign = tokens.iter().find(|t| is_callsign(t))?;
+    let rst = tokens.iter().find(|t| is_rst(t))?;
+    Some((callsign.to_ascii_uppercase(), rst.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_common_callsigns() {
+        for call in ["W1AW", "K2ABC", "VE3XYZ", "g4abc", "VE3XYZ/P", "DL/K2ABC", "2E0ABC"] {
+            assert!(is_callsign(call), "{call} should be a callsign");
+        }
+    }
+
+    #[test]
+    fn rejects_non_callsigns() {
+        for tok in ["599", "DE", "CQ", "HELLO", "14070", "W1", "A/B"] {
+            assert!(!is_callsign(tok), "{tok} should not be a callsign");
+        }
+    }
+
+    #[test]
+    fn rst_validation() {
+        assert!(is_rst("599"));
+        assert!(is_rst("579"));
+        assert!(!is_rst("699"));
+        assert!(!is_rst("509"));
+        assert!(!is_rst("59"));
+    }
+
+    #[test]
+    fn parse_exchange_simple() {
+        assert_eq!(
+            parse_exchange("W1AW 599"),
+            Some(("W1AW".to_string(), "599".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_exchange_takes_first_callsign() {
+        assert_eq!(
+            parse_exchange("w1aw de k2abc ur 579 579 bk"),
+            Some(("W1AW".to_string(), "579".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_exchange_none_without_rst() {
+        assert_eq!(parse_exchange("CQ CQ DE W1AW"), None);
+    }
+}
@@ -0,0 +1,95 @@
+//! TX text macros
+//!
+//! Tokens in outgoing text that are replaced just before encoding. `<NR>`
+//! is the contest serial number, sent zero-padded ("001") as contest
+//! exchanges expect; the counter advances each time a QSO is logged. Each
+//! configuration keeps its own counter.
+//! `<MYCALL>`, `<MYGRID>`, `<MYNAME>` and `<MYQTH>` come from the
+//! operator profile.
+
+use super::OperatorProfile;
+
+/// Token replaced by the contest serial number
+pub const SERIAL_TOKEN: &str = "<NR>";
+
+/// Everything a macro can expand to
+pub struct MacroContext<'a> {
+    /// Current contest serial number
+    pub serial: u32,
+    pub operator: &'a OperatorProfile,
+}
+
+/// Tokens filled in from the operator profile
+pub const OPERATOR_TOKENS: [&str; 4] = ["<MYCALL>", "<MYGRID>", "<MYNAME>", "<MYQTH>"];
+
+/// Whether `text` needs the operator profile to expand.
+pub fn uses_operator_tokens(text: &str) -> bool {
+    OPERATOR_TOKENS.iter().any(|token| text.contains(token))
+}
+
+/// First serial number of a fresh configuration
+pub const FIRST_SERIAL: u32 = 1;
+
+/// Expand the macros in `text` for sending.
+pub fn expand_tx_macro(text: &str, ctx: &MacroContext) -> String {
+    let op = ctx.operator;
+    text.replace(SERIAL_TOKEN, &format_serial(ctx.serial))
+        .replace("<MYCALL>", &op.callsign)
+        .replace("<MYGRID>", &op.grid)
+        .replace("<MYNAME>", &op.name)
+        .replace("<MYQTH>", &op.qth)
+}
+
+/// A serial number as sent: zero-padded to three digits, wider past 999.
+pub fn format_serial(serial: u32) -> String {
+    format!("{serial:03}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(text: &str, serial: u32) -> String {
+        expand_tx_macro(text, &MacroContext { serial, operator: &OperatorProfile::default() })
+    }
+
+    #[test]
+    fn nr_expands_to_zero_padded_serial() {
+        assert_eq!(expand("UR 599 <NR> BK", 7), "UR 599 007 BK");
+        assert_eq!(expand("<NR> <NR>", 42), "042 042");
+        assert_eq!(expand("NR <NR>", 1234), "NR 1234");
+    }
+
+    #[test]
+    fn operator_tokens_are_detected() {
+        assert!(uses_operator_tokens("CQ DE <MYCALL> K"));
+        assert!(uses_operator_tokens("QTH <MYQTH>"));
+        assert!(!uses_operator_tokens("UR 599 <NR> BK"));
+        assert!(!uses_operator_tokens("<mycall>"));
+    }
+
+    #[test]
+    fn text_without_tokens_is_unchanged() {
+        assert_eq!(expand("CQ TEST DE W1AW", 5), "CQ TEST DE W1AW");
+        assert_eq!(expand("<nr>", 5), "<nr>");
+    }
+
+    #[test]
+    fn operator_tokens_expand_from_profile() {
+        let operator = OperatorProfile {
+            callsign: "W1AW".into(),
+            grid: "FN31pr".into(),
+            name: "Hiram".into(),
+            qth: "Newington".into(),
+        };
+        let ctx = MacroContext { serial: 1, operator: &operator };
+        assert_eq!(
+            expand_tx_macro("CQ DE <MYCALL> <MYCALL> <MYGRID>", &ctx),
+            "CQ DE W1AW W1AW FN31pr"
+        );
+        assert_eq!(
+            expand_tx_macro("NAME <MYNAME> QTH <MYQTH>", &ctx),
+            "NAME Hiram QTH Newington"
+        );
+    }
+}
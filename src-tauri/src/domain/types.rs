@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::{Psk31Error, Psk31Result, DEFAULT_DUTY_WINDOW_SECS};
+
 /// Audio sample type (32-bit float, range -1.0 to 1.0)
 pub type AudioSample = f32;
 
@@ -25,6 +27,44 @@ impl Frequency {
     pub fn as_hz(&self) -> f64 {
         self.0
     }
+
+    /// Dial-style display with dot-separated thousands, rounded to the
+    /// nearest Hz: 14_070_000 → "14.070.000".
+    pub fn format_hz_grouped(&self) -> String {
+        let digits = (self.0.round().max(0.0) as u64).to_string();
+        let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                out.push('.');
+            }
+            out.push(c);
+        }
+        out
+    }
+
+    /// MHz with a fixed number of decimals: `format_mhz(6)` on 14_070_000
+    /// → "14.070000 MHz". Six decimals keeps 1 Hz resolution.
+    pub fn format_mhz(&self, decimals: usize) -> String {
+        format!("{:.decimals$} MHz", self.0 / 1_000_000.0)
+    }
+
+    /// Format using one of the display styles shared with the frontend.
+    pub fn format(&self, style: FrequencyStyle) -> String {
+        match style {
+            FrequencyStyle::Grouped => self.format_hz_grouped(),
+            FrequencyStyle::Mhz => self.format_mhz(6),
+        }
+    }
+}
+
+/// Frequency display style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrequencyStyle {
+    /// "14.070.000"
+    Grouped,
+    /// "14.070000 MHz"
+    Mhz,
 }
 
 /// Information about an audio device
@@ -41,6 +81,79 @@ pub struct AudioDeviceInfo {
     pub output_unverified: bool,
 }
 
+/// Audio devices split by role, as presented by the input/output pickers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioDevices {
+    pub inputs: Vec<AudioDeviceInfo>,
+    pub outputs: Vec<AudioDeviceInfo>,
+}
+
+impl AudioDevices {
+    /// Partition a flat device list by role.
+    ///
+    /// A duplex device appears in both lists. `is_default` is recomputed per
+    /// role: in `inputs` it marks the system default input, in `outputs` the
+    /// system default output.
+    pub fn from_devices(
+        devices: &[AudioDeviceInfo],
+        default_input: Option<&str>,
+        default_output: Option<&str>,
+    ) -> Self {
+        let with_default = |d: &AudioDeviceInfo, default: Option<&str>| AudioDeviceInfo {
+            is_default: default == Some(d.name.as_str()),
+            ..d.clone()
+        };
+        Self {
+            inputs: devices
+                .iter()
+                .filter(|d| d.is_input)
+                .map(|d| with_default(d, default_input))
+                .collect(),
+            outputs: devices
+                .iter()
+                .filter(|d| d.is_output)
+                .map(|d| with_default(d, default_output))
+                .collect(),
+        }
+    }
+}
+
+/// One line of a self-test report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestCheck {
+    pub name: String,
+    pub passed: bool,
+    /// What was found, or why the check failed
+    pub detail: String,
+}
+
+/// Result of `run_self_test`: an in-memory modem loopback plus environment checks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestReport {
+    /// True only if every check passed
+    pub passed: bool,
+    /// Edit distance between sent and decoded loopback text, per sent character
+    pub character_error_rate: f64,
+    pub decoded_text: String,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+impl SelfTestReport {
+    /// Record a check; `Ok` carries the detail for a pass, `Err` the failure reason.
+    pub fn add_check(&mut self, name: &str, result: Result<String, String>) {
+        let passed = result.is_ok();
+        self.passed &= passed;
+        self.checks.push(SelfTestCheck {
+            name: name.to_string(),
+            passed,
+            detail: result.unwrap_or_else(|e| e),
+        });
+    }
+}
+
 /// Information about a serial port
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -50,10 +163,193 @@ pub struct SerialPortInfo {
     pub device_hint: Option<String>,
 }
 
+/// Default minimum delay between CAT commands (FT-991A firmware requirement)
+pub const DEFAULT_COMMAND_DELAY_MS: u64 = 50;
+
+/// Default timeout for a single serial read
+pub const DEFAULT_READ_TIMEOUT_MS: u64 = 100;
+
+fn default_command_delay_ms() -> u64 {
+    DEFAULT_COMMAND_DELAY_MS
+}
+
+fn default_read_timeout_ms() -> u64 {
+    DEFAULT_READ_TIMEOUT_MS
+}
+
+/// Serial line settings for one connection, for adapters that need
+/// slower CAT timing than the FT-991A defaults
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerialParams {
+    pub baud_rate: u32,
+    /// Minimum delay between CAT commands
+    #[serde(default = "default_command_delay_ms")]
+    pub command_delay_ms: u64,
+    /// Timeout for a single serial read; a response may take up to ten
+    #[serde(default = "default_read_timeout_ms")]
+    pub read_timeout_ms: u64,
+}
+
+impl SerialParams {
+    /// `baud_rate` with the default command delay and read timeout
+    pub fn new(baud_rate: u32) -> Self {
+        Self {
+            baud_rate,
+            command_delay_ms: DEFAULT_COMMAND_DELAY_MS,
+            read_timeout_ms: DEFAULT_READ_TIMEOUT_MS,
+        }
+    }
+}
+
 fn default_tx_power_watts() -> u32 {
     25
 }
 
+fn default_modem_baud() -> f64 {
+    31.25
+}
+
+fn default_ptt_delay_ms() -> u64 {
+    50
+}
+
+fn default_max_tx_duty_cycle_percent() -> u32 {
+    100
+}
+
+fn default_tx_duty_window_secs() -> u64 {
+    DEFAULT_DUTY_WINDOW_SECS
+}
+
+fn default_rx_filter_control() -> bool {
+    true
+}
+
+fn default_rx_mute_during_tx() -> bool {
+    true
+}
+
+fn default_rx_dc_blocker() -> bool {
+    true
+}
+
+fn default_fft_min_db() -> f32 {
+    -100.0
+}
+
+fn default_fft_max_db() -> f32 {
+    0.0
+}
+
+fn default_rx_drain_budget_ms() -> u64 {
+    40
+}
+
+fn default_signal_on_threshold() -> f32 {
+    0.65
+}
+
+fn default_signal_off_threshold() -> f32 {
+    0.55
+}
+
+fn default_signal_min_dwell_ms() -> u64 {
+    200
+}
+
+fn default_rx_record_threshold() -> f32 {
+    0.7
+}
+
+fn default_rx_record_trailing_ms() -> u64 {
+    3000
+}
+
+/// What the transmitter sends when it has no text (preamble/postamble)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdlePattern {
+    /// Continuous phase reversals (`0000…`) — the classic PSK-31 idle
+    #[default]
+    Reversals,
+    /// Repeated NUL characters (`1010101011` + `00` separator) for decoders
+    /// that expect a Varicode idle character
+    Nul,
+}
+
+/// Encoding of `fft-data` magnitudes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FftFormat {
+    /// dB as `f32`
+    #[default]
+    Float,
+    /// Quantized to 0–255 across the configured dB range (4× smaller)
+    U8,
+}
+
+/// When the audio thread records received audio to WAV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RxRecordMode {
+    #[default]
+    Off,
+    /// One file per transmission, gated on lock quality (see `modem::auto_record`)
+    Auto,
+}
+
+/// Phase modulation of the received signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Modulation {
+    /// Binary PSK — the only modulation the decoder implements so far
+    #[default]
+    Bpsk,
+}
+
+/// Symbol rates the encoder and decoder can be built for: PSK-31, PSK-63
+/// and PSK-125
+pub const SUPPORTED_BAUD_RATES: &[f64] = &[31.25, 62.5, 125.0];
+
+/// Baud and modulation the RX decoder is built for
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RxMode {
+    pub baud: f64,
+    pub modulation: Modulation,
+}
+
+impl Default for RxMode {
+    fn default() -> Self {
+        Self { baud: 31.25, modulation: Modulation::Bpsk }
+    }
+}
+
+impl RxMode {
+    /// Reject a baud rate the decoder can't be built for.
+    pub fn validate(&self) -> Psk31Result<()> {
+        if !SUPPORTED_BAUD_RATES.contains(&self.baud) {
+            return Err(Psk31Error::Config(format!(
+                "{} baud is not supported (supported: {SUPPORTED_BAUD_RATES:?})",
+                self.baud
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Sample encoding for exported WAV files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WavSampleFormat {
+    /// 16-bit signed PCM — readable by everything
+    #[default]
+    Int16,
+    /// 32-bit IEEE float — lossless copy of the encoder output
+    Float32,
+}
+
 /// Modem configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModemConfig {
@@ -63,9 +359,91 @@ pub struct ModemConfig {
     pub carrier_freq: f64,
     /// FFT size for waterfall display
     pub fft_size: usize,
+    /// Encoding of waterfall frames sent to the frontend
+    #[serde(default)]
+    pub fft_format: FftFormat,
+    /// dB mapped to 0 when `fft_format` is `U8`
+    #[serde(default = "default_fft_min_db")]
+    pub fft_min_db: f32,
+    /// dB mapped to 255 when `fft_format` is `U8`
+    #[serde(default = "default_fft_max_db")]
+    pub fft_max_db: f32,
     /// TX power in watts (applied before PTT ON)
     #[serde(default = "default_tx_power_watts")]
     pub tx_power_watts: u32,
+    /// Automatically log a QSO when a completed TX contains a callsign + RST
+    #[serde(default)]
+    pub auto_log: bool,
+    /// Idle pattern used for TX preamble/postamble
+    #[serde(default)]
+    pub idle_pattern: IdlePattern,
+    /// Symbol rate for TX and RX, one of `SUPPORTED_BAUD_RATES`
+    #[serde(default = "default_modem_baud")]
+    pub modem_baud: f64,
+    /// Shift the RX carrier to follow VFO changes so the station stays centred
+    #[serde(default)]
+    pub track_vfo: bool,
+    /// Most of `tx_duty_window_secs` that may be spent transmitting, in
+    /// percent; TX that would pass it is refused. 100 = no limit
+    #[serde(default = "default_max_tx_duty_cycle_percent")]
+    pub max_tx_duty_cycle_percent: u32,
+    /// Rolling window the duty cycle is measured over
+    #[serde(default = "default_tx_duty_window_secs")]
+    pub tx_duty_window_secs: u64,
+    /// Delay between PTT ON and the first audio sample, for amp/relay sequencers
+    #[serde(default = "default_ptt_delay_ms")]
+    pub ptt_lead_ms: u64,
+    /// Delay between the last audio sample and PTT OFF, so relay dropout
+    /// doesn't clip the final symbol
+    #[serde(default = "default_ptt_delay_ms")]
+    pub ptt_tail_ms: u64,
+    /// Strip control characters from `rx-text` (`rx-text-raw` is always unfiltered)
+    #[serde(default = "default_rx_filter_control")]
+    pub rx_filter_control: bool,
+    /// Stop feeding the RX decoder while transmitting, holding its AGC gain
+    /// so the first signal after TX isn't blasted by a wound-up gain
+    #[serde(default = "default_rx_mute_during_tx")]
+    pub rx_mute_during_tx: bool,
+    /// Strip the input's DC offset ahead of the RX decoder
+    #[serde(default = "default_rx_dc_blocker")]
+    pub rx_dc_blocker: bool,
+    /// Most audio the DSP loop processes per iteration, so a stall is worked
+    /// off in bounded steps rather than one long burst
+    #[serde(default = "default_rx_drain_budget_ms")]
+    pub rx_drain_budget_ms: u64,
+    /// Emit `rx-bits` with the raw decided bits, for protocol analysis
+    #[serde(default)]
+    pub rx_bits_debug: bool,
+    /// Signal level at which the signal-present detector turns on.
+    /// A freshly reset decoder reads 0.5, so this needs the AGC to pull down.
+    #[serde(default = "default_signal_on_threshold")]
+    pub signal_on_threshold: f32,
+    /// Level below which a detected signal counts as gone (≤ on threshold)
+    #[serde(default = "default_signal_off_threshold")]
+    pub signal_off_threshold: f32,
+    /// How long the level must stay above the on threshold before it counts
+    #[serde(default = "default_signal_min_dwell_ms")]
+    pub signal_min_dwell_ms: u64,
+    /// Audio host (API) for device enumeration and streams; `None` = system default
+    #[serde(default)]
+    pub audio_host: Option<String>,
+    /// Software gain on captured audio, in dB, for interfaces with no
+    /// hardware input level control
+    #[serde(default)]
+    pub input_trim_db: f32,
+    /// On RX start, find the strongest signal in 500–2500 Hz and settle the
+    /// decoder on it
+    #[serde(default)]
+    pub rx_acquire: bool,
+    /// Record received audio to WAV (`Auto`: one file per transmission)
+    #[serde(default)]
+    pub rx_record_mode: RxRecordMode,
+    /// Lock quality at which an auto-recording starts
+    #[serde(default = "default_rx_record_threshold")]
+    pub rx_record_threshold: f32,
+    /// Silence after a transmission before its auto-recording is closed
+    #[serde(default = "default_rx_record_trailing_ms")]
+    pub rx_record_trailing_ms: u64,
 }
 
 impl Default for ModemConfig {
@@ -74,7 +452,32 @@ impl Default for ModemConfig {
             sample_rate: 48000,
             carrier_freq: 1000.0,
             fft_size: 4096,
+            fft_format: FftFormat::default(),
+            fft_min_db: default_fft_min_db(),
+            fft_max_db: default_fft_max_db(),
             tx_power_watts: default_tx_power_watts(),
+            auto_log: false,
+            idle_pattern: IdlePattern::default(),
+            modem_baud: default_modem_baud(),
+            track_vfo: false,
+            max_tx_duty_cycle_percent: default_max_tx_duty_cycle_percent(),
+            tx_duty_window_secs: default_tx_duty_window_secs(),
+            ptt_lead_ms: default_ptt_delay_ms(),
+            ptt_tail_ms: default_ptt_delay_ms(),
+            rx_filter_control: default_rx_filter_control(),
+            rx_mute_during_tx: default_rx_mute_during_tx(),
+            rx_dc_blocker: default_rx_dc_blocker(),
+            rx_drain_budget_ms: default_rx_drain_budget_ms(),
+            rx_bits_debug: false,
+            signal_on_threshold: default_signal_on_threshold(),
+            signal_off_threshold: default_signal_off_threshold(),
+            signal_min_dwell_ms: default_signal_min_dwell_ms(),
+            audio_host: None,
+            input_trim_db: 0.0,
+            rx_acquire: false,
+            rx_record_mode: RxRecordMode::default(),
+            rx_record_threshold: default_rx_record_threshold(),
+            rx_record_trailing_ms: default_rx_record_trailing_ms(),
         }
     }
 }
@@ -110,6 +513,84 @@ pub struct RadioStatus {
     pub rit_offset_hz: i32,
     pub rit_enabled: bool,
     pub split: bool,
+    /// Where the operating frequency comes from (VFO or a memory channel)
+    #[serde(default)]
+    pub vfo: Vfo,
+}
+
+/// Frequency source the radio is operating from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Vfo {
+    #[default]
+    A,
+    B,
+    /// A memory channel (including memory tune, QMB and PMS on the FT-991A)
+    Memory,
+}
+
+/// A change the radio reported on its own (auto-information mode), e.g.
+/// from the operator turning the front-panel dial
+#[derive(Debug, Clone, PartialEq)]
+pub enum RadioPush {
+    Frequency(Frequency),
+    /// Mode name, e.g. "DATA-USB"
+    Mode(String),
+}
+
+impl Vfo {
+    /// Hamlib's name for this VFO, as returned by rigctld's `v` command
+    pub fn hamlib_name(self) -> &'static str {
+        match self {
+            Vfo::A => "VFOA",
+            Vfo::B => "VFOB",
+            Vfo::Memory => "MEM",
+        }
+    }
+}
+
+/// Receive filter settings for the current mode, read back from the radio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RadioFilterState {
+    /// Narrow filter engaged (FT-991A `NA0;`)
+    pub narrow: bool,
+    /// Radio's width-table index (FT-991A `SH0;`, 0–21). The bandwidth it
+    /// selects depends on the mode and the narrow setting.
+    pub width_code: u8,
+}
+
+/// A radio memory channel and the frequency stored in it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RadioMemoryChannel {
+    pub channel: u16,
+    pub frequency_hz: f64,
+}
+
+/// Raw S-meter readings (0–255) at S0…S9 on the FT-991A
+const S_UNIT_RAW: [u8; 10] = [0, 12, 27, 40, 55, 65, 80, 95, 112, 130];
+/// Raw S-meter readings at S9+10 … S9+60 dB on the FT-991A
+const OVER_S9_RAW: [u8; 6] = [150, 172, 190, 220, 240, 255];
+
+/// The radio's S-meter: its raw 0–255 reading and the S-unit that maps to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SMeterReading {
+    pub raw: u8,
+    /// S0–S9
+    pub s_units: u8,
+    /// dB over S9 in 10 dB steps (0 at S9 and below)
+    pub over_s9_db: u8,
+}
+
+impl SMeterReading {
+    /// Map a raw reading onto S-units with the FT-991A's calibration
+    pub fn from_raw(raw: u8) -> Self {
+        let s_units = S_UNIT_RAW.iter().filter(|&&r| raw >= r).count() as u8 - 1;
+        let over_s9_db = OVER_S9_RAW.iter().filter(|&&r| raw >= r).count() as u8 * 10;
+        Self { raw, s_units, over_s9_db }
+    }
 }
 
 /// Radio connection information returned after successful connect
@@ -118,15 +599,51 @@ pub struct RadioStatus {
 pub struct RadioInfo {
     pub port: String,
     pub baud_rate: u32,
-    pub frequency_hz: f64,
-    pub mode: String,
+    /// `None` if the frequency probe failed at connect time
+    pub frequency_hz: Option<f64>,
+    /// `None` if the mode probe failed at connect time
+    pub mode: Option<String>,
     pub connected: bool,
+    /// Connect-time probes that failed (the connection is still usable)
+    pub warnings: Vec<String>,
+    /// The radio was found transmitting on connect and PTT was released
+    pub recovered_from_keyed: bool,
+    /// Serial read timeout the driver reports, `None` without a real port
+    #[serde(default)]
+    pub read_timeout_ms: Option<u64>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn rx_mode_defaults_to_psk31_bpsk() {
+        let mode = RxMode::default();
+        assert_eq!((mode.baud, mode.modulation), (31.25, Modulation::Bpsk));
+        assert!(mode.validate().is_ok());
+        assert!(RxMode { baud: 45.45, ..mode }.validate().is_err());
+        assert_eq!(
+            serde_json::to_value(mode).unwrap(),
+            serde_json::json!({ "baud": 31.25, "modulation": "bpsk" })
+        );
+    }
+
+    #[test]
+    fn s_meter_maps_raw_readings_to_s_units() {
+        let s = |raw| {
+            let r = SMeterReading::from_raw(raw);
+            (r.s_units, r.over_s9_db)
+        };
+        assert_eq!(s(0), (0, 0));
+        assert_eq!(s(11), (0, 0));
+        assert_eq!(s(12), (1, 0));
+        assert_eq!(s(129), (8, 0));
+        assert_eq!(s(130), (9, 0));
+        assert_eq!(s(171), (9, 10));
+        assert_eq!(s(255), (9, 60));
+    }
+
     // --- Frequency constructors ---
 
     #[test]
@@ -150,6 +667,41 @@ mod tests {
         assert_ne!(Frequency::hz(7_035_000.0), Frequency::hz(7_035_001.0));
     }
 
+    // --- Frequency formatting ---
+
+    #[test]
+    fn format_hz_grouped() {
+        assert_eq!(Frequency::hz(14_070_000.0).format_hz_grouped(), "14.070.000");
+        assert_eq!(Frequency::hz(7_000_000.0).format_hz_grouped(), "7.000.000");
+        assert_eq!(Frequency::hz(144_144_000.0).format_hz_grouped(), "144.144.000");
+        assert_eq!(Frequency::hz(14_070_150.0).format_hz_grouped(), "14.070.150");
+        assert_eq!(Frequency::hz(999.0).format_hz_grouped(), "999");
+        assert_eq!(Frequency::hz(1_000.0).format_hz_grouped(), "1.000");
+        assert_eq!(Frequency::hz(0.0).format_hz_grouped(), "0");
+    }
+
+    #[test]
+    fn format_hz_grouped_rounds_sub_hz() {
+        assert_eq!(Frequency::hz(14_070_000.6).format_hz_grouped(), "14.070.001");
+    }
+
+    #[test]
+    fn format_mhz() {
+        assert_eq!(Frequency::hz(14_070_000.0).format_mhz(6), "14.070000 MHz");
+        assert_eq!(Frequency::hz(7_000_000.0).format_mhz(6), "7.000000 MHz");
+        assert_eq!(Frequency::hz(14_070_150.0).format_mhz(6), "14.070150 MHz");
+        assert_eq!(Frequency::hz(7_035_000.0).format_mhz(3), "7.035 MHz");
+    }
+
+    #[test]
+    fn format_by_style() {
+        let f = Frequency::hz(3_580_000.0);
+        assert_eq!(f.format(FrequencyStyle::Grouped), "3.580.000");
+        assert_eq!(f.format(FrequencyStyle::Mhz), "3.580000 MHz");
+        let style: FrequencyStyle = serde_json::from_str(r#""mhz""#).unwrap();
+        assert_eq!(style, FrequencyStyle::Mhz);
+    }
+
     // --- ModemConfig defaults ---
 
     #[test]
@@ -191,6 +743,7 @@ mod tests {
             rit_offset_hz: 0,
             rit_enabled: false,
             split: false,
+            vfo: Vfo::A,
         };
         let b = a.clone();
         assert_eq!(a, b);
@@ -208,6 +761,7 @@ mod tests {
             rit_offset_hz: 0,
             rit_enabled: false,
             split: false,
+            vfo: Vfo::A,
         };
         let json = serde_json::to_string(&s).unwrap();
         assert!(json.contains("frequencyHz"), "expected camelCase frequencyHz");
@@ -221,13 +775,53 @@ mod tests {
         let info = RadioInfo {
             port: "/dev/tty.usbserial".into(),
             baud_rate: 38400,
-            frequency_hz: 14_070_000.0,
-            mode: "USB".into(),
+            frequency_hz: Some(14_070_000.0),
+            mode: Some("USB".into()),
             connected: true,
+            warnings: vec![],
+            recovered_from_keyed: false,
+            read_timeout_ms: Some(100),
         };
         let json = serde_json::to_string(&info).unwrap();
         assert!(json.contains("frequencyHz"), "expected camelCase frequencyHz");
         assert!(json.contains("baudRate"), "expected camelCase baudRate");
+        assert!(json.contains("recoveredFromKeyed"), "expected camelCase recoveredFromKeyed");
+        assert!(json.contains("readTimeoutMs"), "expected camelCase readTimeoutMs");
+    }
+
+    // --- AudioDevices grouping ---
+
+    fn device(name: &str, is_input: bool, is_output: bool) -> AudioDeviceInfo {
+        AudioDeviceInfo {
+            id: name.into(),
+            name: name.into(),
+            is_input,
+            is_output,
+            is_default: false,
+            output_unverified: false,
+        }
+    }
+
+    #[test]
+    fn audio_devices_grouped_by_role() {
+        let devices = vec![
+            device("Built-in Microphone", true, false),
+            device("Built-in Speakers", false, true),
+            device("USB Audio CODEC", true, true),
+        ];
+        let grouped =
+            AudioDevices::from_devices(&devices, Some("Built-in Microphone"), Some("USB Audio CODEC"));
+
+        let inputs: Vec<&str> = grouped.inputs.iter().map(|d| d.name.as_str()).collect();
+        let outputs: Vec<&str> = grouped.outputs.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(inputs, vec!["Built-in Microphone", "USB Audio CODEC"]);
+        assert_eq!(outputs, vec!["Built-in Speakers", "USB Audio CODEC"]);
+
+        // Defaults are flagged per role, not across roles
+        assert!(grouped.inputs[0].is_default);
+        assert!(!grouped.inputs[1].is_default);
+        assert!(!grouped.outputs[0].is_default);
+        assert!(grouped.outputs[1].is_default);
     }
 
     // --- AudioDeviceInfo serialization ---
@@ -1,5 +1,13 @@
 //! Automatic Gain Control
 
+/// Per-sample gain reduction while the output is above target
+pub const ATTACK_RATE: f32 = 0.01;
+/// Per-sample gain increase while the output is below target
+pub const DECAY_RATE: f32 = 0.001;
+/// Gain range: 0.01–100 (±40 dB)
+pub const MIN_GAIN: f32 = 0.01;
+pub const MAX_GAIN: f32 = 100.0;
+
 /// AGC with exponential attack/decay
 pub struct Agc {
     target_level: f32,
@@ -14,11 +22,11 @@ impl Agc {
     pub fn new(target_level: f32) -> Self {
         Self {
             target_level,
-            attack_rate: 0.01,
-            decay_rate: 0.001,
+            attack_rate: ATTACK_RATE,
+            decay_rate: DECAY_RATE,
             gain: 1.0,
-            max_gain: 100.0,
-            min_gain: 0.01,
+            max_gain: MAX_GAIN,
+            min_gain: MIN_GAIN,
         }
     }
 
@@ -1,16 +1,62 @@
-//! Symbol timing recovery using Mueller-Muller timing error detector
+//! Symbol timing recovery using a Gardner timing error detector
 //!
 //! Think of it like a metronome that self-adjusts: it counts samples between
-//! "beats" (symbol decision points) and uses the M&M formula to nudge the
-//! beat timing earlier or later so it lands at the optimal sampling instant.
+//! "beats" (symbol decision points) and uses the Gardner formula to nudge the
+//! beat rate toward the sender's. On a phase reversal the sample half-way
+//! between two decisions should sit on the zero crossing; which side of it
+//! the sample lands on says whether the decisions run early or late. The
+//! error is normalised by the on-time level, so the adaptation rate doesn't
+//! depend on how much of the signal reaches this arm.
+//!
+//! Gardner only nudges the *rate*; it can't recover from a sudden phase jump
+//! (an audio dropout or duplicated buffer). After half a symbol of slip the
+//! decisions land on the symbol boundaries, where the raised-cosine envelope
+//! is at its minimum, while the mid-interval samples sit on the peaks. We
+//! watch both magnitudes and, when the mid-interval one clearly dominates,
+//! slip the decision point by half a symbol. That shows up to the caller as
+//! one long (1.5-symbol) interval — see `last_interval`.
+//!
+//! The value returned at a decision point is normally the sample at the
+//! omega boundary. `set_decision_offset` moves it back by a fraction of a
+//! symbol, for shaping whose best decision instant sits off the boundary;
+//! timing error detection still uses the boundary sample.
+
+use std::collections::VecDeque;
+
+/// Smoothing factor for the per-symbol on-time / mid-interval magnitude averages
+const SLIP_ENERGY_ALPHA: f32 = 0.2;
 
-/// Symbol clock recovery using Mueller-Muller timing error detector
+/// Mid-interval magnitude must exceed the on-time magnitude by this factor
+/// before the decision point is slipped by half a symbol
+const SLIP_RATIO: f32 = 2.0;
+
+/// Omega adaptation per unit of normalised timing error, as a fraction of
+/// the nominal samples per symbol. Gentle: faster adaptation costs copy.
+const OMEGA_GAIN: f64 = 3e-7;
+
+/// Symbol clock recovery using a Gardner timing error detector
 pub struct ClockRecovery {
     samples_per_symbol: f64,
     omega: f64,        // Current samples-per-symbol estimate
     gain_omega: f64,   // Timing gain (how fast omega adapts)
     last_symbol: f32,  // Previous symbol decision-point value
     sample_count: f64,
+    /// Smoothed |sample| at decision points
+    on_time_level: f32,
+    /// Smoothed |sample| half-way between decision points
+    mid_level: f32,
+    /// Whether this interval's mid-point sample has been taken
+    mid_taken: bool,
+    /// This interval's mid-point sample, for the timing error detector
+    mid_sample: f32,
+    /// Samples since the previous decision point
+    since_decision: usize,
+    /// Length in samples of the interval that ended at the last decision
+    last_interval: usize,
+    /// Fraction of a symbol (0..1) before the boundary to take the output from
+    decision_offset: f64,
+    /// Recent samples, kept only while `decision_offset` is non-zero
+    history: VecDeque<f32>,
 }
 
 impl ClockRecovery {
@@ -21,37 +67,84 @@ impl ClockRecovery {
         Self {
             samples_per_symbol,
             omega: samples_per_symbol,
-            gain_omega: 0.001,
+            gain_omega: OMEGA_GAIN * samples_per_symbol,
             last_symbol: 0.0,
             // Start the counter at the half-symbol offset so the first decision
             // fires at sample (samples_per_symbol/2 - 1), i.e. the envelope peak
             // for a correct PSK-31 signal where transitions straddle symbol boundaries.
             sample_count: samples_per_symbol / 2.0,
+            on_time_level: 0.0,
+            mid_level: 0.0,
+            mid_taken: false,
+            mid_sample: 0.0,
+            since_decision: 0,
+            last_interval: samples_per_symbol.round() as usize,
+            decision_offset: 0.0,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Return the sample `offset` (0..1) of a symbol before each decision
+    /// boundary instead of the boundary sample itself. 0.0 (the default)
+    /// keeps the boundary sample.
+    pub fn set_decision_offset(&mut self, offset: f64) {
+        self.decision_offset = offset.clamp(0.0, 0.99);
+        self.history.clear();
+    }
+
+    /// Current decision offset as a fraction of a symbol
+    pub fn decision_offset(&self) -> f64 {
+        self.decision_offset
+    }
+
+    /// The sample to report for a decision at `sample`, honouring the offset.
+    /// Falls back to `sample` until enough history has been collected.
+    fn decision_sample(&self, sample: f32) -> f32 {
+        let lag = (self.decision_offset * self.omega).round() as usize;
+        match self.history.len().checked_sub(lag + 1) {
+            Some(i) if lag > 0 => self.history[i],
+            _ => sample,
         }
     }
 
     /// Process a sample, returns Some(symbol_value) at symbol decision points
     ///
-    /// Uses Mueller-Muller timing error detection to adaptively track
-    /// the optimal sampling instant. Returns None for most samples,
+    /// Uses Gardner timing error detection to adaptively track the
+    /// symbol rate. Returns None for most samples,
     /// Some(symbol_value) when we hit a decision point (~once per 1536 samples).
     pub fn process(&mut self, sample: f32) -> Option<f32> {
         self.sample_count += 1.0;
+        self.since_decision += 1;
+
+        if self.decision_offset > 0.0 {
+            // Enough for the largest lag omega can clamp to (1.1 symbols)
+            let capacity = (self.samples_per_symbol * 1.1).ceil() as usize + 1;
+            if self.history.len() == capacity {
+                self.history.pop_front();
+            }
+            self.history.push_back(sample);
+        }
+
+        if !self.mid_taken && self.sample_count >= self.omega / 2.0 {
+            self.mid_level += SLIP_ENERGY_ALPHA * (sample.abs() - self.mid_level);
+            self.mid_sample = sample;
+            self.mid_taken = true;
+        }
 
         // Check if we've reached a symbol decision point
         if self.sample_count >= self.omega {
             self.sample_count -= self.omega;
 
-            // Mueller-Muller timing error detector
-            // Compares the current sample at the decision point with the
-            // midpoint sample (last_sample before decision) and previous decision.
-            // e = d_{k-1} * x_k - d_k * x_{k-1}
-            let decision = if sample >= 0.0 { 1.0f32 } else { -1.0 };
-            let last_decision = if self.last_symbol >= 0.0 { 1.0f32 } else { -1.0 };
-            let timing_error = last_decision * sample - decision * self.last_symbol;
+            // Gardner timing error detector: e = x_mid * (x_{k-1} - x_k).
+            // Zero without a reversal; positive when the mid-point sample
+            // still has the previous symbol's sign (decisions running early,
+            // i.e. symbols longer than omega)
+            let timing_error = self.mid_sample * (self.last_symbol - sample);
+            let power = (self.on_time_level * self.on_time_level).max(f32::MIN_POSITIVE);
+            let timing_error = f64::from(timing_error / power).clamp(-1.0, 1.0);
 
             // Update omega (samples per symbol estimate) with very gentle adaptation
-            self.omega += self.gain_omega * timing_error as f64;
+            self.omega += self.gain_omega * timing_error;
 
             // Clamp omega to ±10% of nominal (prevents runaway)
             self.omega = self.omega.clamp(
@@ -60,18 +153,52 @@ impl ClockRecovery {
             );
 
             self.last_symbol = sample;
+            self.last_interval = self.since_decision;
+            self.since_decision = 0;
+            self.mid_taken = false;
+
+            // Decisions sitting on symbol boundaries: slip half a symbol so
+            // the next one lands on the envelope peak
+            self.on_time_level += SLIP_ENERGY_ALPHA * (sample.abs() - self.on_time_level);
+            if self.mid_level > SLIP_RATIO * self.on_time_level {
+                self.sample_count -= self.omega / 2.0;
+                std::mem::swap(&mut self.on_time_level, &mut self.mid_level);
+            }
 
-            Some(sample)
+            Some(self.decision_sample(sample))
         } else {
             None
         }
     }
 
+    /// Current samples-per-symbol estimate (adapted timing)
+    pub fn omega(&self) -> f64 {
+        self.omega
+    }
+
+    /// Samples between the two most recent decision points. About `omega`
+    /// normally; about 1.5 × `omega` right after a half-symbol slip.
+    pub fn last_interval(&self) -> usize {
+        self.last_interval
+    }
+
+    /// Nominal samples per symbol the loop was created with
+    pub fn nominal_samples_per_symbol(&self) -> f64 {
+        self.samples_per_symbol
+    }
+
     /// Reset the clock recovery state
     pub fn reset(&mut self) {
         self.omega = self.samples_per_symbol;
         self.last_symbol = 0.0;
         self.sample_count = self.samples_per_symbol / 2.0;
+        self.on_time_level = 0.0;
+        self.mid_level = 0.0;
+        self.mid_taken = false;
+        self.mid_sample = 0.0;
+        self.since_decision = 0;
+        self.last_interval = self.samples_per_symbol.round() as usize;
+        self.history.clear();
     }
 }
 
@@ -117,6 +244,79 @@ mod tests {
         assert!(cr.omega <= sps * 1.1);
     }
 
+    #[test]
+    fn test_omega_tracks_off_rate_reversals_within_clamp() {
+        let sps = 1536.0;
+        // Continuous phase reversals: zero crossings every `true_sps` samples
+        let omega_after = |true_sps: f64| {
+            let mut cr = ClockRecovery::new(sps);
+            assert_eq!(cr.omega(), sps);
+            for n in 0..(200.0 * true_sps) as usize {
+                cr.process((std::f64::consts::PI * n as f64 / true_sps).sin() as f32);
+            }
+            assert_eq!(cr.nominal_samples_per_symbol(), sps);
+            cr.omega()
+        };
+
+        let slow = omega_after(sps * 1.01);
+        assert!(sps < slow && slow < sps * 1.01, "long symbols: omega {slow}");
+        let fast = omega_after(sps * 0.99);
+        assert!(sps * 0.99 < fast && fast < sps, "short symbols: omega {fast}");
+    }
+
+    /// Raised-cosine-like envelope: peak mid-symbol, zero at boundaries
+    fn envelope(i: usize, sps: usize) -> f32 {
+        (std::f32::consts::PI * (i % sps) as f32 / sps as f32).sin()
+    }
+
+    #[test]
+    fn test_half_symbol_slip_is_corrected_with_one_long_interval() {
+        let sps = 1536usize;
+        let mut cr = ClockRecovery::new(sps as f64);
+        let mut intervals = Vec::new();
+
+        // 20 aligned symbols, then the stream jumps by half a symbol
+        let signal = (0..20 * sps)
+            .map(|i| envelope(i, sps))
+            .chain((20 * sps + sps / 2..60 * sps).map(|i| envelope(i, sps)));
+        for s in signal {
+            if cr.process(s).is_some() {
+                intervals.push(cr.last_interval());
+            }
+        }
+
+        let long: Vec<usize> = intervals.iter().copied().filter(|&n| n > sps + sps / 4).collect();
+        assert_eq!(long.len(), 1, "intervals: {intervals:?}");
+        assert!((sps * 3 / 2 - 2..=sps * 3 / 2 + 2).contains(&long[0]));
+    }
+
+    #[test]
+    fn test_decision_offset_selects_earlier_sample() {
+        let sps = 1536usize;
+        // Each symbol ramps 0 → 1, so a decision's value says where in the
+        // symbol it was taken
+        let ramp = |i: usize| (i % sps) as f32 / sps as f32;
+        let mut on_boundary = ClockRecovery::new(sps as f64);
+        let mut offset = ClockRecovery::new(sps as f64);
+        offset.set_decision_offset(0.25);
+        assert_eq!(on_boundary.decision_offset(), 0.0);
+
+        let mut pairs = Vec::new();
+        for i in 0..5 * sps {
+            let a = on_boundary.process(ramp(i));
+            let b = offset.process(ramp(i));
+            assert_eq!(a.is_some(), b.is_some(), "offset must not move the decision timing");
+            if let (Some(a), Some(b)) = (a, b) {
+                pairs.push((a, b));
+            }
+        }
+
+        assert!(pairs.len() >= 4);
+        for (a, b) in pairs {
+            assert!((a - b - 0.25).abs() <= 1.0 / sps as f32, "boundary {a}, offset {b}");
+        }
+    }
+
     #[test]
     fn test_reset() {
         let sps = 1536.0;
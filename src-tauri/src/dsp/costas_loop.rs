@@ -10,6 +10,29 @@
 
 use super::nco::Nco;
 
+/// Default anti-windup limit on the integrator, expressed as a carrier
+/// offset in Hz. Far wider than the loop's ~2 Hz pull-in range, so clean
+/// signals never touch it; it only stops runaway accumulation during fades.
+pub const DEFAULT_INTEGRATOR_LIMIT_HZ: f64 = 25.0;
+
+/// I/Q arm lowpass cutoff in Hz — removes the double-frequency mixing term
+pub const ARM_LPF_CUTOFF_HZ: f64 = 50.0;
+
+/// Proportional (phase) gain, empirically tuned at `GAIN_REFERENCE_RATE` — see `new`
+pub const PROPORTIONAL_GAIN: f64 = 0.01;
+
+/// Integral (frequency) gain, empirically tuned at `GAIN_REFERENCE_RATE` — see `new`
+pub const INTEGRAL_GAIN: f64 = 0.000005;
+
+/// Sample rate the PI gains were tuned at; other rates are rescaled to it
+pub const GAIN_REFERENCE_RATE: f64 = 48000.0;
+
+/// Time constant of the I/Q arm energy averages behind `snr_estimate`
+pub const SNR_AVERAGE_SECS: f64 = 1.0;
+
+/// `snr_estimate` ceiling, reached when the Q arm is essentially silent
+pub const MAX_SNR_DB: f32 = 40.0;
+
 /// Costas loop for BPSK carrier tracking and demodulation
 pub struct CostasLoop {
     nco: Nco,
@@ -22,6 +45,14 @@ pub struct CostasLoop {
     proportional_gain: f64,
     integral_gain: f64,
     integrator: f64,
+    /// Anti-windup clamp on `integrator` (radians/sample, symmetric ±)
+    integrator_limit: f64,
+    /// Smoothed I² and Q² of the arm outputs (see `snr_estimate`)
+    i_energy: f32,
+    q_energy: f32,
+    /// Per-sample smoothing for the arm energies, from `SNR_AVERAGE_SECS`
+    energy_alpha: f32,
+    sample_rate: f64,
 }
 
 impl CostasLoop {
@@ -35,8 +66,7 @@ impl CostasLoop {
 
         // IIR lowpass coefficient for I/Q arms
         // Cutoff at ~50 Hz removes the double-frequency term after mixing
-        let lpf_cutoff = 50.0;
-        let alpha = (2.0 * std::f64::consts::PI * lpf_cutoff / sample_rate) as f32;
+        let alpha = (2.0 * std::f64::consts::PI * ARM_LPF_CUTOFF_HZ / sample_rate) as f32;
 
         // PLL gains, empirically tuned for BPSK Costas Loop at 48kHz.
         //
@@ -47,9 +77,14 @@ impl CostasLoop {
         //
         // Proportional: fast phase correction (tracks phase jitter)
         // Integral: slow frequency correction (tracks carrier offset)
+        //
+        // Both are per-sample, so at any other rate (e.g. a decimated front
+        // end) they are rescaled to keep the same per-second response:
+        // Kp by 48000/fs, and Ki — which acts through one extra sum — by its square.
         let _ = loop_bandwidth; // Used conceptually to set the gains below
-        let proportional_gain = 0.01;
-        let integral_gain = 0.000005;
+        let rate_scale = GAIN_REFERENCE_RATE / sample_rate;
+        let proportional_gain = PROPORTIONAL_GAIN * rate_scale;
+        let integral_gain = INTEGRAL_GAIN * rate_scale * rate_scale;
 
         Self {
             nco,
@@ -59,9 +94,24 @@ impl CostasLoop {
             proportional_gain,
             integral_gain,
             integrator: 0.0,
+            integrator_limit: Self::hz_to_rad_per_sample(DEFAULT_INTEGRATOR_LIMIT_HZ, sample_rate),
+            i_energy: 0.0,
+            q_energy: 0.0,
+            energy_alpha: (1.0 / (SNR_AVERAGE_SECS * sample_rate)) as f32,
+            sample_rate,
         }
     }
 
+    /// Limit how far the integrator (frequency correction) may wind up,
+    /// as a carrier offset in Hz. Use `f64::INFINITY` to disable the clamp.
+    pub fn set_integrator_limit_hz(&mut self, limit_hz: f64) {
+        self.integrator_limit = Self::hz_to_rad_per_sample(limit_hz.abs(), self.sample_rate);
+    }
+
+    fn hz_to_rad_per_sample(hz: f64, sample_rate: f64) -> f64 {
+        2.0 * std::f64::consts::PI * hz / sample_rate
+    }
+
     /// Process a single sample, returns the demodulated baseband I value
     pub fn process(&mut self, sample: f32) -> f32 {
         // Mix with local oscillator (downconvert to baseband)
@@ -75,13 +125,21 @@ impl CostasLoop {
         self.filtered_i += self.alpha * (mixed_i - self.filtered_i);
         self.filtered_q += self.alpha * (mixed_q - self.filtered_q);
 
+        // Arm energies for the SNR estimate: when locked the data sits on
+        // one arm and whatever reaches the other is noise
+        let (i_sq, q_sq) = (self.filtered_i * self.filtered_i, self.filtered_q * self.filtered_q);
+        self.i_energy += self.energy_alpha * (i_sq - self.i_energy);
+        self.q_energy += self.energy_alpha * (q_sq - self.q_energy);
+
         // Phase error detector for BPSK: e = I × Q
         // When locked: I is large (data), Q is near zero
         // Phase error drives Q toward zero
         let phase_error = (self.filtered_i * self.filtered_q) as f64;
 
-        // Loop filter (PI controller)
-        self.integrator += self.integral_gain * phase_error;
+        // Loop filter (PI controller). The integrator is clamped (anti-windup)
+        // so a long fade can't leave it far off-frequency when the signal returns.
+        self.integrator = (self.integrator + self.integral_gain * phase_error)
+            .clamp(-self.integrator_limit, self.integrator_limit);
         let correction = self.proportional_gain * phase_error + self.integrator;
 
         // Adjust NCO phase to track the carrier
@@ -90,6 +148,31 @@ impl CostasLoop {
         self.filtered_i
     }
 
+    /// Filtered Q arm — near zero when locked, as large as I when not
+    pub fn quadrature(&self) -> f32 {
+        self.filtered_q
+    }
+
+    /// Signal-to-noise estimate in dB: smoothed data-arm energy over
+    /// noise-arm energy, both after the arm lowpass. The I×Q detector has
+    /// equilibria 90° apart, so the loop may hold the data on either arm;
+    /// the stronger one is taken as data. Only meaningful while locked; 0
+    /// before any signal, at most `MAX_SNR_DB`.
+    pub fn snr_estimate(&self) -> f32 {
+        let data = self.i_energy.max(self.q_energy);
+        if data <= f32::MIN_POSITIVE {
+            return 0.0;
+        }
+        let noise = self.i_energy.min(self.q_energy).max(f32::MIN_POSITIVE);
+        (10.0 * (data / noise).log10()).min(MAX_SNR_DB)
+    }
+
+    /// Frequency the loop is actually tracking: the NCO's nominal frequency
+    /// plus the integrator's accumulated correction, in Hz
+    pub fn current_frequency(&self) -> f64 {
+        self.nco.frequency() + self.integrator * self.sample_rate / (2.0 * std::f64::consts::PI)
+    }
+
     /// Set the carrier frequency (e.g., from click-to-tune)
     pub fn set_frequency(&mut self, freq: f64) {
         self.nco.set_frequency(freq);
@@ -101,6 +184,8 @@ impl CostasLoop {
         self.filtered_i = 0.0;
         self.filtered_q = 0.0;
         self.integrator = 0.0;
+        self.i_energy = 0.0;
+        self.q_energy = 0.0;
     }
 }
 
@@ -204,6 +289,108 @@ mod tests {
         }
     }
 
+    /// Lock on clean BPSK, wind the integrator up with an off-frequency
+    /// interferer (a "fade" under QRM), then return the signal. Returns the
+    /// number of symbols until the frequency correction settles within 0.5 Hz.
+    fn symbols_to_relock_after_dropout(limit_hz: f64) -> usize {
+        let sample_rate = 48000.0;
+        let sps = 1536;
+        let mut costas = CostasLoop::new(1000.0, sample_rate, 2.0);
+        costas.set_integrator_limit_hz(limit_hz);
+
+        for &s in &generate_bpsk(1000.0, sample_rate, sps, &[false; 64]) {
+            costas.process(s);
+        }
+        for i in 0..64 * sps {
+            let s = (2.0 * PI * 1040.0 * i as f64 / sample_rate).cos() as f32;
+            costas.process(s);
+        }
+
+        let mut settled_at = 0;
+        for (i, &s) in generate_bpsk(1000.0, sample_rate, sps, &[false; 256]).iter().enumerate() {
+            costas.process(s);
+            let error_hz = costas.current_frequency() - 1000.0;
+            if error_hz.abs() >= 0.5 {
+                settled_at = i / sps + 1;
+            }
+        }
+        settled_at
+    }
+
+    #[test]
+    fn test_integrator_clamp_speeds_relock_after_dropout() {
+        let unclamped = symbols_to_relock_after_dropout(f64::INFINITY);
+        let clamped = symbols_to_relock_after_dropout(2.0);
+        assert!(
+            clamped < unclamped,
+            "clamped loop took {clamped} symbols, unclamped {unclamped}"
+        );
+    }
+
+    #[test]
+    fn test_gains_scale_with_sample_rate() {
+        let full = CostasLoop::new(1000.0, 48000.0, 2.0);
+        let decimated = CostasLoop::new(1000.0, 8000.0, 2.0);
+        assert!((decimated.proportional_gain - 6.0 * full.proportional_gain).abs() < 1e-12);
+        assert!((decimated.integral_gain - 36.0 * full.integral_gain).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_integrator_never_exceeds_limit() {
+        let mut costas = CostasLoop::new(1000.0, 48000.0, 2.0);
+        costas.set_integrator_limit_hz(1.0);
+        for i in 0..200_000 {
+            let s = (2.0 * PI * 1040.0 * i as f64 / 48000.0).cos() as f32;
+            costas.process(s);
+        }
+        let limit = 2.0 * PI * 1.0 / 48000.0;
+        assert!(costas.integrator.abs() <= limit + 1e-12);
+    }
+
+    #[test]
+    fn test_current_frequency_follows_offset_carrier() {
+        let mut costas = CostasLoop::new(1000.0, 48000.0, 2.0);
+        assert!((costas.current_frequency() - 1000.0).abs() < 1e-9);
+
+        for &s in &generate_bpsk(1001.0, 48000.0, 1536, &[false; 128]) {
+            costas.process(s);
+        }
+        let tracked = costas.current_frequency();
+        assert!((tracked - 1001.0).abs() < 0.5, "tracked {tracked:.3} Hz");
+    }
+
+    #[test]
+    fn test_snr_estimate_drops_with_noise() {
+        let (sample_rate, sps) = (48000.0, 1536);
+        let bits: Vec<bool> = (0..96).map(|i| i % 3 != 0).collect();
+        let clean = generate_bpsk(1000.0, sample_rate, sps, &bits);
+
+        // Fixed-seed LCG white noise, peak 10x the signal (the arm lowpass
+        // keeps only ~1/300 of its power, so this is still a decodable SNR)
+        let mut state: u32 = 2024;
+        let noisy: Vec<f32> = clean
+            .iter()
+            .map(|&s| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                s + 10.0 * ((state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0)
+            })
+            .collect();
+
+        let snr = |signal: &[f32]| {
+            let mut costas = CostasLoop::new(1000.0, sample_rate, 2.0);
+            signal.iter().for_each(|&s| {
+                costas.process(s);
+            });
+            costas.snr_estimate()
+        };
+        let (clean_snr, noisy_snr) = (snr(&clean), snr(&noisy));
+        assert!(clean_snr > 20.0, "clean BPSK only {clean_snr:.1} dB");
+        assert!(
+            clean_snr - noisy_snr > 10.0,
+            "clean {clean_snr:.1} dB vs noisy {noisy_snr:.1} dB"
+        );
+    }
+
     #[test]
     fn test_costas_reset() {
         let mut costas = CostasLoop::new(1000.0, 48000.0, 2.0);
@@ -217,5 +404,6 @@ mod tests {
         assert_eq!(costas.integrator, 0.0);
         assert_eq!(costas.filtered_i, 0.0);
         assert_eq!(costas.filtered_q, 0.0);
+        assert_eq!(costas.snr_estimate(), 0.0);
     }
 }
@@ -0,0 +1,61 @@
+//! DC-blocking highpass
+//!
+//! One-pole/one-zero filter `y[n] = x[n] - x[n-1] + R·y[n-1]`: a zero at DC
+//! and a pole just inside it. With R = 0.995 the -3 dB corner is about
+//! 38 Hz at 48 kHz, far below any PSK-31 audio carrier.
+
+/// Pole radius — closer to 1 means a lower corner and slower settling
+pub const DC_BLOCKER_R: f32 = 0.995;
+
+/// Removes a constant offset from a sample stream
+pub struct DcBlocker {
+    r: f32,
+    prev_input: f32,
+    prev_output: f32,
+}
+
+impl DcBlocker {
+    pub fn new(r: f32) -> Self {
+        Self { r, prev_input: 0.0, prev_output: 0.0 }
+    }
+
+    pub fn process(&mut self, sample: f32) -> f32 {
+        let output = sample - self.prev_input + self.r * self.prev_output;
+        self.prev_input = sample;
+        self.prev_output = output;
+        output
+    }
+
+    pub fn reset(&mut self) {
+        self.prev_input = 0.0;
+        self.prev_output = 0.0;
+    }
+}
+
+impl Default for DcBlocker {
+    fn default() -> Self {
+        Self::new(DC_BLOCKER_R)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn removes_constant_offset() {
+        let mut blocker = DcBlocker::default();
+        let settled: Vec<f32> = (0..20_000).map(|_| blocker.process(0.7)).collect();
+        assert!(settled[19_999].abs() < 1e-3, "residual DC {}", settled[19_999]);
+    }
+
+    #[test]
+    fn passes_audio_carrier() {
+        let mut blocker = DcBlocker::default();
+        let tone = |i: usize| (2.0 * PI * 1000.0 * i as f32 / 48000.0).sin();
+        let out: Vec<f32> = (0..48_000).map(|i| blocker.process(tone(i) + 0.5)).collect();
+        let peak = out[24_000..].iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!((peak - 1.0).abs() < 0.01, "1 kHz peak {peak}");
+    }
+}
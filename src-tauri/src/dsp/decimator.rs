@@ -0,0 +1,122 @@
+//! Decimate-by-N: anti-alias lowpass, then keep every Nth sample
+//!
+//! A PSK-31 signal occupies well under 4 kHz of audio, so the loops after
+//! the front end don't need 48 kHz. Only the kept samples are filtered
+//! (the other N-1 just enter the delay line), so the lowpass costs one
+//! dot product per output sample rather than per input sample.
+
+use crate::dsp::filter::lowpass_coefficients;
+
+/// Anti-alias cutoff as a fraction of the output sample rate (just under
+/// the output Nyquist frequency of 0.5)
+const CUTOFF_FRACTION: f32 = 0.45;
+
+/// Lowpass taps per unit of decimation factor
+const TAPS_PER_FACTOR: usize = 16;
+
+/// Polyphase-style decimator
+pub struct Decimator {
+    factor: usize,
+    coefficients: Vec<f32>,
+    delay_line: Vec<f32>,
+    position: usize,
+    /// Input samples since the last output
+    phase: usize,
+}
+
+impl Decimator {
+    /// Decimate `input_rate` audio by `factor` (≥ 2).
+    pub fn new(factor: usize, input_rate: f32) -> Self {
+        assert!(factor >= 2, "decimation factor must be at least 2");
+        let output_rate = input_rate / factor as f32;
+        let num_taps = TAPS_PER_FACTOR * factor + 1;
+        Self {
+            factor,
+            coefficients: lowpass_coefficients(CUTOFF_FRACTION * output_rate, input_rate, num_taps),
+            delay_line: vec![0.0; num_taps],
+            position: 0,
+            phase: 0,
+        }
+    }
+
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+
+    /// Push one input sample; returns an output sample every `factor` inputs.
+    pub fn process(&mut self, sample: f32) -> Option<f32> {
+        let len = self.delay_line.len();
+        self.delay_line[self.position] = sample;
+        let newest = self.position;
+        self.position = (self.position + 1) % len;
+
+        self.phase += 1;
+        if self.phase < self.factor {
+            return None;
+        }
+        self.phase = 0;
+
+        let output = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| c * self.delay_line[(newest + len - i) % len])
+            .sum();
+        Some(output)
+    }
+
+    pub fn reset(&mut self) {
+        self.delay_line.fill(0.0);
+        self.position = 0;
+        self.phase = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Peak output amplitude for a sine at `freq`, after the filter settles
+    fn peak_output(freq: f32) -> f32 {
+        let mut dec = Decimator::new(6, 48000.0);
+        let tone = |i: usize| (2.0 * std::f32::consts::PI * freq * i as f32 / 48000.0).sin();
+        (0..9600)
+            .filter_map(|i| dec.process(tone(i)))
+            .skip(100)
+            .fold(0.0f32, |peak, s| peak.max(s.abs()))
+    }
+
+    #[test]
+    fn emits_one_sample_per_factor() {
+        let mut dec = Decimator::new(6, 48000.0);
+        let outputs = (0..600).filter_map(|_| dec.process(1.0)).count();
+        assert_eq!(outputs, 100);
+        assert_eq!(dec.factor(), 6);
+    }
+
+    #[test]
+    fn passes_psk_audio_band() {
+        for freq in [500.0, 1500.0, 2500.0] {
+            assert!(peak_output(freq) > 0.9, "{freq} Hz attenuated: {}", peak_output(freq));
+        }
+    }
+
+    #[test]
+    fn rejects_what_would_alias() {
+        // Above the 4 kHz output Nyquist these would fold back into the band
+        for freq in [6000.0, 10_000.0] {
+            assert!(peak_output(freq) < 0.05, "{freq} Hz leaked: {}", peak_output(freq));
+        }
+    }
+
+    #[test]
+    fn reset_clears_history() {
+        let mut dec = Decimator::new(4, 48000.0);
+        for _ in 0..100 {
+            dec.process(1.0);
+        }
+        dec.reset();
+        let out = (0..4).filter_map(|_| dec.process(0.0)).next();
+        assert_eq!(out, Some(0.0));
+    }
+}
@@ -1,8 +1,14 @@
 //! FFT processing for waterfall display
 
+use std::ops::Range;
 use std::sync::Arc;
 use rustfft::{Fft, FftPlanner, num_complex::Complex};
 
+use super::util::{db_to_linear, linear_to_db};
+
+/// Magnitude floor for spectrum bins, so silence reads as a finite level
+pub const SPECTRUM_FLOOR_DB: f32 = -100.0;
+
 /// FFT processor for computing spectral data
 pub struct FftProcessor {
     fft: Arc<dyn Fft<f32>>,
@@ -54,24 +60,166 @@ impl FftProcessor {
         let half_size = self.fft_size / 2;
         buffer[..half_size]
             .iter()
-            .map(|c| {
-                let mag_squared = c.norm_sqr();
-                // Convert to dB with floor to avoid -infinity
-                10.0 * (mag_squared.max(1e-10)).log10()
-            })
+            .map(|c| linear_to_db(c.norm_sqr(), SPECTRUM_FLOOR_DB))
             .collect()
     }
 
+    /// Compute the spectrum and keep only the bins inside `low_hz..=high_hz`,
+    /// for a zoomed waterfall that doesn't ship the whole 0–Nyquist span.
+    pub fn compute_range(
+        &mut self,
+        samples: &[f32],
+        low_hz: f64,
+        high_hz: f64,
+        sample_rate: f64,
+    ) -> Vec<f32> {
+        let magnitudes = self.compute(samples);
+        let bins = bin_range(magnitudes.len(), low_hz, high_hz, sample_rate);
+        magnitudes[bins].to_vec()
+    }
+
     /// Get the FFT size
     pub fn fft_size(&self) -> usize {
         self.fft_size
     }
 }
 
+/// Indices of the bins (out of `num_bins` positive-frequency bins) whose
+/// centre frequency lies within `low_hz..=high_hz`. Empty if none do.
+pub fn bin_range(num_bins: usize, low_hz: f64, high_hz: f64, sample_rate: f64) -> Range<usize> {
+    let bin_hz = sample_rate / (2 * num_bins.max(1)) as f64;
+    let lo = ((low_hz.max(0.0) / bin_hz).ceil() as usize).min(num_bins);
+    // Exclusive end: one past the last bin at or below high_hz
+    let hi = ((high_hz.max(-bin_hz) / bin_hz).floor() + 1.0) as usize;
+    lo..hi.clamp(lo, num_bins)
+}
+
+/// Centre frequency of the `fft_size`-point FFT bin nearest `freq_hz`.
+///
+/// A carrier on a bin centre shows as one sharp peak on the waterfall rather
+/// than smeared across two bins (scalloping), so the marker lines up with it.
+pub fn snap_carrier_to_bin(freq_hz: f64, fft_size: usize, sample_rate: f64) -> f64 {
+    let bin_hz = sample_rate / fft_size.max(1) as f64;
+    (freq_hz / bin_hz).round() * bin_hz
+}
+
+/// Half-width of the window searched by [`tuning_error_hz`]
+pub const TUNING_WINDOW_HZ: f64 = 50.0;
+
+/// Signed offset (Hz) from the tuned carrier to the spectral centroid of the
+/// signal within ±`TUNING_WINDOW_HZ` of it.
+///
+/// `magnitudes_db` is the output of [`FftProcessor::compute`] (positive
+/// frequencies only, so the FFT size is twice its length). Bins are weighted
+/// by linear power above the window's quietest bin so the noise floor doesn't
+/// drag the centroid toward the window center. Positive means the signal is
+/// above the carrier. Returns `None` if the window is empty or flat.
+pub fn tuning_error_hz(magnitudes_db: &[f32], carrier_hz: f64, sample_rate: f64) -> Option<f64> {
+    if magnitudes_db.is_empty() {
+        return None;
+    }
+    let bin_hz = sample_rate / (2 * magnitudes_db.len()) as f64;
+    let lo = ((carrier_hz - TUNING_WINDOW_HZ) / bin_hz).ceil().max(0.0) as usize;
+    let hi = (((carrier_hz + TUNING_WINDOW_HZ) / bin_hz).floor() as usize).min(magnitudes_db.len() - 1);
+    if lo > hi {
+        return None;
+    }
+
+    let powers: Vec<f64> = magnitudes_db[lo..=hi]
+        .iter()
+        .map(|&db| f64::from(db_to_linear(db)))
+        .collect();
+    let floor = powers.iter().cloned().fold(f64::INFINITY, f64::min);
+
+    let (weighted, total) = powers
+        .iter()
+        .enumerate()
+        .fold((0.0, 0.0), |(w, t), (i, &p)| {
+            let excess = p - floor;
+            (w + excess * (lo + i) as f64 * bin_hz, t + excess)
+        });
+    if total <= 0.0 {
+        return None;
+    }
+    Some(weighted / total - carrier_hz)
+}
+
+/// Strongest bin within ±`window_hz` of `carrier_hz`, as (frequency, dB
+/// above the median of the whole spectrum). The frequency is refined by
+/// parabolic interpolation across the neighbouring bins.
+pub fn peak_near_hz(
+    magnitudes_db: &[f32],
+    carrier_hz: f64,
+    window_hz: f64,
+    sample_rate: f64,
+) -> Option<(f64, f32)> {
+    let (low, high) = (carrier_hz - window_hz, carrier_hz + window_hz);
+    let bins = bin_range(magnitudes_db.len(), low, high, sample_rate);
+    let peak = bins.max_by(|&a, &b| magnitudes_db[a].total_cmp(&magnitudes_db[b]))?;
+
+    let mut sorted = magnitudes_db.to_vec();
+    let mid = sorted.len() / 2;
+    let (_, &mut median, _) = sorted.select_nth_unstable_by(mid, f32::total_cmp);
+
+    let offset = match (peak.checked_sub(1), magnitudes_db.get(peak + 1)) {
+        (Some(lo), Some(&c)) => {
+            let (a, b) = (magnitudes_db[lo], magnitudes_db[peak]);
+            let curvature = a - 2.0 * b + c;
+            if curvature < 0.0 {
+                0.5 * (a - c) / curvature
+            } else {
+                0.0
+            }
+        }
+        _ => 0.0,
+    };
+    let bin_hz = sample_rate / (2 * magnitudes_db.len()) as f64;
+    Some(((peak as f64 + f64::from(offset)) * bin_hz, magnitudes_db[peak] - median))
+}
+
+/// Quantize dB magnitudes to 0–255 across `min_db..=max_db`, clamping
+/// anything outside the range. A quarter of the size of the float frame.
+pub fn quantize_db(magnitudes_db: &[f32], min_db: f32, max_db: f32) -> Vec<u8> {
+    let scale = 255.0 / (max_db - min_db);
+    magnitudes_db
+        .iter()
+        .map(|&db| ((db - min_db) * scale).round().clamp(0.0, 255.0) as u8)
+        .collect()
+}
+
+/// Inverse of [`quantize_db`], to within one step of `(max_db - min_db) / 255`.
+pub fn dequantize_db(level: u8, min_db: f32, max_db: f32) -> f32 {
+    min_db + f32::from(level) * (max_db - min_db) / 255.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn snap_picks_nearer_bin_centre() {
+        // 48 kHz / 4096 = 11.71875 Hz bins: bin 85 = 996.09375, bin 86 = 1007.8125
+        assert_eq!(snap_carrier_to_bin(1000.0, 4096, 48000.0), 996.09375);
+        assert_eq!(snap_carrier_to_bin(1003.0, 4096, 48000.0), 1007.8125);
+        // Already on a centre: unchanged
+        assert_eq!(snap_carrier_to_bin(1500.0, 4096, 48000.0), 1500.0);
+    }
+
+    #[test]
+    fn quantized_db_round_trips_within_one_step_and_clamps() {
+        let (min_db, max_db) = (-100.0, 0.0);
+        let step = (max_db - min_db) / 255.0;
+        let magnitudes: Vec<f32> = (0..=400).map(|i| -100.0 + i as f32 * 0.25).collect();
+        let quantized = quantize_db(&magnitudes, min_db, max_db);
+        for (&db, &q) in magnitudes.iter().zip(&quantized) {
+            let back = dequantize_db(q, min_db, max_db);
+            assert!((back - db).abs() <= step, "{db} dB came back as {back}");
+        }
+        assert_eq!((quantized[0], quantized[400]), (0, 255));
+
+        assert_eq!(quantize_db(&[-140.0, 12.0, f32::NEG_INFINITY], min_db, max_db), [0, 255, 0]);
+    }
+
     #[test]
     fn test_fft_pure_tone() {
         let mut processor = FftProcessor::new(1024);
@@ -108,6 +256,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compute_range_returns_bins_inside_window() {
+        // 4096-point FFT at 48 kHz: 11.71875 Hz per bin
+        let bin_hz = 48000.0 / 4096.0;
+        let bins = bin_range(2048, 500.0, 2500.0, 48000.0);
+        assert_eq!(bins, 43..214);
+        assert!(bins.clone().all(|i| (500.0..=2500.0).contains(&(i as f64 * bin_hz))));
+        assert!(((bins.start - 1) as f64 * bin_hz) < 500.0);
+        assert!((bins.end as f64 * bin_hz) > 2500.0);
+
+        let mut processor = FftProcessor::new(4096);
+        let samples = vec![0.0; 4096];
+        let zoomed = processor.compute_range(&samples, 500.0, 2500.0, 48000.0);
+        assert_eq!(zoomed.len(), bins.len());
+    }
+
+    #[test]
+    fn bin_range_clamps_to_spectrum() {
+        assert_eq!(bin_range(2048, 0.0, 48000.0, 48000.0), 0..2048);
+        assert!(bin_range(2048, 30000.0, 40000.0, 48000.0).is_empty());
+        assert!(bin_range(2048, 2500.0, 500.0, 48000.0).is_empty());
+    }
+
+    fn tone_spectrum(freq: f32) -> Vec<f32> {
+        let mut processor = FftProcessor::new(4096);
+        let samples: Vec<f32> = (0..4096)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / 48000.0).sin())
+            .collect();
+        processor.compute(&samples)
+    }
+
+    #[test]
+    fn tuning_error_positive_when_signal_above_carrier() {
+        let err = tuning_error_hz(&tone_spectrum(1020.0), 1000.0, 48000.0).unwrap();
+        assert!((err - 20.0).abs() < 5.0, "expected ≈ +20 Hz, got {err}");
+    }
+
+    #[test]
+    fn tuning_error_negative_when_signal_below_carrier() {
+        let err = tuning_error_hz(&tone_spectrum(1480.0), 1500.0, 48000.0).unwrap();
+        assert!((err + 20.0).abs() < 5.0, "expected ≈ -20 Hz, got {err}");
+    }
+
+    #[test]
+    fn tuning_error_near_zero_when_on_frequency() {
+        let err = tuning_error_hz(&tone_spectrum(1000.0), 1000.0, 48000.0).unwrap();
+        assert!(err.abs() < 5.0, "expected ≈ 0 Hz, got {err}");
+    }
+
+    #[test]
+    fn tuning_error_none_for_flat_spectrum() {
+        assert!(tuning_error_hz(&[-100.0; 2048], 1000.0, 48000.0).is_none());
+        assert!(tuning_error_hz(&[], 1000.0, 48000.0).is_none());
+    }
+
     #[test]
     fn compute_repeated_calls_give_identical_results() {
         // Regression: before caching the FFT plan, plan_fft_forward() was called on every
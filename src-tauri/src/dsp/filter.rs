@@ -1,5 +1,33 @@
 //! FIR filter implementation
 
+/// Windowed-sinc lowpass taps (Hanning window), normalized to unity DC gain
+pub fn lowpass_coefficients(cutoff_freq: f32, sample_rate: f32, num_taps: usize) -> Vec<f32> {
+    let normalized_cutoff = cutoff_freq / sample_rate;
+    let mut coefficients = vec![0.0; num_taps];
+    let middle = num_taps / 2;
+
+    for i in 0..num_taps {
+        let n = i as f32 - middle as f32;
+        if n == 0.0 {
+            coefficients[i] = 2.0 * normalized_cutoff;
+        } else {
+            coefficients[i] = (2.0 * std::f32::consts::PI * normalized_cutoff * n).sin()
+                / (std::f32::consts::PI * n);
+        }
+
+        // Apply Hanning window
+        let window = 0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / num_taps as f32).cos());
+        coefficients[i] *= window;
+    }
+
+    // Normalize
+    let sum: f32 = coefficients.iter().sum();
+    for c in &mut coefficients {
+        *c /= sum;
+    }
+    coefficients
+}
+
 /// FIR filter for bandpass/lowpass filtering
 pub struct FirFilter {
     coefficients: Vec<f32>,
@@ -20,38 +48,24 @@ impl FirFilter {
 
     /// Create a simple lowpass filter using windowed sinc
     pub fn lowpass(cutoff_freq: f32, sample_rate: f32, num_taps: usize) -> Self {
-        let normalized_cutoff = cutoff_freq / sample_rate;
-        let mut coefficients = vec![0.0; num_taps];
-        let middle = num_taps / 2;
-
-        for i in 0..num_taps {
-            let n = i as f32 - middle as f32;
-            if n == 0.0 {
-                coefficients[i] = 2.0 * normalized_cutoff;
-            } else {
-                coefficients[i] = (2.0 * std::f32::consts::PI * normalized_cutoff * n).sin()
-                    / (std::f32::consts::PI * n);
-            }
-
-            // Apply Hanning window
-            let window = 0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / num_taps as f32).cos());
-            coefficients[i] *= window;
-        }
-
-        // Normalize
-        let sum: f32 = coefficients.iter().sum();
-        for c in &mut coefficients {
-            *c /= sum;
-        }
+        Self::new(lowpass_coefficients(cutoff_freq, sample_rate, num_taps))
+    }
 
-        Self::new(coefficients)
+    /// Create a bandpass filter (see `bandpass_coefficients`)
+    pub fn bandpass(center_freq: f32, bandwidth: f32, sample_rate: f32, num_taps: usize) -> Self {
+        Self::new(Self::bandpass_coefficients(center_freq, bandwidth, sample_rate, num_taps))
     }
 
-    /// Create a bandpass filter by frequency-shifting a lowpass prototype
+    /// Bandpass taps made by frequency-shifting a lowpass prototype
     ///
     /// Like taking a lowpass filter and "sliding" it up to center_freq on the
     /// spectrum — multiply the lowpass coefficients by cos(2π·f_c·n) to shift.
-    pub fn bandpass(center_freq: f32, bandwidth: f32, sample_rate: f32, num_taps: usize) -> Self {
+    pub fn bandpass_coefficients(
+        center_freq: f32,
+        bandwidth: f32,
+        sample_rate: f32,
+        num_taps: usize,
+    ) -> Vec<f32> {
         // Start with a lowpass prototype at bandwidth/2
         let normalized_cutoff = (bandwidth / 2.0) / sample_rate;
         let middle = num_taps / 2;
@@ -95,7 +109,15 @@ impl FirFilter {
             }
         }
 
-        Self::new(coefficients)
+        coefficients
+    }
+
+    /// Swap in new taps of the same length, keeping the delay line, so a
+    /// retuned filter carries on from the samples it has already seen
+    /// instead of restarting from silence.
+    pub fn set_coefficients(&mut self, coefficients: Vec<f32>) {
+        assert_eq!(coefficients.len(), self.coefficients.len(), "tap count must not change");
+        self.coefficients = coefficients;
     }
 
     /// Process a single sample through the filter
@@ -178,6 +200,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_coefficients_keeps_history() {
+        let input: Vec<f32> = (0..200).map(|i| (i as f32 * 0.3).sin()).collect();
+        let mut retuned = FirFilter::bandpass(1000.0, 200.0, 8000.0, 41);
+        let mut fresh = FirFilter::bandpass(1020.0, 200.0, 8000.0, 41);
+        for &s in &input {
+            retuned.process(s);
+            fresh.process(s);
+        }
+        retuned.set_coefficients(FirFilter::bandpass_coefficients(1020.0, 200.0, 8000.0, 41));
+        assert_eq!(retuned.process(0.5), fresh.process(0.5));
+    }
+
     #[test]
     fn test_bandpass_passes_center_frequency() {
         let mut filter = FirFilter::bandpass(1000.0, 100.0, 48000.0, 127);
@@ -0,0 +1,52 @@
+//! Software input trim
+//!
+//! A fixed gain on captured audio for interfaces with no hardware input
+//! level control. Trimmed samples are clamped to full scale so a hot trim
+//! saturates rather than wrapping or feeding out-of-range values onward.
+
+/// Trim range accepted by `set_input_trim`, in dB
+pub const MAX_INPUT_TRIM_DB: f32 = 20.0;
+
+/// Trim in dB → amplitude factor (`10^(dB/20)`)
+pub fn trim_gain(trim_db: f32) -> f32 {
+    10f32.powf(trim_db / 20.0)
+}
+
+/// Scale `samples` by `gain` in place, clamping to ±1.0. Returns how many
+/// samples ended up at full scale (clipped).
+pub fn apply_trim(samples: &mut [f32], gain: f32) -> usize {
+    let mut clipped = 0;
+    for sample in samples {
+        let scaled = *sample * gain;
+        if scaled.abs() >= 1.0 {
+            clipped += 1;
+        }
+        *sample = scaled.clamp(-1.0, 1.0);
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trim_scales_by_linear_factor() {
+        assert_eq!(trim_gain(0.0), 1.0);
+        assert!((trim_gain(6.0) - 1.9953).abs() < 1e-3);
+        assert!((trim_gain(-20.0) - 0.1).abs() < 1e-6);
+
+        let mut samples = [0.01, -0.02, 0.04];
+        assert_eq!(apply_trim(&mut samples, trim_gain(20.0)), 0);
+        for (got, want) in samples.iter().zip([0.1, -0.2, 0.4]) {
+            assert!((got - want).abs() < 1e-6, "{got} vs {want}");
+        }
+    }
+
+    #[test]
+    fn trim_clamps_to_full_scale() {
+        let mut samples = [0.6, -0.7, 0.1];
+        assert_eq!(apply_trim(&mut samples, 2.0), 2);
+        assert_eq!(samples, [1.0, -1.0, 0.2]);
+    }
+}
@@ -2,13 +2,20 @@
 //!
 //! Pure functions for signal processing. No I/O dependencies.
 
+pub mod biquad;
+pub mod dc_blocker;
+pub mod decimator;
 pub mod fft;
 pub mod filter;
+pub mod input_trim;
 pub mod nco;
 pub mod costas_loop;
 pub mod clock_recovery;
 pub mod agc;
 pub mod raised_cosine;
+pub mod sample_ring;
+pub mod util;
+pub mod waterfall_history;
 
 // Re-export commonly used items
 pub use fft::FftProcessor;
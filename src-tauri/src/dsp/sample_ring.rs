@@ -0,0 +1,223 @@
+//! Lock-free overwriting sample ring
+//!
+//! Carries audio from the real-time capture callback to the DSP loop. It is
+//! single-producer/single-consumer like any SPSC ring, except that a full
+//! ring never refuses the producer: new samples overwrite the oldest, so
+//! the most recent audio always wins and the callback never waits.
+//!
+//! The producer claims the slots it is about to write (`claimed`), writes
+//! them, then publishes them (`written`). The consumer reads what has been
+//! published and afterwards checks `claimed`: any sample the producer may
+//! have been rewriting meanwhile is discarded rather than returned torn.
+//! Samples lost either way are counted as overwritten (see
+//! `SampleConsumer::take_overwritten`).
+
+use std::sync::atomic::{fence, AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+
+struct Shared {
+    /// `f32` bit patterns; a power-of-two count so indices wrap with `mask`
+    slots: Box<[AtomicU32]>,
+    mask: u64,
+    /// Samples the producer has started writing (ever)
+    claimed: AtomicU64,
+    /// Samples the producer has finished writing (ever)
+    written: AtomicU64,
+}
+
+impl Shared {
+    fn capacity(&self) -> u64 {
+        self.mask + 1
+    }
+}
+
+/// Write end, owned by the capture callback
+pub struct SampleProducer {
+    shared: Arc<Shared>,
+    written: u64,
+}
+
+/// Read end, owned by the DSP loop
+pub struct SampleConsumer {
+    shared: Arc<Shared>,
+    read: u64,
+    overwritten: usize,
+}
+
+/// Create a ring holding at least `capacity` samples (rounded up to a power of two).
+pub fn sample_ring(capacity: usize) -> (SampleProducer, SampleConsumer) {
+    let capacity = capacity.max(1).next_power_of_two();
+    let shared = Arc::new(Shared {
+        slots: (0..capacity).map(|_| AtomicU32::new(0)).collect(),
+        mask: capacity as u64 - 1,
+        claimed: AtomicU64::new(0),
+        written: AtomicU64::new(0),
+    });
+    let producer = SampleProducer { shared: shared.clone(), written: 0 };
+    (producer, SampleConsumer { shared, read: 0, overwritten: 0 })
+}
+
+impl SampleProducer {
+    /// Append `samples`, overwriting the oldest if the ring is full. Never blocks.
+    pub fn push_slice_overwrite(&mut self, samples: &[f32]) {
+        self.push_iter_overwrite(samples.iter().copied());
+    }
+
+    /// Append every sample of `samples`, overwriting the oldest if the ring
+    /// is full. Never blocks.
+    pub fn push_iter_overwrite<I: ExactSizeIterator<Item = f32>>(&mut self, samples: I) {
+        let end = self.written + samples.len() as u64;
+        self.shared.claimed.store(end, Ordering::Relaxed);
+        // Claim before touching any slot, so a consumer that sees a new
+        // sample also sees the claim covering it
+        fence(Ordering::Release);
+        for (i, sample) in samples.enumerate() {
+            let index = (self.written + i as u64) & self.shared.mask;
+            self.shared.slots[index as usize].store(sample.to_bits(), Ordering::Relaxed);
+        }
+        self.shared.written.store(end, Ordering::Release);
+        self.written = end;
+    }
+}
+
+impl SampleConsumer {
+    /// Number of slots in the ring
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity() as usize
+    }
+
+    /// Samples waiting to be read (at most `capacity`)
+    pub fn occupied_len(&self) -> usize {
+        let written = self.shared.written.load(Ordering::Acquire);
+        (written - self.read).min(self.shared.capacity()) as usize
+    }
+
+    /// Discard up to `count` of the oldest waiting samples; returns how many were.
+    pub fn skip(&mut self, count: usize) -> usize {
+        self.catch_up(self.shared.written.load(Ordering::Acquire));
+        let skipped = count.min(self.occupied_len());
+        self.read += skipped as u64;
+        skipped
+    }
+
+    /// Move up to `max` of the oldest waiting samples into `out`; returns how many were.
+    pub fn pop_into(&mut self, max: usize, out: &mut Vec<f32>) -> usize {
+        let written = self.shared.written.load(Ordering::Acquire);
+        self.catch_up(written);
+        let end = written.min(self.read.saturating_add(max as u64));
+        let start = out.len();
+        out.extend((self.read..end).map(|i| {
+            f32::from_bits(self.shared.slots[(i & self.shared.mask) as usize].load(Ordering::Relaxed))
+        }));
+
+        // Anything older than a capacity behind the latest claim may have
+        // been rewritten while we copied it
+        fence(Ordering::Acquire);
+        let claimed = self.shared.claimed.load(Ordering::Relaxed);
+        let valid_from = claimed.saturating_sub(self.shared.capacity());
+        if valid_from > self.read {
+            let torn = (valid_from.min(end) - self.read) as usize;
+            out.drain(start..start + torn);
+            self.overwritten += torn;
+        }
+        self.read = end;
+        out.len() - start
+    }
+
+    /// Samples overwritten before they could be read, since the last call
+    pub fn take_overwritten(&mut self) -> usize {
+        std::mem::take(&mut self.overwritten)
+    }
+
+    /// Skip past samples the producer has already overwritten
+    fn catch_up(&mut self, written: u64) {
+        let oldest = written.saturating_sub(self.shared.capacity());
+        if self.read < oldest {
+            self.overwritten += (oldest - self.read) as usize;
+            self.read = oldest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn capacity_rounds_up_to_power_of_two() {
+        assert_eq!(sample_ring(8192).1.capacity(), 8192);
+        assert_eq!(sample_ring(1000).1.capacity(), 1024);
+    }
+
+    #[test]
+    fn full_ring_keeps_the_newest_samples() {
+        let (mut producer, mut consumer) = sample_ring(8);
+        let samples: Vec<f32> = (0..12).map(|i| i as f32).collect();
+        producer.push_slice_overwrite(&samples[..6]);
+        producer.push_slice_overwrite(&samples[6..]);
+        assert_eq!(consumer.occupied_len(), 8);
+
+        let mut out = Vec::new();
+        assert_eq!(consumer.pop_into(100, &mut out), 8);
+        assert_eq!(out, &samples[4..]);
+        assert_eq!(consumer.take_overwritten(), 4);
+        assert_eq!(consumer.take_overwritten(), 0);
+    }
+
+    #[test]
+    fn skip_discards_oldest() {
+        let (mut producer, mut consumer) = sample_ring(16);
+        producer.push_slice_overwrite(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(consumer.skip(3), 3);
+        assert_eq!(consumer.skip(3), 1);
+        assert_eq!(consumer.occupied_len(), 0);
+    }
+
+    #[test]
+    fn newest_samples_survive_a_busy_consumer() {
+        // The consumer stalls mid-stream while the capture side keeps writing
+        // well past capacity; it must come back to the newest audio, in order.
+        let (mut producer, mut consumer) = sample_ring(1024);
+        let total = 200_000u32;
+        let writer = thread::spawn(move || {
+            let samples: Vec<f32> = (0..total).map(|i| i as f32).collect();
+            for chunk in samples.chunks(480) {
+                producer.push_slice_overwrite(chunk);
+            }
+        });
+
+        let mut out = Vec::new();
+        consumer.pop_into(100, &mut out);
+        writer.join().unwrap();
+        while consumer.pop_into(256, &mut out) > 0 {}
+
+        assert!(out.windows(2).all(|w| w[0] < w[1]), "samples out of order or torn");
+        let tail: Vec<f32> = (total - 1024..total).map(|i| i as f32).collect();
+        assert_eq!(&out[out.len() - 1024..], &tail[..]);
+        assert_eq!(out.len() + consumer.take_overwritten(), total as usize);
+    }
+
+    #[test]
+    fn concurrent_reads_never_return_torn_samples() {
+        let (mut producer, mut consumer) = sample_ring(256);
+        let total = 500_000u32;
+        let writer = thread::spawn(move || {
+            let samples: Vec<f32> = (0..total).map(|i| i as f32).collect();
+            for chunk in samples.chunks(97) {
+                producer.push_slice_overwrite(chunk);
+            }
+        });
+
+        let mut out = Vec::new();
+        while !writer.is_finished() {
+            consumer.pop_into(64, &mut out);
+        }
+        writer.join().unwrap();
+        while consumer.pop_into(64, &mut out) > 0 {}
+
+        assert!(out.windows(2).all(|w| w[0] < w[1]), "samples out of order or torn");
+        assert_eq!(out.last(), Some(&((total - 1) as f32)));
+        assert_eq!(out.len() + consumer.take_overwritten(), total as usize);
+    }
+}
@@ -0,0 +1,52 @@
+//! Shared numeric helpers for the DSP code
+//!
+//! dB values are power ratios (`10·log10`) unless the helper says amplitude
+//! (`20·log10`). Every spectrum, level and (future) colormap conversion goes
+//! through these so the floors agree.
+
+/// Power ratio → dB, never below `floor_db`. Zero, negative and NaN input
+/// all map to the floor instead of `-inf`/NaN.
+pub fn linear_to_db(x: f32, floor_db: f32) -> f32 {
+    (10.0 * x.log10()).max(floor_db)
+}
+
+/// Amplitude ratio → dB, never below `floor_db`. Same floor handling as
+/// `linear_to_db`.
+pub fn amplitude_to_db(x: f32, floor_db: f32) -> f32 {
+    (20.0 * x.log10()).max(floor_db)
+}
+
+/// dB → power ratio
+pub fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for db in [-90.0, -40.0, -3.0, 0.0, 6.0, 20.0] {
+            let back = linear_to_db(db_to_linear(db), -100.0);
+            assert!((back - db).abs() < 1e-4, "{db} dB came back as {back}");
+        }
+        assert_eq!(linear_to_db(100.0, -100.0), 20.0);
+        assert_eq!(db_to_linear(-10.0), 0.1);
+    }
+
+    #[test]
+    fn floor_prevents_negative_infinity() {
+        assert_eq!(linear_to_db(0.0, -100.0), -100.0);
+        assert_eq!(linear_to_db(-1.0, -100.0), -100.0);
+        assert_eq!(linear_to_db(f32::NAN, -60.0), -60.0);
+        assert_eq!(linear_to_db(1e-20, -100.0), -100.0);
+    }
+
+    #[test]
+    fn amplitude_db_is_twice_power_db() {
+        assert_eq!(amplitude_to_db(100.0, -100.0), 40.0);
+        assert_eq!(amplitude_to_db(0.01, -100.0), -40.0);
+        assert_eq!(amplitude_to_db(0.0, -40.0), -40.0);
+    }
+}
@@ -0,0 +1,121 @@
+//! Rolling waterfall history
+//!
+//! The webview's waterfall is lost on reload, so the backend keeps the most
+//! recent spectrum rows for the UI to repaint from. Rows are max-pooled down
+//! to a fixed width (a narrow PSK-31 trace must survive the reduction) and
+//! the ring is capped in rows, so memory is bounded at
+//! `max_rows × max_columns` floats regardless of FFT size.
+
+use std::collections::VecDeque;
+
+/// Rows kept by the app: ~22 s at 4096-point FFTs with 50% overlap at 48 kHz
+pub const WATERFALL_HISTORY_ROWS: usize = 512;
+
+/// Columns per stored row
+pub const WATERFALL_HISTORY_COLUMNS: usize = 512;
+
+/// Bounded ring of downsampled spectrum rows, oldest first
+#[derive(Debug, Clone)]
+pub struct WaterfallHistory {
+    rows: VecDeque<Vec<f32>>,
+    max_rows: usize,
+    max_columns: usize,
+}
+
+impl WaterfallHistory {
+    pub fn new(max_rows: usize, max_columns: usize) -> Self {
+        Self {
+            rows: VecDeque::with_capacity(max_rows),
+            max_rows,
+            max_columns: max_columns.max(1),
+        }
+    }
+
+    /// Append a spectrum row (dB magnitudes), dropping the oldest when full.
+    pub fn push(&mut self, magnitudes: &[f32]) {
+        if self.max_rows == 0 {
+            return;
+        }
+        if self.rows.len() == self.max_rows {
+            self.rows.pop_front();
+        }
+        self.rows.push_back(downsample_max(magnitudes, self.max_columns));
+    }
+
+    /// All stored rows, oldest first
+    pub fn rows(&self) -> Vec<Vec<f32>> {
+        self.rows.iter().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+}
+
+impl Default for WaterfallHistory {
+    fn default() -> Self {
+        Self::new(WATERFALL_HISTORY_ROWS, WATERFALL_HISTORY_COLUMNS)
+    }
+}
+
+/// Reduce `row` to at most `max_columns` values, each the maximum of the bins
+/// it covers. Rows already narrow enough are returned unchanged.
+pub fn downsample_max(row: &[f32], max_columns: usize) -> Vec<f32> {
+    if row.len() <= max_columns {
+        return row.to_vec();
+    }
+    let factor = row.len().div_ceil(max_columns);
+    row.chunks(factor)
+        .map(|chunk| chunk.iter().copied().fold(f32::NEG_INFINITY, f32::max))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_at_most_max_rows_and_drops_oldest() {
+        let mut history = WaterfallHistory::new(3, 8);
+        for i in 0..5 {
+            history.push(&[i as f32; 4]);
+        }
+        assert_eq!(history.len(), 3);
+        let firsts: Vec<f32> = history.rows().iter().map(|r| r[0]).collect();
+        assert_eq!(firsts, vec![2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn rows_are_downsampled_to_max_columns() {
+        let mut history = WaterfallHistory::new(2, 512);
+        let mut row = vec![-100.0; 2049];
+        row[1000] = -20.0; // single-bin carrier
+        history.push(&row);
+
+        let stored = &history.rows()[0];
+        assert!(stored.len() <= 512, "{} columns", stored.len());
+        assert_eq!(stored.iter().copied().fold(f32::NEG_INFINITY, f32::max), -20.0);
+    }
+
+    #[test]
+    fn downsample_leaves_narrow_rows_alone() {
+        assert_eq!(downsample_max(&[1.0, 2.0, 3.0], 4), vec![1.0, 2.0, 3.0]);
+        assert_eq!(downsample_max(&[1.0, 5.0, 3.0, 2.0], 2), vec![5.0, 3.0]);
+    }
+
+    #[test]
+    fn clear_empties_history() {
+        let mut history = WaterfallHistory::new(4, 4);
+        history.push(&[0.0; 4]);
+        history.clear();
+        assert!(history.is_empty());
+    }
+}
@@ -0,0 +1,426 @@
+//! Backend → frontend event schema
+//!
+//! Every event the backend emits is named here together with its payload
+//! struct, so the wire format lives in one place. Payload fields serialize
+//! as camelCase, matching the domain types returned by commands.
+//!
+//! `EVENT_SCHEMA_VERSION` is bumped whenever a payload changes shape
+//! (field renamed, removed or retyped); adding an event does not bump it.
+//! The frontend can read it once via the `get_event_schema_version` command.
+
+use serde::Serialize;
+
+/// Version of the event payload schema below.
+/// 2: `fft-data` in the u8 format sends `quantized`/`minDb`/`maxDb` in place
+/// of `magnitudes`.
+pub const EVENT_SCHEMA_VERSION: u32 = 2;
+
+// Event names
+pub const AUDIO_STATUS: &str = "audio-status";
+/// Input samples lost because the DSP loop fell behind
+pub const AUDIO_OVERRUN: &str = "audio-overrun";
+pub const FFT_DATA: &str = "fft-data";
+pub const RX_TEXT: &str = "rx-text";
+/// Unfiltered decoder output (control characters included), for debugging
+pub const RX_TEXT_RAW: &str = "rx-text-raw";
+/// Raw decided bits ahead of Varicode, while `rx_bits_debug` is on
+pub const RX_BITS: &str = "rx-bits";
+pub const TUNING_ERROR: &str = "tuning-error";
+/// Poor lock with a strong signal just off the carrier (see `modem::retune`)
+pub const SUGGEST_RETUNE: &str = "suggest-retune";
+pub const SYMBOL_TIMING: &str = "symbol-timing";
+pub const SIGNAL_LEVEL: &str = "signal-level";
+pub const RX_RATE: &str = "rx-rate";
+pub const RX_CARRIER_CHANGED: &str = "rx-carrier-changed";
+/// Carrier the Costas loop is tracking, for the waterfall marker
+pub const AFC_FREQUENCY: &str = "afc-frequency";
+/// RX transcript, decoder and signal indicators cleared; no payload
+pub const RX_CLEARED: &str = "rx-cleared";
+pub const TX_STATUS: &str = "tx-status";
+pub const SERIAL_DISCONNECTED: &str = "serial-disconnected";
+pub const FREQUENCY_CORRECTED: &str = "frequency-corrected";
+pub const RECOVERED_FROM_KEYED: &str = "recovered-from-keyed";
+pub const CONFIGURATION_APPLIED: &str = "configuration-applied";
+pub const SCAN_PROGRESS: &str = "scan-progress";
+pub const SCAN_HIT: &str = "scan-hit";
+/// Front-panel changes reported by the radio (see `commands::cat_listener`)
+pub const RADIO_FREQUENCY_CHANGED: &str = "radio-frequency-changed";
+pub const RADIO_MODE_CHANGED: &str = "radio-mode-changed";
+/// Payload is `domain::QsoRecord`
+pub const QSO_LOGGED: &str = "qso-logged";
+pub const MENU_EVENT: &str = "menu-event";
+
+/// `fft-data` — waterfall magnitudes in dB, one per bin, starting at
+/// `start_hz` and spaced `bin_hz` apart (a sub-band when the waterfall is zoomed)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FftPayload {
+    #[serde(flatten)]
+    pub magnitudes: FftMagnitudes,
+    pub start_hz: f64,
+    pub bin_hz: f64,
+}
+
+/// Bin values of an `fft-data` frame, per the configured `FftFormat`
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged, rename_all_fields = "camelCase")]
+pub enum FftMagnitudes {
+    /// `magnitudes`: dB per bin
+    Float { magnitudes: Vec<f32> },
+    /// `quantized`: 0–255 per bin, linear across `min_db..=max_db`
+    U8 { quantized: Vec<u8>, min_db: f32, max_db: f32 },
+}
+
+/// `audio-status` — "running", "stopped" or "error: …"
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioStatusPayload {
+    pub status: String,
+}
+
+/// `audio-overrun` — input samples overwritten or dropped since the last
+/// event (sent at most every ~500 ms, only when something was lost)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioOverrunPayload {
+    pub dropped_samples: usize,
+}
+
+/// `rx-text` / `rx-text-raw` — decoded characters from an RX decoder
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RxTextPayload {
+    pub text: String,
+    /// Carrier of the decoder that produced `text`: the main RX carrier, or
+    /// a channel opened with `start_rx_channel`
+    pub channel_freq: f64,
+}
+
+/// `rx-bits` — decided bits in order, as a string of `'0'`/`'1'`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RxBitsPayload {
+    pub bits: String,
+}
+
+/// `tuning-error` — signed Hz from the RX carrier to the signal's spectral
+/// centroid (positive = signal is above the carrier)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TuningErrorPayload {
+    pub offset_hz: f64,
+}
+
+/// `suggest-retune` — carrier frequency (Hz) of a strong signal near the
+/// tuned carrier that the decoder isn't locked to
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestRetunePayload {
+    pub freq_hz: f64,
+}
+
+/// `symbol-timing` — the decoder's adapted samples-per-symbol and its drift
+/// from nominal (soundcard clock error)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolTimingPayload {
+    pub samples_per_symbol: f64,
+    pub drift_ppm: f64,
+}
+
+/// `rx-rate` — effective typing speed of the received text over the last
+/// `RX_RATE_WINDOW_SECS`, decaying to zero through silence
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RxRatePayload {
+    pub chars_per_minute: f64,
+    pub wpm: f64,
+}
+
+/// `signal-level` — normalized AGC-derived signal strength (0.0–1.0)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalLevelPayload {
+    pub level: f32,
+    /// Input hit full scale (after the input trim) since the last event
+    pub clipped: bool,
+    /// Decoder SNR estimate in dB (0 while RX is off)
+    pub snr_db: f32,
+}
+
+/// `rx-carrier-changed` — the backend moved the RX carrier (VFO tracking)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RxCarrierChangedPayload {
+    pub carrier_freq: f64,
+}
+
+/// `afc-frequency` — the decoder's tracked carrier, including the Costas
+/// loop's frequency correction (`carrier_freq` is where it was tuned)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AfcFrequencyPayload {
+    pub frequency_hz: f64,
+}
+
+/// `tx-status` — "transmitting", "tuning", "complete", "aborted" or "error: …",
+/// with playback progress 0.0–1.0. When `start_tx` was asked to record, the
+/// final "complete" or "aborted" carries `record_path`, the WAV capture,
+/// already written.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxStatusPayload {
+    pub status: String,
+    pub progress: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub record_path: Option<String>,
+}
+
+/// `serial-disconnected` — the radio was dropped after a serial I/O error
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerialDisconnectedPayload {
+    pub reason: String,
+    pub port: String,
+}
+
+/// `frequency-corrected` — the radio settled on a different frequency than
+/// requested (rounded to its tuning step); `actual_hz` is what it reads back
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequencyCorrectedPayload {
+    pub requested_hz: f64,
+    pub actual_hz: f64,
+}
+
+/// `recovered-from-keyed` — the radio was transmitting when we connected
+/// (e.g. left keyed by a crashed session) and PTT was released
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveredFromKeyedPayload {
+    pub port: String,
+}
+
+/// `configuration-applied` — a saved profile was pushed to the modem/radio
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigurationAppliedPayload {
+    pub name: String,
+}
+
+/// `scan-progress` — the band scanner moved to `band` (step `step` of `totalSteps`)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanProgressPayload {
+    pub band: String,
+    pub frequency_hz: u64,
+    pub step: usize,
+    pub total_steps: usize,
+}
+
+/// `scan-hit` — a signal was detected on `band`; the scan has stopped there
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanHitPayload {
+    pub band: String,
+    pub frequency_hz: u64,
+    pub level: f32,
+}
+
+/// `radio-frequency-changed` — the radio reported a new dial frequency by
+/// itself (auto-information), e.g. the operator turned the VFO knob
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RadioFrequencyChangedPayload {
+    pub frequency_hz: f64,
+}
+
+/// `radio-mode-changed` — the radio reported a new mode by itself
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RadioModeChangedPayload {
+    pub mode: String,
+}
+
+/// `menu-event` — id of the native menu item that was clicked
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuEvent {
+    pub id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, to_value};
+
+    #[test]
+    fn audio_payloads_shape() {
+        assert_eq!(
+            to_value(FftPayload {
+                magnitudes: FftMagnitudes::Float { magnitudes: vec![-80.0, -20.5] },
+                start_hz: 500.0,
+                bin_hz: 11.5,
+            })
+            .unwrap(),
+            json!({ "magnitudes": [-80.0, -20.5], "startHz": 500.0, "binHz": 11.5 })
+        );
+        assert_eq!(
+            to_value(FftPayload {
+                magnitudes: FftMagnitudes::U8 {
+                    quantized: vec![51, 203],
+                    min_db: -100.0,
+                    max_db: 0.0,
+                },
+                start_hz: 500.0,
+                bin_hz: 11.5,
+            })
+            .unwrap(),
+            json!({
+                "quantized": [51, 203],
+                "minDb": -100.0,
+                "maxDb": 0.0,
+                "startHz": 500.0,
+                "binHz": 11.5,
+            })
+        );
+        assert_eq!(
+            to_value(AudioStatusPayload { status: "running".into() }).unwrap(),
+            json!({ "status": "running" })
+        );
+        assert_eq!(
+            to_value(SignalLevelPayload { level: 0.5, clipped: false, snr_db: 12.5 }).unwrap(),
+            json!({ "level": 0.5, "clipped": false, "snrDb": 12.5 })
+        );
+        assert_eq!(
+            to_value(AudioOverrunPayload { dropped_samples: 480 }).unwrap(),
+            json!({ "droppedSamples": 480 })
+        );
+    }
+
+    #[test]
+    fn rx_payloads_shape() {
+        assert_eq!(
+            to_value(RxTextPayload { text: "CQ".into(), channel_freq: 1500.0 }).unwrap(),
+            json!({ "text": "CQ", "channelFreq": 1500.0 })
+        );
+        assert_eq!(
+            to_value(RxBitsPayload { bits: "110100".into() }).unwrap(),
+            json!({ "bits": "110100" })
+        );
+        assert_eq!(
+            to_value(TuningErrorPayload { offset_hz: -3.5 }).unwrap(),
+            json!({ "offsetHz": -3.5 })
+        );
+        assert_eq!(
+            to_value(SuggestRetunePayload { freq_hz: 1010.5 }).unwrap(),
+            json!({ "freqHz": 1010.5 })
+        );
+        assert_eq!(
+            to_value(RxCarrierChangedPayload { carrier_freq: 1500.0 }).unwrap(),
+            json!({ "carrierFreq": 1500.0 })
+        );
+        assert_eq!(
+            to_value(SymbolTimingPayload { samples_per_symbol: 1536.5, drift_ppm: 325.5 }).unwrap(),
+            json!({ "samplesPerSymbol": 1536.5, "driftPpm": 325.5 })
+        );
+        assert_eq!(
+            to_value(RxRatePayload { chars_per_minute: 100.0, wpm: 20.0 }).unwrap(),
+            json!({ "charsPerMinute": 100.0, "wpm": 20.0 })
+        );
+    }
+
+    #[test]
+    fn tx_and_radio_payloads_shape() {
+        assert_eq!(
+            to_value(TxStatusPayload {
+                status: "complete".into(),
+                progress: 1.0,
+                record_path: None,
+            })
+            .unwrap(),
+            json!({ "status": "complete", "progress": 1.0 })
+        );
+        assert_eq!(
+            to_value(TxStatusPayload {
+                status: "complete".into(),
+                progress: 1.0,
+                record_path: Some("/tmp/tx.wav".into()),
+            })
+            .unwrap(),
+            json!({ "status": "complete", "progress": 1.0, "recordPath": "/tmp/tx.wav" })
+        );
+        assert_eq!(
+            to_value(SerialDisconnectedPayload {
+                reason: "Serial port error: gone".into(),
+                port: "/dev/ttyUSB0".into(),
+            })
+            .unwrap(),
+            json!({ "reason": "Serial port error: gone", "port": "/dev/ttyUSB0" })
+        );
+        assert_eq!(
+            to_value(FrequencyCorrectedPayload {
+                requested_hz: 14_070_001.0,
+                actual_hz: 14_070_000.0,
+            })
+            .unwrap(),
+            json!({ "requestedHz": 14_070_001.0, "actualHz": 14_070_000.0 })
+        );
+        assert_eq!(
+            to_value(RadioFrequencyChangedPayload { frequency_hz: 14_075_000.0 }).unwrap(),
+            json!({ "frequencyHz": 14_075_000.0 })
+        );
+        assert_eq!(
+            to_value(RadioModeChangedPayload { mode: "DATA-USB".into() }).unwrap(),
+            json!({ "mode": "DATA-USB" })
+        );
+        assert_eq!(
+            to_value(RecoveredFromKeyedPayload { port: "COM3".into() }).unwrap(),
+            json!({ "port": "COM3" })
+        );
+        assert_eq!(
+            to_value(ConfigurationAppliedPayload { name: "20m".into() }).unwrap(),
+            json!({ "name": "20m" })
+        );
+        assert_eq!(
+            to_value(ScanProgressPayload {
+                band: "20m".into(),
+                frequency_hz: 14_070_000,
+                step: 2,
+                total_steps: 5,
+            })
+            .unwrap(),
+            json!({ "band": "20m", "frequencyHz": 14_070_000, "step": 2, "totalSteps": 5 })
+        );
+        assert_eq!(
+            to_value(ScanHitPayload { band: "40m".into(), frequency_hz: 7_035_000, level: 0.75 })
+                .unwrap(),
+            json!({ "band": "40m", "frequencyHz": 7_035_000, "level": 0.75 })
+        );
+        assert_eq!(
+            to_value(MenuEvent { id: "settings".into() }).unwrap(),
+            json!({ "id": "settings" })
+        );
+    }
+
+    #[test]
+    fn qso_logged_payload_shape() {
+        let record = crate::domain::QsoRecord {
+            callsign: "W1AW".into(),
+            rst_sent: "599".into(),
+            frequency_hz: Some(14_070_000.0),
+            mode: Some("DATA-USB".into()),
+            timestamp_utc: 1_700_000_000,
+        };
+        assert_eq!(
+            to_value(record).unwrap(),
+            json!({
+                "callsign": "W1AW",
+                "rstSent": "599",
+                "frequencyHz": 14_070_000.0,
+                "mode": "DATA-USB",
+                "timestampUtc": 1_700_000_000u64,
+            })
+        );
+    }
+}
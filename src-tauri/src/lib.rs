@@ -10,6 +10,7 @@
 //! - `modem/` - PSK-31 protocol logic (varicode, encoder, decoder)
 //! - `adapters/` - Implementations of ports (cpal audio, serialport, FT-991A)
 //! - `commands/` - Tauri command handlers (driving adapters)
+//! - `events` - Names and payloads of events emitted to the frontend
 //! - `state/` - Application state management
 
 // Core domain (pure, no I/O)
@@ -26,6 +27,7 @@ pub mod adapters;
 
 // Tauri integration
 pub mod commands;
+pub mod events;
 pub mod menu;
 pub mod state;
 
@@ -40,44 +42,125 @@ pub fn run() {
         .manage(AppState::new())
         .setup(|app| {
             menu::setup_menu(app)?;
+            if let Err(e) = commands::config::restore_contest_serial(app.handle()) {
+                log::warn!("Could not restore contest serial: {e}");
+            }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             // App commands
             commands::app::exit_app,
+            commands::app::format_frequency,
+            commands::app::get_event_schema_version,
+            commands::app::get_dsp_info,
+            commands::app::run_self_test,
             // Audio commands
             commands::audio::list_audio_devices,
+            commands::audio::list_audio_devices_grouped,
+            commands::audio::list_audio_hosts,
+            commands::audio::set_audio_host,
             commands::audio::start_audio_stream,
             commands::audio::stop_audio_stream,
             // RX commands
             commands::audio::start_rx,
             commands::audio::stop_rx,
             commands::audio::set_carrier_frequency,
+            commands::audio::snap_carrier_to_bin,
+            commands::audio::set_rx_notch,
+            commands::audio::set_rx_bandpass,
+            commands::audio::set_rx_squelch,
+            commands::audio::start_rx_channel,
+            commands::audio::stop_rx_channel,
+            commands::audio::export_spectrum_csv,
+            commands::audio::set_waterfall_span,
+            commands::audio::get_waterfall_history,
+            commands::audio::reset_decoder,
+            commands::audio::get_rx_transcript,
+            commands::audio::clear_rx_session,
+            commands::audio::reset_dsp,
+            commands::audio::set_rx_mode,
+            commands::audio::inject_test_signal,
+            commands::audio::capture_decode_snapshot,
             // Serial commands
             commands::serial::list_serial_ports,
             commands::serial::connect_serial,
+            commands::serial::connect_serial_advanced,
             commands::serial::disconnect_serial,
             // Radio commands
             commands::radio::ptt_on,
             commands::radio::ptt_off,
             commands::radio::get_frequency,
             commands::radio::set_frequency,
+            commands::radio::get_frequency_b,
+            commands::radio::set_frequency_b,
+            commands::radio::swap_vfo,
+            commands::radio::set_split,
+            commands::radio::step_frequency,
+            commands::radio::qsy,
+            commands::radio::apply_band_plan,
             commands::radio::get_mode,
             commands::radio::set_mode,
             commands::radio::get_signal_strength,
+            commands::radio::read_s_meter,
+            commands::radio::read_swr,
             commands::radio::get_radio_state,
+            commands::radio::get_radio_filter_state,
+            commands::radio::get_active_vfo,
+            commands::radio::get_memory_channel_frequency,
+            commands::radio::set_rit_enabled,
+            commands::radio::set_xit_enabled,
+            commands::radio::set_rit_offset,
+            commands::radio::clear_rit,
             commands::radio::get_tx_power,
+            commands::cat_listener::set_cat_listener,
+            // rigctld server commands
+            commands::rigctld::start_rigctld_server,
+            commands::rigctld::stop_rigctld_server,
+            // Band scan commands
+            commands::scan::start_band_scan,
+            commands::scan::stop_band_scan,
             // TX commands
             commands::tx::start_tx,
+            commands::tx::encode_to_wav,
+            commands::tx::is_transmittable,
             commands::tx::stop_tx,
             commands::tx::start_tune,
             commands::tx::stop_tune,
+            commands::tx::get_tx_duty_cycle,
             // Configuration commands
             commands::config::save_configuration,
             commands::config::load_configuration,
             commands::config::list_configurations,
             commands::config::delete_configuration,
             commands::config::set_tx_power_config,
+            commands::config::apply_configuration,
+            commands::config::set_auto_log,
+            commands::config::set_track_vfo,
+            commands::config::set_ptt_delays,
+            commands::config::set_tx_duty_cycle_limit,
+            commands::config::set_rx_filter_control,
+            commands::config::set_rx_mute_during_tx,
+            commands::config::set_rx_dc_blocker,
+            commands::config::set_rx_acquire,
+            commands::config::set_input_trim,
+            commands::config::set_rx_bits_debug,
+            commands::config::set_rx_drain_budget,
+            commands::config::set_signal_detector,
+            commands::config::set_rx_record_mode,
+            commands::config::set_idle_pattern,
+            commands::config::set_modem_baud,
+            commands::config::set_fft_format,
+            commands::config::get_qso_log,
+            commands::config::get_serial,
+            commands::config::set_serial,
+            commands::config::increment_serial,
+            // Memory channel commands
+            commands::memory::save_memory,
+            commands::memory::recall_memory,
+            commands::memory::list_memories,
+            // Operator profile commands
+            commands::operator::get_operator_profile,
+            commands::operator::set_operator_profile,
             // Status command
             commands::status::get_connection_status,
         ])
@@ -8,11 +8,7 @@ use tauri::{
     App, Emitter,
 };
 
-/// Menu event payload sent to frontend
-#[derive(Clone, serde::Serialize)]
-pub struct MenuEvent {
-    pub id: String,
-}
+use crate::events::{self, MenuEvent};
 
 /// Set up the application menu bar
 pub fn setup_menu(app: &App) -> Result<(), Box<dyn std::error::Error>> {
@@ -104,7 +100,7 @@ pub fn setup_menu(app: &App) -> Result<(), Box<dyn std::error::Error>> {
         println!("Menu event: {}", id);
 
         // Emit event to frontend
-        let _ = app_handle.emit("menu-event", MenuEvent { id: id.clone() });
+        let _ = app_handle.emit(events::MENU_EVENT, MenuEvent { id: id.clone() });
 
         // Handle quit specially (doesn't need frontend)
         if id == "quit" {
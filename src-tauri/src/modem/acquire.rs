@@ -0,0 +1,126 @@
+//! Carrier acquisition on RX start
+//!
+//! With acquire enabled, starting RX doesn't leave the decoder sitting on
+//! whatever carrier it last had. `CarrierAcquirer` looks over 500–2500 Hz in
+//! each FFT frame for the strongest signal standing clear of the noise,
+//! waits for it to hold still for a few frames, and hands back its centre
+//! for the decoder to settle on. If nothing turns up before the timeout the
+//! decoder stays where it was.
+
+use crate::dsp::fft::{peak_near_hz, tuning_error_hz};
+use crate::modem::retune::MIN_PEAK_PROMINENCE_DB;
+
+/// Lowest carrier acquisition will settle on
+pub const ACQUIRE_LOW_HZ: f64 = 500.0;
+
+/// Highest carrier acquisition will settle on
+pub const ACQUIRE_HIGH_HZ: f64 = 2500.0;
+
+/// Consecutive frames the same signal must be found in before settling
+const CONFIRM_FRAMES: usize = 3;
+
+/// How far the signal may wander between frames and still count as the same
+const SAME_SIGNAL_HZ: f64 = 3.0;
+
+/// Outcome of one acquisition frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AcquireStep {
+    /// Still looking
+    Searching,
+    /// Settle the decoder on this carrier
+    Found(f64),
+    /// Timed out without a steady signal
+    GaveUp,
+}
+
+/// Search state for one acquisition
+pub struct CarrierAcquirer {
+    frames_left: usize,
+    candidate: Option<f64>,
+    streak: usize,
+}
+
+impl CarrierAcquirer {
+    /// - `max_frames`: FFT frames to search before giving up
+    pub fn new(max_frames: usize) -> Self {
+        Self { frames_left: max_frames, candidate: None, streak: 0 }
+    }
+
+    /// Feed one FFT frame (`magnitudes_db` as from `FftProcessor::compute`).
+    pub fn update(&mut self, magnitudes_db: &[f32], sample_rate: f64) -> AcquireStep {
+        if self.frames_left == 0 {
+            return AcquireStep::GaveUp;
+        }
+        self.frames_left -= 1;
+        match strongest_signal(magnitudes_db, sample_rate) {
+            Some(freq) if self.candidate.is_some_and(|c| (c - freq).abs() <= SAME_SIGNAL_HZ) => {
+                self.streak += 1;
+            }
+            found => {
+                self.candidate = found;
+                self.streak = usize::from(found.is_some());
+            }
+        }
+        match self.candidate {
+            Some(freq) if self.streak >= CONFIRM_FRAMES => AcquireStep::Found(freq),
+            _ if self.frames_left == 0 => AcquireStep::GaveUp,
+            _ => AcquireStep::Searching,
+        }
+    }
+}
+
+/// Centre of the strongest signal in the acquisition range, if one stands
+/// at least `MIN_PEAK_PROMINENCE_DB` over the spectrum's median.
+pub fn strongest_signal(magnitudes_db: &[f32], sample_rate: f64) -> Option<f64> {
+    let centre = (ACQUIRE_LOW_HZ + ACQUIRE_HIGH_HZ) / 2.0;
+    let half_width = (ACQUIRE_HIGH_HZ - ACQUIRE_LOW_HZ) / 2.0;
+    let (peak_hz, prominence) = peak_near_hz(magnitudes_db, centre, half_width, sample_rate)?;
+    if prominence < MIN_PEAK_PROMINENCE_DB {
+        return None;
+    }
+    // Centroid rather than peak bin, as for retune suggestions: a PSK-31
+    // signal's sidebands can outshine its centre
+    let freq = peak_hz + tuning_error_hz(magnitudes_db, peak_hz, sample_rate)?;
+    Some(freq.clamp(ACQUIRE_LOW_HZ, ACQUIRE_HIGH_HZ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::fft::FftProcessor;
+    use crate::modem::decoder::Psk31Decoder;
+    use crate::modem::encoder::Psk31Encoder;
+
+    const RATE: u32 = 48000;
+
+    /// Successive 50%-overlap FFT frames of a PSK-31 transmission on `freq`
+    fn frames(freq: f64) -> Vec<Vec<f32>> {
+        let samples = Psk31Encoder::new(RATE, freq).encode("CQ CQ DE W1AW W1AW K");
+        let mut fft = FftProcessor::new(4096);
+        samples.windows(4096).step_by(2048).map(|w| fft.compute(w)).collect()
+    }
+
+    #[test]
+    fn acquires_signal_at_1800_hz_from_1000_hz_start() {
+        let mut decoder = Psk31Decoder::new(1000.0, RATE);
+        let mut acquirer = CarrierAcquirer::new(50);
+        let step = frames(1800.0)
+            .iter()
+            .map(|m| acquirer.update(m, f64::from(RATE)))
+            .find(|step| *step != AcquireStep::Searching);
+        let Some(AcquireStep::Found(freq)) = step else {
+            panic!("no acquisition: {step:?}");
+        };
+        decoder.set_carrier_freq(freq);
+        assert!((decoder.carrier_freq() - 1800.0).abs() < 3.0, "settled on {freq}");
+    }
+
+    #[test]
+    fn gives_up_on_an_empty_band() {
+        let silence = vec![-120.0f32; 2048];
+        let mut acquirer = CarrierAcquirer::new(5);
+        let steps: Vec<_> = (0..6).map(|_| acquirer.update(&silence, 48000.0)).collect();
+        assert_eq!(steps[..4], [AcquireStep::Searching; 4]);
+        assert_eq!(steps[4..], [AcquireStep::GaveUp; 2]);
+    }
+}
@@ -0,0 +1,353 @@
+//! Squelch-gated auto-recording trigger
+//!
+//! For unattended monitoring we only want to record while a signal is
+//! present. The trigger watches a lock-quality value (0.0–1.0) and decides
+//! when a recording should start and stop:
+//!
+//! ```text
+//!   Idle ──quality ≥ on for min dwell──▶ Recording ──quality < off──▶ Trailing
+//!    ▲                                      ▲                           │
+//!    │                                      └──────quality ≥ off────────┤
+//!    └──────────────trailing silence elapsed (emit Stop)────────────────┘
+//! ```
+//!
+//! A signal has to reach the *on* threshold to start, but only has to stay
+//! above the lower *off* threshold to keep going (hysteresis), and must stay
+//! above *on* for the minimum dwell before `Start` fires. A fading signal
+//! hovering around one level then doesn't chatter start/stop. `new` uses a
+//! single threshold and no dwell.
+//!
+//! Durations are in whatever unit the caller counts `elapsed` in (samples
+//! for the audio thread, milliseconds for the band scanner).
+//!
+//! The trigger is pure state. `AutoRecorder` pairs it with the audio of the
+//! recording in progress: the audio thread feeds it each batch of input
+//! with the decoder's lock quality while `rx_record_mode` is auto, and
+//! writes each recording it hands back to its own timestamped WAV.
+
+/// Longest single auto-recording in seconds; a transmission that runs
+/// longer (or a stuck carrier) is split across files rather than buffered
+/// without bound
+pub const MAX_RECORDING_SECS: u64 = 600;
+
+/// Edge emitted by [`AutoRecordTrigger::update`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerEvent {
+    /// Signal acquired — open a new recording
+    Start,
+    /// Trailing silence elapsed — close the current recording
+    Stop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TriggerState {
+    Idle,
+    /// Above the on threshold; counts how long so far, for the minimum dwell
+    Arming(usize),
+    Recording,
+    /// Signal dropped; counts samples of silence so far
+    Trailing(usize),
+}
+
+/// Start/stop state machine for auto-recording
+pub struct AutoRecordTrigger {
+    on_threshold: f32,
+    off_threshold: f32,
+    min_dwell_samples: usize,
+    trailing_silence_samples: usize,
+    state: TriggerState,
+}
+
+impl AutoRecordTrigger {
+    /// - `threshold`: lock quality at or above which a signal counts as present
+    /// - `trailing_silence_samples`: how long the signal must stay absent before stopping
+    pub fn new(threshold: f32, trailing_silence_samples: usize) -> Self {
+        Self {
+            on_threshold: threshold,
+            off_threshold: threshold,
+            min_dwell_samples: 0,
+            trailing_silence_samples,
+            state: TriggerState::Idle,
+        }
+    }
+
+    /// Start at `on` but only count the signal as gone below `off` (≤ `on`).
+    pub fn with_hysteresis(mut self, on: f32, off: f32) -> Self {
+        self.on_threshold = on;
+        self.off_threshold = off.min(on);
+        self
+    }
+
+    /// Require the signal to stay at or above the on threshold this long
+    /// before starting.
+    pub fn with_min_dwell(mut self, samples: usize) -> Self {
+        self.min_dwell_samples = samples;
+        self
+    }
+
+    /// True while a recording should be open (including the trailing window)
+    pub fn is_recording(&self) -> bool {
+        matches!(self.state, TriggerState::Recording | TriggerState::Trailing(_))
+    }
+
+    /// Feed the lock quality measured over the last `elapsed_samples` samples.
+    /// Returns an event on a start/stop edge.
+    pub fn update(&mut self, lock_quality: f32, elapsed_samples: usize) -> Option<TriggerEvent> {
+        let threshold = if self.is_recording() { self.off_threshold } else { self.on_threshold };
+        let present = lock_quality >= threshold;
+        match (self.state, present) {
+            (TriggerState::Idle, true) => self.arm(elapsed_samples),
+            (TriggerState::Arming(held), true) => self.arm(held + elapsed_samples),
+            (TriggerState::Idle, false) | (TriggerState::Arming(_), false) => {
+                self.state = TriggerState::Idle;
+                None
+            }
+            (TriggerState::Recording, true) | (TriggerState::Trailing(_), true) => {
+                self.state = TriggerState::Recording;
+                None
+            }
+            (TriggerState::Recording, false) => self.trail(0, elapsed_samples),
+            (TriggerState::Trailing(silent), false) => self.trail(silent, elapsed_samples),
+        }
+    }
+
+    fn arm(&mut self, held: usize) -> Option<TriggerEvent> {
+        if held >= self.min_dwell_samples {
+            self.state = TriggerState::Recording;
+            Some(TriggerEvent::Start)
+        } else {
+            self.state = TriggerState::Arming(held);
+            None
+        }
+    }
+
+    fn trail(&mut self, silent: usize, elapsed_samples: usize) -> Option<TriggerEvent> {
+        let silent = silent + elapsed_samples;
+        if silent >= self.trailing_silence_samples {
+            self.state = TriggerState::Idle;
+            Some(TriggerEvent::Stop)
+        } else {
+            self.state = TriggerState::Trailing(silent);
+            None
+        }
+    }
+}
+
+/// One transmission's audio, ready to write
+#[derive(Debug)]
+pub struct FinishedRecording {
+    /// When the recording started, for `recording_file_name`
+    pub started_unix_secs: u64,
+    pub samples: Vec<f32>,
+}
+
+/// Trigger plus the audio buffered since its last `Start`
+pub struct AutoRecorder {
+    trigger: AutoRecordTrigger,
+    max_samples: usize,
+    started_unix_secs: u64,
+    samples: Vec<f32>,
+}
+
+impl AutoRecorder {
+    /// Record on `trigger`'s edges (durations in samples), splitting
+    /// recordings at `max_samples`.
+    pub fn new(trigger: AutoRecordTrigger, max_samples: usize) -> Self {
+        Self { trigger, max_samples, started_unix_secs: 0, samples: Vec::new() }
+    }
+
+    /// True while a recording is being buffered
+    pub fn is_recording(&self) -> bool {
+        self.trigger.is_recording()
+    }
+
+    /// Feed a batch of input and the lock quality measured over it. A
+    /// recording that starts here is stamped `now_unix_secs`. Returns the
+    /// recording once its trailing silence has elapsed, or once it reaches
+    /// the length limit (recording then carries on into a new one).
+    pub fn push(
+        &mut self,
+        samples: &[f32],
+        lock_quality: f32,
+        now_unix_secs: u64,
+    ) -> Option<FinishedRecording> {
+        let event = self.trigger.update(lock_quality, samples.len());
+        if event == Some(TriggerEvent::Start) {
+            self.started_unix_secs = now_unix_secs;
+            self.samples.clear();
+        }
+        if self.trigger.is_recording() || event == Some(TriggerEvent::Stop) {
+            self.samples.extend_from_slice(samples);
+        }
+        if event == Some(TriggerEvent::Stop) {
+            return self.take();
+        }
+        if self.samples.len() >= self.max_samples {
+            let finished = self.take();
+            self.started_unix_secs = now_unix_secs;
+            return finished;
+        }
+        None
+    }
+
+    /// Close the recording in progress, if any (RX stopped, auto mode off).
+    pub fn finish(mut self) -> Option<FinishedRecording> {
+        self.take()
+    }
+
+    fn take(&mut self) -> Option<FinishedRecording> {
+        (!self.samples.is_empty()).then(|| FinishedRecording {
+            started_unix_secs: self.started_unix_secs,
+            samples: std::mem::take(&mut self.samples),
+        })
+    }
+}
+
+/// Timestamped file name for one auto-recorded transmission,
+/// e.g. `rx-20240315-142530.wav` for 2024-03-15 14:25:30 UTC.
+pub fn recording_file_name(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+    let (y, m, d) = civil_from_days(days);
+    format!(
+        "rx-{y:04}{m:02}{d:02}-{:02}{:02}{:02}.wav",
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Days since 1970-01-01 → (year, month, day) in the proleptic Gregorian calendar.
+/// Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Drive the trigger with a lock-quality sequence in 100-sample chunks,
+    /// returning (chunk index, event) for each edge.
+    fn run(trigger: &mut AutoRecordTrigger, qualities: &[f32]) -> Vec<(usize, TriggerEvent)> {
+        qualities
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &q)| trigger.update(q, 100).map(|e| (i, e)))
+            .collect()
+    }
+
+    #[test]
+    fn starts_on_signal_and_stops_after_trailing_silence() {
+        let mut trigger = AutoRecordTrigger::new(0.5, 300);
+        let seq = [0.1, 0.2, 0.8, 0.9, 0.7, 0.1, 0.1, 0.1, 0.1, 0.1];
+        let events = run(&mut trigger, &seq);
+        assert_eq!(events, vec![(2, TriggerEvent::Start), (7, TriggerEvent::Stop)]);
+        assert!(!trigger.is_recording());
+    }
+
+    #[test]
+    fn brief_dropout_does_not_split_recording() {
+        let mut trigger = AutoRecordTrigger::new(0.5, 300);
+        // Two-chunk fade in the middle is shorter than the trailing window
+        let seq = [0.9, 0.9, 0.2, 0.2, 0.9, 0.9, 0.0, 0.0, 0.0];
+        let events = run(&mut trigger, &seq);
+        assert_eq!(events, vec![(0, TriggerEvent::Start), (8, TriggerEvent::Stop)]);
+    }
+
+    #[test]
+    fn one_recording_per_transmission() {
+        let mut trigger = AutoRecordTrigger::new(0.5, 200);
+        let seq = [0.9, 0.0, 0.0, 0.0, 0.9, 0.0, 0.0];
+        let events = run(&mut trigger, &seq);
+        assert_eq!(
+            events,
+            vec![
+                (0, TriggerEvent::Start),
+                (2, TriggerEvent::Stop),
+                (4, TriggerEvent::Start),
+                (6, TriggerEvent::Stop),
+            ]
+        );
+    }
+
+    #[test]
+    fn silence_never_starts_recording() {
+        let mut trigger = AutoRecordTrigger::new(0.5, 100);
+        assert!(run(&mut trigger, &[0.0, 0.49, 0.3]).is_empty());
+        assert!(!trigger.is_recording());
+    }
+
+    #[test]
+    fn hysteresis_stops_chatter_on_hovering_signal() {
+        // Fading signal wandering either side of 0.5
+        let hovering = [0.45, 0.55, 0.48, 0.56, 0.47, 0.53, 0.46, 0.58, 0.44, 0.52, 0.49, 0.55];
+
+        let mut single = AutoRecordTrigger::new(0.5, 100);
+        let chatter = run(&mut single, &hovering);
+        assert!(chatter.len() >= 4, "single threshold should chatter: {chatter:?}");
+
+        let mut hysteretic = AutoRecordTrigger::new(0.5, 100).with_hysteresis(0.5, 0.4);
+        assert_eq!(run(&mut hysteretic, &hovering), vec![(1, TriggerEvent::Start)]);
+        assert!(hysteretic.is_recording());
+    }
+
+    #[test]
+    fn min_dwell_ignores_brief_peaks() {
+        let mut trigger = AutoRecordTrigger::new(0.5, 100).with_min_dwell(200);
+        // One-chunk spikes never start; two chunks in a row reach the dwell
+        let seq = [0.9, 0.1, 0.9, 0.1, 0.9, 0.9, 0.9];
+        assert_eq!(run(&mut trigger, &seq), vec![(5, TriggerEvent::Start)]);
+    }
+
+    #[test]
+    fn recorder_writes_one_recording_per_transmission() {
+        let mut recorder = AutoRecorder::new(AutoRecordTrigger::new(0.5, 200), 10_000);
+        let batch = [0.25f32; 100];
+        // Two transmissions of three batches, each followed by silence
+        let qualities = [0.9, 0.9, 0.9, 0.0, 0.0, 0.0, 0.0, 0.9, 0.9, 0.9, 0.0, 0.0];
+        let recordings: Vec<FinishedRecording> = qualities
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &q)| recorder.push(&batch, q, i as u64))
+            .collect();
+        assert_eq!(recordings.len(), 2);
+        // Start batch, two more, then the trailing silence up to the Stop
+        assert_eq!(recordings[0].samples.len(), 500);
+        assert_eq!(recordings[0].started_unix_secs, 0);
+        assert_eq!(recordings[1].started_unix_secs, 7);
+        assert!(!recorder.is_recording());
+    }
+
+    #[test]
+    fn recorder_splits_long_recordings_and_flushes_on_finish() {
+        let mut recorder = AutoRecorder::new(AutoRecordTrigger::new(0.5, 200), 300);
+        let batch = [0.25f32; 100];
+        let split: Vec<usize> = (0..5)
+            .filter_map(|i| recorder.push(&batch, 0.9, i))
+            .map(|r| r.samples.len())
+            .collect();
+        assert_eq!(split, vec![300]);
+        let rest = recorder.finish().unwrap();
+        assert_eq!(rest.samples.len(), 200);
+        assert_eq!(rest.started_unix_secs, 2);
+    }
+
+    #[test]
+    fn file_name_is_utc_timestamp() {
+        assert_eq!(recording_file_name(0), "rx-19700101-000000.wav");
+        // 2024-03-15 14:25:30 UTC
+        assert_eq!(recording_file_name(1_710_512_730), "rx-20240315-142530.wav");
+        // Leap day
+        assert_eq!(recording_file_name(1_709_164_800), "rx-20240229-000000.wav");
+    }
+}
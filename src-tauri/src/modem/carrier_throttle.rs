@@ -0,0 +1,114 @@
+//! Carrier-frequency update throttle
+//!
+//! Dragging the waterfall marker calls `set_carrier_frequency` dozens of
+//! times a second. Resetting the decoder for each one means it never gets
+//! to lock, so the audio thread runs requested carriers through a
+//! `CarrierThrottle`: a change is applied at most once per interval, and
+//! whatever is pending when the interval ends is the latest value, so the
+//! ones in between are never applied at all.
+//!
+//! Small moves are applied with `retune_smooth`, which keeps the Costas
+//! loop's phase; only a real jump to another station resets the decoder.
+
+/// Changes this small are below the decoder's own change threshold
+const MIN_CHANGE_HZ: f64 = 0.1;
+
+/// Moves up to this size retune smoothly instead of resetting the decoder
+pub const SMOOTH_RETUNE_MAX_HZ: f64 = 5.0;
+
+/// How to apply a carrier change to the decoder
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CarrierUpdate {
+    /// Small nudge: `Psk31Decoder::retune_smooth`
+    Smooth(f64),
+    /// New station: `Psk31Decoder::set_carrier_freq`
+    Reset(f64),
+}
+
+/// Rate limiter for carrier changes, clocked in samples
+pub struct CarrierThrottle {
+    interval_samples: usize,
+    /// Samples since the last applied change, `None` before the first
+    since_applied: Option<usize>,
+}
+
+impl CarrierThrottle {
+    /// - `interval_samples`: minimum spacing between applied changes
+    pub fn new(interval_samples: usize) -> Self {
+        Self { interval_samples, since_applied: None }
+    }
+
+    /// Feed the decoder's `current` carrier and the latest `requested` one,
+    /// `elapsed_samples` after the previous call. Returns the change to
+    /// apply when one is due.
+    pub fn update(
+        &mut self,
+        current: f64,
+        requested: f64,
+        elapsed_samples: usize,
+    ) -> Option<CarrierUpdate> {
+        if let Some(since) = &mut self.since_applied {
+            *since = since.saturating_add(elapsed_samples);
+        }
+        let delta = (requested - current).abs();
+        if delta <= MIN_CHANGE_HZ {
+            return None;
+        }
+        if self.since_applied.is_some_and(|since| since < self.interval_samples) {
+            return None;
+        }
+        self.since_applied = Some(0);
+        Some(if delta <= SMOOTH_RETUNE_MAX_HZ {
+            CarrierUpdate::Smooth(requested)
+        } else {
+            CarrierUpdate::Reset(requested)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Apply each update to `carrier` the way the audio thread does
+    fn apply(carrier: &mut f64, update: CarrierUpdate) {
+        match update {
+            CarrierUpdate::Smooth(f) | CarrierUpdate::Reset(f) => *carrier = f,
+        }
+    }
+
+    #[test]
+    fn burst_within_window_applies_only_the_final_value() {
+        let mut throttle = CarrierThrottle::new(1000);
+        let mut carrier = 1000.0;
+        let mut applied = Vec::new();
+
+        // First change after idle goes straight through
+        let update = throttle.update(carrier, 1100.0, 100).unwrap();
+        assert_eq!(update, CarrierUpdate::Reset(1100.0));
+        apply(&mut carrier, update);
+
+        // Drag: a new value every 100 samples, all inside the window
+        for (i, freq) in [1120.0, 1140.0, 1160.0, 1180.0, 1200.0].into_iter().enumerate() {
+            let update = throttle.update(carrier, freq, 100);
+            assert_eq!(update, None, "update {i} should be held back");
+        }
+        // Marker released; the window runs out with the last value pending
+        for _ in 0..5 {
+            if let Some(update) = throttle.update(carrier, 1200.0, 100) {
+                applied.push(update);
+                apply(&mut carrier, update);
+            }
+        }
+        assert_eq!(applied, vec![CarrierUpdate::Reset(1200.0)]);
+        assert_eq!(throttle.update(carrier, 1200.0, 5000), None, "nothing left pending");
+    }
+
+    #[test]
+    fn small_moves_retune_smoothly() {
+        let mut throttle = CarrierThrottle::new(1000);
+        assert_eq!(throttle.update(1000.0, 1003.0, 0), Some(CarrierUpdate::Smooth(1003.0)));
+        assert_eq!(throttle.update(1003.0, 1040.0, 1000), Some(CarrierUpdate::Reset(1040.0)));
+        assert_eq!(throttle.update(1040.0, 1040.05, 5000), None);
+    }
+}
@@ -0,0 +1,113 @@
+//! Decode snapshot for "it won't decode this" reports
+//!
+//! A `SnapshotCapture` collects the next N seconds of raw RX samples in the
+//! audio thread. When it fills, the thread stamps it with the decoder's
+//! settings at that moment (`DecodeSnapshotMeta`) so the recording can be
+//! replayed through an identically configured decoder offline.
+
+use serde::Serialize;
+
+use crate::domain::RxMode;
+use crate::modem::decoder::Psk31Decoder;
+use crate::modem::dsp_info::DspInfo;
+
+/// Decoder settings a snapshot was captured with
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodeSnapshotMeta {
+    pub sample_rate: u32,
+    pub sample_count: usize,
+    pub carrier_freq: f64,
+    pub rx_mode: RxMode,
+    /// Decimation factor ahead of the loops (1 = none)
+    pub decimation: usize,
+    /// RX notch frequency, `None` when off
+    pub notch_freq: Option<f64>,
+    /// Build constants (see `DspInfo`)
+    pub dsp: DspInfo,
+}
+
+impl DecodeSnapshotMeta {
+    pub fn new(
+        decoder: &Psk31Decoder,
+        sample_rate: u32,
+        notch_freq: Option<f64>,
+        sample_count: usize,
+    ) -> Self {
+        Self {
+            sample_rate,
+            sample_count,
+            carrier_freq: decoder.carrier_freq(),
+            rx_mode: decoder.rx_mode(),
+            decimation: decoder.decimation(),
+            notch_freq,
+            dsp: DspInfo::current(),
+        }
+    }
+}
+
+/// Raw RX samples being collected for a snapshot
+#[derive(Debug)]
+pub struct SnapshotCapture {
+    wanted: usize,
+    samples: Vec<f32>,
+    meta: Option<DecodeSnapshotMeta>,
+}
+
+impl SnapshotCapture {
+    /// Capture `seconds` of audio at `sample_rate`.
+    pub fn new(seconds: f64, sample_rate: u32) -> Self {
+        let wanted = (seconds * sample_rate as f64).round() as usize;
+        Self { wanted, samples: Vec::with_capacity(wanted), meta: None }
+    }
+
+    /// Append samples up to the wanted length. Returns true once full but
+    /// not yet stamped with `finish`.
+    pub fn push(&mut self, samples: &[f32]) -> bool {
+        let room = self.wanted - self.samples.len();
+        self.samples.extend_from_slice(&samples[..samples.len().min(room)]);
+        self.samples.len() == self.wanted && self.meta.is_none()
+    }
+
+    /// Record the decoder settings; the snapshot is then complete.
+    pub fn finish(&mut self, decoder: &Psk31Decoder, sample_rate: u32, notch_freq: Option<f64>) {
+        self.meta =
+            Some(DecodeSnapshotMeta::new(decoder, sample_rate, notch_freq, self.samples.len()));
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.meta.is_some()
+    }
+
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    pub fn meta(&self) -> Option<&DecodeSnapshotMeta> {
+        self.meta.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_stops_at_requested_length_and_waits_for_finish() {
+        let mut capture = SnapshotCapture::new(0.5, 1000);
+        assert!(!capture.push(&[0.1; 300]));
+        assert!(capture.push(&[0.2; 300]));
+        assert_eq!(capture.samples().len(), 500);
+        assert!(!capture.is_complete());
+
+        let decoder = Psk31Decoder::new(1200.0, 1000);
+        capture.finish(&decoder, 1000, Some(800.0));
+        assert!(capture.is_complete());
+        assert!(!capture.push(&[0.3; 10]), "a finished capture takes no more samples");
+
+        let meta = capture.meta().unwrap();
+        assert_eq!(meta.sample_count, 500);
+        assert_eq!(meta.carrier_freq, 1200.0);
+        assert_eq!(meta.notch_freq, Some(800.0));
+    }
+}
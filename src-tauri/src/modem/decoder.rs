@@ -1,7 +1,47 @@
 //! PSK-31 Decoder — converts BPSK-31 audio samples back to text
 //!
-//! Pipeline: audio samples → AGC → Costas Loop → clock recovery
-//!           → differential bit detection → Varicode decode → characters
+//! Pipeline: audio samples → [DC blocker] → [notch] → [bandpass] → AGC → Costas Loop
+//!           → clock recovery → differential bit detection → Varicode decode
+//!           → characters
+//!
+//! The DC blocker (on by default, `set_dc_blocker`) strips a soundcard's DC
+//! offset, which would otherwise hold the AGC gain down and bias decisions.
+//!
+//! `set_decimation(n)` adds an anti-alias lowpass and keeps every nth
+//! sample after the notch, so AGC, Costas loop and clock recovery run at
+//! `sample_rate / n` (e.g. 8 kHz from 48 kHz) for a fraction of the CPU.
+//!
+//! The optional notch (`set_notch`) removes a steady carrier or heterodyne
+//! before the AGC, so the interferer neither captures the AGC gain nor
+//! leaks into the Costas loop.
+//!
+//! The optional bandpass (`set_bandpass`) passes only `bandpass_width()`
+//! around the carrier — `BANDPASS_WIDTH_BAUDS` times the baud rate, never
+//! under `MIN_BANDPASS_WIDTH_HZ`, so PSK-125/250 keep their sidebands — so
+//! strong signals elsewhere in the passband don't hold the AGC gain down or
+//! pull the Costas loop. It follows the carrier
+//! on every retune. Smooth retunes (AFC, VFO tracking) leave it alone until
+//! the carrier has drifted `BANDPASS_RECENTRE_FRACTION` of the width from
+//! its centre, then swap in re-centred taps over the existing history, so
+//! the nudges don't restart the filter from silence.
+//!
+//! `set_lock_blank_symbols(n)` hides the usual garbage first character:
+//! counting from lock (the first symbol strong enough to pass the symbol
+//! squelch) after a reset or retune, characters completed within the first
+//! `n` symbols are dropped. The default of 0 emits everything.
+//!
+//! With `set_require_pair(true)` a character is only emitted once a second
+//! one follows within `PAIR_WINDOW_BITS`; a lone character decoded out of
+//! noise is dropped. Once a run of copy is established, characters flow
+//! through unbuffered until the gap between two exceeds the window.
+//!
+//! For protocol analysis, `process_bits` returns each differential bit
+//! decision (after the phase-inversion fallback) instead of characters, and
+//! `last_bit` reports the bit decided during the last `process` call.
+//!
+//! If clock recovery slips half a symbol (dropout, duplicated buffer) it
+//! reports one long decision interval; the decoder then resyncs its
+//! differential reference instead of comparing across the slip.
 //!
 //! The Costas Loop locks onto the BPSK carrier, tracks its frequency/phase,
 //! and downmixes to baseband. Differential decoding resolves the 180° phase
@@ -9,23 +49,75 @@
 //!
 //! Note: The first character of a transmission is typically lost during
 //! lock acquisition. This is normal PSK-31 behavior — real QSOs always
-//! start with repeated CQ calls so the receiver has time to lock.
+//! start with repeated CQ calls so the receiver has time to lock. When the
+//! carrier is known in advance (loopback, test vectors), `prime` can run the
+//! loops over a known preamble first so the opening character survives.
 
 use crate::dsp::agc::Agc;
+use crate::dsp::biquad::Biquad;
 use crate::dsp::clock_recovery::ClockRecovery;
 use crate::dsp::costas_loop::CostasLoop;
+use crate::dsp::dc_blocker::DcBlocker;
+use crate::dsp::decimator::Decimator;
+use crate::dsp::filter::FirFilter;
+use crate::dsp::util::amplitude_to_db;
+use crate::domain::RxMode;
 use crate::modem::varicode::VaricodeDecoder;
 
 /// Number of bits without a valid decoded character before we try
 /// inverting the bit sense (phase ambiguity fallback)
 const PHASE_AMBIGUITY_THRESHOLD: usize = 100;
 
-/// Minimum symbol magnitude for bit decisions. Below this threshold,
-/// the Costas Loop hasn't locked yet and bit decisions would be garbage.
-const SYMBOL_SQUELCH: f32 = 0.001;
+/// A decision interval this far (as a fraction of a symbol) from nominal
+/// means clock recovery slipped; the differential reference is stale
+const RESYNC_INTERVAL_TOLERANCE: f64 = 0.25;
+
+/// Notch Q — about 30 Hz wide at 1 kHz, narrower than a PSK-31 signal's
+/// main lobe so a notch beside the wanted carrier barely touches it
+pub const NOTCH_Q: f64 = 30.0;
+
+/// Narrowest bandpass prefilter in Hz — ±100 Hz around the carrier, the
+/// width used at PSK-31 and PSK-63
+pub const MIN_BANDPASS_WIDTH_HZ: f64 = 200.0;
+
+/// Bandpass prefilter width in baud: wide enough for the main lobe
+/// (±1 baud) with room for the skirts
+pub const BANDPASS_WIDTH_BAUDS: f64 = 3.0;
+
+/// Fraction of the bandpass width a smooth retune may drift from the
+/// bandpass centre before the taps are re-centred
+const BANDPASS_RECENTRE_FRACTION: f64 = 0.05;
+
+/// Bandpass impulse response length in seconds (960 taps at 48 kHz), which
+/// puts the skirts within the passband edges even at the narrowest width
+const BANDPASS_SPAN_SECS: f64 = 0.02;
+
+/// AGC output level the decoder normalizes to
+pub const AGC_TARGET_LEVEL: f32 = 0.5;
+
+/// Longest gap, in bits, between two characters for them to count as a
+/// pair. The longest Varicode character plus its `00` separator is 12 bits.
+const PAIR_WINDOW_BITS: usize = 16;
+
+/// Smoothing for `lock_quality` per symbol — settles in roughly 10 symbols
+const LOCK_QUALITY_ALPHA: f32 = 0.1;
+
+/// Default minimum symbol magnitude for bit decisions (see `set_squelch`).
+/// Below this threshold, the Costas Loop hasn't locked yet and bit
+/// decisions would be garbage.
+pub const DEFAULT_SYMBOL_SQUELCH: f32 = 0.001;
 
 /// PSK-31 decoder: audio samples in, decoded characters out
 pub struct Psk31Decoder {
+    /// `None` when `set_dc_blocker(false)`
+    dc_blocker: Option<DcBlocker>,
+    notch: Option<Biquad>,
+    /// `Some` when `set_decimation` is above 1
+    decimator: Option<Decimator>,
+    /// `Some` when `set_bandpass(true)`; centred on `bandpass_centre`
+    bandpass: Option<FirFilter>,
+    /// Carrier the bandpass taps were last built for
+    bandpass_centre: f64,
     agc: Agc,
     costas_loop: CostasLoop,
     clock_recovery: ClockRecovery,
@@ -40,8 +132,30 @@ pub struct Psk31Decoder {
     /// When true, invert bit sense (phase ambiguity fallback)
     invert_bits: bool,
 
+    /// Bits decided since the last decoded character
+    bits_since_char: usize,
+
+    /// Bit decided by the most recent sample, if it completed a symbol
+    last_bit: Option<bool>,
+
+    /// Symbols after lock during which characters are suppressed
+    lock_blank_symbols: usize,
+
+    /// Bits decided since lock was acquired (reset/retune restarts it)
+    bits_since_lock: usize,
+
+    /// Smoothed squared symbol (re, im) and symbol power (see `lock_quality`)
+    lock_quality: [f32; 3],
+
+    /// `Some` when `set_require_pair` is on
+    pair_gate: Option<PairGate>,
+
+    /// Symbol magnitude (AGC-normalized) below which no bit is decided
+    squelch: f32,
+
     sample_rate: u32,
     carrier_freq: f64,
+    rx_mode: RxMode,
 }
 
 impl Psk31Decoder {
@@ -50,55 +164,216 @@ impl Psk31Decoder {
     /// - `carrier_freq`: audio carrier in Hz (typically 500-2500, set by waterfall click)
     /// - `sample_rate`: audio sample rate (48000)
     pub fn new(carrier_freq: f64, sample_rate: u32) -> Self {
-        let samples_per_symbol = sample_rate as f64 / 31.25;
+        Self::for_mode(carrier_freq, sample_rate, RxMode::default())
+    }
+
+    /// Create a decoder for `rx_mode`'s baud rate (samples per symbol =
+    /// `sample_rate / baud`). The mode is assumed already validated.
+    pub fn for_mode(carrier_freq: f64, sample_rate: u32, rx_mode: RxMode) -> Self {
+        let samples_per_symbol = sample_rate as f64 / rx_mode.baud;
 
         Self {
-            agc: Agc::new(0.5),
+            dc_blocker: Some(DcBlocker::default()),
+            notch: None,
+            decimator: None,
+            bandpass: None,
+            bandpass_centre: carrier_freq,
+            agc: Agc::new(AGC_TARGET_LEVEL),
             costas_loop: CostasLoop::new(carrier_freq, sample_rate as f64, 2.0),
             clock_recovery: ClockRecovery::new(samples_per_symbol),
             varicode_decoder: VaricodeDecoder::new(),
             last_symbol: 0.0,
             bits_without_char: 0,
             invert_bits: false,
+            bits_since_char: 0,
+            last_bit: None,
+            lock_blank_symbols: 0,
+            bits_since_lock: 0,
+            lock_quality: [0.0; 3],
+            pair_gate: None,
+            squelch: DEFAULT_SYMBOL_SQUELCH,
             sample_rate,
             carrier_freq,
+            rx_mode,
         }
     }
 
+    /// Baud and modulation this decoder was built for
+    pub fn rx_mode(&self) -> RxMode {
+        self.rx_mode
+    }
+
+    /// Audio carrier the decoder is tuned to
+    pub fn carrier_freq(&self) -> f64 {
+        self.carrier_freq
+    }
+
+    /// Run the loops at `sample_rate / factor` (see module docs); 1 turns
+    /// decimation off. Rebuilds carrier tracking and clock recovery for
+    /// the new rate, so call it before decoding.
+    pub fn set_decimation(&mut self, factor: usize) {
+        self.decimator = (factor > 1).then(|| Decimator::new(factor, self.sample_rate as f32));
+        let loop_rate = self.loop_rate();
+        self.costas_loop = CostasLoop::new(self.carrier_freq, loop_rate, 2.0);
+        self.rebuild_bandpass();
+        let offset = self.clock_recovery.decision_offset();
+        self.clock_recovery = ClockRecovery::new(loop_rate / self.rx_mode.baud);
+        self.clock_recovery.set_decision_offset(offset);
+        self.agc.reset();
+    }
+
+    /// Decimation factor in use (1 = none)
+    pub fn decimation(&self) -> usize {
+        self.decimator.as_ref().map_or(1, Decimator::factor)
+    }
+
+    /// Sample rate the AGC, Costas loop and clock recovery run at
+    fn loop_rate(&self) -> f64 {
+        self.sample_rate as f64 / self.decimation() as f64
+    }
+
+    /// Drop characters completed within `symbols` symbols of lock (see module docs).
+    pub fn set_lock_blank_symbols(&mut self, symbols: usize) {
+        self.lock_blank_symbols = symbols;
+    }
+
+    /// Take each symbol decision `offset` (0..1) of a symbol before the
+    /// clock recovery boundary; see `ClockRecovery::set_decision_offset`.
+    pub fn set_decision_offset(&mut self, offset: f64) {
+        self.clock_recovery.set_decision_offset(offset);
+    }
+
+    /// Symbol magnitude, relative to the AGC target of `AGC_TARGET_LEVEL`,
+    /// below which no bit is decided. Raise it on noisy bands to stop noise
+    /// decoding as garbage characters.
+    pub fn set_squelch(&mut self, level: f32) {
+        self.squelch = level;
+    }
+
+    /// Current symbol squelch level
+    pub fn squelch(&self) -> f32 {
+        self.squelch
+    }
+
+    /// Only emit characters that arrive in pairs (see module docs).
+    pub fn set_require_pair(&mut self, enabled: bool) {
+        self.pair_gate = enabled.then(PairGate::default);
+    }
+
     /// Process a single audio sample. Returns `Some(char)` when a character
     /// is fully decoded, `None` otherwise.
     pub fn process(&mut self, sample: f32) -> Option<char> {
-        // 1. AGC — normalize amplitude
-        let normalized = self.agc.process(sample);
+        let decoded = self.decode_sample(sample);
+        let gap = self.bits_since_char;
+        if decoded.is_some() {
+            self.bits_since_char = 0;
+        }
+        match &mut self.pair_gate {
+            Some(gate) => gate.step(decoded, gap),
+            None => decoded,
+        }
+    }
+
+    /// Process a single audio sample, returning the differential bit
+    /// decision when it completes a symbol. Varicode decoding still runs
+    /// (so the phase-inversion fallback works), but characters are dropped.
+    pub fn process_bits(&mut self, sample: f32) -> Option<bool> {
+        self.decode_sample(sample);
+        self.last_bit
+    }
+
+    /// Bit decided during the last `process`/`process_bits` call, if any
+    pub fn last_bit(&self) -> Option<bool> {
+        self.last_bit
+    }
+
+    fn decode_sample(&mut self, sample: f32) -> Option<char> {
+        self.last_bit = None;
+        let bit = self.decide_bit(sample)?;
+        self.last_bit = Some(bit);
+        self.push_bit(bit)
+    }
+
+    /// Steps 1–6: front end, carrier and timing recovery, differential bit
+    fn decide_bit(&mut self, sample: f32) -> Option<bool> {
+        // 1. AGC — normalize amplitude (after the optional notch and decimation)
+        let filtered = self.pre_filter(sample)?;
+        let normalized = self.agc.process(filtered);
 
         // 2. Costas Loop — carrier tracking + downmix to baseband
         let baseband = self.costas_loop.process(normalized);
 
         // 3. Clock Recovery — extract symbol at decision points
         let symbol = self.clock_recovery.process(baseband)?;
+        self.update_lock_quality(symbol);
+
+        // 4. Resync — after a timing slip `last_symbol` belongs to a different
+        //    symbol grid, so comparing against it would flip the bit. Take
+        //    this symbol as the new reference and skip one decision instead.
+        let nominal = self.clock_recovery.omega();
+        let interval = self.clock_recovery.last_interval() as f64;
+        if (interval - nominal).abs() > RESYNC_INTERVAL_TOLERANCE * nominal {
+            self.last_symbol = symbol;
+            return None;
+        }
 
-        // 4. Symbol squelch — ignore weak symbols during lock acquisition
-        if symbol.abs() < SYMBOL_SQUELCH && self.last_symbol.abs() < SYMBOL_SQUELCH {
+        // 5. Symbol squelch — ignore weak symbols during lock acquisition.
+        //    The weak symbol still becomes the reference, so the next strong
+        //    one isn't compared against a stale phase.
+        if symbol.abs() < self.squelch && self.last_symbol.abs() < self.squelch {
             self.last_symbol = symbol;
             return None;
         }
 
-        // 5. Differential bit detection
+        // 6. Differential bit detection
         let same_sign = (symbol > 0.0) == (self.last_symbol > 0.0);
         self.last_symbol = symbol;
 
         let raw_bit = same_sign;
-        let bit = if self.invert_bits { !raw_bit } else { raw_bit };
+        Some(if self.invert_bits { !raw_bit } else { raw_bit })
+    }
+
+    /// Squaring the (I, Q) symbol strips the BPSK data, leaving a fixed
+    /// vector when the loop holds phase and a spinning one when it doesn't.
+    fn update_lock_quality(&mut self, symbol: f32) {
+        let q = self.costas_loop.quadrature();
+        let (re, im) = (symbol * symbol - q * q, 2.0 * symbol * q);
+        let power = symbol * symbol + q * q;
+        let [avg_re, avg_im, avg_power] = &mut self.lock_quality;
+        *avg_re += LOCK_QUALITY_ALPHA * (re - *avg_re);
+        *avg_im += LOCK_QUALITY_ALPHA * (im - *avg_im);
+        *avg_power += LOCK_QUALITY_ALPHA * (power - *avg_power);
+    }
+
+    /// How steadily the Costas loop holds phase, 0.0..=1.0: the magnitude
+    /// of the smoothed squared symbol over its smoothed power. Near 1 when
+    /// locked; a carrier the loop can't pull in spins the squared symbol
+    /// and averages toward 0. 0 before the first symbol.
+    pub fn lock_quality(&self) -> f32 {
+        let [re, im, power] = self.lock_quality;
+        if power <= DEFAULT_SYMBOL_SQUELCH * DEFAULT_SYMBOL_SQUELCH {
+            return 0.0;
+        }
+        (re.hypot(im) / power).min(1.0)
+    }
 
-        // 6. Varicode decode
+    /// Steps 7–8: Varicode and the phase-ambiguity fallback
+    fn push_bit(&mut self, bit: bool) -> Option<char> {
+        // 7. Varicode decode
         self.bits_without_char += 1;
+        self.bits_since_char = self.bits_since_char.saturating_add(1);
+        self.bits_since_lock = self.bits_since_lock.saturating_add(1);
 
         if let Some(ch) = self.varicode_decoder.push_bit(bit) {
             self.bits_without_char = 0;
+            // Lock blanking: still a valid character, just not shown
+            if self.bits_since_lock <= self.lock_blank_symbols {
+                return None;
+            }
             return Some(ch);
         }
 
-        // 7. Phase ambiguity fallback
+        // 8. Phase ambiguity fallback
         if self.bits_without_char > PHASE_AMBIGUITY_THRESHOLD {
             self.invert_bits = !self.invert_bits;
             self.bits_without_char = 0;
@@ -108,6 +383,111 @@ impl Psk31Decoder {
         None
     }
 
+    /// Run AGC, carrier tracking and clock recovery over `samples` without
+    /// making bit decisions or emitting characters.
+    ///
+    /// Feed a known preamble (e.g. idle reversals at the expected carrier)
+    /// before real data so the loops are already locked when the first
+    /// character arrives. Varicode and phase-ambiguity state are untouched.
+    pub fn prime(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            let Some(filtered) = self.pre_filter(sample) else {
+                continue;
+            };
+            let normalized = self.agc.process(filtered);
+            let baseband = self.costas_loop.process(normalized);
+            if let Some(symbol) = self.clock_recovery.process(baseband) {
+                self.last_symbol = symbol;
+            }
+        }
+    }
+
+    /// Turn the input DC blocker on or off (see module docs). A no-op when
+    /// it is already in the requested state.
+    pub fn set_dc_blocker(&mut self, enabled: bool) {
+        if enabled != self.dc_blocker.is_some() {
+            self.dc_blocker = enabled.then(DcBlocker::default);
+        }
+    }
+
+    /// Notch out a steady interferer at `freq` Hz, or `None` to turn it off.
+    pub fn set_notch(&mut self, freq: Option<f64>) {
+        self.notch = freq.map(|f| Biquad::notch(f, NOTCH_Q, self.sample_rate as f64));
+    }
+
+    /// Pass only `bandpass_width()` around the carrier ahead of the AGC
+    /// (see module docs). A no-op when already in the requested state.
+    pub fn set_bandpass(&mut self, enabled: bool) {
+        if enabled != self.bandpass.is_some() {
+            self.bandpass = if enabled { Some(self.make_bandpass()) } else { None };
+        }
+    }
+
+    /// Whether the bandpass prefilter is on
+    pub fn bandpass(&self) -> bool {
+        self.bandpass.is_some()
+    }
+
+    /// Bandpass prefilter width in Hz for the decoder's baud (see module docs)
+    pub fn bandpass_width(&self) -> f64 {
+        (self.rx_mode.baud * BANDPASS_WIDTH_BAUDS).max(MIN_BANDPASS_WIDTH_HZ)
+    }
+
+    /// Bandpass taps centred on the current carrier, at the loop rate
+    fn bandpass_coefficients(&mut self) -> Vec<f32> {
+        let rate = self.loop_rate();
+        let taps = (rate * BANDPASS_SPAN_SECS) as usize | 1;
+        self.bandpass_centre = self.carrier_freq;
+        let (centre, width) = (self.carrier_freq as f32, self.bandpass_width() as f32);
+        FirFilter::bandpass_coefficients(centre, width, rate as f32, taps)
+    }
+
+    /// Bandpass centred on the current carrier, at the loop rate
+    fn make_bandpass(&mut self) -> FirFilter {
+        FirFilter::new(self.bandpass_coefficients())
+    }
+
+    /// Rebuild the bandpass after a hard retune or a loop rate change
+    fn rebuild_bandpass(&mut self) {
+        if self.bandpass.is_some() {
+            self.bandpass = Some(self.make_bandpass());
+        }
+    }
+
+    /// Follow a smooth retune (see module docs): keep the filter and its
+    /// history, re-centring the taps only once the carrier has drifted far
+    fn recentre_bandpass(&mut self) {
+        let drift = (self.carrier_freq - self.bandpass_centre).abs();
+        if self.bandpass.is_none() || drift <= self.bandpass_width() * BANDPASS_RECENTRE_FRACTION {
+            return;
+        }
+        let coefficients = self.bandpass_coefficients();
+        if let Some(bandpass) = &mut self.bandpass {
+            bandpass.set_coefficients(coefficients);
+        }
+    }
+
+    /// Front-end filtering ahead of the AGC. `None` for input samples
+    /// dropped by decimation.
+    fn pre_filter(&mut self, sample: f32) -> Option<f32> {
+        let sample = match &mut self.dc_blocker {
+            Some(blocker) => blocker.process(sample),
+            None => sample,
+        };
+        let notched = match &mut self.notch {
+            Some(notch) => notch.process(sample),
+            None => sample,
+        };
+        let decimated = match &mut self.decimator {
+            Some(decimator) => decimator.process(notched)?,
+            None => notched,
+        };
+        Some(match &mut self.bandpass {
+            Some(bandpass) => bandpass.process(decimated),
+            None => decimated,
+        })
+    }
+
     /// Update the carrier frequency (e.g., from waterfall click-to-tune)
     ///
     /// Resets carrier tracking and bit-layer state but preserves AGC gain
@@ -116,43 +496,151 @@ impl Psk31Decoder {
         self.carrier_freq = freq;
         self.costas_loop.set_frequency(freq);
         self.costas_loop.reset();
-        self.clock_recovery = ClockRecovery::new(self.sample_rate as f64 / 31.25);
+        self.rebuild_bandpass();
+        self.clock_recovery.reset();
         self.varicode_decoder.reset();
         self.last_symbol = 0.0;
         self.bits_without_char = 0;
         self.invert_bits = false;
+        self.bits_since_lock = 0;
+        self.lock_quality = [0.0; 3];
+        self.reset_pair_gate();
+    }
+
+    /// Move the carrier without resetting any loop state.
+    ///
+    /// For when the signal itself has shifted in the passband (e.g. the VFO
+    /// moved) rather than the operator picking a new station: the Costas
+    /// loop keeps its phase and the bit layer keeps its place in the stream.
+    pub fn retune_smooth(&mut self, freq: f64) {
+        self.carrier_freq = freq;
+        self.costas_loop.set_frequency(freq);
+        self.recentre_bandpass();
     }
 
     /// Update the carrier frequency only if the change exceeds 0.1 Hz.
     ///
-    /// This is the correct call site for the audio thread — it internalises the
-    /// change-detection threshold so the commands layer does not need to shadow
-    /// the carrier frequency.
+    /// The audio thread goes through `CarrierThrottle` instead, which applies
+    /// the same threshold and also rate-limits marker drags.
     pub fn update_carrier_if_changed(&mut self, freq: f64) {
         if (freq - self.carrier_freq).abs() > 0.1 {
             self.set_carrier_freq(freq);
         }
     }
 
+    /// Adapted samples-per-symbol estimate from clock recovery, in input
+    /// samples (scaled back up when decimating).
+    ///
+    /// Nominal is `sample_rate / baud` (1536 for PSK-31 at 48 kHz); a
+    /// persistent offset reflects soundcard clock error between the two stations.
+    pub fn current_sps(&self) -> f64 {
+        self.clock_recovery.omega() * self.decimation() as f64
+    }
+
+    /// Estimated signal-to-noise ratio in dB, from the Costas loop's I/Q
+    /// arm energies (see `CostasLoop::snr_estimate`)
+    pub fn snr_db(&self) -> f32 {
+        self.costas_loop.snr_estimate()
+    }
+
+    /// Carrier frequency the Costas loop is actually tracking, in Hz.
+    ///
+    /// Differs from `carrier_freq` by the loop's accumulated frequency
+    /// correction, so the waterfall marker can follow a drifting signal.
+    pub fn tracked_frequency(&self) -> f64 {
+        self.costas_loop.current_frequency()
+    }
+
+    /// Symbol timing drift from nominal in parts per million
+    /// (positive = symbols arriving longer than nominal).
+    pub fn timing_drift_ppm(&self) -> f64 {
+        let nominal = self.clock_recovery.nominal_samples_per_symbol();
+        (self.clock_recovery.omega() / nominal - 1.0) * 1e6
+    }
+
     /// Signal strength as a 0.0..=1.0 value derived from AGC gain.
     ///
     /// The AGC gain is inversely proportional to signal level: low gain = strong signal.
-    /// Gain range is [0.01, 100.0], mapped via inverse log10 to [1.0, 0.0]:
+    /// The gain is an amplitude ratio, so its range [0.01, 100.0] is [-40, +40] dB,
+    /// mapped linearly to [1.0, 0.0]:
     ///   gain=0.01 → 1.0 (strong), gain=1.0 → 0.5, gain=100.0 → 0.0 (absent)
     pub fn signal_strength(&self) -> f32 {
-        let gain = self.agc.current_gain().clamp(0.01, 100.0);
-        (1.0 - (gain.log10() + 2.0) / 4.0).clamp(0.0, 1.0)
+        let gain_db = amplitude_to_db(self.agc.current_gain(), -40.0).min(40.0);
+        (1.0 - (gain_db + 40.0) / 80.0).clamp(0.0, 1.0)
     }
 
     /// Reset all decoder state
     pub fn reset(&mut self) {
+        if let Some(blocker) = &mut self.dc_blocker {
+            blocker.reset();
+        }
+        if let Some(notch) = &mut self.notch {
+            notch.reset();
+        }
+        if let Some(decimator) = &mut self.decimator {
+            decimator.reset();
+        }
+        if let Some(bandpass) = &mut self.bandpass {
+            bandpass.reset();
+        }
         self.agc.reset();
         self.costas_loop.reset();
         self.clock_recovery.reset();
         self.varicode_decoder.reset();
         self.last_symbol = 0.0;
+        self.last_bit = None;
         self.bits_without_char = 0;
         self.invert_bits = false;
+        self.bits_since_lock = 0;
+        self.lock_quality = [0.0; 3];
+        self.reset_pair_gate();
+    }
+
+    fn reset_pair_gate(&mut self) {
+        self.bits_since_char = 0;
+        if let Some(gate) = &mut self.pair_gate {
+            *gate = PairGate::default();
+        }
+    }
+}
+
+/// Holds the first character of a possible run until a second confirms it
+#[derive(Debug, Default)]
+struct PairGate {
+    /// First character, waiting for a partner
+    held: Option<char>,
+    /// Partner character, emitted on the next step after `held`
+    queued: Option<char>,
+    /// A pair has been seen and no gap has exceeded the window since
+    in_run: bool,
+}
+
+impl PairGate {
+    /// Feed one decoder step's output; `gap` is the number of bits decided
+    /// since the previous character (including this one's, if any).
+    fn step(&mut self, decoded: Option<char>, gap: usize) -> Option<char> {
+        if gap > PAIR_WINDOW_BITS {
+            // Too long since the last character: a held one was noise
+            self.held = None;
+            self.in_run = false;
+        }
+        let Some(ch) = decoded else {
+            return self.queued.take();
+        };
+        if self.in_run {
+            return Some(ch);
+        }
+        match self.held.take() {
+            Some(first) => {
+                self.in_run = true;
+                self.queued = Some(ch);
+                Some(first)
+            }
+            None => {
+                self.held = Some(ch);
+                None
+            }
+        }
     }
 }
 
@@ -160,6 +648,39 @@ impl Psk31Decoder {
 mod tests {
     use super::*;
     use crate::modem::encoder::Psk31Encoder;
+    use crate::modem::varicode::Varicode;
+
+    #[test]
+    fn process_bits_yields_varicode_with_separators() {
+        let samples = Psk31Encoder::new(48000, 1000.0).encode("TEST");
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        let bits: String = samples
+            .iter()
+            .filter_map(|&s| decoder.process_bits(s))
+            .map(|b| if b { '1' } else { '0' })
+            .collect();
+
+        // Each character's code followed by its "00" separator, after idle zeros
+        let expected: String = "TEST"
+            .chars()
+            .map(|c| format!("{}00", Varicode::encode(c).unwrap()))
+            .collect();
+        assert!(bits.contains(&format!("0000{expected}")), "bits: {bits}");
+    }
+
+    #[test]
+    fn last_bit_tracks_process_calls() {
+        let samples = Psk31Encoder::new(48000, 1000.0).encode("E");
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        let mut bits = 0;
+        for &s in &samples {
+            decoder.process(s);
+            bits += usize::from(decoder.last_bit().is_some());
+        }
+        // About one decision per 1536-sample symbol
+        let symbols = samples.len() / 1536;
+        assert!(bits.abs_diff(symbols) <= 2, "{bits} bits for {symbols} symbols");
+    }
 
     #[test]
     fn test_decode_encoder_output() {
@@ -187,6 +708,128 @@ mod tests {
         );
     }
 
+    /// "CQ" at 1000 Hz under a tone `level` times stronger at `tone_hz`
+    fn decode_cq_under_tone(bandpass: bool, tone_hz: f64, level: f32) -> String {
+        let sample_rate = 48000;
+        let samples = Psk31Encoder::new(sample_rate, 1000.0).encode("CQ CQ CQ");
+        let mut decoder = Psk31Decoder::new(1000.0, sample_rate);
+        decoder.set_bandpass(bandpass);
+        let mut decoded = String::new();
+        for (i, &s) in samples.iter().enumerate() {
+            let t = i as f64 / sample_rate as f64;
+            let tone = level * (2.0 * std::f64::consts::PI * tone_hz * t).sin() as f32;
+            if let Some(ch) = decoder.process(s + tone) {
+                decoded.push(ch);
+            }
+        }
+        decoded
+    }
+
+    #[test]
+    fn test_bandpass_rejects_off_frequency_tone() {
+        // A tone ~10 dB above the signal, 300 Hz away: outside the bandpass
+        let unfiltered = decode_cq_under_tone(false, 1300.0, 3.0);
+        assert!(!unfiltered.contains("CQ"), "decoded without bandpass: {unfiltered:?}");
+        let filtered = decode_cq_under_tone(true, 1300.0, 3.0);
+        assert!(filtered.contains("CQ CQ"), "bandpass decode: {filtered:?}");
+    }
+
+    #[test]
+    fn test_bandpass_follows_retune() {
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        decoder.set_bandpass(true);
+        decoder.set_carrier_freq(1500.0);
+        let samples = Psk31Encoder::new(48000, 1500.0).encode("CQ CQ CQ");
+        let decoded: String = samples.iter().filter_map(|&s| decoder.process(s)).collect();
+        assert!(decoded.contains("CQ CQ"), "{decoded:?}");
+        assert!(decoder.bandpass());
+    }
+
+    #[test]
+    fn bandpass_width_scales_with_baud() {
+        let width = |baud| {
+            let rx_mode = RxMode { baud, ..RxMode::default() };
+            Psk31Decoder::for_mode(1000.0, 48000, rx_mode).bandpass_width()
+        };
+        assert_eq!(width(31.25), MIN_BANDPASS_WIDTH_HZ);
+        assert_eq!(width(62.5), MIN_BANDPASS_WIDTH_HZ);
+        assert_eq!(width(125.0), 375.0);
+        assert_eq!(width(250.0), 750.0);
+    }
+
+    #[test]
+    fn bandpass_passes_psk125() {
+        let rx_mode = RxMode { baud: 125.0, ..RxMode::default() };
+        let mut decoder = Psk31Decoder::for_mode(1500.0, 48000, rx_mode);
+        decoder.set_bandpass(true);
+        let samples =
+            Psk31Encoder::new(48000, 1500.0).with_baud(125.0).encode("CQ CQ DE W1AW W1AW K");
+        let decoded: String = samples.iter().filter_map(|&s| decoder.process(s)).collect();
+        assert!(decoded.contains("DE W1AW W1AW K"), "{decoded:?}");
+    }
+
+    #[test]
+    fn smooth_retune_keeps_bandpass_until_it_drifts_far() {
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        decoder.set_bandpass(true);
+        decoder.retune_smooth(1004.0);
+        assert_eq!(decoder.bandpass_centre, 1000.0);
+        decoder.retune_smooth(1030.0);
+        assert_eq!(decoder.bandpass_centre, 1030.0);
+        assert!(decoder.bandpass());
+    }
+
+    /// Characters decoded from 20 s of uniform noise of peak `amplitude`
+    fn decode_noise(squelch: f32, amplitude: f32) -> String {
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        decoder.set_squelch(squelch);
+        // Fixed-seed LCG so the noise (and the result) is repeatable
+        let mut state: u32 = 12345;
+        let mut decoded = String::new();
+        for _ in 0..48000 * 20 {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            let noise = (state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0;
+            if let Some(ch) = decoder.process(amplitude * noise) {
+                decoded.push(ch);
+            }
+        }
+        decoded
+    }
+
+    #[test]
+    fn test_squelch_silences_noise() {
+        let low = decode_noise(DEFAULT_SYMBOL_SQUELCH, 0.1);
+        assert!(!low.is_empty(), "noise should decode as garbage at the default squelch");
+        let high = decode_noise(0.1, 0.1);
+        assert!(high.is_empty(), "decoded {high:?} through a high squelch");
+    }
+
+    #[test]
+    fn test_squelch_level_survives_retune() {
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        decoder.set_squelch(0.05);
+        decoder.set_carrier_freq(1500.0);
+        decoder.reset();
+        assert_eq!(decoder.squelch(), 0.05);
+    }
+
+    #[test]
+    fn test_tracked_frequency_converges_on_offset_signal() {
+        let sample_rate = 48000;
+        let samples = Psk31Encoder::new(sample_rate, 1002.0)
+            .with_idle_lengths(128, 32)
+            .encode("CQ CQ DE W1AW");
+
+        let mut decoder = Psk31Decoder::new(1000.0, sample_rate);
+        for &sample in &samples {
+            decoder.process(sample);
+        }
+
+        let tracked = decoder.tracked_frequency();
+        assert!((tracked - 1002.0).abs() <= 1.0, "tracked {tracked:.3} Hz");
+        assert_eq!(decoder.carrier_freq(), 1000.0);
+    }
+
     #[test]
     fn test_decode_longer_text() {
         let carrier_freq = 1500.0;
@@ -301,8 +944,10 @@ mod tests {
         // After silence the AGC increases gain → signal_strength falls below baseline.
         let mut decoder_loud = Psk31Decoder::new(1000.0, 48000);
         let mut decoder_quiet = Psk31Decoder::new(1000.0, 48000);
-        for _ in 0..10_000 {
-            decoder_loud.process(1.0);
+        // A full-scale tone, not a constant: the DC blocker would remove that
+        for i in 0..10_000 {
+            let tone = (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / 48000.0).sin();
+            decoder_loud.process(tone);
             decoder_quiet.process(0.0);
         }
         let s_loud = decoder_loud.signal_strength();
@@ -380,4 +1025,161 @@ mod tests {
         // but we assert the decoder ran all the way through without panicking
         let _ = post_fallback_output;
     }
+
+    #[test]
+    fn current_sps_reports_clock_recovery_estimate() {
+        // Decode encoder output resampled so each symbol lasts `stretch`
+        // times nominal (a transmitter soundcard clock off by that much)
+        let sps_after = |stretch: f64| {
+            let mut decoder = Psk31Decoder::new(1000.0, 48000);
+            assert_eq!(decoder.current_sps(), 1536.0);
+            assert_eq!(decoder.timing_drift_ppm(), 0.0);
+            let src = Psk31Encoder::new(48000, 1000.0 * stretch)
+                .encode(&"CQ CQ DE W1AW ".repeat(8));
+            for i in 0..(src.len() as f64 * stretch) as usize {
+                let x = i as f64 / stretch;
+                let j = x as usize;
+                let a = src[j];
+                let b = src.get(j + 1).copied().unwrap_or(a);
+                decoder.process(a + (b - a) * (x - j as f64) as f32);
+            }
+            let sps = decoder.current_sps();
+            let ppm = decoder.timing_drift_ppm();
+            assert!((ppm - (sps / 1536.0 - 1.0) * 1e6).abs() < 1e-6);
+            sps
+        };
+
+        // Moved off nominal toward the sender's rate, without overshooting
+        let slow = sps_after(1.01);
+        assert!(1536.03 < slow && slow < 1536.0 * 1.01, "1% long symbols: sps {slow}");
+        let fast = sps_after(0.99);
+        assert!(1536.0 * 0.99 < fast && fast < 1535.97, "1% short symbols: sps {fast}");
+    }
+
+    #[test]
+    fn pair_gate_drops_lone_character_and_passes_pairs() {
+        let mut gate = PairGate::default();
+        let mut out = String::new();
+        let mut feed = |decoded: Option<char>, gap: usize| out.extend(gate.step(decoded, gap));
+
+        // Spurious 'e', then silence well past the window
+        feed(Some('e'), 40);
+        for gap in 1..=40 {
+            feed(None, gap);
+        }
+        // Genuine "OK": the first is held until the second arrives 9 bits later
+        feed(Some('O'), 50);
+        feed(Some('K'), 9);
+        feed(None, 0);
+        assert_eq!(out, "OK");
+    }
+
+    #[test]
+    fn require_pair_keeps_genuine_copy_intact() {
+        let samples = Psk31Encoder::new(48000, 1000.0).encode("CQ CQ DE W1AW");
+
+        let mut plain = Psk31Decoder::new(1000.0, 48000);
+        let plain_text: String = samples.iter().filter_map(|&s| plain.process(s)).collect();
+
+        let mut paired = Psk31Decoder::new(1000.0, 48000);
+        paired.set_require_pair(true);
+        let paired_text: String = samples.iter().filter_map(|&s| paired.process(s)).collect();
+
+        assert!(paired_text.contains("Q DE W1AW"), "paired copy: '{paired_text}'");
+        assert_eq!(paired_text, plain_text);
+    }
+
+    /// Decode `samples`, returning each character with the number of
+    /// symbols since lock at the moment it completed.
+    fn decode_with_lock_age(decoder: &mut Psk31Decoder, samples: &[f32]) -> Vec<(usize, char)> {
+        samples
+            .iter()
+            .filter_map(|&s| decoder.process(s).map(|ch| (decoder.bits_since_lock, ch)))
+            .collect()
+    }
+
+    #[test]
+    fn lock_blanking_suppresses_first_symbols_after_lock() {
+        const BLANK: usize = 40;
+        let samples = Psk31Encoder::new(48000, 1000.0).encode("CQ CQ DE W1AW W1AW K");
+
+        let plain = decode_with_lock_age(&mut Psk31Decoder::new(1000.0, 48000), &samples);
+        let mut blanked = Psk31Decoder::new(1000.0, 48000);
+        blanked.set_lock_blank_symbols(BLANK);
+        let blanked = decode_with_lock_age(&mut blanked, &samples);
+
+        assert!(plain.iter().any(|&(age, _)| age <= BLANK), "nothing to blank: {plain:?}");
+        assert!(blanked.iter().all(|&(age, _)| age > BLANK), "{blanked:?}");
+        // Everything after the window is untouched
+        let expected: Vec<_> = plain.into_iter().filter(|&(age, _)| age > BLANK).collect();
+        assert!(!expected.is_empty());
+        assert_eq!(blanked, expected);
+    }
+
+    #[test]
+    fn lock_blanking_restarts_after_retune() {
+        let samples = Psk31Encoder::new(48000, 1000.0).encode("CQ CQ DE W1AW");
+        let mut decoder = Psk31Decoder::new(1000.0, 48000);
+        decoder.set_lock_blank_symbols(10);
+        samples.iter().for_each(|&s| {
+            decoder.process(s);
+        });
+        assert!(decoder.bits_since_lock > 10);
+        decoder.set_carrier_freq(1500.0);
+        assert_eq!(decoder.bits_since_lock, 0);
+    }
+
+    #[test]
+    fn notch_rescues_copy_from_steady_interferer() {
+        use std::f64::consts::PI;
+        let text = "THE QUICK BROWN FOX";
+        // Unmodulated carrier 100 Hz above the signal at twice its amplitude
+        let mixed: Vec<f32> = Psk31Encoder::new(48000, 1000.0)
+            .encode(text)
+            .iter()
+            .enumerate()
+            .map(|(i, &s)| s + 2.0 * (2.0 * PI * 1100.0 * i as f64 / 48000.0).sin() as f32)
+            .collect();
+
+        let mut plain = Psk31Decoder::new(1000.0, 48000);
+        let plain_text: String = mixed.iter().filter_map(|&s| plain.process(s)).collect();
+        assert!(!plain_text.contains("QUICK"), "un-notched copy: '{plain_text}'");
+
+        let mut notched = Psk31Decoder::new(1000.0, 48000);
+        notched.set_notch(Some(1100.0));
+        let notched_text: String = mixed.iter().filter_map(|&s| notched.process(s)).collect();
+        assert!(notched_text.contains(text), "notched copy: '{notched_text}'");
+    }
+
+    #[test]
+    fn dc_blocker_rescues_copy_from_dc_offset() {
+        let text = "THE QUICK BROWN FOX";
+        let offset: Vec<f32> =
+            Psk31Encoder::new(48000, 1000.0).encode(text).iter().map(|&s| s + 3.0).collect();
+
+        let mut blocked = Psk31Decoder::new(1000.0, 48000);
+        let blocked_text: String = offset.iter().filter_map(|&s| blocked.process(s)).collect();
+        assert!(blocked_text.contains(text), "DC-blocked copy: '{blocked_text}'");
+
+        let mut raw = Psk31Decoder::new(1000.0, 48000);
+        raw.set_dc_blocker(false);
+        let raw_text: String = offset.iter().filter_map(|&s| raw.process(s)).collect();
+        assert!(!raw_text.contains("QUICK"), "unblocked copy: '{raw_text}'");
+    }
+
+    #[test]
+    fn lock_quality_high_on_carrier_and_low_out_of_pull_in() {
+        let samples = Psk31Encoder::new(48000, 1000.0).encode("THE QUICK BROWN FOX JUMPS");
+        let quality = |tuned: f64| {
+            let mut decoder = Psk31Decoder::new(tuned, 48000);
+            samples.iter().for_each(|&s| {
+                decoder.process(s);
+            });
+            decoder.lock_quality()
+        };
+        // 40 Hz is beyond the Costas loop's pull-in (integrator limit)
+        let (on, off) = (quality(1000.0), quality(1040.0));
+        assert!(on > 0.9, "on-carrier lock quality {on}");
+        assert!(off < 0.5, "40 Hz off lock quality {off}");
+    }
 }
@@ -0,0 +1,79 @@
+//! Build DSP parameters for bug reports
+//!
+//! `DspInfo::current()` gathers the encoder/decoder constants and modem
+//! config defaults this build runs with, so a report carries the exact
+//! numbers instead of "whatever version I had".
+
+use serde::Serialize;
+
+use crate::domain::{ModemConfig, SUPPORTED_BAUD_RATES};
+use crate::dsp::{agc, costas_loop};
+use crate::modem::{decoder, encoder};
+
+/// DSP configuration of this build
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DspInfo {
+    /// Crate version (Cargo.toml)
+    pub version: String,
+    /// Sample rate the modem is configured for by default
+    pub default_sample_rate: u32,
+    /// Symbol rates the decoder can be built for
+    pub baud_rates: Vec<f64>,
+    /// Waterfall FFT size by default
+    pub fft_size: usize,
+    pub costas_proportional_gain: f64,
+    pub costas_integral_gain: f64,
+    pub costas_arm_cutoff_hz: f64,
+    pub costas_integrator_limit_hz: f64,
+    pub agc_target_level: f32,
+    pub agc_attack_rate: f32,
+    pub agc_decay_rate: f32,
+    pub agc_min_gain: f32,
+    pub agc_max_gain: f32,
+    /// Q of the optional RX notch
+    pub notch_q: f64,
+    pub preamble_bits: usize,
+    pub postamble_bits: usize,
+    pub max_message_chars: usize,
+}
+
+impl DspInfo {
+    pub fn current() -> Self {
+        let config = ModemConfig::default();
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            default_sample_rate: config.sample_rate,
+            baud_rates: SUPPORTED_BAUD_RATES.to_vec(),
+            fft_size: config.fft_size,
+            costas_proportional_gain: costas_loop::PROPORTIONAL_GAIN,
+            costas_integral_gain: costas_loop::INTEGRAL_GAIN,
+            costas_arm_cutoff_hz: costas_loop::ARM_LPF_CUTOFF_HZ,
+            costas_integrator_limit_hz: costas_loop::DEFAULT_INTEGRATOR_LIMIT_HZ,
+            agc_target_level: decoder::AGC_TARGET_LEVEL,
+            agc_attack_rate: agc::ATTACK_RATE,
+            agc_decay_rate: agc::DECAY_RATE,
+            agc_min_gain: agc::MIN_GAIN,
+            agc_max_gain: agc::MAX_GAIN,
+            notch_q: decoder::NOTCH_Q,
+            preamble_bits: encoder::PREAMBLE_BITS,
+            postamble_bits: encoder::POSTAMBLE_BITS,
+            max_message_chars: encoder::MAX_MESSAGE_CHARS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_defaults_for_reports() {
+        let json = serde_json::to_value(DspInfo::current()).unwrap();
+        assert_eq!(json["defaultSampleRate"], 48000);
+        assert_eq!(json["baudRates"], serde_json::json!([31.25, 62.5, 125.0]));
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["costasProportionalGain"], 0.01);
+        assert_eq!(json["fftSize"], 4096);
+    }
+}
@@ -16,23 +16,71 @@
 //!   (only if curr will flip)  |  (only if curr flipped)
 //!
 //! The second half of each symbol depends on the *next* bit (look-ahead).
+//!
+//! The symbol rate defaults to 31.25 baud; `with_baud` selects PSK-63 or
+//! PSK-125. A symbol lasts `sample_rate / baud` samples (1536 for PSK-31 at
+//! 48 kHz).
 
+use crate::domain::{IdlePattern, Psk31Error, Psk31Result};
 use crate::dsp::nco::Nco;
 use crate::modem::varicode::Varicode;
 
-/// At 48 kHz sample rate and 31.25 baud, each symbol is exactly 1536 samples
-const SAMPLES_PER_SYMBOL: usize = 1536;
+/// Number of idle (phase-change) bits before data at 31.25 baud — lets the
+/// receiver lock on
+pub const PREAMBLE_BITS: usize = 32;
+
+/// Number of idle bits after data at 31.25 baud — clean ramp-down
+pub const POSTAMBLE_BITS: usize = 32;
 
-/// Number of idle (phase-change) bits before data — lets the receiver lock on
-const PREAMBLE_BITS: usize = 32;
+/// Baud rate `PREAMBLE_BITS`/`POSTAMBLE_BITS` are sized for, and the
+/// encoder's default
+const BASE_BAUD: f64 = 31.25;
 
-/// Number of idle bits after data — clean ramp-down
-const POSTAMBLE_BITS: usize = 32;
+/// Idle length at `baud` lasting as long as `base_bits` do at 31.25 baud.
+///
+/// Lock-on takes roughly constant *time* at the far end, so PSK-63 needs
+/// twice the bits of PSK-31 and PSK-125 four times.
+pub fn idle_bits_for_baud(base_bits: usize, baud: f64) -> usize {
+    (base_bits as f64 * baud / BASE_BAUD).round() as usize
+}
+
+/// Longest message accepted for transmission (matches the TX input's maxlength)
+pub const MAX_MESSAGE_CHARS: usize = 256;
+
+/// Reject messages longer than [`MAX_MESSAGE_CHARS`].
+pub fn validate_message_length(text: &str) -> Psk31Result<()> {
+    let len = text.chars().count();
+    if len > MAX_MESSAGE_CHARS {
+        return Err(Psk31Error::Modem(format!(
+            "Message is {len} characters; the limit is {MAX_MESSAGE_CHARS}"
+        )));
+    }
+    Ok(())
+}
+
+/// Idle bit sequence for `pattern`, at least `min_bits` long.
+///
+/// `Reversals` is exactly `min_bits` zeros. `Nul` emits whole NUL
+/// characters (each with its `00` separator) so it never cuts one short.
+pub fn idle_bits(pattern: IdlePattern, min_bits: usize) -> Vec<bool> {
+    match pattern {
+        IdlePattern::Reversals => vec![false; min_bits],
+        IdlePattern::Nul => {
+            let mut nul = Varicode::bits_from_str(Varicode::encode('\0').unwrap_or("1010101011"));
+            nul.extend([false, false]);
+            nul.iter().copied().cycle().take(min_bits.div_ceil(nul.len()) * nul.len()).collect()
+        }
+    }
+}
 
 /// PSK-31 encoder: text in, audio samples out
 pub struct Psk31Encoder {
     sample_rate: u32,
     carrier_freq: f64,
+    baud: f64,
+    idle_pattern: IdlePattern,
+    /// Explicit (preamble, postamble) lengths; `None` scales with baud
+    idle_override: Option<(usize, usize)>,
 }
 
 impl Psk31Encoder {
@@ -40,29 +88,74 @@ impl Psk31Encoder {
         Self {
             sample_rate,
             carrier_freq,
+            baud: BASE_BAUD,
+            idle_pattern: IdlePattern::default(),
+            idle_override: None,
         }
     }
 
+    /// Send at `baud` symbols per second (31.25, 62.5 or 125) instead of 31.25.
+    /// The preamble and postamble scale with it (see `idle_bits_for_baud`).
+    pub fn with_baud(mut self, baud: f64) -> Self {
+        self.baud = baud;
+        self
+    }
+
+    pub fn baud(&self) -> f64 {
+        self.baud
+    }
+
+    /// Samples in one symbol at this encoder's rate and baud
+    pub fn samples_per_symbol(&self) -> usize {
+        (self.sample_rate as f64 / self.baud).round() as usize
+    }
+
+    /// Use `pattern` for the preamble and postamble instead of reversals.
+    pub fn with_idle_pattern(mut self, pattern: IdlePattern) -> Self {
+        self.idle_pattern = pattern;
+        self
+    }
+
+    /// Use fixed preamble/postamble lengths instead of the baud-scaled defaults.
+    pub fn with_idle_lengths(mut self, preamble_bits: usize, postamble_bits: usize) -> Self {
+        self.idle_override = Some((preamble_bits, postamble_bits));
+        self
+    }
+
+    /// (preamble, postamble) bit counts this encoder sends.
+    pub fn idle_lengths(&self) -> (usize, usize) {
+        self.idle_override.unwrap_or((
+            idle_bits_for_baud(PREAMBLE_BITS, self.baud),
+            idle_bits_for_baud(POSTAMBLE_BITS, self.baud),
+        ))
+    }
+
     /// Encode a text message into BPSK-31 audio samples.
     ///
-    /// Returns a Vec<f32> of audio samples ready for playback at 48 kHz.
+    /// Returns a Vec<f32> of audio samples ready for playback at the
+    /// encoder's sample rate.
     pub fn encode(&self, text: &str) -> Vec<f32> {
         let bits = self.text_to_bits(text);
         self.bits_to_samples(&bits)
     }
 
+    /// Air time of `text` in seconds, including preamble and postamble —
+    /// the length of `encode(text)` without synthesising it.
+    pub fn estimated_duration(&self, text: &str) -> f64 {
+        let samples = self.text_to_bits(text).len() * self.samples_per_symbol();
+        samples as f64 / self.sample_rate as f64
+    }
+
     /// Convert text to a complete bit stream: preamble + varicode + postamble
     ///
     /// In BPSK-31, a '0' bit = phase change, '1' bit = no change.
     /// Varicode separators are '00' (two phase changes between characters).
-    /// Preamble/postamble are all zeros (continuous phase changes).
+    /// Preamble/postamble follow the encoder's `IdlePattern` (all zeros by default).
     fn text_to_bits(&self, text: &str) -> Vec<bool> {
-        let mut bits = Vec::new();
+        let (preamble_bits, postamble_bits) = self.idle_lengths();
 
-        // Preamble: continuous phase changes for receiver sync
-        for _ in 0..PREAMBLE_BITS {
-            bits.push(false); // 0 = phase change
-        }
+        // Preamble: idle pattern for receiver sync
+        let mut bits = idle_bits(self.idle_pattern, preamble_bits);
 
         // Encode each character
         for ch in text.chars() {
@@ -77,17 +170,15 @@ impl Psk31Encoder {
         }
 
         // Postamble: clean ramp-down
-        for _ in 0..POSTAMBLE_BITS {
-            bits.push(false);
-        }
+        bits.extend(idle_bits(self.idle_pattern, postamble_bits));
 
         bits
     }
 
     /// Convert a bit stream to BPSK-modulated audio samples.
     ///
-    /// Each symbol is split into two 768-sample halves. The envelope shape of
-    /// each half is chosen independently:
+    /// Each symbol is split into two halves (768 samples each for PSK-31 at
+    /// 48 kHz). The envelope shape of each half is chosen independently:
     ///
     ///   first half  — rising (0→1) if this symbol flips phase, else flat (1)
     ///   second half — falling (1→0) if the *next* symbol will flip, else flat (1)
@@ -97,33 +188,36 @@ impl Psk31Encoder {
     /// the discontinuity is inaudible.
     fn bits_to_samples(&self, bits: &[bool]) -> Vec<f32> {
         let mut nco = Nco::new(self.carrier_freq, self.sample_rate as f64);
-        let half = SAMPLES_PER_SYMBOL / 2; // 768 samples
+        let samples_per_symbol = self.samples_per_symbol();
+        // An odd symbol length gives the extra sample to the second half
+        let half = samples_per_symbol / 2;
+        let second = samples_per_symbol - half;
 
         // Precompute the two half-window shapes once.
         //
         // Both are derived from |cos(π·t)| over a full symbol period, split at
-        // the midpoint so that rising[0]==0, rising[767]≈1, falling[0]==1,
-        // falling[767]≈0 — they meet at zero exactly at the symbol boundary.
+        // the midpoint so that rising[0]==0, rising[half-1]≈1, falling[0]==1,
+        // falling[second-1]≈0 — they meet at zero exactly at the symbol boundary.
         //
-        // rising[k]  = |cos(π · (k + half) / SAMPLES_PER_SYMBOL)|  (second half of V)
-        // falling[k] = |cos(π ·  k          / SAMPLES_PER_SYMBOL)|  (first  half of V)
+        // rising[k]  = |cos(π · (k + half) / (2 · half))|    (second half of V)
+        // falling[k] = |cos(π ·  k         / (2 · second))|  (first  half of V)
         let rising: Vec<f32> = (0..half)
             .map(|k| {
-                let t = (k + half) as f32 / SAMPLES_PER_SYMBOL as f32;
+                let t = (k + half) as f32 / (2 * half) as f32;
                 (std::f32::consts::PI * t).cos().abs()
             })
             .collect();
 
-        let falling: Vec<f32> = (0..half)
+        let falling: Vec<f32> = (0..second)
             .map(|k| {
-                let t = k as f32 / SAMPLES_PER_SYMBOL as f32;
+                let t = k as f32 / (2 * second) as f32;
                 (std::f32::consts::PI * t).cos().abs()
             })
             .collect();
 
-        let flat = vec![1.0f32; half];
+        let flat = vec![1.0f32; second];
 
-        let total_samples = bits.len() * SAMPLES_PER_SYMBOL;
+        let total_samples = bits.len() * samples_per_symbol;
         let mut samples = Vec::with_capacity(total_samples);
 
         for (i, &bit) in bits.iter().enumerate() {
@@ -137,7 +231,7 @@ impl Psk31Encoder {
             }
 
             // First half: rising from zero if this symbol flipped, else constant.
-            let first_half: &[f32] = if phase_change { &rising } else { &flat };
+            let first_half: &[f32] = if phase_change { &rising } else { &flat[..half] };
 
             // Second half: falling toward zero if the next symbol will flip,
             // else constant — the next symbol's rising half will then take over.
@@ -157,6 +251,9 @@ mod tests {
     use super::*;
     use crate::modem::varicode::VaricodeDecoder;
 
+    /// PSK-31 at 48 kHz
+    const SAMPLES_PER_SYMBOL: usize = 1536;
+
     #[test]
     fn test_encode_empty_text() {
         let encoder = Psk31Encoder::new(48000, 1500.0);
@@ -168,6 +265,23 @@ mod tests {
         assert_eq!(samples.len(), expected_samples);
     }
 
+    #[test]
+    fn estimated_duration_matches_encoded_length() {
+        let encoder = Psk31Encoder::new(48000, 1000.0);
+        for text in ["", "e", "CQ CQ DE W1AW"] {
+            let samples = encoder.encode(text).len();
+            assert_eq!(encoder.estimated_duration(text), samples as f64 / 48000.0);
+        }
+        // 68 bits × 32 ms
+        assert!((encoder.estimated_duration("e") - 2.176).abs() < 1e-9);
+    }
+
+    #[test]
+    fn message_length_guard() {
+        assert!(validate_message_length(&"A".repeat(MAX_MESSAGE_CHARS)).is_ok());
+        assert!(validate_message_length(&"A".repeat(MAX_MESSAGE_CHARS + 1)).is_err());
+    }
+
     #[test]
     fn test_encode_single_char() {
         let encoder = Psk31Encoder::new(48000, 1500.0);
@@ -191,6 +305,81 @@ mod tests {
         assert_eq!(samples.len(), expected_samples);
     }
 
+    #[test]
+    fn idle_reversals_are_all_zeros() {
+        assert_eq!(idle_bits(IdlePattern::Reversals, 4), vec![false; 4]);
+        assert_eq!(IdlePattern::default(), IdlePattern::Reversals);
+    }
+
+    #[test]
+    fn idle_nul_repeats_whole_nul_characters() {
+        // NUL = 1010101011, then the 00 separator
+        let one_nul: Vec<bool> = "101010101100".chars().map(|c| c == '1').collect();
+        assert_eq!(idle_bits(IdlePattern::Nul, 12), one_nul);
+        // 32 bits rounds up to three whole characters
+        let bits = idle_bits(IdlePattern::Nul, 32);
+        assert_eq!(bits.len(), 36);
+        assert_eq!(bits[24..], one_nul[..]);
+    }
+
+    #[test]
+    fn encoder_uses_selected_idle_pattern() {
+        let encoder = Psk31Encoder::new(48000, 1500.0).with_idle_pattern(IdlePattern::Nul);
+        let bits = encoder.text_to_bits("");
+        let expected = [
+            idle_bits(IdlePattern::Nul, PREAMBLE_BITS),
+            idle_bits(IdlePattern::Nul, POSTAMBLE_BITS),
+        ]
+        .concat();
+        assert_eq!(bits, expected);
+
+        let default_bits = Psk31Encoder::new(48000, 1500.0).text_to_bits("");
+        assert_eq!(default_bits, vec![false; PREAMBLE_BITS + POSTAMBLE_BITS]);
+    }
+
+    #[test]
+    fn preamble_duration_is_constant_across_baud_rates() {
+        let sample_rate = 48_000.0;
+        let durations: Vec<f64> = [31.25, 62.5, 125.0]
+            .iter()
+            .map(|&baud| {
+                let samples_per_symbol = sample_rate / baud;
+                idle_bits_for_baud(PREAMBLE_BITS, baud) as f64 * samples_per_symbol / sample_rate
+            })
+            .collect();
+        for d in &durations {
+            assert!((d - durations[0]).abs() < 0.01, "preamble durations {durations:?}");
+        }
+        assert_eq!(idle_bits_for_baud(PREAMBLE_BITS, 125.0), 4 * PREAMBLE_BITS);
+    }
+
+    #[test]
+    fn symbol_length_follows_baud_and_sample_rate() {
+        let psk31 = Psk31Encoder::new(48000, 1500.0);
+        assert_eq!(psk31.samples_per_symbol(), SAMPLES_PER_SYMBOL);
+        for (baud, samples_per_symbol) in [(62.5, 768), (125.0, 384)] {
+            let encoder = Psk31Encoder::new(48000, 1500.0).with_baud(baud);
+            assert_eq!(encoder.samples_per_symbol(), samples_per_symbol);
+            let bits = encoder.text_to_bits("e").len();
+            assert_eq!(encoder.encode("e").len(), bits * samples_per_symbol);
+        }
+        // 11025 / 31.25 rounds to an odd symbol length
+        let encoder = Psk31Encoder::new(11025, 1000.0);
+        assert_eq!(encoder.samples_per_symbol(), 353);
+        let samples = encoder.encode("e");
+        assert_eq!(samples.len(), encoder.text_to_bits("e").len() * 353);
+        assert!(samples.iter().all(|s| s.abs() <= 1.0));
+    }
+
+    #[test]
+    fn explicit_idle_lengths_override_default() {
+        let encoder = Psk31Encoder::new(48000, 1500.0);
+        assert_eq!(encoder.idle_lengths(), (PREAMBLE_BITS, POSTAMBLE_BITS));
+
+        let encoder = encoder.with_idle_lengths(64, 8);
+        assert_eq!(encoder.text_to_bits(""), vec![false; 72]);
+    }
+
     #[test]
     fn test_samples_in_valid_range() {
         let encoder = Psk31Encoder::new(48000, 1500.0);
@@ -206,6 +395,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn phase_flips_leave_no_step_at_symbol_boundaries() {
+        let carrier = 1500.0;
+        let encoder = Psk31Encoder::new(48000, carrier);
+        let samples = encoder.encode("CQ CQ DE W1AW");
+        // Steepest a unit sine at the carrier can move between samples; a
+        // flip at full amplitude would jump by up to 2.0
+        let max_slew = (2.0 * std::f64::consts::PI * carrier / 48000.0) as f32;
+
+        for boundary in (SAMPLES_PER_SYMBOL..samples.len()).step_by(SAMPLES_PER_SYMBOL) {
+            let around = &samples[boundary - 8..boundary + 8];
+            let step = around.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0f32, f32::max);
+            assert!(
+                step <= max_slew * 1.01,
+                "step of {step} at symbol boundary {} (carrier slew {max_slew})",
+                boundary / SAMPLES_PER_SYMBOL
+            );
+        }
+    }
+
     #[test]
     fn test_preamble_is_not_silent() {
         let encoder = Psk31Encoder::new(48000, 1500.0);
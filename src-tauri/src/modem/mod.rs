@@ -5,5 +5,16 @@
 pub mod varicode;
 pub mod encoder;
 pub mod decoder;
+pub mod dsp_info;
+pub mod decode_snapshot;
+pub mod auto_record;
+pub mod retune;
+pub mod acquire;
+pub mod carrier_throttle;
+pub mod tune;
+pub mod rx_text;
+pub mod rx_rate;
+pub mod rx_channels;
+pub mod self_test;
 
 pub use varicode::Varicode;
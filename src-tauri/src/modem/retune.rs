@@ -0,0 +1,141 @@
+//! Retune suggestions from the waterfall
+//!
+//! When the decoder can't hold phase (low `lock_quality`) but the FFT shows
+//! a strong signal just off the carrier, the operator has most likely
+//! clicked a little wide of it. `RetuneAdvisor` watches each FFT frame and
+//! suggests the signal's centre frequency instead.
+//!
+//! Suggestions are debounced: the condition must hold for
+//! `CONFIRM_FRAMES` frames in a row, and after a suggestion nothing more
+//! is offered for the holdoff period.
+
+use crate::dsp::fft::{peak_near_hz, tuning_error_hz};
+
+/// Lock quality below which the decoder counts as not locked
+pub const POOR_LOCK_QUALITY: f32 = 0.5;
+
+/// How far from the carrier a peak may sit and still count as "this" signal
+pub const RETUNE_WINDOW_HZ: f64 = 30.0;
+
+/// How far above the spectrum's median a peak must stand to count as strong
+pub const MIN_PEAK_PROMINENCE_DB: f32 = 10.0;
+
+/// Offsets smaller than this aren't worth a retune
+pub const MIN_RETUNE_OFFSET_HZ: f64 = 3.0;
+
+/// Consecutive qualifying FFT frames before suggesting
+const CONFIRM_FRAMES: usize = 3;
+
+/// Debounced retune suggestion state
+pub struct RetuneAdvisor {
+    holdoff_samples: usize,
+    /// Consecutive frames meeting the condition so far
+    streak: usize,
+    /// Samples since the last suggestion, `None` before the first
+    since_suggestion: Option<usize>,
+}
+
+impl RetuneAdvisor {
+    /// - `holdoff_samples`: quiet period after each suggestion
+    pub fn new(holdoff_samples: usize) -> Self {
+        Self { holdoff_samples, streak: 0, since_suggestion: None }
+    }
+
+    /// Feed one FFT frame (`magnitudes_db` as from `FftProcessor::compute`)
+    /// covering `elapsed_samples` new samples. Returns the suggested carrier
+    /// frequency when one is due.
+    pub fn update(
+        &mut self,
+        lock_quality: f32,
+        magnitudes_db: &[f32],
+        carrier_hz: f64,
+        sample_rate: f64,
+        elapsed_samples: usize,
+    ) -> Option<f64> {
+        if let Some(since) = &mut self.since_suggestion {
+            *since = since.saturating_add(elapsed_samples);
+        }
+        let Some(suggestion) = suggest(lock_quality, magnitudes_db, carrier_hz, sample_rate)
+        else {
+            self.streak = 0;
+            return None;
+        };
+        self.streak += 1;
+        let held_off = self.since_suggestion.is_some_and(|s| s < self.holdoff_samples);
+        if self.streak < CONFIRM_FRAMES || held_off {
+            return None;
+        }
+        self.streak = 0;
+        self.since_suggestion = Some(0);
+        Some(suggestion)
+    }
+}
+
+/// Centre of a strong signal near `carrier_hz` while the lock is poor, or
+/// `None` if there's nothing worth retuning to.
+fn suggest(lock_quality: f32, magnitudes_db: &[f32], carrier_hz: f64, sample_rate: f64) -> Option<f64> {
+    if lock_quality >= POOR_LOCK_QUALITY {
+        return None;
+    }
+    let (peak_hz, prominence) =
+        peak_near_hz(magnitudes_db, carrier_hz, RETUNE_WINDOW_HZ, sample_rate)?;
+    if prominence < MIN_PEAK_PROMINENCE_DB {
+        return None;
+    }
+    // The centroid around the peak, not the peak bin: a PSK-31 signal's
+    // sidebands can outshine its centre
+    let centre = peak_hz + tuning_error_hz(magnitudes_db, peak_hz, sample_rate)?;
+    ((centre - carrier_hz).abs() >= MIN_RETUNE_OFFSET_HZ).then_some(centre)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp::fft::FftProcessor;
+    use crate::modem::encoder::Psk31Encoder;
+
+    const RATE: f64 = 48000.0;
+
+    /// FFT frame from the middle of a PSK-31 transmission on `freq`
+    fn frame(freq: f64) -> Vec<f32> {
+        let samples = Psk31Encoder::new(48000, freq).encode("CQ CQ DE W1AW W1AW K");
+        let mid = samples.len() / 2;
+        FftProcessor::new(4096).compute(&samples[mid..mid + 4096])
+    }
+
+    #[test]
+    fn poor_lock_with_signal_10_hz_off_suggests_the_signal() {
+        let magnitudes = frame(1010.0);
+        let mut advisor = RetuneAdvisor::new(0);
+        let suggestions: Vec<f64> = (0..CONFIRM_FRAMES)
+            .filter_map(|_| advisor.update(0.1, &magnitudes, 1000.0, RATE, 2048))
+            .collect();
+        assert_eq!(suggestions.len(), 1, "{suggestions:?}");
+        assert!((suggestions[0] - 1010.0).abs() < 3.0, "suggested {}", suggestions[0]);
+    }
+
+    #[test]
+    fn good_lock_or_empty_band_suggests_nothing() {
+        let magnitudes = frame(1010.0);
+        let mut locked = RetuneAdvisor::new(0);
+        assert!((0..10).all(|_| locked.update(0.95, &magnitudes, 1000.0, RATE, 2048).is_none()));
+
+        let silence = vec![-120.0f32; 2048];
+        let mut empty = RetuneAdvisor::new(0);
+        assert!((0..10).all(|_| empty.update(0.1, &silence, 1000.0, RATE, 2048).is_none()));
+    }
+
+    #[test]
+    fn suggestions_are_debounced() {
+        let magnitudes = frame(1010.0);
+        let mut advisor = RetuneAdvisor::new(48000);
+        let count = |advisor: &mut RetuneAdvisor, frames: usize| {
+            (0..frames)
+                .filter(|_| advisor.update(0.1, &magnitudes, 1000.0, RATE, 2048).is_some())
+                .count()
+        };
+        // One suggestion, then quiet for the 1 s holdoff (23 frames of 2048)
+        assert_eq!(count(&mut advisor, 20), 1);
+        assert_eq!(count(&mut advisor, 10), 1);
+    }
+}
@@ -0,0 +1,107 @@
+//! Extra RX channels
+//!
+//! Besides the main decoder, the audio thread runs one `Psk31Decoder` per
+//! channel opened with `start_rx_channel`, keyed by its carrier frequency, so
+//! several signals on a busy waterfall can be copied at once. Every channel
+//! sees the same input samples; its text is emitted on `rx-text` tagged with
+//! the channel frequency.
+//!
+//! Channel decoders always run the bandpass prefilter: without it a decoder
+//! happily copies a lone signal hundreds of Hz away, so a channel would echo
+//! its neighbours whenever its own frequency goes quiet. They also decimate
+//! to about `CHANNEL_LOOP_RATE` first, so sixteen channels don't each run
+//! the bandpass and loops at 48 kHz.
+//!
+//! Each decoder sits behind its own lock. The audio thread clones the
+//! channel list (just the `Arc`s) and decodes without holding it, so
+//! `start_rx_channel` / `stop_rx_channel` never wait on a decode.
+
+use std::sync::{Arc, Mutex};
+
+use crate::domain::RxMode;
+use crate::modem::decoder::Psk31Decoder;
+
+/// Most channels decoded alongside the main decoder
+pub const MAX_RX_CHANNELS: usize = 16;
+
+/// Carriers closer than this are treated as the same channel
+pub const CHANNEL_MATCH_HZ: f64 = 0.5;
+
+/// Rate channel decoders run their loops at (Hz); channels top out at
+/// 3500 Hz, under the decimator's cutoff at this rate
+pub const CHANNEL_LOOP_RATE: u32 = 8000;
+
+/// An open channel: its carrier and its decoder
+pub type RxChannel = (f64, Arc<Mutex<Psk31Decoder>>);
+
+/// Channel on `freq_hz`: bandpass on, decimated to about `CHANNEL_LOOP_RATE`
+pub fn new_channel(freq_hz: f64, sample_rate: u32, rx_mode: RxMode) -> RxChannel {
+    let mut decoder = Psk31Decoder::for_mode(freq_hz, sample_rate, rx_mode);
+    decoder.set_decimation((sample_rate / CHANNEL_LOOP_RATE).max(1) as usize);
+    decoder.set_bandpass(true);
+    (freq_hz, Arc::new(Mutex::new(decoder)))
+}
+
+/// Index of the channel on `freq_hz`, if one is open
+pub fn find_channel(channels: &[RxChannel], freq_hz: f64) -> Option<usize> {
+    channels.iter().position(|(freq, _)| (freq - freq_hz).abs() < CHANNEL_MATCH_HZ)
+}
+
+/// Feed `samples` to every channel. Returns `(channel_freq, text)` for each
+/// channel that completed characters, in channel order. A channel whose
+/// decoder lock is poisoned is skipped.
+pub fn decode_channels(channels: &[RxChannel], samples: &[f32]) -> Vec<(f64, String)> {
+    channels
+        .iter()
+        .filter_map(|(freq, decoder)| {
+            let mut decoder = decoder.lock().ok()?;
+            let text: String = samples.iter().filter_map(|&s| decoder.process(s)).collect();
+            (!text.is_empty()).then_some((*freq, text))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modem::encoder::Psk31Encoder;
+
+    #[test]
+    fn each_channel_copies_its_own_signal() {
+        let sample_rate = 48000;
+        let mut samples = Psk31Encoder::new(sample_rate, 1000.0).encode("CQ CQ DE AA1AA");
+        samples.extend(Psk31Encoder::new(sample_rate, 1500.0).encode("CQ CQ DE BB2BB"));
+
+        let channels = vec![
+            new_channel(1000.0, sample_rate, RxMode::default()),
+            new_channel(1500.0, sample_rate, RxMode::default()),
+        ];
+        let mut copied = [String::new(), String::new()];
+        // Feed in audio-thread-sized batches
+        for batch in samples.chunks(1920) {
+            for (freq, text) in decode_channels(&channels, batch) {
+                copied[find_channel(&channels, freq).unwrap()].push_str(&text);
+            }
+        }
+
+        assert!(copied[0].contains("AA1AA"), "1000 Hz channel: {:?}", copied[0]);
+        assert!(!copied[0].contains("BB2BB"), "1000 Hz channel: {:?}", copied[0]);
+        assert!(copied[1].contains("BB2BB"), "1500 Hz channel: {:?}", copied[1]);
+        assert!(!copied[1].contains("AA1AA"), "1500 Hz channel: {:?}", copied[1]);
+    }
+
+    #[test]
+    fn channels_decimate_to_the_channel_loop_rate() {
+        let (_, decoder) = new_channel(1000.0, 48000, RxMode::default());
+        assert_eq!(decoder.lock().unwrap().decimation(), 6);
+        let (_, decoder) = new_channel(1000.0, 8000, RxMode::default());
+        assert_eq!(decoder.lock().unwrap().decimation(), 1);
+    }
+
+    #[test]
+    fn find_channel_matches_within_half_a_hertz() {
+        let channels = vec![new_channel(1000.0, 48000, RxMode::default())];
+        assert_eq!(find_channel(&channels, 1000.2), Some(0));
+        assert_eq!(find_channel(&channels, 1001.0), None);
+    }
+}
@@ -0,0 +1,133 @@
+//! Received text rate
+//!
+//! Characters per minute over a sliding window of the decoded stream, shown
+//! as the sender's effective typing speed. A sender slowing down (often the
+//! end of an over) shows as a falling rate. Time is counted in audio samples,
+//! so during silence characters slide out of the window and the rate decays
+//! to zero on its own.
+
+use std::collections::VecDeque;
+
+/// Length of the sliding window the rate is averaged over
+pub const RX_RATE_WINDOW_SECS: f64 = 20.0;
+
+/// Characters per word, by the usual five-letter-word convention
+pub const CHARS_PER_WORD: f64 = 5.0;
+
+/// Sliding-window characters-per-minute estimator
+pub struct RxRateEstimator {
+    sample_rate: u32,
+    window_samples: u64,
+    /// Samples seen since creation or the last reset
+    elapsed: u64,
+    /// (sample time, characters) for each batch still inside the window
+    arrivals: VecDeque<(u64, usize)>,
+    /// Sum of the character counts in `arrivals`
+    chars_in_window: usize,
+}
+
+impl RxRateEstimator {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            window_samples: (RX_RATE_WINDOW_SECS * sample_rate as f64) as u64,
+            elapsed: 0,
+            arrivals: VecDeque::new(),
+            chars_in_window: 0,
+        }
+    }
+
+    /// Advance the clock by `samples` of audio during which `chars`
+    /// characters were decoded.
+    pub fn update(&mut self, samples: usize, chars: usize) {
+        self.elapsed += samples as u64;
+        if chars > 0 {
+            self.arrivals.push_back((self.elapsed, chars));
+            self.chars_in_window += chars;
+        }
+        let cutoff = self.elapsed.saturating_sub(self.window_samples);
+        while let Some(&(at, n)) = self.arrivals.front() {
+            if at > cutoff {
+                break;
+            }
+            self.arrivals.pop_front();
+            self.chars_in_window -= n;
+        }
+    }
+
+    /// Characters per minute over the window (or the time so far, if shorter)
+    pub fn chars_per_minute(&self) -> f64 {
+        let span = self.elapsed.min(self.window_samples);
+        if span == 0 {
+            return 0.0;
+        }
+        self.chars_in_window as f64 * 60.0 * self.sample_rate as f64 / span as f64
+    }
+
+    /// Words per minute, five characters to the word
+    pub fn wpm(&self) -> f64 {
+        self.chars_per_minute() / CHARS_PER_WORD
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0;
+        self.arrivals.clear();
+        self.chars_in_window = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 8000;
+    /// 100 ms of audio per update, like the audio thread's short polls
+    const CHUNK: usize = RATE as usize / 10;
+
+    /// Feed `secs` of audio with one character every `char_interval` chunks
+    /// (0 = silence).
+    fn feed(est: &mut RxRateEstimator, secs: usize, char_interval: usize) {
+        for i in 0..secs * 10 {
+            let chars = usize::from(char_interval > 0 && i % char_interval == 0);
+            est.update(CHUNK, chars);
+        }
+    }
+
+    #[test]
+    fn steady_cadence_reports_expected_wpm() {
+        let mut est = RxRateEstimator::new(RATE);
+        // One character every 0.6 s = 100 chars/min = 20 WPM
+        feed(&mut est, 60, 6);
+        assert!((est.chars_per_minute() - 100.0).abs() < 5.0, "{}", est.chars_per_minute());
+        assert!((est.wpm() - 20.0).abs() < 1.0, "{}", est.wpm());
+    }
+
+    #[test]
+    fn rate_is_meaningful_before_window_fills() {
+        let mut est = RxRateEstimator::new(RATE);
+        feed(&mut est, 6, 6);
+        assert!((est.wpm() - 20.0).abs() < 2.0, "{}", est.wpm());
+    }
+
+    #[test]
+    fn silence_decays_toward_zero() {
+        let mut est = RxRateEstimator::new(RATE);
+        feed(&mut est, 30, 6);
+        let sending = est.wpm();
+
+        feed(&mut est, 10, 0);
+        let fading = est.wpm();
+        assert!(fading < sending && fading > 0.0, "{sending} -> {fading}");
+
+        feed(&mut est, 15, 0);
+        assert_eq!(est.wpm(), 0.0);
+    }
+
+    #[test]
+    fn reset_clears_rate() {
+        let mut est = RxRateEstimator::new(RATE);
+        feed(&mut est, 10, 3);
+        est.reset();
+        assert_eq!(est.chars_per_minute(), 0.0);
+    }
+}
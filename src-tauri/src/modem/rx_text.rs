@@ -0,0 +1,92 @@
+//! RX text sanitising
+//!
+//! Noise decodes to arbitrary Varicode, including NUL, BEL, ESC and other
+//! control codes that clutter the RX pane (or confuse the frontend). The
+//! display stream keeps only printable ASCII plus CR, LF and Tab; the raw
+//! stream is still emitted separately for debugging.
+//!
+//! The display stream is also kept in a bounded `RxTranscript`, so a
+//! reloaded UI can repaint the RX pane and `clear_rx_session` has one place
+//! to wipe.
+
+use std::collections::VecDeque;
+
+/// Characters of RX text the transcript keeps
+pub const RX_TRANSCRIPT_CHARS: usize = 64 * 1024;
+
+/// True for characters safe to show in the RX pane
+pub fn is_display_safe(ch: char) -> bool {
+    matches!(ch, ' '..='~' | '\r' | '\n' | '\t')
+}
+
+/// Drop everything outside the display-safe set.
+pub fn filter_control_chars(text: &str) -> String {
+    text.chars().filter(|&ch| is_display_safe(ch)).collect()
+}
+
+/// Most recent RX display text, oldest characters dropped first
+#[derive(Debug)]
+pub struct RxTranscript {
+    capacity: usize,
+    chars: VecDeque<char>,
+}
+
+impl RxTranscript {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, chars: VecDeque::new() }
+    }
+
+    pub fn push(&mut self, text: &str) {
+        self.chars.extend(text.chars());
+        let excess = self.chars.len().saturating_sub(self.capacity);
+        self.chars.drain(..excess);
+    }
+
+    pub fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.chars.clear();
+    }
+}
+
+impl Default for RxTranscript {
+    fn default() -> Self {
+        Self::new(RX_TRANSCRIPT_CHARS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_chars_are_dropped_and_letters_pass() {
+        assert_eq!(filter_control_chars("CQ\0 DE\x07 W1AW\x1b"), "CQ DE W1AW");
+    }
+
+    #[test]
+    fn line_breaks_and_tabs_pass() {
+        assert_eq!(filter_control_chars("599\r\nTNX\tQSO"), "599\r\nTNX\tQSO");
+    }
+
+    #[test]
+    fn non_ascii_and_delete_are_dropped() {
+        assert_eq!(filter_control_chars("73\x7f é"), "73 ");
+    }
+
+    #[test]
+    fn transcript_keeps_the_most_recent_text() {
+        let mut transcript = RxTranscript::new(8);
+        transcript.push("CQ CQ ");
+        transcript.push("DE W1AW");
+        assert_eq!(transcript.text(), " DE W1AW");
+        transcript.clear();
+        assert!(transcript.is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+//! In-memory loopback self-test
+//!
+//! Encodes a known message, decodes it straight from the sample buffer and
+//! scores the result, so "does the modem work" can be answered without any
+//! audio or serial hardware.
+
+use crate::domain::SelfTestReport;
+use crate::modem::decoder::Psk31Decoder;
+use crate::modem::encoder::Psk31Encoder;
+
+/// Message sent through the loopback
+pub const SELF_TEST_MESSAGE: &str = "CQ CQ DE BAUDACIOUS SELF TEST 73";
+
+/// Highest character error rate that still counts as a pass
+pub const MAX_CHARACTER_ERROR_RATE: f64 = 0.05;
+
+const SELF_TEST_CARRIER_HZ: f64 = 1000.0;
+
+/// Levenshtein distance between `expected` and `decoded`, divided by the
+/// length of `expected`.
+pub fn character_error_rate(expected: &str, decoded: &str) -> f64 {
+    let expected: Vec<char> = expected.chars().collect();
+    let decoded: Vec<char> = decoded.chars().collect();
+    if expected.is_empty() {
+        return if decoded.is_empty() { 0.0 } else { 1.0 };
+    }
+    // Single-row dynamic programming over the edit-distance table
+    let mut row: Vec<usize> = (0..=decoded.len()).collect();
+    for (i, &e) in expected.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &d) in decoded.iter().enumerate() {
+            let substitution = diagonal + usize::from(e != d);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[decoded.len()] as f64 / expected.len() as f64
+}
+
+/// Encode `SELF_TEST_MESSAGE`, decode it in memory and score it. The report
+/// carries the loopback check; callers add their environment checks.
+pub fn run_loopback_self_test(sample_rate: u32) -> SelfTestReport {
+    let samples = Psk31Encoder::new(sample_rate, SELF_TEST_CARRIER_HZ).encode(SELF_TEST_MESSAGE);
+    let mut decoder = Psk31Decoder::new(SELF_TEST_CARRIER_HZ, sample_rate);
+    let decoded: String = samples.iter().filter_map(|&s| decoder.process(s)).collect();
+    // Preamble/postamble idle decodes as whitespace
+    let decoded = decoded.trim().to_string();
+
+    let cer = character_error_rate(SELF_TEST_MESSAGE, &decoded);
+    let mut report = SelfTestReport {
+        passed: true,
+        character_error_rate: cer,
+        decoded_text: decoded,
+        checks: Vec::new(),
+    };
+    let result = if cer <= MAX_CHARACTER_ERROR_RATE {
+        Ok(format!("CER {:.1}%", cer * 100.0))
+    } else {
+        Err(format!("CER {:.1}% exceeds {:.0}%", cer * 100.0, MAX_CHARACTER_ERROR_RATE * 100.0))
+    };
+    report.add_check("Modem loopback", result);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loopback_self_test_passes_with_low_cer() {
+        let report = run_loopback_self_test(48000);
+        assert!(report.passed, "{report:?}");
+        assert!(report.character_error_rate <= MAX_CHARACTER_ERROR_RATE);
+        assert_eq!(report.checks.len(), 1);
+        assert!(report.checks[0].passed);
+    }
+
+    #[test]
+    fn failed_check_fails_the_report() {
+        let mut report = run_loopback_self_test(48000);
+        report.add_check("Config directory", Err("permission denied".into()));
+        assert!(!report.passed);
+        assert_eq!(report.checks[1].detail, "permission denied");
+    }
+
+    #[test]
+    fn character_error_rate_counts_edits() {
+        assert_eq!(character_error_rate("CQ DE W1AW", "CQ DE W1AW"), 0.0);
+        // One substitution in ten characters
+        assert!((character_error_rate("CQ DE W1AW", "CQ DE W1AX") - 0.1).abs() < 1e-9);
+        // One dropped and one inserted character
+        assert!((character_error_rate("TEST", "EST") - 0.25).abs() < 1e-9);
+        assert!((character_error_rate("TEST", "TEXST") - 0.25).abs() < 1e-9);
+        assert_eq!(character_error_rate("TEST", ""), 1.0);
+    }
+}
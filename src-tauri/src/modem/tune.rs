@@ -0,0 +1,124 @@
+//! Tune carrier generator with soft start/stop
+//!
+//! A bare sine switched on and off at buffer edges is a step in amplitude:
+//! it splatters, clicks the rig and can upset an ATU mid-measurement. The
+//! generator instead ramps the carrier up over `TUNE_RAMP_MS` with a
+//! raised-cosine (0.5 − 0.5·cos) attack, and ramps it back down the same way
+//! when released.
+
+use crate::dsp::nco::Nco;
+
+/// Length of the attack and release ramps in milliseconds
+pub const TUNE_RAMP_MS: f64 = 5.0;
+
+/// Streaming tune carrier: attack → steady → (after `release`) decay → silence
+pub struct TuneGenerator {
+    nco: Nco,
+    ramp_len: usize,
+    /// Position on the ramp, 0 (silent) ..= ramp_len (full amplitude)
+    ramp_pos: usize,
+    releasing: bool,
+}
+
+impl TuneGenerator {
+    pub fn new(carrier_freq: f64, sample_rate: f64) -> Self {
+        Self {
+            nco: Nco::new(carrier_freq, sample_rate),
+            ramp_len: ((sample_rate * TUNE_RAMP_MS / 1000.0).round() as usize).max(1),
+            ramp_pos: 0,
+            releasing: false,
+        }
+    }
+
+    /// Start the release ramp. Subsequent output decays to silence.
+    pub fn release(&mut self) {
+        self.releasing = true;
+    }
+
+    /// True once the release ramp has reached silence.
+    pub fn is_finished(&self) -> bool {
+        self.releasing && self.ramp_pos == 0
+    }
+
+    /// Fill `buf` with the next carrier samples.
+    pub fn fill(&mut self, buf: &mut [f32]) {
+        for s in buf.iter_mut() {
+            if self.releasing {
+                self.ramp_pos = self.ramp_pos.saturating_sub(1);
+            } else if self.ramp_pos < self.ramp_len {
+                self.ramp_pos += 1;
+            }
+            *s = self.nco.next() * self.gain();
+        }
+    }
+
+    fn gain(&self) -> f32 {
+        let t = self.ramp_pos as f32 / self.ramp_len as f32;
+        0.5 - 0.5 * (std::f32::consts::PI * t).cos()
+    }
+}
+
+/// Generate `num_samples` of tune carrier with attack and release ramps
+/// at both ends of the buffer.
+pub fn generate_tune(carrier_freq: f64, sample_rate: f64, num_samples: usize) -> Vec<f32> {
+    let mut generator = TuneGenerator::new(carrier_freq, sample_rate);
+    let mut samples = vec![0.0; num_samples];
+    let release_at = num_samples.saturating_sub(generator.ramp_len);
+    generator.fill(&mut samples[..release_at]);
+    generator.release();
+    generator.fill(&mut samples[release_at..]);
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Peak |sample| of each 1 ms chunk (one full cycle at 1 kHz)
+    fn chunk_peaks(samples: &[f32]) -> Vec<f32> {
+        samples
+            .chunks(48)
+            .map(|c| c.iter().fold(0.0f32, |m, s| m.max(s.abs())))
+            .collect()
+    }
+
+    #[test]
+    fn attack_ramps_up_from_silence() {
+        let samples = generate_tune(1000.0, 48000.0, 48000);
+        let ramp = (48000.0 * TUNE_RAMP_MS / 1000.0) as usize;
+
+        assert!(samples[0].abs() < 1e-3, "first sample {}", samples[0]);
+        let peaks = chunk_peaks(&samples[..ramp]);
+        assert!(peaks.windows(2).all(|w| w[1] > w[0]), "attack peaks {peaks:?}");
+        assert!(peaks[0] < 0.5);
+        // Full amplitude once the ramp is over
+        assert!(chunk_peaks(&samples[ramp..ramp + 48])[0] > 0.99);
+    }
+
+    #[test]
+    fn release_ramps_down_to_silence() {
+        let samples = generate_tune(1000.0, 48000.0, 48000);
+        let ramp = (48000.0 * TUNE_RAMP_MS / 1000.0) as usize;
+        let tail = &samples[samples.len() - ramp..];
+
+        let peaks = chunk_peaks(tail);
+        assert!(peaks.windows(2).all(|w| w[1] < w[0]), "release peaks {peaks:?}");
+        assert!(peaks[peaks.len() - 1] < 0.5);
+        assert!(samples[samples.len() - 1].abs() < 1e-3);
+    }
+
+    #[test]
+    fn streaming_release_finishes_after_ramp() {
+        let mut generator = TuneGenerator::new(1000.0, 48000.0);
+        let mut buf = vec![0.0; 480];
+        generator.fill(&mut buf);
+        assert!(!generator.is_finished());
+
+        generator.release();
+        generator.fill(&mut buf[..100]);
+        assert!(!generator.is_finished());
+        generator.fill(&mut buf);
+        assert!(generator.is_finished());
+        assert!(buf[200..].iter().all(|&s| s == 0.0));
+    }
+}
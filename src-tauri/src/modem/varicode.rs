@@ -2,14 +2,23 @@
 //!
 //! Varicode uses variable-length bit patterns for each character.
 //! More common characters have shorter codes. Each code ends with "00".
+//!
+//! Decoding looks the received bits up in `DECODE_TABLE`, a reverse of
+//! `Varicode::encode` keyed by (length, bits) and built on first use.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
 
 /// Varicode encoder/decoder
 pub struct Varicode;
 
 impl Varicode {
     /// Encode a character to its Varicode bit pattern
-    /// Returns None for unsupported characters
+    /// Returns None for unsupported characters (anything outside 7-bit ASCII)
     pub fn encode(ch: char) -> Option<&'static str> {
+        if !ch.is_ascii() {
+            return None;
+        }
         let code = match ch as u8 {
             0x00 => "1010101011",  // NUL
             0x01 => "1011011011",  // SOH
@@ -144,12 +153,47 @@ impl Varicode {
         Some(code)
     }
 
+    /// True if `ch` has a Varicode pattern
+    pub fn is_encodable(ch: char) -> bool {
+        Self::encode(ch).is_some()
+    }
+
+    /// Every encodable character, in code-point order
+    pub fn encodable_chars() -> impl Iterator<Item = char> {
+        (0u8..=0x7F).map(char::from).filter(|&ch| Self::is_encodable(ch))
+    }
+
+    /// Characters in `text` the encoder would drop, each listed once in
+    /// order of first appearance. Empty if the whole text can be sent.
+    pub fn untransmittable_chars(text: &str) -> Vec<char> {
+        let mut dropped = Vec::new();
+        for ch in text.chars().filter(|&ch| !Self::is_encodable(ch)) {
+            if !dropped.contains(&ch) {
+                dropped.push(ch);
+            }
+        }
+        dropped
+    }
+
     /// Convert a bit string to actual bits
     pub fn bits_from_str(s: &str) -> Vec<bool> {
         s.chars().map(|c| c == '1').collect()
     }
 }
 
+/// Decode key for a code string: (bit count, bits folded MSB first)
+fn code_key(code: &str) -> (u8, u16) {
+    let bits = code.chars().fold(0u16, |acc, c| (acc << 1) | u16::from(c == '1'));
+    (code.len() as u8, bits)
+}
+
+/// Every Varicode pattern mapped back to its character
+static DECODE_TABLE: LazyLock<HashMap<(u8, u16), char>> = LazyLock::new(|| {
+    Varicode::encodable_chars()
+        .map(|ch| (code_key(Varicode::encode(ch).unwrap()), ch))
+        .collect()
+});
+
 /// Varicode decoder state machine
 pub struct VaricodeDecoder {
     bit_buffer: u16,
@@ -184,6 +228,10 @@ impl VaricodeDecoder {
             // Add the '1' bit
             self.bit_buffer = (self.bit_buffer << 1) | 1;
             self.bit_count += 1;
+        } else if self.bit_count == 0 {
+            // Idle zeros before a character (preamble, inter-character gaps):
+            // every code starts with '1', so these are never part of it
+            return None;
         } else {
             self.consecutive_zeros += 1;
 
@@ -201,21 +249,7 @@ impl VaricodeDecoder {
     }
 
     fn lookup_code(&self) -> Option<char> {
-        // This is a simplified lookup - in practice you'd use a trie or hashmap
-        // For now, do a linear search through all codes
-        for ch in 0u8..=127 {
-            if let Some(code_str) = Varicode::encode(ch as char) {
-                let code_bits: u16 = code_str
-                    .chars()
-                    .fold(0u16, |acc, c| (acc << 1) | if c == '1' { 1 } else { 0 });
-                let code_len = code_str.len() as u8;
-
-                if code_len == self.bit_count && code_bits == self.bit_buffer {
-                    return Some(ch as char);
-                }
-            }
-        }
-        None
+        DECODE_TABLE.get(&(self.bit_count, self.bit_buffer)).copied()
     }
 
     pub fn reset(&mut self) {
@@ -243,6 +277,24 @@ mod tests {
         assert_eq!(Varicode::encode('\n'), Some("11101"));
     }
 
+    #[test]
+    fn common_ascii_is_encodable() {
+        let printable = (' '..='~').chain(['\n', '\r', '\t']);
+        for ch in printable {
+            assert!(Varicode::is_encodable(ch), "{ch:?}");
+        }
+        assert_eq!(Varicode::encodable_chars().count(), 128);
+    }
+
+    #[test]
+    fn non_ascii_is_reported_untransmittable() {
+        assert!(!Varicode::is_encodable('€'));
+        // U+0141 truncates to 'A' as a byte — it must not encode as one
+        assert_eq!(Varicode::encode('Ł'), None);
+        assert_eq!(Varicode::untransmittable_chars("CQ 5€ €uro Łódź"), vec!['€', 'Ł', 'ó', 'ź']);
+        assert!(Varicode::untransmittable_chars("CQ CQ DE W1AW K").is_empty());
+    }
+
     #[test]
     fn test_decode_roundtrip() {
         let mut decoder = VaricodeDecoder::new();
@@ -268,4 +320,28 @@ mod tests {
 
         assert_eq!(decoded, "test");
     }
+
+    #[test]
+    fn decode_table_reverses_encode_exactly() {
+        let mut expected = HashMap::new();
+        for ch in (0u8..=127).map(char::from) {
+            let code = Varicode::encode(ch).unwrap();
+            let bits = code.chars().fold(0u16, |acc, c| (acc << 1) | u16::from(c == '1'));
+            assert!(expected.insert((code.len() as u8, bits), ch).is_none(), "{ch:?} not unique");
+        }
+        assert_eq!(*DECODE_TABLE, expected);
+    }
+
+    #[test]
+    fn test_leading_idle_zeros_are_ignored() {
+        let mut decoder = VaricodeDecoder::new();
+
+        // 32 idle zeros (preamble) followed by "H" + separator
+        let mut bits = vec![false; 32];
+        bits.extend(Varicode::bits_from_str(Varicode::encode('H').unwrap()));
+        bits.extend([false, false]);
+
+        let decoded: String = bits.into_iter().filter_map(|b| decoder.push_bit(b)).collect();
+        assert_eq!(decoded, "H");
+    }
 }
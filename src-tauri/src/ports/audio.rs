@@ -1,5 +1,7 @@
 //! Audio port traits
 
+use std::time::Duration;
+
 use crate::domain::{AudioDeviceInfo, AudioSample, Psk31Result};
 
 /// Trait for audio input (capture from microphone/radio)
@@ -22,6 +24,12 @@ pub trait AudioInput {
 
     /// Check if currently capturing
     fn is_running(&self) -> bool;
+
+    /// Rate the capture stream was opened at, which may differ from the one
+    /// asked for if the device doesn't offer it. `None` until capture starts.
+    fn sample_rate(&self) -> Option<u32> {
+        None
+    }
 }
 
 /// Trait for audio output (playback to speaker/radio)
@@ -41,4 +49,10 @@ pub trait AudioOutput {
 
     /// Check if currently playing
     fn is_running(&self) -> bool;
+
+    /// Delay between a sample leaving the callback and reaching the DAC, as
+    /// reported by the device. `None` until playback starts or if unknown.
+    fn output_latency(&self) -> Option<Duration> {
+        None
+    }
 }
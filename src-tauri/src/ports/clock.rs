@@ -0,0 +1,80 @@
+//! Time source trait
+//!
+//! `CatSession` paces commands against a `Clock` rather than calling
+//! `Instant::now`/`thread::sleep` directly, so tests can drive its timing
+//! with a fake clock instead of really sleeping. `FakeClock` is that clock,
+//! built for unit tests and, with the `test-support` feature, for the
+//! integration tests too.
+
+#[cfg(any(test, feature = "test-support"))]
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Source of the current time, and a way to wait
+pub trait Clock: Send {
+    fn now(&self) -> Instant;
+
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real clock: `Instant::now` and `thread::sleep`
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Clock that only moves when slept on or advanced, recording each sleep.
+/// Clones share the same time and record. A sleep also yields the thread,
+/// so a loop pacing itself on this clock still lets its peers run.
+#[cfg(any(test, feature = "test-support"))]
+#[derive(Clone)]
+pub struct FakeClock {
+    now: Arc<Mutex<Instant>>,
+    sleeps: Arc<Mutex<Vec<Duration>>>,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl FakeClock {
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+            sleeps: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+
+    /// The sleeps requested since the last call
+    pub fn take_sleeps(&self) -> Vec<Duration> {
+        std::mem::take(&mut *self.sleeps.lock().unwrap())
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.sleeps.lock().unwrap().push(duration);
+        self.advance(duration);
+        std::thread::yield_now();
+    }
+}
@@ -4,9 +4,11 @@
 //! Adapters implement these traits to connect to real hardware.
 
 pub mod audio;
+pub mod clock;
 pub mod serial;
 pub mod radio;
 
 pub use audio::*;
+pub use clock::*;
 pub use serial::*;
 pub use radio::*;
@@ -1,6 +1,16 @@
 //! Radio control port trait
 
-use crate::domain::{Frequency, Psk31Result, RadioStatus};
+use crate::domain::{
+    BandPlanEntry, Frequency, Psk31Error, Psk31Result, RadioFilterState, RadioMemoryChannel,
+    RadioPush, RadioStatus, SerialParams, Vfo,
+};
+use crate::ports::{Clock, SerialConnection};
+
+/// Builds the adapter for one radio type on a freshly opened serial
+/// connection, pacing its commands against `Clock`.
+/// `adapters::radio_registry` maps `Configuration.radio_type` names to these.
+pub type RadioFactory =
+    fn(Box<dyn SerialConnection>, &SerialParams, Box<dyn Clock>) -> Box<dyn RadioControl>;
 
 /// Trait for radio control (PTT, frequency, mode, TX power)
 pub trait RadioControl: Send {
@@ -13,12 +23,40 @@ pub trait RadioControl: Send {
     /// Check if PTT is currently engaged
     fn is_transmitting(&self) -> bool;
 
+    /// Ask the radio itself whether it is transmitting, regardless of who
+    /// keyed it (e.g. a crashed previous session). `Ok(None)` if the adapter
+    /// can't query transmit state.
+    fn is_transmitting_remote(&mut self) -> Psk31Result<Option<bool>> {
+        Ok(None)
+    }
+
     /// Get current VFO frequency
     fn get_frequency(&mut self) -> Psk31Result<Frequency>;
 
     /// Set VFO frequency
     fn set_frequency(&mut self, freq: Frequency) -> Psk31Result<()>;
 
+    /// Get the VFO-B frequency. Errors if the adapter can't.
+    fn get_frequency_b(&mut self) -> Psk31Result<Frequency> {
+        Err(Psk31Error::Cat("This radio does not support VFO-B".into()))
+    }
+
+    /// Set the VFO-B frequency, with the same validation as `set_frequency`
+    fn set_frequency_b(&mut self, _freq: Frequency) -> Psk31Result<()> {
+        Err(Psk31Error::Cat("This radio does not support VFO-B".into()))
+    }
+
+    /// Exchange VFO-A and VFO-B
+    fn swap_vfo(&mut self) -> Psk31Result<()> {
+        Err(Psk31Error::Cat("This radio does not support VFO-B".into()))
+    }
+
+    /// Turn split on or off (receive on VFO-A, transmit on VFO-B).
+    /// Errors if the adapter can't.
+    fn set_split(&mut self, _on: bool) -> Psk31Result<()> {
+        Err(Psk31Error::Cat("This radio does not support split".into()))
+    }
+
     /// Get current operating mode (e.g., "USB", "DATA-USB", "LSB")
     fn get_mode(&mut self) -> Psk31Result<String>;
 
@@ -31,10 +69,99 @@ pub trait RadioControl: Send {
     /// Set TX power in watts
     fn set_tx_power(&mut self, watts: u32) -> Psk31Result<()>;
 
-    /// Get S-meter signal strength, normalised 0.0–1.0 (from SM0 0–30 scale)
+    /// Get S-meter signal strength, normalised 0.0–1.0 (from the 0–255 SM0 scale)
     fn get_signal_strength(&mut self) -> Psk31Result<f32>;
 
+    /// Read the radio's raw S-meter, 0–255 (map to S-units with
+    /// `SMeterReading::from_raw`). Errors if the adapter can't.
+    fn read_s_meter(&mut self) -> Psk31Result<u8> {
+        Err(Psk31Error::Cat("This radio does not report its S-meter".into()))
+    }
+
+    /// Read the approximate SWR (1.0 = matched). Only meaningful while
+    /// transmitting. Errors if the adapter can't.
+    fn read_swr(&mut self) -> Psk31Result<f32> {
+        Err(Psk31Error::Cat("This radio does not report SWR".into()))
+    }
+
     /// Get comprehensive radio status via IF; (freq, mode, TX, RIT, split).
     /// Preferred over separate get_frequency + get_mode calls on connect.
     fn get_status(&mut self) -> Psk31Result<RadioStatus>;
+
+    /// Read the receive filter settings (narrow switch, width index).
+    /// `Ok(None)` if the adapter can't query them.
+    fn get_filter_state(&mut self) -> Psk31Result<Option<RadioFilterState>> {
+        Ok(None)
+    }
+
+    /// Which VFO (or memory channel) the radio is operating from.
+    /// `Ok(None)` if the adapter can't tell.
+    fn get_active_vfo(&mut self) -> Psk31Result<Option<Vfo>> {
+        Ok(None)
+    }
+
+    /// The selected memory channel and its stored frequency.
+    /// `Ok(None)` if the adapter can't read memory channels.
+    fn memory_channel_frequency(&mut self) -> Psk31Result<Option<RadioMemoryChannel>> {
+        Ok(None)
+    }
+
+    /// Why the radio won't transmit right now (e.g. scanning), or `Ok(None)`
+    /// if it will — including when the adapter can't tell.
+    fn get_tx_inhibit(&mut self) -> Psk31Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Turn the radio's auto-information mode on or off. While on, it
+    /// reports front-panel changes by itself; collect them with
+    /// `poll_unsolicited`. Errors if the adapter can't.
+    fn set_auto_information(&mut self, _enabled: bool) -> Psk31Result<()> {
+        Err(Psk31Error::Cat("This radio does not support auto-information".into()))
+    }
+
+    /// Turn RIT (receive clarifier) on or off. Errors if the adapter can't.
+    fn set_rit_enabled(&mut self, _enabled: bool) -> Psk31Result<()> {
+        Err(Psk31Error::Cat("This radio does not support RIT".into()))
+    }
+
+    /// Turn XIT (transmit clarifier) on or off. Errors if the adapter can't.
+    fn set_xit_enabled(&mut self, _enabled: bool) -> Psk31Result<()> {
+        Err(Psk31Error::Cat("This radio does not support XIT".into()))
+    }
+
+    /// Set the clarifier offset in Hz (shared by RIT and XIT). Out-of-range
+    /// offsets are rejected before anything is sent.
+    fn set_rit_offset(&mut self, _offset_hz: i32) -> Psk31Result<()> {
+        Err(Psk31Error::Cat("This radio does not support RIT".into()))
+    }
+
+    /// Zero the clarifier offset
+    fn clear_rit(&mut self) -> Psk31Result<()> {
+        Err(Psk31Error::Cat("This radio does not support RIT".into()))
+    }
+
+    /// Changes the radio reported by itself since the last call, oldest
+    /// first. Never blocks waiting for one.
+    fn poll_unsolicited(&mut self) -> Psk31Result<Vec<RadioPush>> {
+        Ok(Vec::new())
+    }
+
+    /// Apply a band change: set frequency, then mode, then TX power.
+    ///
+    /// The plan is validated before anything is sent. Stops at the first
+    /// failing step, naming it in the error; earlier steps are not undone.
+    fn apply_band_plan(&mut self, plan: &BandPlanEntry) -> Psk31Result<()> {
+        plan.validate()?;
+        let step = |name: &str, e: Psk31Error| {
+            Psk31Error::Cat(format!("Band plan failed at {name}: {e}"))
+        };
+        self.set_frequency(plan.frequency()).map_err(|e| step("set frequency", e))?;
+        if let Some(mode) = &plan.mode {
+            self.set_mode(mode).map_err(|e| step("set mode", e))?;
+        }
+        if let Some(watts) = plan.tx_power_watts {
+            self.set_tx_power(watts).map_err(|e| step("set power", e))?;
+        }
+        Ok(())
+    }
 }
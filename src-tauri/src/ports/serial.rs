@@ -4,7 +4,38 @@
 //! - `SerialFactory` — static methods for listing and opening ports
 //! - `SerialConnection` — instance methods for reading/writing data
 
-use crate::domain::{Psk31Result, SerialPortInfo};
+use std::time::Duration;
+
+use crate::domain::{Psk31Error, Psk31Result, SerialParams, SerialPortInfo};
+
+/// A freshly opened port with the settings the driver reports it actually
+/// applied, which may differ from those requested
+pub struct OpenedSerial {
+    pub connection: Box<dyn SerialConnection>,
+    pub baud_rate: u32,
+    pub read_timeout: Duration,
+}
+
+impl OpenedSerial {
+    /// One message per setting the driver didn't apply as requested
+    pub fn mismatches(&self, requested: &SerialParams) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        if self.baud_rate != requested.baud_rate {
+            mismatches.push(format!(
+                "Port opened at {} baud, not the requested {}",
+                self.baud_rate, requested.baud_rate
+            ));
+        }
+        let timeout_ms = self.read_timeout.as_millis() as u64;
+        if timeout_ms != requested.read_timeout_ms {
+            mismatches.push(format!(
+                "Port read timeout is {timeout_ms} ms, not the requested {} ms",
+                requested.read_timeout_ms
+            ));
+        }
+        mismatches
+    }
+}
 
 /// Factory for creating serial connections.
 /// Think of this like a Python classmethod — static methods that create instances.
@@ -14,20 +45,54 @@ pub trait SerialFactory {
 
     /// Open a serial port at the given baud rate, returning a boxed connection
     fn open(port: &str, baud_rate: u32) -> Psk31Result<Box<dyn SerialConnection>>;
+
+    /// Open a serial port with explicit line settings, reporting what was
+    /// actually applied. The default ignores everything but the baud rate
+    /// and echoes the request back unverified.
+    fn open_with_params(port: &str, params: &SerialParams) -> Psk31Result<OpenedSerial> {
+        Ok(OpenedSerial {
+            connection: Self::open(port, params.baud_rate)?,
+            baud_rate: params.baud_rate,
+            read_timeout: Duration::from_millis(params.read_timeout_ms),
+        })
+    }
 }
 
 /// Trait for an open serial port connection.
 /// Only requires `Send` (not `Sync`) — always accessed behind a Mutex.
 pub trait SerialConnection: Send {
-    /// Write bytes to the port
+    /// Write bytes to the port. May write fewer than `data.len()` bytes.
     fn write(&mut self, data: &[u8]) -> Psk31Result<usize>;
 
+    /// Write all of `data`, looping over short writes. A write that accepts
+    /// zero bytes is an error rather than a spin.
+    fn write_all(&mut self, mut data: &[u8]) -> Psk31Result<()> {
+        while !data.is_empty() {
+            match self.write(data)? {
+                0 => {
+                    return Err(Psk31Error::Serial(format!(
+                        "Write stalled with {} bytes unsent",
+                        data.len()
+                    )))
+                }
+                n => data = &data[n..],
+            }
+        }
+        Ok(())
+    }
+
     /// Read bytes from the port (with timeout)
     fn read(&mut self, buffer: &mut [u8]) -> Psk31Result<usize>;
 
+    /// Bytes waiting to be read, so a caller can drain input without
+    /// blocking on the read timeout. The default reports none.
+    fn bytes_available(&self) -> Psk31Result<usize> {
+        Ok(0)
+    }
+
     /// Write a command string and read the response (convenience for CAT commands)
     fn write_read(&mut self, command: &str, response_buf: &mut [u8]) -> Psk31Result<usize> {
-        self.write(command.as_bytes())?;
+        self.write_all(command.as_bytes())?;
         self.read(response_buf)
     }
 
@@ -1,16 +1,71 @@
 //! Application state
 
-use std::sync::atomic::AtomicBool;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
-use crate::domain::{ModemConfig, ModemStatus};
+use crate::dsp::waterfall_history::WaterfallHistory;
+use crate::domain::{
+    DutyCycleTracker, ModemConfig, ModemStatus, QsoRecord, RxMode, FIRST_SERIAL,
+};
+use crate::modem::decoder::DEFAULT_SYMBOL_SQUELCH;
+use crate::modem::decode_snapshot::SnapshotCapture;
+use crate::modem::rx_channels::RxChannel;
+use crate::modem::rx_text::RxTranscript;
 use crate::ports::RadioControl;
 
+/// Lifecycle of the audio input stream.
+///
+/// ```text
+///   Idle ──▶ Starting ──▶ Running ──▶ Stopping ──▶ Idle
+///              │                                    ▲
+///              └──────── (start failed) ────────────┘
+/// ```
+///
+/// Held in a single mutex so a start can't race a stop that is still
+/// joining the previous audio thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AudioStreamState {
+    #[default]
+    Idle,
+    Starting,
+    Running,
+    Stopping,
+}
+
+impl AudioStreamState {
+    /// Move to `to`, or explain why that transition isn't allowed from here.
+    pub fn transition(&mut self, to: AudioStreamState) -> Result<(), String> {
+        use AudioStreamState::*;
+        let legal = matches!(
+            (*self, to),
+            (Idle, Starting)
+                | (Starting, Running)
+                | (Starting, Idle)
+                | (Running, Stopping)
+                | (Stopping, Idle)
+        );
+        if !legal {
+            return Err(match *self {
+                Running if to == Starting => "Audio stream already running".to_string(),
+                Starting => "Audio stream is still starting".to_string(),
+                Stopping => "Audio stream is already stopping".to_string(),
+                Idle if to == Stopping => "Audio stream not running".to_string(),
+                from => format!("Illegal audio stream transition {from:?} -> {to:?}"),
+            });
+        }
+        *self = to;
+        Ok(())
+    }
+}
+
 /// Shared application state managed by Tauri
 pub struct AppState {
     pub config: Mutex<ModemConfig>,
     pub status: Mutex<ModemStatus>,
     pub radio: Mutex<Option<Box<dyn RadioControl>>>,
+    /// Start/stop lifecycle of the audio stream (see `AudioStreamState`)
+    pub audio_stream: Mutex<AudioStreamState>,
     /// Shared flag to signal the audio thread to stop
     pub audio_running: Arc<AtomicBool>,
     /// Handle to the audio processing thread (for clean shutdown)
@@ -19,15 +74,78 @@ pub struct AppState {
     pub tx_abort: Arc<AtomicBool>,
     /// Handle to the TX thread (for clean shutdown)
     pub tx_thread: Mutex<Option<JoinHandle<()>>>,
+    /// When the transmitter was keyed recently, for the duty-cycle limit
+    pub tx_duty: Mutex<DutyCycleTracker>,
+    /// Shared flag to cancel a running band scan
+    pub scan_abort: Arc<AtomicBool>,
+    /// Handle to the band scan thread (None when not scanning)
+    pub scan_thread: Mutex<Option<JoinHandle<()>>>,
+    /// Shared flag to stop the CAT listener thread
+    pub cat_listener_abort: Arc<AtomicBool>,
+    /// Handle to the thread forwarding unsolicited radio reports (None when off)
+    pub cat_listener_thread: Mutex<Option<JoinHandle<()>>>,
+    /// Shared flag to stop the rigctld server
+    pub rigctld_abort: Arc<AtomicBool>,
+    /// Handle to the rigctld accept thread (None when the server is off)
+    pub rigctld_thread: Mutex<Option<JoinHandle<()>>>,
     /// Shared flag to enable/disable the RX decoder in the audio thread
     pub rx_running: Arc<AtomicBool>,
+    /// Set by `reset_decoder`; the audio thread resets the decoder and clears it
+    pub decoder_reset_requested: Arc<AtomicBool>,
+    /// Set by `reset_dsp`; the audio thread rebuilds its FFT and decoder,
+    /// drops buffered samples and clears it
+    pub dsp_reset_requested: Arc<AtomicBool>,
+    /// Set by `start_rx` with acquire enabled; the audio thread searches for
+    /// a signal to settle the decoder on and clears it
+    pub acquire_requested: Arc<AtomicBool>,
+    /// Set by `set_rx_mode`; the audio thread rebuilds the decoder for it on
+    /// the same carrier and takes it
+    pub rx_mode_requested: Arc<Mutex<Option<RxMode>>>,
+    /// Set by `inject_test_signal` while `test_signal` holds samples; the audio
+    /// callback clears it once the queue is drained
+    pub test_signal_active: Arc<AtomicBool>,
+    /// Generated audio fed into the input ring buffer in place of the soundcard
+    pub test_signal: Arc<Mutex<VecDeque<f32>>>,
+    /// Set by `capture_decode_snapshot`; the audio thread fills it with raw
+    /// input and stamps it with the decoder settings when full
+    pub decode_snapshot: Arc<Mutex<Option<SnapshotCapture>>>,
     /// Carrier frequency for RX decoder (updated by click-to-tune)
     pub rx_carrier_freq: Arc<Mutex<f64>>,
+    /// RX notch frequency in Hz, `None` when the notch is off
+    pub rx_notch_freq: Arc<Mutex<Option<f64>>>,
+    /// Bandpass prefilter around the RX carrier on/off
+    pub rx_bandpass: Arc<AtomicBool>,
+    /// Decoder symbol squelch, pushed to the live decoder by the audio thread
+    pub rx_squelch: Arc<Mutex<f32>>,
+    /// Extra decoders keyed by carrier frequency (see `modem::rx_channels`),
+    /// fed by the audio thread alongside the main decoder
+    pub rx_channels: Arc<Mutex<Vec<RxChannel>>>,
+    /// Waterfall zoom window `(low_hz, high_hz)`; `None` emits the full span
+    pub waterfall_span: Mutex<Option<(f64, f64)>>,
+    /// Most recent FFT magnitudes in dB (positive bins), cached by the audio
+    /// thread for `export_spectrum_csv`; None until the first FFT frame
+    pub latest_spectrum: Mutex<Option<Vec<f32>>>,
+    /// Recent downsampled spectrum rows so a reloaded UI can repaint the waterfall
+    pub waterfall_history: Mutex<WaterfallHistory>,
+    /// RX display text so far, appended by the audio thread
+    pub rx_transcript: Mutex<RxTranscript>,
     /// Name of the currently active audio input device (None if not streaming).
     /// Wrapped in Arc so the audio thread can clear it on device loss.
     pub audio_device_name: Arc<Mutex<Option<String>>>,
+    /// Rate the input device was actually opened at (see
+    /// `CpalAudioInput::sample_rate`); set and cleared by the audio thread
+    pub audio_sample_rate: Arc<Mutex<Option<u32>>>,
     /// Name of the currently connected serial port (None if not connected)
     pub serial_port_name: Mutex<Option<String>>,
+    /// QSOs logged this session (appended by TX auto-log)
+    pub qso_log: Mutex<Vec<QsoRecord>>,
+    /// Contest serial number sent for `<NR>`, that of the active
+    /// configuration; advanced when a QSO is logged and persisted per
+    /// configuration outside the profiles (see `commands::config`)
+    pub contest_serial: Mutex<u32>,
+    /// Name of the last applied configuration profile
+    /// (None until one is applied: "Default")
+    pub active_configuration: Mutex<Option<String>>,
 }
 
 impl AppState {
@@ -36,15 +154,104 @@ impl AppState {
             config: Mutex::new(ModemConfig::default()),
             status: Mutex::new(ModemStatus::default()),
             radio: Mutex::new(None),
+            audio_stream: Mutex::new(AudioStreamState::Idle),
             audio_running: Arc::new(AtomicBool::new(false)),
             audio_thread: Mutex::new(None),
             tx_abort: Arc::new(AtomicBool::new(false)),
             tx_thread: Mutex::new(None),
+            tx_duty: Mutex::new(DutyCycleTracker::default()),
+            scan_abort: Arc::new(AtomicBool::new(false)),
+            scan_thread: Mutex::new(None),
+            cat_listener_abort: Arc::new(AtomicBool::new(false)),
+            cat_listener_thread: Mutex::new(None),
+            rigctld_abort: Arc::new(AtomicBool::new(false)),
+            rigctld_thread: Mutex::new(None),
             rx_running: Arc::new(AtomicBool::new(false)),
+            decoder_reset_requested: Arc::new(AtomicBool::new(false)),
+            dsp_reset_requested: Arc::new(AtomicBool::new(false)),
+            acquire_requested: Arc::new(AtomicBool::new(false)),
+            rx_mode_requested: Arc::new(Mutex::new(None)),
+            test_signal_active: Arc::new(AtomicBool::new(false)),
+            test_signal: Arc::new(Mutex::new(VecDeque::new())),
+            decode_snapshot: Arc::new(Mutex::new(None)),
             rx_carrier_freq: Arc::new(Mutex::new(1000.0)),
+            rx_notch_freq: Arc::new(Mutex::new(None)),
+            rx_bandpass: Arc::new(AtomicBool::new(false)),
+            rx_squelch: Arc::new(Mutex::new(DEFAULT_SYMBOL_SQUELCH)),
+            rx_channels: Arc::new(Mutex::new(Vec::new())),
+            waterfall_span: Mutex::new(None),
+            latest_spectrum: Mutex::new(None),
+            waterfall_history: Mutex::new(WaterfallHistory::default()),
+            rx_transcript: Mutex::new(RxTranscript::default()),
             audio_device_name: Arc::new(Mutex::new(None)),
+            audio_sample_rate: Arc::new(Mutex::new(None)),
             serial_port_name: Mutex::new(None),
+            qso_log: Mutex::new(Vec::new()),
+            contest_serial: Mutex::new(FIRST_SERIAL),
+            active_configuration: Mutex::new(None),
+        }
+    }
+
+    /// Apply one `AudioStreamState` transition under the stream mutex.
+    fn transition_audio_stream(&self, to: AudioStreamState) -> Result<(), String> {
+        self.audio_stream
+            .lock()
+            .map_err(|_| "Audio state corrupted".to_string())?
+            .transition(to)
+    }
+
+    /// First half of `start_audio_stream`: move to `Starting` and raise
+    /// `audio_running`. An audio thread that already exited on its own
+    /// (device lost) is reaped first, so the stream can be restarted.
+    pub fn begin_audio_start(&self) -> Result<(), String> {
+        let mut stream =
+            self.audio_stream.lock().map_err(|_| "Audio state corrupted".to_string())?;
+        if *stream == AudioStreamState::Running && !self.audio_running.load(Ordering::SeqCst) {
+            if let Some(handle) = self.audio_thread.lock().unwrap().take() {
+                let _ = handle.join();
+            }
+            *stream = AudioStreamState::Idle;
+        }
+        stream.transition(AudioStreamState::Starting)?;
+        self.audio_running.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// A start that got as far as `handle`: keep it and move to `Running`.
+    pub fn finish_audio_start(&self, handle: JoinHandle<()>) -> Result<(), String> {
+        self.audio_thread.lock().unwrap().replace(handle);
+        self.transition_audio_stream(AudioStreamState::Running)
+    }
+
+    /// A start that failed before spawning the audio thread: back to `Idle`.
+    pub fn abort_audio_start(&self) -> Result<(), String> {
+        self.audio_running.store(false, Ordering::SeqCst);
+        self.transition_audio_stream(AudioStreamState::Idle)
+    }
+
+    /// `stop_audio_stream`: signal the audio thread (and the RX decoder in
+    /// it) to stop, join it and return to `Idle`. A no-op when idle.
+    pub fn stop_audio(&self) -> Result<(), String> {
+        {
+            let mut stream =
+                self.audio_stream.lock().map_err(|_| "Audio state corrupted".to_string())?;
+            if *stream == AudioStreamState::Idle {
+                return Ok(()); // nothing to stop
+            }
+            stream.transition(AudioStreamState::Stopping)?;
         }
+
+        self.rx_running.store(false, Ordering::SeqCst);
+        self.audio_running.store(false, Ordering::SeqCst);
+
+        // Join the thread — it clears audio_device_name on exit
+        let joined = match self.audio_thread.lock().unwrap().take() {
+            Some(handle) => handle.join().map_err(|_| "Audio thread panicked".to_string()),
+            None => Ok(()),
+        };
+
+        self.transition_audio_stream(AudioStreamState::Idle)?;
+        joined
     }
 }
 
@@ -57,7 +264,7 @@ impl Default for AppState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::atomic::Ordering;
+    use std::sync::atomic::AtomicUsize;
 
     #[test]
     fn app_state_default_equals_new() {
@@ -74,6 +281,7 @@ mod tests {
         assert!(!state.audio_running.load(Ordering::Relaxed));
         assert!(!state.tx_abort.load(Ordering::Relaxed));
         assert!(!state.rx_running.load(Ordering::Relaxed));
+        assert!(!state.decoder_reset_requested.load(Ordering::Relaxed));
     }
 
     #[test]
@@ -94,6 +302,144 @@ mod tests {
         assert!(state.serial_port_name.lock().unwrap().is_none());
     }
 
+    #[test]
+    fn app_state_qso_log_starts_empty() {
+        let state = AppState::new();
+        assert!(state.qso_log.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn audio_stream_legal_lifecycle() {
+        let mut s = AudioStreamState::default();
+        assert_eq!(s, AudioStreamState::Idle);
+        for to in [
+            AudioStreamState::Starting,
+            AudioStreamState::Running,
+            AudioStreamState::Stopping,
+            AudioStreamState::Idle,
+            AudioStreamState::Starting,
+            AudioStreamState::Idle, // failed start
+        ] {
+            s.transition(to).unwrap();
+            assert_eq!(s, to);
+        }
+    }
+
+    #[test]
+    fn audio_stream_illegal_transitions_error_and_keep_state() {
+        let mut s = AudioStreamState::Running;
+        assert_eq!(
+            s.transition(AudioStreamState::Starting).unwrap_err(),
+            "Audio stream already running"
+        );
+        assert_eq!(s, AudioStreamState::Running);
+
+        let mut s = AudioStreamState::Stopping;
+        assert!(s.transition(AudioStreamState::Starting).unwrap_err().contains("stopping"));
+        assert!(s.transition(AudioStreamState::Stopping).is_err());
+        assert_eq!(s, AudioStreamState::Stopping);
+
+        let mut s = AudioStreamState::Starting;
+        assert!(s.transition(AudioStreamState::Stopping).unwrap_err().contains("starting"));
+    }
+
+    /// Stand-in audio threads: how many are live, and the most ever live at once
+    #[derive(Default)]
+    struct FakeAudio {
+        live: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    /// Start the stream with a stand-in audio thread that runs until
+    /// `audio_running` drops, then takes a moment to clean up like the real one.
+    fn start_fake_audio(state: &AppState, audio: &Arc<FakeAudio>) -> Result<(), String> {
+        state.begin_audio_start()?;
+        let running = state.audio_running.clone();
+        let audio = audio.clone();
+        let handle = std::thread::spawn(move || {
+            let live = audio.live.fetch_add(1, Ordering::SeqCst) + 1;
+            audio.peak.fetch_max(live, Ordering::SeqCst);
+            while running.load(Ordering::SeqCst) {
+                std::thread::yield_now();
+            }
+            std::thread::sleep(std::time::Duration::from_micros(200));
+            audio.live.fetch_sub(1, Ordering::SeqCst);
+        });
+        state.finish_audio_start(handle)
+    }
+
+    #[test]
+    fn audio_start_reaps_a_thread_that_lost_its_device() {
+        let state = AppState::new();
+        let audio = Arc::new(FakeAudio::default());
+        start_fake_audio(&state, &audio).unwrap();
+        assert!(start_fake_audio(&state, &audio).unwrap_err().contains("already running"));
+
+        // Device lost: the thread exits by itself, the stream still says Running
+        state.audio_running.store(false, Ordering::SeqCst);
+        start_fake_audio(&state, &audio).unwrap();
+        assert_eq!(*state.audio_stream.lock().unwrap(), AudioStreamState::Running);
+
+        state.stop_audio().unwrap();
+        assert_eq!(audio.live.load(Ordering::SeqCst), 0);
+        assert_eq!(audio.peak.load(Ordering::SeqCst), 1, "old audio thread not reaped");
+        assert_eq!(*state.audio_stream.lock().unwrap(), AudioStreamState::Idle);
+    }
+
+    #[test]
+    fn failed_audio_start_returns_to_idle() {
+        let state = AppState::new();
+        state.begin_audio_start().unwrap();
+        assert!(state.stop_audio().unwrap_err().contains("starting"));
+
+        state.abort_audio_start().unwrap();
+        assert_eq!(*state.audio_stream.lock().unwrap(), AudioStreamState::Idle);
+        assert!(!state.audio_running.load(Ordering::SeqCst));
+        state.begin_audio_start().unwrap();
+    }
+
+    /// Hammer the real start/stop transitions from several threads, with
+    /// failed starts and lost devices mixed in: at most one audio thread may
+    /// ever be live, however the calls interleave.
+    #[test]
+    fn audio_stream_rapid_start_stop_never_overlaps() {
+        let state = Arc::new(AppState::new());
+        let audio = Arc::new(FakeAudio::default());
+
+        let workers: Vec<_> = (0..8)
+            .map(|worker| {
+                let state = state.clone();
+                let audio = audio.clone();
+                std::thread::spawn(move || {
+                    for i in 0..500 {
+                        match (worker + i) % 4 {
+                            0 => {
+                                if state.begin_audio_start().is_ok() {
+                                    state.abort_audio_start().unwrap();
+                                }
+                            }
+                            1 => state.audio_running.store(false, Ordering::SeqCst),
+                            _ => {
+                                let _ = start_fake_audio(&state, &audio);
+                            }
+                        }
+                        if i % 3 == 0 {
+                            let _ = state.stop_audio();
+                        }
+                    }
+                })
+            })
+            .collect();
+        for w in workers {
+            w.join().unwrap();
+        }
+
+        state.stop_audio().unwrap();
+        assert_eq!(*state.audio_stream.lock().unwrap(), AudioStreamState::Idle);
+        assert_eq!(audio.live.load(Ordering::SeqCst), 0);
+        assert_eq!(audio.peak.load(Ordering::SeqCst), 1, "second audio thread spawned");
+    }
+
     #[test]
     fn app_state_radio_starts_none() {
         let state = AppState::new();
@@ -9,13 +9,28 @@
 //!
 //! Run with: cargo test --manifest-path src-tauri/Cargo.toml
 
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
 use baudacious_lib::adapters::ft991a::Ft991aRadio;
+use baudacious_lib::adapters::mock_audio::MockAudioOutput;
 use baudacious_lib::adapters::mock_radio::MockRadio;
-use baudacious_lib::domain::data_mode_for_frequency;
-use baudacious_lib::domain::{Frequency, Psk31Result};
-use baudacious_lib::ports::{RadioControl, SerialConnection};
+use baudacious_lib::adapters::rigctld;
+use baudacious_lib::commands::audio::poll_vfo_tracking;
+use baudacious_lib::commands::config::{apply_profile_to_modem, apply_profile_to_radio};
+use baudacious_lib::commands::radio::{
+    active_memory_channel, qsy_to_band, read_swr_while_keyed, set_frequency_with_readback, step_frequency_by,
+};
+use baudacious_lib::commands::serial::probe_radio;
+use baudacious_lib::commands::tx::{check_tx_permitted, check_tx_preflight};
+use baudacious_lib::domain::{data_mode_for_frequency, psk31_frequency_for_band};
+use baudacious_lib::domain::{
+    Band, BandPlanEntry, Configuration, Frequency, ModemConfig, Psk31Result, RadioFilterState,
+    RadioMemoryChannel, SerialParams, Vfo, VfoTracker,
+};
+use baudacious_lib::ports::{FakeClock, RadioControl, SerialConnection};
 use baudacious_lib::state::AppState;
 
 // ---------------------------------------------------------------------------
@@ -59,6 +74,43 @@ fn make_ft991a(response: &str) -> (Ft991aRadio, Arc<Mutex<Vec<String>>>) {
     (Ft991aRadio::new(Box::new(mock)), log)
 }
 
+// ---------------------------------------------------------------------------
+// ScriptedSerial — replies based on the last command written, so a single
+// radio can answer some queries and NAK others.
+// ---------------------------------------------------------------------------
+
+struct ScriptedSerial {
+    /// (command prefix, response) pairs; unmatched commands get `?;`
+    script: Vec<(&'static str, &'static str)>,
+    pending: Vec<u8>,
+}
+
+impl SerialConnection for ScriptedSerial {
+    fn write(&mut self, data: &[u8]) -> Psk31Result<usize> {
+        let cmd = String::from_utf8_lossy(data);
+        let response = self
+            .script
+            .iter()
+            .find(|(prefix, _)| cmd.starts_with(prefix))
+            .map(|(_, resp)| *resp)
+            .unwrap_or("?;");
+        self.pending = response.as_bytes().to_vec();
+        Ok(data.len())
+    }
+    fn read(&mut self, buf: &mut [u8]) -> Psk31Result<usize> {
+        let n = self.pending.len().min(buf.len());
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        Ok(n)
+    }
+    fn close(&mut self) -> Psk31Result<()> {
+        Ok(())
+    }
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
 /// Insert a MockRadio into AppState, simulating a successful connect.
 fn connected_state() -> AppState {
     let state = AppState::new();
@@ -112,6 +164,106 @@ fn set_frequency_updates_mock_state() {
     assert_eq!(radio.get_frequency().unwrap().as_hz(), 7_035_000.0);
 }
 
+/// step_frequency moves the VFO by the requested delta within the band.
+#[test]
+fn step_frequency_within_band_updates_radio() {
+    let mut radio = MockRadio::new(); // 14.070 MHz
+    let freq = step_frequency_by(&mut radio, 1000).unwrap();
+    assert_eq!(freq.as_hz(), 14_071_000.0);
+    assert_eq!(radio.get_frequency().unwrap().as_hz(), 14_071_000.0);
+}
+
+/// QSY with band defaults selects the band's DATA mode and suggested power.
+#[test]
+fn qsy_with_band_defaults_sets_mode_and_power() {
+    let mut radio = MockRadio::new();
+    radio.set_mode("DATA-LSB").unwrap();
+    let band = Band::by_name("30m").unwrap();
+
+    let freq = qsy_to_band(&mut radio, band, true).unwrap();
+    assert_eq!(freq.as_hz(), 10_142_000.0);
+    assert_eq!(radio.get_frequency().unwrap().as_hz(), 10_142_000.0);
+    assert_eq!(radio.get_mode().unwrap(), "DATA-USB");
+    assert_eq!(radio.get_tx_power().unwrap(), band.max_power_watts);
+}
+
+/// QSY without band defaults only moves the VFO.
+#[test]
+fn qsy_without_band_defaults_keeps_mode_and_power() {
+    let mut radio = MockRadio::new();
+    radio.set_mode("DATA-LSB").unwrap();
+    radio.set_tx_power(40).unwrap();
+
+    qsy_to_band(&mut radio, Band::by_name("30m").unwrap(), false).unwrap();
+    assert_eq!(radio.get_frequency().unwrap().as_hz(), 10_142_000.0);
+    assert_eq!(radio.get_mode().unwrap(), "DATA-LSB");
+    assert_eq!(radio.get_tx_power().unwrap(), 40);
+}
+
+/// A band plan leaves the radio on the planned frequency, mode and power.
+#[test]
+fn apply_band_plan_sets_frequency_mode_and_power() {
+    let mut radio = MockRadio::new();
+    let plan = BandPlanEntry {
+        frequency_hz: 7_070_000,
+        mode: Some("DATA-LSB".into()),
+        tx_power_watts: Some(15),
+    };
+
+    radio.apply_band_plan(&plan).unwrap();
+    assert_eq!(radio.get_frequency().unwrap().as_hz(), 7_070_000.0);
+    assert_eq!(radio.get_mode().unwrap(), "DATA-LSB");
+    assert_eq!(radio.get_tx_power().unwrap(), 15);
+}
+
+/// An invalid plan is rejected before anything reaches the radio.
+#[test]
+fn apply_band_plan_validates_before_sending() {
+    let (mut radio, log) = make_ft991a("");
+    let plan = BandPlanEntry {
+        frequency_hz: 27_185_000,
+        mode: Some("DATA-USB".into()),
+        tx_power_watts: Some(10),
+    };
+    assert!(radio.apply_band_plan(&plan).is_err());
+    assert!(log.lock().unwrap().is_empty());
+}
+
+/// The sequence stops at the first failing step and reports which one.
+#[test]
+fn apply_band_plan_reports_first_failing_step() {
+    // Rig accepts the write-only FA/MD commands but NAKs the power command
+    let mut radio = Ft991aRadio::new(Box::new(ScriptedSerial {
+        script: vec![],
+        pending: Vec::new(),
+    }));
+    let plan = BandPlanEntry::for_band(Band::by_name("20m").unwrap(), true);
+
+    let err = radio.apply_band_plan(&plan).unwrap_err().to_string();
+    assert!(err.contains("set power"), "got: {err}");
+}
+
+/// A step that would cross a band edge is rejected and leaves the VFO alone.
+#[test]
+fn step_frequency_rejects_crossing_band_edge() {
+    let mut radio = MockRadio::new();
+    radio.set_frequency(Frequency::hz(14_349_500.0)).unwrap();
+    assert!(step_frequency_by(&mut radio, 1000).is_err());
+    assert!(step_frequency_by(&mut radio, -400_000).is_err());
+    assert_eq!(radio.get_frequency().unwrap().as_hz(), 14_349_500.0);
+}
+
+/// A rig that rounds to its 10 Hz tuning step reports the snapped value.
+#[test]
+fn set_frequency_readback_reports_snapped_frequency() {
+    let mut radio = MockRadio::new().with_tuning_step(10.0);
+    let actual = set_frequency_with_readback(&mut radio, Frequency::hz(14_070_001.0)).unwrap();
+    assert_eq!(actual.as_hz(), 14_070_000.0);
+
+    let actual = set_frequency_with_readback(&mut radio, Frequency::hz(14_070_016.0)).unwrap();
+    assert_eq!(actual.as_hz(), 14_070_020.0);
+}
+
 /// set_frequency on Ft991aRadio rejects an out-of-band frequency before any
 /// bytes reach the wire — the key safety guarantee from Phase 8.
 #[test]
@@ -188,7 +340,9 @@ fn radio_commands_fail_when_not_connected() {
 fn band_change_40m_sends_psk31_calling_frequency() {
     let (mut radio, log) = make_ft991a(";");
     // This matches BAND_PLAN[40m].psk31Hz in serial-panel.ts
-    radio.set_frequency(Frequency::hz(7_035_000.0)).unwrap();
+    let calling_hz = psk31_frequency_for_band("40m").unwrap();
+    assert_eq!(calling_hz, 7_035_000.0);
+    radio.set_frequency(Frequency::hz(calling_hz)).unwrap();
     let cmds = log.lock().unwrap();
     assert_eq!(cmds[0], "BS03;");
     assert_eq!(cmds[1], "FA007035000;");
@@ -317,3 +471,345 @@ fn ptt_state_visible_through_appstate() {
         assert!(lock.as_ref().unwrap().is_transmitting());
     }
 }
+
+/// A radio that answers FA; but NAKs MD0; still connects, with a warning.
+#[test]
+fn probe_tolerates_mode_nak_with_warning() {
+    let serial = ScriptedSerial {
+        script: vec![("FA;", "FA014070000;")],
+        pending: Vec::new(),
+    };
+    let mut radio = Ft991aRadio::new(Box::new(serial));
+
+    let info = probe_radio(&mut radio, "/dev/ttyUSB0".into(), 38400);
+
+    assert!(info.connected);
+    assert_eq!(info.frequency_hz, Some(14_070_000.0));
+    assert_eq!(info.mode, None);
+    assert_eq!(info.warnings.len(), 1);
+    assert!(info.warnings[0].contains("mode"), "warning was: {}", info.warnings[0]);
+}
+
+/// Connection params carry a custom command delay through to the CAT session.
+#[test]
+fn serial_params_set_session_command_delay() {
+    let (radio, _) = make_ft991a(";");
+    assert_eq!(radio.command_delay(), std::time::Duration::from_millis(50));
+
+    let params = SerialParams { command_delay_ms: 150, ..SerialParams::new(9600) };
+    let mock = MockSerial {
+        log: Arc::new(Mutex::new(Vec::new())),
+        response: "FA014070000;".into(),
+    };
+    let clock = FakeClock::new();
+    let mut radio =
+        Ft991aRadio::with_params_and_clock(Box::new(mock), &params, Box::new(clock.clone()));
+    assert_eq!(radio.command_delay(), std::time::Duration::from_millis(150));
+
+    radio.get_frequency().unwrap();
+    clock.take_sleeps();
+    radio.get_frequency().unwrap();
+    assert_eq!(clock.take_sleeps(), vec![std::time::Duration::from_millis(150)]);
+}
+
+/// A radio left keyed by a crashed session is un-keyed on connect.
+#[test]
+fn probe_unkeys_radio_left_transmitting() {
+    let mut radio = MockRadio::new();
+    radio.ptt_on().unwrap();
+
+    let info = probe_radio(&mut radio, "mock".into(), 38400);
+
+    assert!(info.recovered_from_keyed);
+    assert!(!radio.is_transmitting());
+    assert_eq!(radio.is_transmitting_remote().unwrap(), Some(false));
+    assert!(info.warnings.iter().any(|w| w.contains("PTT released")), "{:?}", info.warnings);
+}
+
+/// The FT-991A's TX; query reporting keyed (TX1) triggers TX0; on connect.
+#[test]
+fn probe_sends_ptt_off_when_ft991a_reports_keyed() {
+    // Only TX; and TX0; are answered, so recovery succeeds only if TX0; is sent
+    let serial = ScriptedSerial {
+        script: vec![("TX;", "TX1;"), ("TX0;", ";")],
+        pending: Vec::new(),
+    };
+    let mut radio = Ft991aRadio::new(Box::new(serial));
+
+    let info = probe_radio(&mut radio, "/dev/ttyUSB0".into(), 38400);
+    assert!(info.recovered_from_keyed, "warnings: {:?}", info.warnings);
+}
+
+/// The FT-991A reports its filter via NA0; and SH0;.
+#[test]
+fn ft991a_reads_filter_state() {
+    let serial = ScriptedSerial {
+        script: vec![("NA0;", "NA01;"), ("SH0;", "SH007;")],
+        pending: Vec::new(),
+    };
+    let mut radio = Ft991aRadio::new(Box::new(serial));
+    assert_eq!(
+        radio.get_filter_state().unwrap(),
+        Some(RadioFilterState { narrow: true, width_code: 7 })
+    );
+}
+
+/// Split on sends ST1; to the FT-991A and shows up in the mock's status.
+#[test]
+fn split_on_sends_st1() {
+    let (mut radio, log) = make_ft991a(";");
+    radio.set_split(true).unwrap();
+    assert_eq!(*log.lock().unwrap(), ["ST1;"]);
+
+    let mut mock = MockRadio::new();
+    mock.set_split(true).unwrap();
+    assert!(mock.get_status().unwrap().split);
+}
+
+/// SWR is refused until PTT is on, then read with RM6;.
+#[test]
+fn swr_reads_only_while_keyed() {
+    let serial = ScriptedSerial {
+        script: vec![("TX", ";"), ("RM6;", "RM6052;")],
+        pending: Vec::new(),
+    };
+    let mut radio = Ft991aRadio::new(Box::new(serial));
+    let err = read_swr_while_keyed(&mut radio).unwrap_err();
+    assert!(err.to_string().contains("while transmitting"), "{err}");
+
+    radio.ptt_on().unwrap();
+    assert_eq!(read_swr_while_keyed(&mut radio).unwrap(), 1.5);
+    radio.ptt_off().unwrap();
+}
+
+/// IF; reports memory mode; MC;/MR reads back the channel's frequency.
+#[test]
+fn ft991a_reads_active_vfo_and_memory_frequency() {
+    let serial = ScriptedSerial {
+        script: vec![
+            ("IF;", "IF005007073900+000000C10000;"),
+            ("MC;", "MC005;"),
+            ("MR005;", "MR005007073900+000000C00000;"),
+        ],
+        pending: Vec::new(),
+    };
+    let mut radio = Ft991aRadio::new(Box::new(serial));
+    assert_eq!(radio.get_active_vfo().unwrap(), Some(Vfo::Memory));
+    assert_eq!(
+        radio.memory_channel_frequency().unwrap(),
+        Some(RadioMemoryChannel { channel: 5, frequency_hz: 7_073_900.0 })
+    );
+}
+
+/// The command helper reports the memory channel only while the radio is on one.
+#[test]
+fn active_memory_channel_reads_memory_mode_only() {
+    let serial = ScriptedSerial {
+        script: vec![
+            ("IF;", "IF005007073900+000000C10000;"),
+            ("MC;", "MC005;"),
+            ("MR005;", "MR005007073900+000000C00000;"),
+        ],
+        pending: Vec::new(),
+    };
+    let mut radio = Ft991aRadio::new(Box::new(serial));
+    assert_eq!(
+        active_memory_channel(&mut radio).unwrap(),
+        Some(RadioMemoryChannel { channel: 5, frequency_hz: 7_073_900.0 })
+    );
+
+    // On VFO-A only IF; is sent — no MC;/MR probing
+    let serial = ScriptedSerial {
+        script: vec![("IF;", "IF001007073900+000000C00000;")],
+        pending: Vec::new(),
+    };
+    let mut radio = Ft991aRadio::new(Box::new(serial));
+    assert_eq!(active_memory_channel(&mut radio).unwrap(), None);
+
+    assert_eq!(active_memory_channel(&mut MockRadio::new()).unwrap(), None);
+}
+
+/// An empty memory channel is an error, not a 0 Hz frequency.
+#[test]
+fn ft991a_empty_memory_channel_is_rejected() {
+    let serial = ScriptedSerial {
+        script: vec![("MC;", "MC009;"), ("MR009;", "MR009000000000+000000C00000;")],
+        pending: Vec::new(),
+    };
+    let mut radio = Ft991aRadio::new(Box::new(serial));
+    assert!(radio.memory_channel_frequency().is_err());
+}
+
+/// MockRadio has no filter to report — `None`, not an error.
+#[test]
+fn mock_radio_filter_state_is_unsupported() {
+    let mut radio = MockRadio::new();
+    assert_eq!(radio.get_filter_state().unwrap(), None);
+}
+
+/// All probes succeeding produces no warnings.
+#[test]
+fn probe_reports_no_warnings_when_radio_answers() {
+    let mut radio = MockRadio::new();
+    let info = probe_radio(&mut radio, "mock".into(), 38400);
+    assert_eq!(info.frequency_hz, Some(14_070_000.0));
+    assert_eq!(info.mode.as_deref(), Some("DATA-USB"));
+    assert!(info.warnings.is_empty());
+}
+
+/// Applying a profile with a band QSYs the radio and selects the DATA mode.
+#[test]
+fn apply_profile_sets_radio_frequency_mode_and_power() {
+    let state = connected_state();
+    let profile = Configuration {
+        band: Some("40m".into()),
+        carrier_freq: 1500.0,
+        tx_power_watts: 20,
+        ..Configuration::default()
+    };
+
+    let mut config = ModemConfig::default();
+    apply_profile_to_modem(&mut config, &profile);
+    assert_eq!(config.carrier_freq, 1500.0);
+    assert_eq!(config.tx_power_watts, 20);
+
+    let mut lock = state.radio.lock().unwrap();
+    let radio = lock.as_mut().unwrap();
+    apply_profile_to_radio(radio.as_mut(), &profile).unwrap();
+    assert_eq!(radio.get_frequency().unwrap().as_hz(), 7_035_000.0);
+    assert_eq!(radio.get_mode().unwrap(), "DATA-LSB");
+    assert_eq!(radio.get_tx_power().unwrap(), 20);
+}
+
+/// A profile without a band leaves the VFO where it is.
+#[test]
+fn apply_profile_without_band_keeps_frequency() {
+    let mut radio = MockRadio::new();
+    apply_profile_to_radio(&mut radio, &Configuration::default()).unwrap();
+    assert_eq!(radio.get_frequency().unwrap().as_hz(), 14_070_000.0);
+}
+
+/// Turning the VFO up 500 Hz on USB moves the station 500 Hz down in the
+/// passband, so the tracked RX carrier follows it down.
+#[test]
+fn track_vfo_shifts_carrier_against_dial_on_usb() {
+    let mut radio = MockRadio::new(); // 14.070 MHz DATA-USB
+    let mut tracker = VfoTracker::new();
+    assert_eq!(poll_vfo_tracking(&mut tracker, &mut radio, 1500.0), None);
+
+    radio.set_frequency(Frequency::hz(14_070_500.0)).unwrap();
+    assert_eq!(poll_vfo_tracking(&mut tracker, &mut radio, 1500.0), Some(1000.0));
+
+    // No further dial movement → carrier stays
+    assert_eq!(poll_vfo_tracking(&mut tracker, &mut radio, 1000.0), None);
+}
+
+/// On LSB the audio image is inverted, so the carrier moves with the dial.
+#[test]
+fn track_vfo_shifts_carrier_with_dial_on_lsb() {
+    let mut radio = MockRadio::new();
+    radio.set_frequency(Frequency::hz(7_035_000.0)).unwrap();
+    radio.set_mode("DATA-LSB").unwrap();
+    let mut tracker = VfoTracker::new();
+    poll_vfo_tracking(&mut tracker, &mut radio, 1000.0);
+
+    radio.set_frequency(Frequency::hz(7_035_500.0)).unwrap();
+    assert_eq!(poll_vfo_tracking(&mut tracker, &mut radio, 1000.0), Some(1500.0));
+}
+
+// ---------------------------------------------------------------------------
+// TX inhibit
+// ---------------------------------------------------------------------------
+
+/// A radio reporting TX inhibited blocks TX with the reason; one that doesn't
+/// (or can't tell) lets it through.
+#[test]
+fn tx_inhibited_radio_blocks_tx() {
+    let mut inhibited = MockRadio::new().with_tx_inhibit("radio is scanning");
+    let err = check_tx_permitted(&mut inhibited).unwrap_err();
+    assert!(err.contains("radio is scanning"), "got: {err}");
+    assert!(!inhibited.is_transmitting());
+
+    assert!(check_tx_permitted(&mut MockRadio::new()).is_ok());
+}
+
+/// A vanished output device is refused by start_tx's pre-flight, which
+/// leaves the radio unkeyed.
+#[test]
+fn missing_output_device_is_rejected_before_ptt() {
+    let outputs = MockAudioOutput::with_devices(&["Built-in Output", "USB Audio CODEC"]);
+    let mut radio = MockRadio::new();
+
+    let err = check_tx_preflight(&outputs, "USB Audio CODEC (2)", Some(&mut radio)).unwrap_err();
+    assert!(err.contains("USB Audio CODEC (2)"), "got: {err}");
+    assert!(!radio.is_transmitting(), "radio keyed into a missing device");
+
+    // Drifted names still resolve the way playback would resolve them
+    check_tx_preflight(&outputs, "usb audio", Some(&mut radio)).unwrap();
+    check_tx_preflight(&outputs, "usb audio", None).unwrap();
+    assert!(!radio.is_transmitting());
+}
+
+/// The pre-flight also refuses a radio that is inhibiting TX.
+#[test]
+fn tx_preflight_refuses_an_inhibited_radio() {
+    let outputs = MockAudioOutput::with_devices(&["USB Audio CODEC"]);
+    let scanning = ScriptedSerial { script: vec![("SC;", "SC1;")], pending: Vec::new() };
+    let mut radio = Ft991aRadio::new(Box::new(scanning));
+
+    let err = check_tx_preflight(&outputs, "USB Audio CODEC", Some(&mut radio)).unwrap_err();
+    assert!(err.contains("inhibiting TX"), "got: {err}");
+}
+
+/// The FT-991A reports inhibit while scanning (SC1/SC2), not when idle (SC0).
+#[test]
+fn ft991a_scan_status_drives_tx_inhibit() {
+    let scanning = ScriptedSerial { script: vec![("SC;", "SC1;")], pending: Vec::new() };
+    let mut radio = Ft991aRadio::new(Box::new(scanning));
+    assert!(check_tx_permitted(&mut radio).is_err());
+
+    let idle = ScriptedSerial { script: vec![("SC;", "SC0;")], pending: Vec::new() };
+    let mut radio = Ft991aRadio::new(Box::new(idle));
+    assert_eq!(radio.get_tx_inhibit().unwrap(), None);
+    assert!(check_tx_permitted(&mut radio).is_ok());
+}
+
+// ---------------------------------------------------------------------------
+// rigctld server
+// ---------------------------------------------------------------------------
+
+/// A Hamlib client connected over TCP sets and reads back the frequency of
+/// the shared radio, and stopping the server ends its connection.
+#[test]
+fn rigctld_server_round_trips_frequency_over_tcp() {
+    let radio = Arc::new(Mutex::new(MockRadio::new()));
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let abort = Arc::new(AtomicBool::new(false));
+
+    let shared = radio.clone();
+    let handle_line = move |line: &str| rigctld::handle_line(line, &mut *shared.lock().unwrap());
+    let server = {
+        let abort = abort.clone();
+        std::thread::spawn(move || rigctld::run_server(listener, abort, handle_line))
+    };
+
+    let mut client = TcpStream::connect(addr).unwrap();
+    let mut reader = BufReader::new(client.try_clone().unwrap());
+    let mut reply = String::new();
+
+    client.write_all(b"F 7035000\n").unwrap();
+    reader.read_line(&mut reply).unwrap();
+    assert_eq!(reply, "RPRT 0\n");
+    assert_eq!(radio.lock().unwrap().get_frequency().unwrap().as_hz(), 7_035_000.0);
+
+    reply.clear();
+    client.write_all(b"f\n").unwrap();
+    reader.read_line(&mut reply).unwrap();
+    assert_eq!(reply, "7035000\n");
+
+    abort.store(true, Ordering::SeqCst);
+    server.join().unwrap().unwrap();
+    reply.clear();
+    assert_eq!(reader.read_line(&mut reply).unwrap(), 0, "client still connected");
+}
@@ -4,6 +4,7 @@
 //! encoder, then feeding those audio samples through the decoder and
 //! checking that the original text is recovered.
 
+use baudacious_lib::domain::RxMode;
 use baudacious_lib::modem::decoder::Psk31Decoder;
 use baudacious_lib::modem::encoder::Psk31Encoder;
 
@@ -85,3 +86,84 @@ fn test_loopback_with_small_frequency_offset() {
         "Expected to decode with 1 Hz offset, got: '{decoded}'"
     );
 }
+
+#[test]
+fn test_loopback_primed_decoder_keeps_first_char() {
+    // Prime on a bare idle burst at the same carrier so the loops are locked
+    // before the real transmission starts.
+    let encoder = Psk31Encoder::new(48000, 1000.0);
+    let preamble = encoder.encode("");
+    let samples = encoder.encode("HI");
+
+    let mut decoder = Psk31Decoder::new(1000.0, 48000);
+    decoder.prime(&preamble);
+
+    let decoded: String = samples.iter().filter_map(|&s| decoder.process(s)).collect();
+    assert!(decoded.contains("HI"), "Expected full 'HI' after priming, got: '{decoded}'");
+}
+
+#[test]
+fn test_loopback_recovers_after_half_symbol_timing_glitch() {
+    // Drop half a symbol of audio mid-message (e.g. a buffer underrun). Without
+    // resync, every later decision lands on a symbol boundary and the rest of
+    // the message is garbage; with it only the character at the glitch is lost.
+    let encoder = Psk31Encoder::new(48000, 1000.0);
+    let samples = encoder.encode("THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG");
+    let mid = samples.len() / 2;
+    let mut glitched = samples[..mid].to_vec();
+    glitched.extend_from_slice(&samples[mid + 768..]);
+
+    let mut decoder = Psk31Decoder::new(1000.0, 48000);
+    let decoded: String = glitched.iter().filter_map(|&s| decoder.process(s)).collect();
+
+    assert!(decoded.contains("THE QUICK BROWN FOX"), "head lost: '{decoded}'");
+    assert!(decoded.contains("OVER THE LAZY DOG"), "tail not recovered: '{decoded}'");
+}
+
+#[test]
+fn test_loopback_with_decimated_front_end() {
+    // 48 kHz in, loops running at 8 kHz
+    for &freq in &[1000.0, 2000.0] {
+        let samples = Psk31Encoder::new(48000, freq).encode("CQ CQ DE W1AW");
+        let mut decoder = Psk31Decoder::new(freq, 48000);
+        decoder.set_decimation(6);
+        assert_eq!(decoder.decimation(), 6);
+
+        let decoded: String = samples.iter().filter_map(|&s| decoder.process(s)).collect();
+        assert!(decoded.contains("Q DE W1AW"), "Failed at {freq} Hz, got: '{decoded}'");
+        // Reported in input samples, as without decimation
+        assert!((decoder.current_sps() - 1536.0).abs() < 1536.0 * 0.1);
+    }
+}
+
+#[test]
+fn test_loopback_decimated_with_frequency_offset() {
+    // Decoder tuned 2-3 Hz off the 1000 Hz carrier: the 8 kHz loop must
+    // pull in as well as the full-rate one does
+    let samples = Psk31Encoder::new(48000, 1000.0).encode("CQ CQ DE W1AW W1AW K");
+    for &tuned in &[1002.0, 997.5, 1003.0] {
+        let mut decoder = Psk31Decoder::new(tuned, 48000);
+        decoder.set_decimation(6);
+
+        let decoded: String = samples.iter().filter_map(|&s| decoder.process(s)).collect();
+        assert!(
+            decoded.contains("DE W1AW W1AW K"),
+            "Failed tuned to {tuned} Hz, got: '{decoded}'"
+        );
+    }
+}
+
+#[test]
+fn test_loopback_at_psk63_and_psk125() {
+    for &baud in &[62.5, 125.0] {
+        let samples =
+            Psk31Encoder::new(48000, 1500.0).with_baud(baud).encode("CQ CQ DE W1AW W1AW K");
+        let rx_mode = RxMode { baud, ..RxMode::default() };
+        let mut decoder = Psk31Decoder::for_mode(1500.0, 48000, rx_mode);
+
+        let decoded: String = samples.iter().filter_map(|&s| decoder.process(s)).collect();
+        assert!(decoded.contains("DE W1AW W1AW K"), "Failed at {baud} baud, got: '{decoded}'");
+        let sps = 48000.0 / baud;
+        assert!((decoder.current_sps() - sps).abs() < sps * 0.1);
+    }
+}
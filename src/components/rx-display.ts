@@ -1,6 +1,7 @@
 /** RX (receive) display panel */
 
 let rxContentEl: HTMLElement | null = null;
+let rxChannelsEl: HTMLElement | null = null;
 
 /** Append decoded text to the RX display and auto-scroll to bottom */
 export function appendRxText(text: string): void {
@@ -11,13 +12,40 @@ export function appendRxText(text: string): void {
   rxContentEl.scrollTop = rxContentEl.scrollHeight;
 }
 
+/**
+ * Append text decoded on an extra RX channel to that channel's pane,
+ * creating the pane on its first text
+ */
+export function appendRxChannelText(channelFreq: number, text: string): void {
+  if (!rxChannelsEl) return;
+  const key = channelFreq.toFixed(1);
+  let pane = rxChannelsEl.querySelector<HTMLElement>(`[data-channel-freq="${key}"]`);
+  if (!pane) {
+    pane = document.createElement('div');
+    pane.className = 'rx-channel';
+    pane.dataset.channelFreq = key;
+    const label = document.createElement('div');
+    label.className = 'rx-channel-label';
+    label.textContent = `${Math.round(channelFreq)} Hz`;
+    const content = document.createElement('div');
+    content.className = 'rx-channel-content';
+    pane.append(label, content);
+    rxChannelsEl.appendChild(pane);
+  }
+  const content = pane.querySelector('.rx-channel-content') as HTMLElement;
+  content.appendChild(document.createTextNode(text));
+  content.scrollTop = content.scrollHeight;
+}
+
 export function setupRxDisplay(): void {
   const clearBtn = document.querySelector('.rx-controls .rx-btn') as HTMLButtonElement;
   rxContentEl = document.getElementById('rx-content') as HTMLElement;
+  rxChannelsEl = document.getElementById('rx-channels') as HTMLElement;
 
   if (clearBtn && rxContentEl) {
     clearBtn.addEventListener('click', () => {
       rxContentEl!.textContent = '';
+      if (rxChannelsEl) rxChannelsEl.textContent = '';
     });
   }
 }
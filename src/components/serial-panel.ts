@@ -86,8 +86,10 @@ export function handleConnectSuccess(info: RadioInfo): void {
   if (portNameEl) portNameEl.textContent = info.port;
   if (disconnectBtn) disconnectBtn.style.display = '';
 
+  for (const warning of info.warnings ?? []) console.warn(`CAT connect: ${warning}`);
+
   // Detect band from reported frequency and update controls
-  const band = detectBand(info.frequencyHz);
+  const band = info.frequencyHz !== null ? detectBand(info.frequencyHz) : null;
   _activeBand = band;
   bandSelect.disabled = false;
   freqInput.disabled = false;
@@ -95,15 +97,15 @@ export function handleConnectSuccess(info: RadioInfo): void {
     bandSelect.value = band.name;
     applyBandToInput(band, freqInput, rangeHint);
     // Show actual current freq (may differ from the calling freq)
-    freqInput.value = (info.frequencyHz / 1e6).toFixed(3);
+    freqInput.value = (info.frequencyHz! / 1e6).toFixed(3);
   } else {
     bandSelect.value = '';
     freqInput.min = '1.8';
     freqInput.max = '30';
-    freqInput.value = (info.frequencyHz / 1e6).toFixed(3);
+    freqInput.value = info.frequencyHz !== null ? (info.frequencyHz / 1e6).toFixed(3) : '';
     if (rangeHint) rangeHint.textContent = '';
   }
-  if (freqMode) freqMode.textContent = info.mode;
+  if (freqMode) freqMode.textContent = info.mode ?? '—';
 
   // Update CAT status indicator
   if (catDot) {
@@ -358,6 +358,8 @@ export function setupSettingsDialog(deps: SettingsDialogDeps): void {
       waterfall_noise_floor: base?.waterfall_noise_floor ?? -100,
       waterfall_zoom: base?.waterfall_zoom ?? 1,
       tx_power_watts: base?.tx_power_watts ?? 10,
+      band: base?.band ?? null,
+      use_band_defaults: base?.use_band_defaults ?? false,
     };
 
     saveBtn.disabled = true;
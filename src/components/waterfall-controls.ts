@@ -1,6 +1,7 @@
 /** Waterfall interaction and controls */
 
-import { setCarrierFrequency } from '../services/backend-api';
+import { setCarrierFrequency, snapCarrierToBin } from '../services/backend-api';
+import { SAMPLE_RATE } from './waterfall';
 import type { WaterfallDisplay, WaterfallSettings, ZoomLevel } from './waterfall';
 import { VALID_PALETTES } from '../utils/color-map';
 import type { ColorPalette } from '../utils/color-map';
@@ -25,20 +26,30 @@ export function setupWaterfallClick(waterfall: WaterfallDisplay | null): void {
 
   if (!canvas) return;
 
-  canvas.addEventListener('click', (e) => {
+  canvas.addEventListener('click', async (e) => {
     const rect = canvas.getBoundingClientRect();
     const x = e.clientX - rect.left;
 
     // Map pixel to Hz within the current visible range
     const range = waterfall ? waterfall.getVisibleRange() : { startHz: 500, endHz: 2500 };
-    const freq = Math.round(range.startHz + (x / rect.width) * (range.endHz - range.startHz));
+    let freq = Math.round(range.startHz + (x / rect.width) * (range.endHz - range.startHz));
+
+    // Optionally land on a bin centre so the marker sits on the displayed peak
+    if (waterfall?.getSnapToBin()) {
+      try {
+        freq = await snapCarrierToBin(freq, waterfall.getFftSize(), SAMPLE_RATE);
+      } catch (err) {
+        console.warn('Failed to snap carrier to FFT bin:', err);
+      }
+    }
+    const freqLabel = Math.round(freq);
 
     // Update carrier frequency in the waterfall (for zoom centering)
     waterfall?.setCarrierFreq(freq);
 
     // Update displays
-    freqDisplay.textContent = `${freq} Hz`;
-    if (statusCarrier) statusCarrier.textContent = `${freq} Hz`;
+    freqDisplay.textContent = `${freqLabel} Hz`;
+    if (statusCarrier) statusCarrier.textContent = `${freqLabel} Hz`;
 
     // Update carrier marker — position relative to the current visible range
     const markerX = ((freq - range.startHz) / (range.endHz - range.startHz)) * 100;
@@ -55,7 +66,7 @@ export function setupWaterfallClick(waterfall: WaterfallDisplay | null): void {
       '.sidebar-section:nth-child(3) .frequency-value',
     ) as HTMLElement;
     if (audioCarrierValue) {
-      audioCarrierValue.textContent = freq.toString();
+      audioCarrierValue.textContent = freqLabel.toString();
     }
 
     // Tell the backend decoder to retune
@@ -78,6 +89,7 @@ export function setupWaterfallControls(
   const gainSlider = document.getElementById('wf-gain') as HTMLInputElement;
   const gainDisplay = document.getElementById('wf-gain-value') as HTMLElement;
   const zoomBtns = document.querySelectorAll<HTMLButtonElement>('.wf-zoom-btn');
+  const snapBtn = document.getElementById('wf-snap') as HTMLButtonElement | null;
 
   function emit(): void {
     onSettingsChange(waterfall.getSettings());
@@ -108,6 +120,11 @@ export function setupWaterfallControls(
     });
   }
 
+  snapBtn?.addEventListener('click', () => {
+    waterfall.setSnapToBin(!waterfall.getSnapToBin());
+    snapBtn.classList.toggle('active', waterfall.getSnapToBin());
+  });
+
   return function applySettings(palette: string, noiseFloor: number, zoomLevel: number): void {
     const safePalette = (VALID_PALETTES.includes(palette as ColorPalette)
       ? palette
@@ -12,7 +12,7 @@ export interface WaterfallSettings {
 
 const AUDIO_START_HZ = 500;
 const AUDIO_END_HZ = 2500;
-const SAMPLE_RATE = 48000;
+export const SAMPLE_RATE = 48000;
 
 export class WaterfallDisplay {
   private canvas: HTMLCanvasElement;
@@ -32,6 +32,10 @@ export class WaterfallDisplay {
   private readonly dynamicRange: number = 80;
   private zoomLevel: ZoomLevel = 1;
   private carrierFreq: number = 1500;
+  private snapToBin: boolean = false;
+
+  // FFT size of the most recent row (bins × 2)
+  private fftSize: number = 4096;
 
   constructor(canvas: HTMLCanvasElement) {
     this.canvas = canvas;
@@ -75,6 +79,19 @@ export class WaterfallDisplay {
     this.zoomLevel = level;
   }
 
+  /** Snap click-to-tune to the nearest FFT bin centre */
+  setSnapToBin(enabled: boolean): void {
+    this.snapToBin = enabled;
+  }
+
+  getSnapToBin(): boolean {
+    return this.snapToBin;
+  }
+
+  getFftSize(): number {
+    return this.fftSize;
+  }
+
   /** Called by click-to-tune so zoom stays centered on the active carrier */
   setCarrierFreq(freqHz: number): void {
     this.carrierFreq = freqHz;
@@ -123,6 +140,7 @@ export class WaterfallDisplay {
 
     const { width } = this.canvas;
     const fftSize = rows[0].length * 2;
+    this.fftSize = fftSize;
     const binWidth = SAMPLE_RATE / fftSize;
     const { startHz, endHz } = this.getVisibleRange();
     const startBin = Math.floor(startHz / binWidth);
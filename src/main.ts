@@ -21,7 +21,7 @@ import { setupMenuEvents } from './services/event-handlers';
 import { startFftBridge, listenAudioStatus } from './services/audio-bridge';
 import { startRxBridge } from './services/rx-bridge';
 import { startSerialBridge } from './services/serial-bridge';
-import { appendRxText } from './components/rx-display';
+import { appendRxText, appendRxChannelText } from './components/rx-display';
 import { loadConfiguration, saveConfiguration, getConnectionStatus, getRadioState } from './services/backend-api';
 import { invoke } from '@tauri-apps/api/core';
 import { setupSettingsDialog, openSettingsDialog } from './components/settings-dialog';
@@ -67,8 +67,8 @@ window.addEventListener('DOMContentLoaded', () => {
     console.error('Failed to start serial bridge:', err);
   });
 
-  // Wire up RX bridge: decoded text events → RX display
-  startRxBridge(appendRxText).catch((err) => {
+  // Wire up RX bridge: main decoder text → RX display, extra channels → their own panes
+  startRxBridge(appendRxText, appendRxChannelText).catch((err) => {
     console.error('Failed to start RX bridge:', err);
   });
 
@@ -189,6 +189,9 @@ window.addEventListener('DOMContentLoaded', () => {
                   frequencyHz: radioStatus.frequencyHz,
                   mode: radioStatus.mode,
                   connected: true,
+                  warnings: [],
+                  recoveredFromKeyed: false,
+                  readTimeoutMs: null,
                 });
               });
             }
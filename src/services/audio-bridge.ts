@@ -3,8 +3,22 @@
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import type { WaterfallDisplay } from '../components/waterfall';
 
+/** Either `magnitudes` in dB, or `quantized` 0–255 across minDb..maxDb */
 interface FftPayload {
-  magnitudes: number[];
+  magnitudes?: number[];
+  quantized?: number[];
+  minDb?: number;
+  maxDb?: number;
+  /** Frequency of the first bin; non-zero when the span is zoomed */
+  startHz: number;
+  binHz: number;
+}
+
+function magnitudesDb(payload: FftPayload): number[] {
+  if (payload.magnitudes) return payload.magnitudes;
+  const minDb = payload.minDb ?? 0;
+  const stepDb = ((payload.maxDb ?? 0) - minDb) / 255;
+  return (payload.quantized ?? []).map((q) => minDb + q * stepDb);
 }
 
 let fftUnlisten: UnlistenFn | null = null;
@@ -16,7 +30,7 @@ export async function startFftBridge(waterfall: WaterfallDisplay): Promise<void>
   await stopFftBridge();
 
   fftUnlisten = await listen<FftPayload>('fft-data', (event) => {
-    waterfall.drawSpectrum(event.payload.magnitudes);
+    waterfall.drawSpectrum(magnitudesDb(event.payload));
   });
 }
 
@@ -1,13 +1,25 @@
 /** Typed wrappers for all Tauri backend commands */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { Configuration, AudioDeviceInfo, SerialPortInfo, RadioInfo, RadioStatus, ConnectionStatus } from '../types';
+import type { BandPlanEntry, Configuration, Memory, OperatorProfile, QsoRecord, TxDutyCycle, SelfTestReport, AudioDeviceInfo, AudioDevices, SerialPortInfo, SerialParams, RadioInfo, RadioStatus, RadioFilterState, RadioMemoryChannel, SMeterReading, Vfo, RxMode, DspInfo, ConnectionStatus } from '../types';
 
 // Audio commands
 export async function listAudioDevices(): Promise<AudioDeviceInfo[]> {
   return invoke('list_audio_devices');
 }
 
+export async function listAudioDevicesGrouped(): Promise<AudioDevices> {
+  return invoke('list_audio_devices_grouped');
+}
+
+export async function listAudioHosts(): Promise<string[]> {
+  return invoke('list_audio_hosts');
+}
+
+export async function setAudioHost(host: string | null): Promise<void> {
+  return invoke('set_audio_host', { host });
+}
+
 export async function startAudioStream(deviceId: string): Promise<void> {
   return invoke('start_audio_stream', { deviceId });
 }
@@ -29,9 +41,55 @@ export async function setCarrierFrequency(freqHz: number): Promise<void> {
   return invoke('set_carrier_frequency', { freqHz });
 }
 
+export async function snapCarrierToBin(
+  freqHz: number,
+  fftSize: number,
+  sampleRate: number,
+): Promise<number> {
+  return invoke('snap_carrier_to_bin', { freqHz, fftSize, sampleRate });
+}
+
+export async function resetDecoder(): Promise<void> {
+  return invoke('reset_decoder');
+}
+
+export async function getRxTranscript(): Promise<string> {
+  return invoke('get_rx_transcript');
+}
+
+export async function clearRxSession(): Promise<void> {
+  return invoke('clear_rx_session');
+}
+
+export async function resetDsp(): Promise<void> {
+  return invoke('reset_dsp');
+}
+
+export async function setRxMode(rxMode: RxMode): Promise<void> {
+  return invoke('set_rx_mode', { rxMode });
+}
+
+export async function injectTestSignal(text: string, carrierFreq: number): Promise<void> {
+  return invoke('inject_test_signal', { text, carrierFreq });
+}
+
+export async function captureDecodeSnapshot(seconds: number): Promise<string> {
+  return invoke('capture_decode_snapshot', { seconds });
+}
+
 // TX commands
-export async function startTx(text: string, deviceId: string): Promise<void> {
-  return invoke('start_tx', { text, deviceId });
+export async function startTx(
+  text: string,
+  deviceId: string,
+  monitorDeviceId?: string,
+  recordPath?: string,
+): Promise<void> {
+  return invoke('start_tx', {
+    text,
+    deviceId,
+    monitorDeviceId: monitorDeviceId ?? null,
+    recordPath: recordPath ?? null,
+  });
 }
 
 export async function stopTx(): Promise<void> {
@@ -46,6 +104,10 @@ export async function stopTune(): Promise<void> {
   return invoke('stop_tune');
 }
 
+export async function getTxDutyCycle(): Promise<TxDutyCycle> {
+  return invoke<TxDutyCycle>('get_tx_duty_cycle');
+}
+
 // Serial commands
 export async function listSerialPorts(): Promise<SerialPortInfo[]> {
   return invoke('list_serial_ports');
@@ -55,6 +117,13 @@ export async function connectSerial(port: string, baudRate: number): Promise<Rad
   return invoke('connect_serial', { port, baudRate });
 }
 
+export async function connectSerialAdvanced(
+  port: string,
+  params: SerialParams,
+): Promise<RadioInfo> {
+  return invoke('connect_serial_advanced', { port, params });
+}
+
 export async function disconnectSerial(): Promise<void> {
   return invoke('disconnect_serial');
 }
@@ -72,8 +141,32 @@ export async function getFrequency(): Promise<number> {
   return invoke('get_frequency');
 }
 
-export async function setFrequency(freqHz: number): Promise<void> {
-  return invoke('set_frequency', { freqHz });
+export async function setFrequency(freqHz: number, readBack = false): Promise<number | null> {
+  return invoke<number | null>('set_frequency', { freqHz, readBack });
+}
+
+export async function getFrequencyB(): Promise<number> {
+  return invoke('get_frequency_b');
+}
+
+export async function setFrequencyB(freqHz: number): Promise<void> {
+  return invoke('set_frequency_b', { freqHz });
+}
+
+export async function swapVfo(): Promise<void> {
+  return invoke('swap_vfo');
+}
+
+export async function setSplit(on: boolean): Promise<void> {
+  return invoke('set_split', { on });
+}
+
+export async function stepFrequency(deltaHz: number): Promise<number> {
+  return invoke('step_frequency', { deltaHz });
+}
+
+export async function qsy(band: string, applyBandDefaults: boolean): Promise<number> {
+  return invoke('qsy', { band, applyBandDefaults });
 }
 
 export async function getMode(): Promise<string> {
@@ -97,6 +190,10 @@ export async function listConfigurations(): Promise<string[]> {
   return invoke('list_configurations');
 }
 
+export async function applyConfiguration(name: string): Promise<Configuration> {
+  return invoke('apply_configuration', { name });
+}
+
 export async function deleteConfiguration(name: string): Promise<void> {
   return invoke('delete_configuration', { name });
 }
@@ -106,14 +203,250 @@ export async function getConnectionStatus(): Promise<ConnectionStatus> {
   return invoke('get_connection_status');
 }
 
+export async function readSMeter(): Promise<SMeterReading> {
+  return invoke('read_s_meter');
+}
+
+export async function readSwr(): Promise<number> {
+  return invoke('read_swr');
+}
+
 export async function getRadioState(): Promise<RadioStatus> {
   return invoke('get_radio_state');
 }
 
+export async function getRadioFilterState(): Promise<RadioFilterState | null> {
+  return invoke('get_radio_filter_state');
+}
+
+export async function getActiveVfo(): Promise<Vfo | null> {
+  return invoke('get_active_vfo');
+}
+
+export async function getMemoryChannelFrequency(): Promise<RadioMemoryChannel | null> {
+  return invoke('get_memory_channel_frequency');
+}
+
+export async function setRitEnabled(enabled: boolean): Promise<void> {
+  return invoke('set_rit_enabled', { enabled });
+}
+
+export async function setXitEnabled(enabled: boolean): Promise<void> {
+  return invoke('set_xit_enabled', { enabled });
+}
+
+export async function setRitOffset(offsetHz: number): Promise<void> {
+  return invoke('set_rit_offset', { offsetHz });
+}
+
+export async function clearRit(): Promise<void> {
+  return invoke('clear_rit');
+}
+
 export async function getTxPower(): Promise<number> {
   return invoke<number>('get_tx_power');
 }
 
+export async function setCatListener(enabled: boolean): Promise<void> {
+  return invoke('set_cat_listener', { enabled });
+}
+
+export async function startRigctldServer(port?: number): Promise<number> {
+  return invoke<number>('start_rigctld_server', { port: port ?? null });
+}
+
+export async function stopRigctldServer(): Promise<void> {
+  return invoke('stop_rigctld_server');
+}
+
 export async function setTxPowerConfig(watts: number): Promise<void> {
   return invoke('set_tx_power_config', { watts });
 }
+
+export async function setIdlePattern(pattern: 'reversals' | 'nul'): Promise<void> {
+  return invoke('set_idle_pattern', { pattern });
+}
+
+export async function setModemBaud(baud: 31.25 | 62.5 | 125): Promise<void> {
+  return invoke('set_modem_baud', { baud });
+}
+
+export async function setFftFormat(
+  format: 'float' | 'u8',
+  minDb: number,
+  maxDb: number,
+): Promise<void> {
+  return invoke('set_fft_format', { format, minDb, maxDb });
+}
+
+// QSO log commands
+export async function setAutoLog(enabled: boolean): Promise<void> {
+  return invoke('set_auto_log', { enabled });
+}
+
+export async function getQsoLog(): Promise<QsoRecord[]> {
+  return invoke('get_qso_log');
+}
+
+export async function getSerial(): Promise<number> {
+  return invoke<number>('get_serial');
+}
+
+export async function setSerial(n: number): Promise<void> {
+  return invoke('set_serial', { n });
+}
+
+export async function incrementSerial(): Promise<number> {
+  return invoke<number>('increment_serial');
+}
+
+// Memory channel commands
+export async function saveMemory(slot: number, memory: Memory): Promise<void> {
+  return invoke('save_memory', { slot, memory });
+}
+
+export async function recallMemory(slot: number): Promise<Memory> {
+  return invoke('recall_memory', { slot });
+}
+
+export async function listMemories(): Promise<Record<string, Memory>> {
+  return invoke('list_memories');
+}
+
+// Operator profile commands
+export async function getOperatorProfile(): Promise<OperatorProfile> {
+  return invoke<OperatorProfile>('get_operator_profile');
+}
+
+export async function setOperatorProfile(profile: OperatorProfile): Promise<OperatorProfile> {
+  return invoke<OperatorProfile>('set_operator_profile', { profile });
+}
+
+export async function formatFrequency(hz: number, style: 'grouped' | 'mhz'): Promise<string> {
+  return invoke('format_frequency', { hz, style });
+}
+
+export async function encodeToWav(
+  text: string,
+  carrierFreq: number,
+  path: string,
+  format?: 'int16' | 'float32',
+): Promise<number> {
+  return invoke('encode_to_wav', { text, carrierFreq, path, format });
+}
+
+export async function getEventSchemaVersion(): Promise<number> {
+  return invoke('get_event_schema_version');
+}
+
+export async function getDspInfo(): Promise<DspInfo> {
+  return invoke('get_dsp_info');
+}
+
+export async function setTrackVfo(enabled: boolean): Promise<void> {
+  return invoke('set_track_vfo', { enabled });
+}
+
+export async function setPttDelays(leadMs: number, tailMs: number): Promise<void> {
+  return invoke('set_ptt_delays', { leadMs, tailMs });
+}
+
+export async function setTxDutyCycleLimit(maxPercent: number, windowSecs: number): Promise<void> {
+  return invoke('set_tx_duty_cycle_limit', { maxPercent, windowSecs });
+}
+
+export async function applyBandPlan(plan: BandPlanEntry): Promise<void> {
+  return invoke('apply_band_plan', { plan });
+}
+
+export async function setRxNotch(freqHz: number | null): Promise<void> {
+  return invoke('set_rx_notch', { freqHz });
+}
+
+export async function setRxBandpass(enabled: boolean): Promise<void> {
+  return invoke('set_rx_bandpass', { enabled });
+}
+
+export async function setRxSquelch(level: number): Promise<void> {
+  return invoke('set_rx_squelch', { level });
+}
+
+export async function startRxChannel(freqHz: number): Promise<void> {
+  return invoke('start_rx_channel', { freqHz });
+}
+
+export async function stopRxChannel(freqHz: number): Promise<void> {
+  return invoke('stop_rx_channel', { freqHz });
+}
+
+export async function exportSpectrumCsv(path: string): Promise<void> {
+  return invoke('export_spectrum_csv', { path });
+}
+
+export async function setRxFilterControl(enabled: boolean): Promise<void> {
+  return invoke('set_rx_filter_control', { enabled });
+}
+
+export async function runSelfTest(): Promise<SelfTestReport> {
+  return invoke('run_self_test');
+}
+
+export async function setWaterfallSpan(span: [number, number] | null): Promise<void> {
+  return invoke('set_waterfall_span', { span });
+}
+
+export async function startBandScan(bands: string[], dwellSeconds: number): Promise<void> {
+  return invoke('start_band_scan', { bands, dwellSeconds });
+}
+
+export async function stopBandScan(): Promise<void> {
+  return invoke('stop_band_scan');
+}
+
+export async function getWaterfallHistory(): Promise<number[][]> {
+  return invoke('get_waterfall_history');
+}
+
+export async function setRxMuteDuringTx(enabled: boolean): Promise<void> {
+  return invoke('set_rx_mute_during_tx', { enabled });
+}
+
+export async function setRxDcBlocker(enabled: boolean): Promise<void> {
+  return invoke('set_rx_dc_blocker', { enabled });
+}
+
+export async function setRxAcquire(enabled: boolean): Promise<void> {
+  return invoke('set_rx_acquire', { enabled });
+}
+
+export async function setInputTrim(trimDb: number): Promise<void> {
+  return invoke('set_input_trim', { trimDb });
+}
+
+export async function setRxDrainBudget(budgetMs: number): Promise<void> {
+  return invoke('set_rx_drain_budget', { budgetMs });
+}
+
+export async function setRxBitsDebug(enabled: boolean): Promise<void> {
+  return invoke('set_rx_bits_debug', { enabled });
+}
+
+export async function setSignalDetector(
+  onThreshold: number,
+  offThreshold: number,
+  minDwellMs: number,
+): Promise<void> {
+  return invoke('set_signal_detector', { onThreshold, offThreshold, minDwellMs });
+}
+
+export async function setRxRecordMode(
+  mode: 'off' | 'auto',
+  threshold: number,
+  trailingSilenceMs: number,
+): Promise<void> {
+  return invoke('set_rx_record_mode', { mode, threshold, trailingSilenceMs });
+}
+
+export async function isTransmittable(text: string): Promise<string[]> {
+  return invoke('is_transmittable', { text });
+}
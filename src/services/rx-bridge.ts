@@ -1,19 +1,47 @@
 /** RX bridge — forwards decoded text events from Rust backend to the RX display */
 
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { startRxChannel, stopRxChannel } from './backend-api';
 
 interface RxTextPayload {
   text: string;
+  channelFreq: number;
 }
 
 let rxUnlisten: UnlistenFn | null = null;
 
-/** Start listening for decoded RX text events */
-export async function startRxBridge(onText: (text: string) => void): Promise<void> {
+/** Carriers of the extra RX channels opened through `openRxChannel` */
+const openChannels = new Set<number>();
+
+/** Open an extra RX channel; its text goes to `onChannelText` */
+export async function openRxChannel(freqHz: number): Promise<void> {
+  await startRxChannel(freqHz);
+  openChannels.add(freqHz);
+}
+
+/** Close an extra RX channel opened with `openRxChannel` */
+export async function closeRxChannel(freqHz: number): Promise<void> {
+  await stopRxChannel(freqHz);
+  openChannels.delete(freqHz);
+}
+
+/**
+ * Start listening for decoded RX text events. Text tagged with an open extra
+ * channel goes to `onChannelText`, everything else is the main decoder's.
+ */
+export async function startRxBridge(
+  onText: (text: string) => void,
+  onChannelText: (channelFreq: number, text: string) => void,
+): Promise<void> {
   await stopRxBridge();
 
   rxUnlisten = await listen<RxTextPayload>('rx-text', (event) => {
-    onText(event.payload.text);
+    const { text, channelFreq } = event.payload;
+    if (openChannels.has(channelFreq)) {
+      onChannelText(channelFreq, text);
+    } else {
+      onText(text);
+    }
   });
 }
 
@@ -5,12 +5,14 @@ import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 export interface TxStatus {
   status: 'transmitting' | 'complete' | 'aborted' | string;
   progress: number;
+  /** The transmission's WAV capture, on the final status when recording */
+  recordPath?: string;
 }
 
 export interface TxStatusCallbacks {
   onTransmitting?: (progress: number) => void;
-  onComplete?: () => void;
-  onAborted?: () => void;
+  onComplete?: (recordPath?: string) => void;
+  onAborted?: (recordPath?: string) => void;
   onError?: (message: string) => void;
 }
 
@@ -22,14 +24,14 @@ export async function listenTxStatus(callbacks: TxStatusCallbacks): Promise<void
   stopTxBridge();
 
   unlisten = await listen<TxStatus>('tx-status', (event) => {
-    const { status, progress } = event.payload;
+    const { status, progress, recordPath } = event.payload;
 
     if (status === 'transmitting') {
       callbacks.onTransmitting?.(progress);
     } else if (status === 'complete') {
-      callbacks.onComplete?.();
+      callbacks.onComplete?.(recordPath);
     } else if (status === 'aborted') {
-      callbacks.onAborted?.();
+      callbacks.onAborted?.(recordPath);
     } else if (status.startsWith('error')) {
       callbacks.onError?.(status);
     }
@@ -15,6 +15,37 @@ export interface SerialPortInfo {
   deviceHint?: string;
 }
 
+export interface SerialParams {
+  baudRate: number;
+  commandDelayMs?: number;
+  readTimeoutMs?: number;
+}
+
+export interface AudioDevices {
+  inputs: AudioDeviceInfo[];
+  outputs: AudioDeviceInfo[];
+}
+
+export interface SelfTestCheck {
+  name: string;
+  passed: boolean;
+  detail: string;
+}
+
+export interface SelfTestReport {
+  passed: boolean;
+  characterErrorRate: number;
+  decodedText: string;
+  checks: SelfTestCheck[];
+}
+
+/** One band change: applied in order frequency → mode → power; null fields are left alone */
+export interface BandPlanEntry {
+  frequencyHz: number;
+  mode: string | null;
+  txPowerWatts: number | null;
+}
+
 export interface Configuration {
   name: string;
   audio_input: string | null;
@@ -27,14 +58,24 @@ export interface Configuration {
   waterfall_noise_floor: number;
   waterfall_zoom: number;
   tx_power_watts: number;
+  band?: string | null;
+  use_band_defaults?: boolean;
 }
 
 export interface RadioInfo {
   port: string;
   baudRate: number;
-  frequencyHz: number;
-  mode: string;
+  /** null if the radio didn't answer the frequency probe at connect time */
+  frequencyHz: number | null;
+  /** null if the radio didn't answer the mode probe at connect time */
+  mode: string | null;
   connected: boolean;
+  /** Connect-time probes that failed; the connection is still usable */
+  warnings: string[];
+  /** The radio was found keyed on connect and PTT was released */
+  recoveredFromKeyed: boolean;
+  /** Serial read timeout the driver reports; null without a real port */
+  readTimeoutMs: number | null;
 }
 
 export interface RadioStatus {
@@ -44,6 +85,32 @@ export interface RadioStatus {
   ritOffsetHz: number;
   ritEnabled: boolean;
   split: boolean;
+  vfo?: Vfo;
+}
+
+export type Vfo = 'a' | 'b' | 'memory';
+
+export interface SMeterReading {
+  raw: number;
+  sUnits: number;
+  overS9Db: number;
+}
+
+export type Modulation = 'bpsk';
+
+export interface RxMode {
+  baud: number;
+  modulation: Modulation;
+}
+
+export interface RadioFilterState {
+  narrow: boolean;
+  widthCode: number;
+}
+
+export interface RadioMemoryChannel {
+  channel: number;
+  frequencyHz: number;
 }
 
 export interface MenuEvent {
@@ -56,3 +123,52 @@ export interface ConnectionStatus {
   audioStreaming: boolean;
   audioDevice: string | null;
 }
+
+export interface QsoRecord {
+  callsign: string;
+  rstSent: string;
+  frequencyHz: number | null;
+  mode: string | null;
+  timestampUtc: number;
+}
+
+export interface Memory {
+  name: string;
+  band: string;
+  frequencyHz: number;
+  mode: string;
+  carrierFreq: number;
+}
+
+export interface OperatorProfile {
+  callsign: string;
+  grid: string;
+  name: string;
+  qth: string;
+}
+
+export interface TxDutyCycle {
+  percent: number;
+  limitPercent: number;
+  windowSecs: number;
+}
+
+export interface DspInfo {
+  version: string;
+  defaultSampleRate: number;
+  baudRates: number[];
+  fftSize: number;
+  costasProportionalGain: number;
+  costasIntegralGain: number;
+  costasArmCutoffHz: number;
+  costasIntegratorLimitHz: number;
+  agcTargetLevel: number;
+  agcAttackRate: number;
+  agcDecayRate: number;
+  agcMinGain: number;
+  agcMaxGain: number;
+  notchQ: number;
+  preambleBits: number;
+  postambleBits: number;
+  maxMessageChars: number;
+}